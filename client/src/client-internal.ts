@@ -1,13 +1,20 @@
-import { AnchorProvider, BN, Program, web3, type IdlAccounts, type ProgramAccount } from '@coral-xyz/anchor'
+import { AnchorProvider, BN, Program, utils, web3, type IdlAccounts, type ProgramAccount } from '@coral-xyz/anchor'
 import { TOKEN_PROGRAM_ID } from '@solana/spl-token'
 
 import { getErrorMessage } from '@gpahal/std/error'
 
 import type { SuperstreamClient } from '@/client'
 import {
+  ALLOWED_MINT_ACCOUNT_SEED,
   DEPOSIT_AMOUNT_PERIOD_IN_SECS_BN,
+  GLOBAL_STATS_ACCOUNT_SEED,
   MAX_STREAM_NAME_LENGTH,
+  MAX_STREAM_NAME_SEED_LENGTH,
   MIN_STREAM_NAME_LENGTH,
+  MINT_REGISTRY_ACCOUNT_SEED,
+  MINT_STATS_ACCOUNT_SEED,
+  RECIPIENT_INDEX_ACCOUNT_SEED,
+  SENDER_INDEX_ACCOUNT_SEED,
   STREAM_ACCOUNT_SEED,
   SUPERSTREAM_PROGRAM_ID,
 } from '@/constants'
@@ -78,13 +85,62 @@ export class SuperstreamClientInternal implements SuperstreamClient {
     return new BN(Date.now())
   }
 
-  readonly getStreamPublicKey = (seed: BN, mint: web3.PublicKey, name: string): [web3.PublicKey, number] => {
+  readonly getStreamPublicKey = (
+    seed: BN,
+    mint: web3.PublicKey,
+    name: string,
+    derivationSender?: web3.PublicKey,
+    derivationRecipient?: web3.PublicKey,
+  ): [web3.PublicKey, number] => {
     return web3.PublicKey.findProgramAddressSync(
-      [Buffer.from(STREAM_ACCOUNT_SEED), seed.toArrayLike(Buffer, 'le', 8), mint.toBuffer(), Buffer.from(name)],
+      [
+        Buffer.from(STREAM_ACCOUNT_SEED),
+        seed.toArrayLike(Buffer, 'le', 8),
+        mint.toBuffer(),
+        getStreamNameSeed(name),
+        derivationSender ? derivationSender.toBuffer() : Buffer.alloc(0),
+        derivationRecipient ? derivationRecipient.toBuffer() : Buffer.alloc(0),
+      ],
       this.program.programId,
     )
   }
 
+  readonly getMintRegistryPublicKey = (): [web3.PublicKey, number] => {
+    return web3.PublicKey.findProgramAddressSync([Buffer.from(MINT_REGISTRY_ACCOUNT_SEED)], this.program.programId)
+  }
+
+  readonly getAllowedMintPublicKey = (mint: web3.PublicKey): [web3.PublicKey, number] => {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(ALLOWED_MINT_ACCOUNT_SEED), mint.toBuffer()],
+      this.program.programId,
+    )
+  }
+
+  readonly getSenderIndexPublicKey = (sender: web3.PublicKey): [web3.PublicKey, number] => {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(SENDER_INDEX_ACCOUNT_SEED), sender.toBuffer()],
+      this.program.programId,
+    )
+  }
+
+  readonly getRecipientIndexPublicKey = (recipient: web3.PublicKey): [web3.PublicKey, number] => {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(RECIPIENT_INDEX_ACCOUNT_SEED), recipient.toBuffer()],
+      this.program.programId,
+    )
+  }
+
+  readonly getMintStatsPublicKey = (mint: web3.PublicKey): [web3.PublicKey, number] => {
+    return web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(MINT_STATS_ACCOUNT_SEED), mint.toBuffer()],
+      this.program.programId,
+    )
+  }
+
+  readonly getGlobalStatsPublicKey = (): [web3.PublicKey, number] => {
+    return web3.PublicKey.findProgramAddressSync([Buffer.from(GLOBAL_STATS_ACCOUNT_SEED)], this.program.programId)
+  }
+
   readonly maybeGetStream = async (publicKey: web3.PublicKey): Promise<Stream | undefined> => {
     const streamAccount: StreamAccount | undefined =
       (await this.program.account.stream.fetchNullable(publicKey)) || undefined
@@ -223,9 +279,9 @@ export class SuperstreamClientInternal implements SuperstreamClient {
     startsAt = at.gt(startsAt) ? at : startsAt
     if (recipient.equals(web3.PublicKey.default)) {
       throw new Error('Invalid recipient. Recipient has to be a valid address')
-    } else if (name.length < MIN_STREAM_NAME_LENGTH) {
+    } else if ([...name].length < MIN_STREAM_NAME_LENGTH) {
       throw new Error(`Min stream name length should be ${MIN_STREAM_NAME_LENGTH}`)
-    } else if (name.length > MAX_STREAM_NAME_LENGTH) {
+    } else if ([...name].length > MAX_STREAM_NAME_LENGTH) {
       throw new Error(`Max stream name length can be ${MAX_STREAM_NAME_LENGTH}`)
     } else if (recipient.equals(this.provider.wallet.publicKey)) {
       throw new Error('Sender and recipient of the stream cannot be the same')
@@ -359,6 +415,12 @@ export class SuperstreamClientInternal implements SuperstreamClient {
         mint,
         senderToken,
         escrowToken,
+        mintRegistry: this.getMintRegistryPublicKey()[0],
+        allowedMint: this.getAllowedMintPublicKey(mint)[0],
+        senderIndex: this.getSenderIndexPublicKey(sender)[0],
+        recipientIndex: this.getRecipientIndexPublicKey(recipient)[0],
+        mintStats: this.getMintStatsPublicKey(mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
       })
@@ -450,6 +512,12 @@ export class SuperstreamClientInternal implements SuperstreamClient {
         mint,
         senderToken,
         escrowToken,
+        mintRegistry: this.getMintRegistryPublicKey()[0],
+        allowedMint: this.getAllowedMintPublicKey(mint)[0],
+        senderIndex: this.getSenderIndexPublicKey(sender)[0],
+        recipientIndex: this.getRecipientIndexPublicKey(recipient)[0],
+        mintStats: this.getMintStatsPublicKey(mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
       })
@@ -479,6 +547,8 @@ export class SuperstreamClientInternal implements SuperstreamClient {
         senderToken,
         recipientToken,
         escrowToken,
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -501,12 +571,14 @@ export class SuperstreamClientInternal implements SuperstreamClient {
         signer,
         senderToken,
         escrowToken,
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
   }
 
-  readonly topupNonPrepaidStream = async (stream: Stream, topupAmount: BN) => {
+  readonly topupNonPrepaidStream = async (stream: Stream, topupAmount: BN, isStrict = false) => {
     this.checkForValidWallet()
 
     const signer = this.getWalletPublicKey()
@@ -515,13 +587,15 @@ export class SuperstreamClientInternal implements SuperstreamClient {
     const escrowToken = await stream.mustGetAssociatedTokenAccount(streamKey)
 
     await this.program.methods
-      .topupNonPrepaid(stream.seed, stream.name, topupAmount)
+      .topupNonPrepaid(stream.seed, stream.name, topupAmount, isStrict)
       .accounts({
         stream: streamKey,
         mint: stream.mint,
         signer,
         signerToken,
         escrowToken,
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -543,6 +617,26 @@ export class SuperstreamClientInternal implements SuperstreamClient {
       .rpc()
   }
 
+  readonly acceptSenderChangeNonPrepaidStream = async (stream: Stream) => {
+    this.checkForValidWallet()
+
+    const newSender = this.getWalletPublicKey()
+    const [streamKey] = stream.getStreamPublicKey()
+
+    await this.program.methods
+      .acceptSenderChangeNonPrepaid(stream.seed, stream.name)
+      .accounts({
+        stream: streamKey,
+        newSender,
+        mint: stream.mint,
+        sender: stream.sender,
+        senderIndex: this.getSenderIndexPublicKey(stream.sender)[0],
+        newSenderIndex: this.getSenderIndexPublicKey(newSender)[0],
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+  }
+
   readonly withdrawFromStream = async (stream: Stream) => {
     this.checkForValidWallet()
 
@@ -557,8 +651,11 @@ export class SuperstreamClientInternal implements SuperstreamClient {
         stream: streamKey,
         mint: stream.mint,
         signer,
+        signerToken: null,
         recipientToken,
         escrowToken,
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -578,13 +675,43 @@ export class SuperstreamClientInternal implements SuperstreamClient {
         stream: streamKey,
         mint: stream.mint,
         signer,
+        signerToken: null,
         recipientToken,
         escrowToken,
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
   }
 
+  readonly acceptRecipientChangeStream = async (stream: Stream) => {
+    this.checkForValidWallet()
+
+    const newRecipient = this.getWalletPublicKey()
+    const recipientToken = await stream.getOrCreateRecipientAssociatedTokenAccount()
+    const [streamKey] = stream.getStreamPublicKey()
+    const escrowToken = await stream.mustGetAssociatedTokenAccount(streamKey)
+
+    await this.program.methods
+      .acceptRecipientChange(stream.seed, stream.name, stream.recipient)
+      .accounts({
+        stream: streamKey,
+        newRecipient,
+        mint: stream.mint,
+        currentRecipient: stream.recipient,
+        recipientToken,
+        escrowToken,
+        recipientIndex: this.getRecipientIndexPublicKey(stream.recipient)[0],
+        newRecipientIndex: this.getRecipientIndexPublicKey(newRecipient)[0],
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+  }
+
   readonly pauseNonPrepaidStream = async (stream: Stream) => {
     this.checkForValidWallet()
 
@@ -616,4 +743,44 @@ export class SuperstreamClientInternal implements SuperstreamClient {
       })
       .rpc()
   }
+
+  readonly closeStream = async (stream: Stream) => {
+    this.checkForValidWallet()
+
+    const signer = this.getWalletPublicKey()
+    const senderToken = await stream.getOrCreateSenderAssociatedTokenAccount()
+    const [streamKey] = stream.getStreamPublicKey()
+    const escrowToken = await stream.mustGetAssociatedTokenAccount(streamKey)
+
+    await this.program.methods
+      .close(stream.seed, stream.name)
+      .accounts({
+        stream: streamKey,
+        signer,
+        sender: stream.sender,
+        recipient: stream.recipient,
+        mint: stream.mint,
+        senderToken,
+        escrowToken,
+        senderIndex: this.getSenderIndexPublicKey(stream.sender)[0],
+        recipientIndex: this.getRecipientIndexPublicKey(stream.recipient)[0],
+        mintStats: this.getMintStatsPublicKey(stream.mint)[0],
+        globalStats: this.getGlobalStatsPublicKey()[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
+  }
+}
+
+/**
+ * Get the stream name seed used to derive the stream PDA. Names up to {@link MAX_STREAM_NAME_SEED_LENGTH} bytes are used
+ * as is and longer names are hashed using SHA-256.
+ *
+ * @param name The stream name
+ *
+ * @returns The stream name seed
+ */
+export function getStreamNameSeed(name: string): Buffer {
+  const nameBuffer = Buffer.from(name)
+  return nameBuffer.length <= MAX_STREAM_NAME_SEED_LENGTH ? nameBuffer : Buffer.from(utils.sha256.hash(name), 'hex')
 }
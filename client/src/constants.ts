@@ -8,15 +8,43 @@ export const SUPERSTREAM_PROGRAM_ID = '4WLNkJ6RKt54sv85iTgJPLgoaxfrxAasZWBxAPLUf
  * PDA account seed to create new stream PDA accounts.
  */
 export const STREAM_ACCOUNT_SEED = 'stream'
+/**
+ * PDA account seed of the mint registry.
+ */
+export const MINT_REGISTRY_ACCOUNT_SEED = 'mint_registry'
+/**
+ * PDA account seed of the allowed mint entries in the mint registry.
+ */
+export const ALLOWED_MINT_ACCOUNT_SEED = 'allowed_mint'
+/**
+ * PDA account seed of the stream index of a sender.
+ */
+export const SENDER_INDEX_ACCOUNT_SEED = 'sender_index'
+/**
+ * PDA account seed of the stream index of a recipient.
+ */
+export const RECIPIENT_INDEX_ACCOUNT_SEED = 'recipient_index'
+/**
+ * PDA account seed of the stats of a mint.
+ */
+export const MINT_STATS_ACCOUNT_SEED = 'mint_stats'
+/**
+ * PDA account seed of the global stats.
+ */
+export const GLOBAL_STATS_ACCOUNT_SEED = 'global_stats'
 
 /**
- * Minimum length of a stream name.
+ * Minimum length of a stream name (in unicode chars).
  */
 export const MIN_STREAM_NAME_LENGTH = 2
 /**
- * Maximum length of a stream name.
+ * Maximum length of a stream name (in unicode chars).
  */
 export const MAX_STREAM_NAME_LENGTH = 100
+/**
+ * Maximum length of a stream name (in bytes) used as is in the stream PDA seeds. Longer names are hashed using SHA-256.
+ */
+export const MAX_STREAM_NAME_SEED_LENGTH = 32
 
 /**
  * Deposit amount period (in seconds) for a non-prepaid stream. If a non-prepaid stream has unlimited lifetime or
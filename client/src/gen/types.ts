@@ -5,14 +5,172 @@ export type Superstream = {
     "Module for superstream cpi methods and other utilities."
   ],
   "instructions": [
+    {
+      "name": "createStream",
+      "docs": [
+        "Create a new stream. This is the same as `create_prepaid` and `create_non_prepaid`, but takes the options of",
+        "the stream as a single versioned struct, so new options don't change the arguments of the instruction.",
+        "",
+        "# Arguments",
+        "",
+        "* `derivation` - Mode used to derive the stream PDA address. Streams scoped to the sender can't be front-run by",
+        "someone else creating a stream with the same seed and name. See [`StreamDerivation`].",
+        "* `params` - Kind, schedule, permissions and other options of the stream. See [`CreateStreamParams`].",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is initialized by the program."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Stream sender wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. It might not have been initialized, in which case streams can be created for any",
+            "mint.",
+            ""
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.",
+            ""
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "derivation",
+          "type": {
+            "defined": "StreamDerivation"
+          }
+        },
+        {
+          "name": "params",
+          "type": {
+            "defined": "CreateStreamParams"
+          }
+        }
+      ]
+    },
     {
       "name": "createPrepaid",
       "docs": [
-        "Create a new prepaid stream.",
+        "Create a new prepaid stream. This is a thin wrapper around `create_stream` with [`StreamKind::Prepaid`] and the",
+        "defaults of the options added after it: the [`StreamDerivation::Global`] derivation, no permission expiry, no",
+        "arbiter and no recipient acceptance. Use `create_stream` for these options.",
         "",
         "# Arguments",
         "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "* `flow_interval` - Interval in which `flow_rate` tokens are streamed. Pass",
+        "[`FLOW_RATE_SCALE`](crate::state::FLOW_RATE_SCALE) to make `flow_rate` a fixed-point amount per second. See",
+        "[`FlowRate`](crate::state::FlowRate).",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
       ],
       "accounts": [
         {
@@ -55,6 +213,59 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. It might not have been initialized, in which case streams can be created for any",
+            "mint.",
+            ""
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.",
+            ""
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account. It is created if it doesn't exist."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -150,10 +361,16 @@ export type Superstream = {
     {
       "name": "createNonPrepaid",
       "docs": [
-        "Create a new non-prepaid stream.",
+        "Create a new non-prepaid stream. This is a thin wrapper around `create_stream` with [`StreamKind::NonPrepaid`]",
+        "and the defaults of the options added after it: the [`StreamDerivation::Global`] derivation, no permission",
+        "expiry, no arbiter, no recipient acceptance, no insolvency grace period and the whole deposit going to whoever",
+        "cancels an insolvent stream. Use `create_stream` for these options.",
         "",
         "# Arguments",
         "",
+        "* `flow_interval` - Interval in which `flow_rate` tokens are streamed. Pass",
+        "[`FLOW_RATE_SCALE`](crate::state::FLOW_RATE_SCALE) to make `flow_rate` a fixed-point amount per second. See",
+        "[`FlowRate`](crate::state::FlowRate).",
         "* `topup_amount` - Initial topup amount for the stream. The topup amount should be >= minimum deposit required.",
         "See [`DEPOSIT_AMOUNT_PERIOD_IN_SECS`](crate::state::DEPOSIT_AMOUNT_PERIOD_IN_SECS) for more information.",
         "",
@@ -200,6 +417,59 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. It might not have been initialized, in which case streams can be created for any",
+            "mint.",
+            ""
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.",
+            ""
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account. It is created if it doesn't exist."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -299,7 +569,8 @@ export type Superstream = {
     {
       "name": "cancel",
       "docs": [
-        "Cancel a stream.",
+        "Cancel a stream. If the stream is pending recipient acceptance, the sender can always cancel it to revoke it and",
+        "recover the full escrow, and the recipient can cancel it to refuse it.",
         "",
         "# Arguments",
         "",
@@ -319,8 +590,8 @@ export type Superstream = {
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent. After insolvency,",
-            "anyone can cancel."
+            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the",
+            "insolvency grace period. After that, anyone can cancel."
           ]
         },
         {
@@ -373,6 +644,22 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -398,13 +685,10 @@ export type Superstream = {
       ]
     },
     {
-      "name": "withdrawExcessTopupNonPrepaidEnded",
+      "name": "cancelV2",
       "docs": [
-        "Withdraw excess sender topup from a non-prepaid stream.",
-        "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "Same as [`cancel`](crate::superstream::cancel), but the seed, name and recipient are read from the stream",
+        "account instead of being passed as arguments."
       ],
       "accounts": [
         {
@@ -420,7 +704,8 @@ export type Superstream = {
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet."
+            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the",
+            "insolvency grace period. After that, anyone can cancel."
           ]
         },
         {
@@ -441,6 +726,14 @@ export type Superstream = {
             "SPL token mint account."
           ]
         },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
         {
           "name": "senderToken",
           "isMut": true,
@@ -449,6 +742,14 @@ export type Superstream = {
             "Associated token account of the sender."
           ]
         },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
         {
           "name": "escrowToken",
           "isMut": true,
@@ -457,6 +758,22 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -466,27 +783,16 @@ export type Superstream = {
           ]
         }
       ],
-      "args": [
-        {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "topupNonPrepaid",
+      "name": "withdrawExcessTopupNonPrepaidEnded",
       "docs": [
-        "Topup a non-prepaid stream.",
+        "Withdraw excess sender topup from a non-prepaid stream.",
         "",
         "# Arguments",
         "",
-        "* `topup_amount` - Topup amount for the stream. The topup amount should be <= maximum acceptable topup amount.",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "For more information on the arguments, see fields of the [`Stream`] struct."
       ],
       "accounts": [
         {
@@ -502,8 +808,17 @@ export type Superstream = {
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet. Anyone can topup a stream. But the refund when the stream gets cancelled will only go to the",
-            "stream sender."
+            "Signer wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
           ]
         },
         {
@@ -515,11 +830,11 @@ export type Superstream = {
           ]
         },
         {
-          "name": "signerToken",
+          "name": "senderToken",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token account of the signer."
+            "Associated token account of the sender."
           ]
         },
         {
@@ -530,6 +845,22 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -547,23 +878,15 @@ export type Superstream = {
         {
           "name": "name",
           "type": "string"
-        },
-        {
-          "name": "topupAmount",
-          "type": "u64"
         }
       ]
     },
     {
-      "name": "changeSenderNonPrepaid",
+      "name": "withdrawExcessTopupNonPrepaidEndedV2",
       "docs": [
-        "Change sender of a non-prepaid stream.",
-        "",
-        "# Arguments",
-        "",
-        "* `new_sender` - The new sender",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "Same as",
+        "[`withdraw_excess_topup_non_prepaid_ended`](crate::superstream::withdraw_excess_topup_non_prepaid_ended), but",
+        "the seed and name are read from the stream account instead of being passed as arguments."
       ],
       "accounts": [
         {
@@ -574,10 +897,23 @@ export type Superstream = {
             "Stream PDA account."
           ]
         },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet."
+          ]
+        },
         {
           "name": "sender",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
         },
         {
           "name": "mint",
@@ -586,31 +922,64 @@ export type Superstream = {
           "docs": [
             "SPL token mint account."
           ]
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "seed",
-          "type": "u64"
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
         },
         {
-          "name": "name",
-          "type": "string"
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
         },
         {
-          "name": "newSender",
-          "type": "publicKey"
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "withdraw",
+      "name": "topupNonPrepaid",
       "docs": [
-        "Withdraw recipient funds from a stream.",
+        "Topup a non-prepaid stream. Returns the topup amount accepted by the stream, which is the only amount",
+        "transferred to the escrow account.",
         "",
         "# Arguments",
         "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "* `topup_amount` - Topup amount for the stream. If it is more than the maximum acceptable topup amount, only the",
+        "maximum acceptable topup amount is accepted.",
+        "* `is_strict` - If true, the topup fails instead if the topup amount is more than the maximum acceptable topup",
+        "amount.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
       ],
       "accounts": [
         {
@@ -626,10 +995,8 @@ export type Superstream = {
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
-            "signer. Recipient is passed as an argument, based on which the stream PDA is accessed, so if a malicious user",
-            "tries to send themselves as a recipient, but a different stream account, the constraint for the stream account",
-            "will fail."
+            "Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But",
+            "the refund when the stream gets cancelled will only go to the stream sender."
           ]
         },
         {
@@ -641,11 +1008,11 @@ export type Superstream = {
           ]
         },
         {
-          "name": "recipientToken",
+          "name": "signerToken",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token account of the recipient."
+            "Associated token account of the signer."
           ]
         },
         {
@@ -656,6 +1023,22 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -675,21 +1058,21 @@ export type Superstream = {
           "type": "string"
         },
         {
-          "name": "recipient",
-          "type": "publicKey"
+          "name": "topupAmount",
+          "type": "u64"
+        },
+        {
+          "name": "isStrict",
+          "type": "bool"
         }
-      ]
+      ],
+      "returns": "u64"
     },
     {
-      "name": "withdrawAndChangeRecipient",
+      "name": "topupNonPrepaidV2",
       "docs": [
-        "Withdraw recipient funds from a stream and change recipient of a stream.",
-        "",
-        "# Arguments",
-        "",
-        "* `new_recipient` - The new recipient",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "Same as [`topup_non_prepaid`](crate::superstream::topup_non_prepaid), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
       ],
       "accounts": [
         {
@@ -705,10 +1088,8 @@ export type Superstream = {
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
-            "signer. Recipient is passed as an argument, based on which the stream PDA is accessed, so if a malicious user",
-            "tries to send themselves as a recipient, but a different stream account, the constraint for the stream account",
-            "will fail."
+            "Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But",
+            "the refund when the stream gets cancelled will only go to the stream sender."
           ]
         },
         {
@@ -720,11 +1101,11 @@ export type Superstream = {
           ]
         },
         {
-          "name": "recipientToken",
+          "name": "signerToken",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token account of the recipient."
+            "Associated token account of the signer."
           ]
         },
         {
@@ -735,6 +1116,22 @@ export type Superstream = {
             "Associated token escrow account holding the funds for this stream."
           ]
         },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -746,31 +1143,26 @@ export type Superstream = {
       ],
       "args": [
         {
-          "name": "seed",
+          "name": "topupAmount",
           "type": "u64"
         },
         {
-          "name": "name",
-          "type": "string"
-        },
-        {
-          "name": "recipient",
-          "type": "publicKey"
-        },
-        {
-          "name": "newRecipient",
-          "type": "publicKey"
+          "name": "isStrict",
+          "type": "bool"
         }
-      ]
+      ],
+      "returns": "u64"
     },
     {
-      "name": "pauseNonPrepaid",
+      "name": "changeSenderNonPrepaid",
       "docs": [
-        "Pause a non-prepaid stream.",
+        "Propose to change sender of a non-prepaid stream. The sender changes only when the new sender accepts the change.",
         "",
         "# Arguments",
         "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "* `new_sender` - The new sender",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
       ],
       "accounts": [
         {
@@ -782,12 +1174,9 @@ export type Superstream = {
           ]
         },
         {
-          "name": "signer",
+          "name": "sender",
           "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient."
-          ]
+          "isSigner": true
         },
         {
           "name": "mint",
@@ -806,17 +1195,18 @@ export type Superstream = {
         {
           "name": "name",
           "type": "string"
+        },
+        {
+          "name": "newSender",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "resumeNonPrepaid",
+      "name": "changeSenderNonPrepaidV2",
       "docs": [
-        "Resume a non-prepaid stream.",
-        "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "Same as [`change_sender_non_prepaid`](crate::superstream::change_sender_non_prepaid), but the seed and name are",
+        "read from the stream account instead of being passed as arguments."
       ],
       "accounts": [
         {
@@ -828,13 +1218,9 @@ export type Superstream = {
           ]
         },
         {
-          "name": "signer",
+          "name": "sender",
           "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if",
-            "the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender)."
-          ]
+          "isSigner": true
         },
         {
           "name": "mint",
@@ -847,590 +1233,8497 @@ export type Superstream = {
       ],
       "args": [
         {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
+          "name": "newSender",
+          "type": "publicKey"
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "stream",
+      "name": "acceptSenderChangeNonPrepaid",
       "docs": [
-        "A payment stream with support for SPL tokens, prepaid and limited upfront payment, unlimited lifetime, cliffs and",
-        "cancellations.",
+        "Accept the proposed sender change of a non-prepaid stream. The signer needs to be the proposed new sender.",
         "",
-        "Possible states of a stream:",
-        "- Not started",
-        "- Scheduled",
-        "- Cancelled before start",
-        "- Started but not stopped",
-        "- Streaming",
-        "- Paused",
-        "- Stopped",
-        "- Cancelled after start",
-        "- Ended"
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
       ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "isPrepaid",
-            "docs": [
-              "If true, the stream is prepaid - all the required amount needs to be deposited on creation. Prepaid streams",
-              "cannot have unlimited lifetime."
-            ],
-            "type": "bool"
-          },
-          {
-            "name": "mint",
-            "docs": [
-              "SPL token mint address."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "sender",
-            "docs": [
-              "Sender address."
-            ],
-            "type": "publicKey"
-          },
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newSender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new sender wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream sender account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current sender.",
+            ""
+          ]
+        },
+        {
+          "name": "newSenderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "acceptSenderChangeNonPrepaidV2",
+      "docs": [
+        "Same as [`accept_sender_change_non_prepaid`](crate::superstream::accept_sender_change_non_prepaid), but the seed",
+        "and name are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newSender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new sender wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream sender account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current sender.",
+            ""
+          ]
+        },
+        {
+          "name": "newSenderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelSenderChangeNonPrepaid",
+      "docs": [
+        "Cancel the proposed sender change of a non-prepaid stream. The signer needs to be the current sender.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "cancelSenderChangeNonPrepaidV2",
+      "docs": [
+        "Same as [`cancel_sender_change_non_prepaid`](crate::superstream::cancel_sender_change_non_prepaid), but the seed",
+        "and name are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdraw",
+      "docs": [
+        "Withdraw recipient funds from a stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "withdrawV2",
+      "docs": [
+        "Same as [`withdraw`](crate::superstream::withdraw), but the seed, name and recipient are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawAndChangeRecipient",
+      "docs": [
+        "Withdraw recipient funds from a stream and propose to change recipient of a stream. The recipient changes only",
+        "when the new recipient accepts the change. Fails if the stream is cancelled, including when the withdrawal",
+        "cancels an insolvent stream.",
+        "",
+        "# Arguments",
+        "",
+        "* `new_recipient` - The new recipient",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "newRecipient",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "withdrawAndChangeRecipientV2",
+      "docs": [
+        "Same as [`withdraw_and_change_recipient`](crate::superstream::withdraw_and_change_recipient), but the seed, name",
+        "and recipient are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newRecipient",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptRecipientChange",
+      "docs": [
+        "Accept the proposed recipient change of a stream. The signer needs to be the proposed new recipient. Recipient",
+        "funds owed till now are withdrawn to the current recipient before the change. If the withdrawal cancels an",
+        "insolvent stream, the recipient is not changed and the reward for cancelling it goes to the current recipient.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newRecipient",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new recipient wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "currentRecipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the current recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "newRecipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptRecipientChangeV2",
+      "docs": [
+        "Same as [`accept_recipient_change`](crate::superstream::accept_recipient_change), but the seed, name and",
+        "recipient are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newRecipient",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new recipient wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "currentRecipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the current recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "newRecipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelRecipientChange",
+      "docs": [
+        "Cancel the proposed recipient change of a stream. The signer needs to be the current recipient.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "cancelRecipientChangeV2",
+      "docs": [
+        "Same as [`cancel_recipient_change`](crate::superstream::cancel_recipient_change), but the seed and name are read",
+        "from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "pauseNonPrepaid",
+      "docs": [
+        "Pause a non-prepaid stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "pauseNonPrepaidV2",
+      "docs": [
+        "Same as [`pause_non_prepaid`](crate::superstream::pause_non_prepaid), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resumeNonPrepaid",
+      "docs": [
+        "Resume a non-prepaid stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if",
+            "the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender)."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "resumeNonPrepaidV2",
+      "docs": [
+        "Same as [`resume_non_prepaid`](crate::superstream::resume_non_prepaid), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if",
+            "the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender)."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "accept",
+      "docs": [
+        "Accept a stream pending recipient acceptance. The flow starts at the time of acceptance if the stream was",
+        "scheduled to start earlier.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "acceptV2",
+      "docs": [
+        "Same as [`accept`](crate::superstream::accept), but the seed and name are read from the stream account instead",
+        "of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "proposeSettlement",
+      "docs": [
+        "Propose a settlement to terminate a stream. Either the sender or the recipient can propose a settlement, and it",
+        "replaces any previous proposal.",
+        "",
+        "# Arguments",
+        "",
+        "* `settlement_amount` - Total amount owed to the recipient on termination, including the amount already",
+        "withdrawn. Should be >= total withdrawn amount and <= total amount paid by the sender.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "proposeSettlementV2",
+      "docs": [
+        "Same as [`propose_settlement`](crate::superstream::propose_settlement), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancelSettlementProposal",
+      "docs": [
+        "Cancel the proposed settlement of a stream. The proposer can revoke it and the other party can reject it.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "cancelSettlementProposalV2",
+      "docs": [
+        "Same as [`cancel_settlement_proposal`](crate::superstream::cancel_settlement_proposal), but the seed and name",
+        "are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "terminateWithSettlement",
+      "docs": [
+        "Accept the proposed settlement of a stream and terminate it. The escrow is split according to the settlement and",
+        "the stream is marked cancelled.",
+        "",
+        "# Arguments",
+        "",
+        "* `settlement_amount` - The proposed settlement amount. Should be equal to the amount stored on the stream.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be the party which did not propose the settlement."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "terminateWithSettlementV2",
+      "docs": [
+        "Same as [`terminate_with_settlement`](crate::superstream::terminate_with_settlement), but the seed, name and",
+        "recipient are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be the party which did not propose the settlement."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "freeze",
+      "docs": [
+        "Freeze a stream in case of a dispute. Only the arbiter of the stream can freeze it. Withdrawals, cancellations,",
+        "settlements and refunds are halted till the stream is unfrozen or resolved.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "freezeV2",
+      "docs": [
+        "Same as [`freeze`](crate::superstream::freeze), but the seed and name are read from the stream account instead",
+        "of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "unfreeze",
+      "docs": [
+        "Unfreeze a frozen stream. Only the arbiter of the stream can unfreeze it.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "unfreezeV2",
+      "docs": [
+        "Same as [`unfreeze`](crate::superstream::unfreeze), but the seed and name are read from the stream account",
+        "instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resolve",
+      "docs": [
+        "Resolve the dispute of a frozen stream. Only the arbiter of the stream can resolve it. The escrow is split",
+        "according to the resolution and the stream is marked cancelled.",
+        "",
+        "# Arguments",
+        "",
+        "* `recipient_amount` - Total amount owed to the recipient, including the amount already withdrawn. Should be >=",
+        "total withdrawn amount and <= total amount paid by the sender. The rest is returned to the sender.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "recipientAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "resolveV2",
+      "docs": [
+        "Same as [`resolve`](crate::superstream::resolve), but the seed, name and recipient are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "recipientAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "close",
+      "docs": [
+        "Close a stream which has stopped and has nothing left to withdraw or refund. The signer needs to be the sender",
+        "or the recipient. The stream is removed from the stream indexes, and the rent of the stream and escrow accounts",
+        "is returned to the sender. Any tokens sent to the escrow account directly are transferred to the sender.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is closed by the program and the rent is returned to the sender."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the recipient can close the stream."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream. This is closed by the program and the rent",
+            "is returned to the sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "closeV2",
+      "docs": [
+        "Same as [`close`](crate::superstream::close), but the seed and name are read from the stream account instead of",
+        "being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is closed by the program and the rent is returned to the sender."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the recipient can close the stream."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream. This is closed by the program and the rent",
+            "is returned to the sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateStream",
+      "docs": [
+        "Migrate a stream in the legacy account layout to the newest layout. The stream account is reallocated and the",
+        "payer pays for the extra rent. Anyone can migrate a stream as it doesn't change the state of the stream.",
+        "",
+        "Streams in the legacy layout can be used with all the instructions, but the features introduced with the newest",
+        "layout, e.g. settlements or recipient changes, need the stream to be migrated first.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Payer of the extra rent of the stream account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "migrateStreamV2",
+      "docs": [
+        "Same as [`migrate_stream`](crate::superstream::migrate_stream), but the seed and name are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Payer of the extra rent of the stream account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "convertStreamToZeroCopy",
+      "docs": [
+        "Convert a stream to the zero-copy layout. The signer needs to be the sender or the recipient and pays the rent of",
+        "the extra space. Zero-copy streams can only be used with `withdraw_zero_copy`, `topup_non_prepaid_zero_copy` and",
+        "`cancel_zero_copy` and need to be converted back for everything else. See [`ZeroCopyStream`]."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account in any of the [`VersionedStream`] layouts."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient. They pay the rent of the extra space."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "convertStreamFromZeroCopy",
+      "docs": [
+        "Convert a zero-copy stream back to the newest stream layout. The signer needs to be the sender or the",
+        "recipient."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Zero-copy stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawZeroCopy",
+      "docs": [
+        "Withdraw recipient funds from a zero-copy stream. See [`withdraw`](crate::superstream::withdraw)."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Zero-copy stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The withdrawn amount always goes to the recipient of the",
+            "stream."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "topupNonPrepaidZeroCopy",
+      "docs": [
+        "Topup a non-prepaid zero-copy stream. See [`topup_non_prepaid`](crate::superstream::topup_non_prepaid)."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Zero-copy stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But",
+            "the refund when the stream gets cancelled will only go to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "topupAmount",
+          "type": "u64"
+        },
+        {
+          "name": "isStrict",
+          "type": "bool"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "cancelZeroCopy",
+      "docs": [
+        "Cancel a zero-copy stream. See [`cancel`](crate::superstream::cancel)."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Zero-copy stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the",
+            "insolvency grace period. After that, anyone can cancel."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeMintRegistry",
+      "docs": [
+        "Initialize the mint registry. Only the upgrade authority of the program can initialize the registry and they",
+        "become the authority of the registry.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`MintRegistry`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. This is initialized by the program."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Upgrade authority of the program. They become the authority of the mint registry."
+          ]
+        },
+        {
+          "name": "program",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Superstream program."
+          ]
+        },
+        {
+          "name": "programData",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Program data account of the Superstream program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "isEnabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setMintRegistryEnabled",
+      "docs": [
+        "Enable or disable the mint registry. When disabled, streams can be created for any mint.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`MintRegistry`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Authority of the mint registry."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "isEnabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "changeMintRegistryAuthority",
+      "docs": [
+        "Change the authority of the mint registry.",
+        "",
+        "# Arguments",
+        "",
+        "* `new_authority` - The new authority"
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Authority of the mint registry."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "addAllowedMint",
+      "docs": [
+        "Add a mint to the mint registry."
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account."
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. This is initialized by the program."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Authority of the mint registry."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeAllowedMint",
+      "docs": [
+        "Remove a mint from the mint registry. Existing streams of the mint are not affected."
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account."
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. This is closed by the program and the rent is returned to the authority."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Authority of the mint registry."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "getWithdrawable",
+      "docs": [
+        "Get the withdrawable amount of a stream at the current time. The result is returned through the return data of",
+        "the transaction. Programs calling this through CPI can decode it using `get()` on the returned value.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawableView"
+      }
+    },
+    {
+      "name": "getWithdrawableV2",
+      "docs": [
+        "Same as [`get_withdrawable`](crate::superstream::get_withdrawable), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "WithdrawableView"
+      }
+    },
+    {
+      "name": "getSolvency",
+      "docs": [
+        "Get the solvency of a stream at the current time. The result is returned through the return data of the",
+        "transaction. Programs calling this through CPI can decode it using `get()` on the returned value.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ],
+      "returns": {
+        "defined": "SolvencyView"
+      }
+    },
+    {
+      "name": "getSolvencyV2",
+      "docs": [
+        "Same as [`get_solvency`](crate::superstream::get_solvency), but the seed and name are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "SolvencyView"
+      }
+    },
+    {
+      "name": "getMaxTopup",
+      "docs": [
+        "Get the maximum acceptable topup amount of a stream at the current time. The result is returned through the",
+        "return data of the transaction. Programs calling this through CPI can decode it using `get()` on the returned",
+        "value.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ],
+      "returns": {
+        "defined": "MaxTopupView"
+      }
+    },
+    {
+      "name": "getMaxTopupV2",
+      "docs": [
+        "Same as [`get_max_topup`](crate::superstream::get_max_topup), but the seed and name are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "MaxTopupView"
+      }
+    }
+  ],
+  "accounts": [
+    {
+      "name": "stream",
+      "docs": [
+        "A payment stream with support for SPL tokens, prepaid and limited upfront payment, unlimited lifetime, cliffs and",
+        "cancellations.",
+        "",
+        "Possible states of a stream:",
+        "- Not started",
+        "- Pending recipient acceptance",
+        "- Scheduled",
+        "- Cancelled before start",
+        "- Started but not stopped",
+        "- Streaming",
+        "- Paused",
+        "- Insolvent",
+        "- Stopped",
+        "- Cancelled after start",
+        "- Ended"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "isPrepaid",
+            "docs": [
+              "If true, the stream is prepaid - all the required amount needs to be deposited on creation. Prepaid streams",
+              "cannot have unlimited lifetime."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "mint",
+            "docs": [
+              "SPL token mint address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "sender",
+            "docs": [
+              "Sender address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "recipient",
+            "docs": [
+              "Recipient address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "createdAt",
+            "docs": [
+              "Time at which the stream was created."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "startsAt",
+            "docs": [
+              "Start time of the stream.",
+              "",
+              "INVARIANT: >= created_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "endsAt",
+            "docs": [
+              "End time of the stream. If the stream is unbounded, this can be 0 to indicate no end time.",
+              "",
+              "INVARIANT: prepaid: >= starts_at",
+              "INVARIANT: unbounded: == 0 || >= starts_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "initialAmount",
+            "docs": [
+              "Amount available to the recipient once stream starts."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flowInterval",
+            "docs": [
+              "Flow interval is the interval in which flow payments are released. If == FLOW_RATE_SCALE, the flow rate is a",
+              "fixed-point amount per second. See [`FlowRate`]."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flowRate",
+            "docs": [
+              "Flow rate is the number of tokens to stream per interval."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isCancelled",
+            "docs": [
+              "If true, the stream has been cancelled."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isCancelledBeforeStart",
+            "docs": [
+              "If true, the stream has been cancelled before start.",
+              "",
+              "INVARIANT: !is_cancelled => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isCancelledBySender",
+            "docs": [
+              "If true, the stream has been cancelled by the sender.",
+              "",
+              "INVARIANT: !is_cancelled || !sender_can_cancel => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "cancelledAt",
+            "docs": [
+              "Time at which the stream was cancelled. If it is > 0, it means the stream has been cancelled and any funds in",
+              "the escrow account not available to be withdrawn by the recipient have been retrieved.",
+              "",
+              "INVARIANT: cancelled_at > 0 iff is_cancelled == true"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanCancel",
+            "docs": [
+              "True if a solvent stream can be cancelled by the sender."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanCancelAt",
+            "docs": [
+              "Time at which the sender is allowed to cancel a solvent stream. This can be in the future."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSender",
+            "docs": [
+              "True if the sender can change the sender of the stream who will do the upcoming topups.",
+              "",
+              "INVARIANT: prepaid: false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanChangeSenderAt",
+            "docs": [
+              "Time at which the sender is allowed to change the sender.",
+              "",
+              "INVARIANT: prepaid: == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isPaused",
+            "docs": [
+              "If true, the stream is paused.",
+              "",
+              "INVARIANT: prepaid: == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isPausedBySender",
+            "docs": [
+              "If true, the stream is paused by sender.",
+              "",
+              "INVARIANT: prepaid: == false",
+              "INVARIANT: runtime: unbounded: !is_paused || !sender_can_pause => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanPause",
+            "docs": [
+              "True if a stream can be paused by the sender.",
+              "",
+              "INVARIANT: prepaid: false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanPauseAt",
+            "docs": [
+              "Time at which the sender is allowed to pause a stream.",
+              "",
+              "INVARIANT: prepaid: == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySender",
+            "docs": [
+              "True if a stream can be resumed by the recipient if it was paused by the sender.",
+              "",
+              "INVARIANT: prepaid: false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderAt",
+            "docs": [
+              "Time at which the recipient is allowed to resume a stream which was paused by the sender.",
+              "",
+              "INVARIANT: prepaid: == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipient",
+            "docs": [
+              "True if anyone can withdraw on behalf of the recipient. The amount will go in recipients' account."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientAt",
+            "docs": [
+              "Time at which anyone can withdraw on behalf of the recipient."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastResumedAt",
+            "docs": [
+              "Time at which the stream was last resumed.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: (== 0 || >= starts_at) && (ends_at == 0 || < ends_at)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "accumulatedActiveTime",
+            "docs": [
+              "Total accumulated active (!is_paused) time since starts_at. This does not include (current_time -",
+              "last_resumed_at) time if the stream is not paused.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: == 0 || (current_time > starts_at && == current_time - starts_at - total_paused_time)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalWithdrawnAmount",
+            "docs": [
+              "Total amount withdrawn by the recipient.",
+              "",
+              "INVARIANT: runtime: prepaid: <= amount_owed && <= prepaid_amount_needed",
+              "INVARIANT: runtime: unbounded: <= amount_owed && <= total_topup_amount"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAt",
+            "docs": [
+              "Last time at which recipient withdrew any amount."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAmount",
+            "docs": [
+              "Last amount which recipient withdrew."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalTopupAmount",
+            "docs": [
+              "Total topup amount added for the stream.",
+              "",
+              "INVARIANT: prepaid: == total_prepaid_amount",
+              "INVARIANT: unbounded: >= initial_amount + streaming_amount_owed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAt",
+            "docs": [
+              "Last time at which sender topped up the stream."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAmount",
+            "docs": [
+              "Last topup amount."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "depositNeeded",
+            "docs": [
+              "Total deposit amount needed for the non-prepaid stream. These are needed in case the sender does not topup the",
+              "stream in time and the amount owed becomes > total topup amount. When that happens and the insolvency grace",
+              "period is over, anyone can cancel the stream. The deposit amount will be split between the recipient and",
+              "whoever finds the insolvency and cancels the stream as a reward.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: == DEPOSIT_AMOUNT_PERIOD_IN_SECS of streaming payments"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "version",
+            "docs": [
+              "Layout version of the stream account. It is at the same offset in all the layouts, so that the layout of an",
+              "account can be found before deserializing it. See [`VersionedStream`].",
+              "",
+              "INVARIANT: == LEGACY_STREAM_LAYOUT_VERSION || == STREAM_LAYOUT_VERSION"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "insolvencyGracePeriod",
+            "docs": [
+              "Active (!is_paused) streaming time after the stream becomes insolvent during which only the sender can cure the",
+              "insolvency by topping up. The flow keeps accruing as debt which is covered by the deposit. Only after this",
+              "period, the stream can be cancelled by anyone and the deposit is given as a reward.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: <= DEPOSIT_AMOUNT_PERIOD_IN_SECS"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipientDepositShareBps",
+            "docs": [
+              "Share of the deposit (in basis points) given to the recipient when an insolvent stream is cancelled. The rest",
+              "of the deposit is given as a reward to whoever cancels the stream.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: <= BPS_DENOMINATOR"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "settlementAmount",
+            "docs": [
+              "Total amount owed to the recipient proposed by either the sender or the recipient to terminate the stream. If",
+              "the other party accepts the settlement, the stream is cancelled and the escrow is split accordingly.",
+              "",
+              "INVARIANT: !is_settlement_proposed && !is_settled => == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isSettlementProposed",
+            "docs": [
+              "If true, a settlement has been proposed and is waiting to be accepted.",
+              "",
+              "INVARIANT: is_cancelled => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isSettlementProposedBySender",
+            "docs": [
+              "If true, the settlement has been proposed by the sender.",
+              "",
+              "INVARIANT: !is_settlement_proposed => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isSettled",
+            "docs": [
+              "If true, the stream has been terminated with a settlement accepted by both parties.",
+              "",
+              "INVARIANT: is_settled => is_cancelled && total_topup_amount == settlement_amount"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "arbiter",
+            "docs": [
+              "Arbiter address. The arbiter is a neutral third party who can freeze the stream in case of a dispute and later",
+              "resolve it by deciding how the escrow is split. If it is the default public key, the stream has no arbiter."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "isFrozen",
+            "docs": [
+              "If true, the stream has been frozen by the arbiter. Withdrawals, cancellations, settlements and refunds are",
+              "halted until the arbiter unfreezes or resolves the stream. The flow is not affected.",
+              "",
+              "INVARIANT: arbiter == Pubkey::default() || is_cancelled => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isPendingAcceptance",
+            "docs": [
+              "If true, the stream is waiting for the recipient to accept it and the flow has not started. When the recipient",
+              "accepts, the schedule is shifted so that the stream effectively starts at the time of acceptance if it was",
+              "scheduled to start earlier. Before that, the sender can cancel the stream and recover the full escrow."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "pendingSender",
+            "docs": [
+              "Proposed new sender address. The sender changes only when the new sender accepts. If it is the default public",
+              "key, no sender change has been proposed.",
+              "",
+              "INVARIANT: prepaid: == Pubkey::default()"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingRecipient",
+            "docs": [
+              "Proposed new recipient address. The recipient changes only when the new recipient accepts. If it is the default",
+              "public key, no recipient change has been proposed."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "senderCanCancelExpiresAt",
+            "docs": [
+              "Time after which the sender is no longer allowed to cancel a solvent stream. If it is 0, the permission never",
+              "expires.",
+              "",
+              "INVARIANT: == 0 || > sender_can_cancel_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSenderExpiresAt",
+            "docs": [
+              "Time after which the sender is no longer allowed to change the sender. If it is 0, the permission never expires.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: == 0 || > sender_can_change_sender_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanPauseExpiresAt",
+            "docs": [
+              "Time after which the sender is no longer allowed to pause a stream. If it is 0, the permission never expires.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: == 0 || > sender_can_pause_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderExpiresAt",
+            "docs": [
+              "Time after which the recipient is no longer allowed to resume a stream which was paused by the sender. If it is",
+              "0, the permission never expires.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: == 0 || > recipient_can_resume_pause_by_sender_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientExpiresAt",
+            "docs": [
+              "Time after which anyone is no longer allowed to withdraw on behalf of the recipient. If it is 0, the permission",
+              "never expires.",
+              "",
+              "INVARIANT: == 0 || > anyone_can_withdraw_for_recipient_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "derivation",
+            "docs": [
+              "Mode used to derive the stream PDA address."
+            ],
+            "type": {
+              "defined": "StreamDerivation"
+            }
+          },
+          {
+            "name": "derivationSender",
+            "docs": [
+              "Sender at the time of creation if it is part of the stream PDA seeds. It doesn't change when the sender is",
+              "changed. If it is the default public key, the sender is not part of the seeds.",
+              "",
+              "INVARIANT: !derivation.includes_sender() => == Pubkey::default()"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "derivationRecipient",
+            "docs": [
+              "Recipient at the time of creation if it is part of the stream PDA seeds. It doesn't change when the recipient",
+              "is changed. If it is the default public key, the recipient is not part of the seeds.",
+              "",
+              "INVARIANT: !derivation.includes_recipient() => == Pubkey::default()"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "isCountedInMintStats",
+            "docs": [
+              "True if the stream is counted in the [`MintStats`] of its mint. Streams created before mint stats were",
+              "introduced are not counted."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "seed",
+            "docs": [
+              "Seed of the stream PDA. It's upto the client how they choose the seed. Each tuple (seed, mint, name) corresponds",
+              "to a unique stream for a particular derivation mode and derivation keys."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "name",
+            "docs": [
+              "Name of the stream. Should be unique for a particular set of (seed, mint). Names longer than 32 bytes are hashed",
+              "to derive the stream PDA address. See [`get_stream_name_seed`](crate::get_stream_name_seed).",
+              "",
+              "INVARIANT: Length <= 100 unicode chars or 400 bytes"
+            ],
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "zeroCopyStream",
+      "docs": [
+        "A stream in the zero-copy layout. Zero-copy streams are accessed in place through an `AccountLoader` instead of",
+        "being deserialized and serialized again by every instruction. The name is stored in a fixed-size buffer, the booleans",
+        "are packed in [`Self::flags`] and the fields are ordered so that they are aligned without any padding.",
+        "",
+        "Streams are converted to and from this layout by the `convert_stream_to_zero_copy` and",
+        "`convert_stream_from_zero_copy` instructions. Zero-copy streams can only be used with `withdraw_zero_copy`,",
+        "`topup_non_prepaid_zero_copy` and `cancel_zero_copy`. They need to be converted back for everything else. See",
+        "[`Stream`] for the documentation of the fields."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "sender",
+            "type": "publicKey"
+          },
+          {
+            "name": "recipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "arbiter",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingSender",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingRecipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "derivationSender",
+            "type": "publicKey"
+          },
+          {
+            "name": "derivationRecipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "createdAt",
+            "type": "u64"
+          },
+          {
+            "name": "startsAt",
+            "type": "u64"
+          },
+          {
+            "name": "endsAt",
+            "type": "u64"
+          },
+          {
+            "name": "initialAmount",
+            "type": "u64"
+          },
+          {
+            "name": "flowInterval",
+            "type": "u64"
+          },
+          {
+            "name": "flowRate",
+            "type": "u64"
+          },
+          {
+            "name": "cancelledAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanCancelAt",
+            "type": "u64"
+          },
           {
-            "name": "recipient",
+            "name": "senderCanChangeSenderAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanPauseAt",
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderAt",
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientAt",
+            "type": "u64"
+          },
+          {
+            "name": "lastResumedAt",
+            "type": "u64"
+          },
+          {
+            "name": "accumulatedActiveTime",
+            "type": "u64"
+          },
+          {
+            "name": "totalWithdrawnAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAt",
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAmount",
+            "type": "u64"
+          },
+          {
+            "name": "totalTopupAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAt",
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAmount",
+            "type": "u64"
+          },
+          {
+            "name": "depositNeeded",
+            "type": "u64"
+          },
+          {
+            "name": "insolvencyGracePeriod",
+            "type": "u64"
+          },
+          {
+            "name": "settlementAmount",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanCancelExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSenderExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanPauseExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "seed",
+            "type": "u64"
+          },
+          {
+            "name": "flags",
+            "docs": [
+              "Booleans of the stream packed as bit flags. See the `ZeroCopyStream::IS_*` and `ZeroCopyStream::*_CAN_*`",
+              "constants."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "recipientDepositShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "nameLength",
+            "docs": [
+              "Length of the name in bytes.",
+              "",
+              "INVARIANT: <= MAX_STREAM_NAME_BYTES"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "derivation",
+            "docs": [
+              "[`StreamDerivation`] as a `u8`."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "name",
+            "docs": [
+              "UTF-8 bytes of the name followed by zeroes."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                400
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintRegistry",
+      "docs": [
+        "Registry of SPL token mints for which streams can be created. There is only 1 registry per deployment of the",
+        "program.",
+        "",
+        "If the registry has not been initialized or is disabled, streams can be created for any mint, i.e. the deployment is",
+        "permissionless. If it is enabled, streams can only be created for mints which have an [`AllowedMint`] account."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "docs": [
+              "Protocol authority address. Only the authority can manage the registry and the allowed mints."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "isEnabled",
+            "docs": [
+              "If true, the registry is enabled and streams can only be created for allowed mints."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "allowedMint",
+      "docs": [
+        "An SPL token mint for which streams can be created when the [`MintRegistry`] is enabled."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "docs": [
+              "SPL token mint address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "addedAt",
+            "docs": [
+              "Time at which the mint was added to the registry."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "streamIndex",
+      "docs": [
+        "Index of the streams of a user, either as the sender or as the recipient, so that wallets can list all the streams",
+        "of a user with a single account fetch. There are 2 index accounts per user - one for the streams sent by the user and",
+        "one for the streams received by the user.",
+        "",
+        "The index is updated when streams are created or closed and when their sender or recipient changes. Streams created",
+        "before the indexes were introduced are not indexed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "docs": [
+              "Address of the user whose streams are indexed."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "streams",
+            "docs": [
+              "Stream PDA addresses. The order is not maintained when streams are removed."
+            ],
+            "type": {
+              "vec": "publicKey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintStats",
+      "docs": [
+        "Figures of all the streams of an SPL token mint, so that dashboards can read protocol-wide figures with a single",
+        "account fetch instead of scanning all the stream accounts. There is 1 stats account per mint and it is created along",
+        "with the first stream of the mint.",
+        "",
+        "The stats are updated by every instruction which moves funds in or out of an escrow account or stops a stream.",
+        "Streams created before mint stats were introduced are not counted. Amounts never decrease and saturate instead of",
+        "overflowing, so that they can never block an instruction.",
+        "",
+        "INVARIANT: total_deposited_amount ==",
+        "total_withdrawn_amount + total_refunded_amount + total_rewarded_amount",
+        "+ sum of escrow amounts of the counted streams (excluding tokens sent to the escrows directly)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "docs": [
+              "SPL token mint address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "streamCount",
+            "docs": [
+              "Total number of streams created."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "activeStreamCount",
+            "docs": [
+              "Number of streams which have been neither cancelled nor closed. Streams which have ended are active till they",
+              "are closed."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "insolvencyCancellationCount",
+            "docs": [
+              "Number of streams cancelled because they were insolvent and their insolvency grace period was over."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalDepositedAmount",
+            "docs": [
+              "Total amount transferred to the escrow accounts by senders - prepaid amounts and topups."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "totalWithdrawnAmount",
             "docs": [
-              "Recipient address."
+              "Total amount transferred from the escrow accounts to recipients."
             ],
-            "type": "publicKey"
+            "type": "u128"
           },
           {
-            "name": "createdAt",
+            "name": "totalRefundedAmount",
             "docs": [
-              "Time at which the stream was created."
+              "Total amount returned from the escrow accounts to senders."
             ],
-            "type": "u64"
+            "type": "u128"
           },
           {
-            "name": "startsAt",
+            "name": "totalRewardedAmount",
             "docs": [
-              "Start time of the stream.",
-              "",
-              "INVARIANT: >= created_at"
+              "Total amount of deposits given as rewards to the accounts which cancelled insolvent streams."
             ],
-            "type": "u64"
+            "type": "u128"
           },
           {
-            "name": "endsAt",
+            "name": "reserved",
             "docs": [
-              "End time of the stream. If the stream is unbounded, this can be 0 to indicate no end time.",
-              "",
-              "INVARIANT: prepaid: >= starts_at",
-              "INVARIANT: unbounded: == 0 || >= starts_at"
+              "Extra space for program upgrades."
             ],
-            "type": "u64"
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
           },
           {
-            "name": "initialAmount",
+            "name": "bump",
             "docs": [
-              "Amount available to the recipient once stream starts."
+              "The PDA bump."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "globalStats",
+      "docs": [
+        "Figures of all the streams of all the mints, which are the sums of the figures of all the [`MintStats`] accounts.",
+        "There is a single global stats account and it is created along with the first stream.",
+        "",
+        "The global stats are updated with the same changes as the mint stats and count the same streams.",
+        "",
+        "INVARIANT: total_deposited_amount ==",
+        "total_withdrawn_amount + total_refunded_amount + total_rewarded_amount",
+        "+ sum of escrow amounts of the counted streams (excluding tokens sent to the escrows directly)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "streamCount",
+            "docs": [
+              "Total number of streams created."
             ],
             "type": "u64"
           },
           {
-            "name": "flowInterval",
+            "name": "activeStreamCount",
             "docs": [
-              "Flow interval is the interval in which flow payments are released."
+              "Number of streams which have been neither cancelled nor closed. Streams which have ended are active till they",
+              "are closed."
             ],
             "type": "u64"
           },
           {
-            "name": "flowRate",
+            "name": "insolvencyCancellationCount",
             "docs": [
-              "Flow rate is the number of tokens to stream per interval."
+              "Number of streams cancelled because they were insolvent and their insolvency grace period was over."
             ],
             "type": "u64"
           },
           {
-            "name": "isCancelled",
+            "name": "totalDepositedAmount",
             "docs": [
-              "If true, the stream has been cancelled."
+              "Total amount transferred to the escrow accounts by senders - prepaid amounts and topups."
             ],
-            "type": "bool"
+            "type": "u128"
           },
           {
-            "name": "isCancelledBeforeStart",
+            "name": "totalWithdrawnAmount",
             "docs": [
-              "If true, the stream has been cancelled before start.",
-              "",
-              "INVARIANT: !is_cancelled => == false"
+              "Total amount transferred from the escrow accounts to recipients."
             ],
-            "type": "bool"
+            "type": "u128"
           },
           {
-            "name": "isCancelledBySender",
+            "name": "totalRefundedAmount",
             "docs": [
-              "If true, the stream has been cancelled by the sender.",
-              "",
-              "INVARIANT: !is_cancelled || !sender_can_cancel => == false"
+              "Total amount returned from the escrow accounts to senders."
             ],
-            "type": "bool"
+            "type": "u128"
           },
           {
-            "name": "cancelledAt",
+            "name": "totalRewardedAmount",
             "docs": [
-              "Time at which the stream was cancelled. If it is > 0, it means the stream has been cancelled and any funds in",
-              "the escrow account not available to be withdrawn by the recipient have been retrieved.",
-              "",
-              "INVARIANT: cancelled_at > 0 iff is_cancelled == true"
+              "Total amount of deposits given as rewards to the accounts which cancelled insolvent streams."
             ],
-            "type": "u64"
+            "type": "u128"
           },
           {
-            "name": "senderCanCancel",
+            "name": "reserved",
             "docs": [
-              "True if a solvent stream can be cancelled by the sender."
+              "Extra space for program upgrades."
             ],
-            "type": "bool"
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
           },
           {
-            "name": "senderCanCancelAt",
+            "name": "bump",
             "docs": [
-              "Time at which the sender is allowed to cancel a solvent stream."
+              "The PDA bump."
             ],
-            "type": "u64"
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "CreateStreamParamsV1",
+      "docs": [
+        "Version 1 of [`CreateStreamParams`]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "docs": [
+              "Kind of the stream along with the options specific to the kind."
+            ],
+            "type": {
+              "defined": "StreamKind"
+            }
           },
           {
-            "name": "senderCanChangeSender",
+            "name": "schedule",
             "docs": [
-              "True if the sender can change the sender of the stream who will do the upcoming topups.",
-              "",
-              "INVARIANT: prepaid: false"
+              "Schedule and amounts of the streaming payments."
             ],
-            "type": "bool"
+            "type": {
+              "defined": "StreamSchedule"
+            }
           },
           {
-            "name": "senderCanChangeSenderAt",
+            "name": "permissions",
             "docs": [
-              "Time at which the sender is allowed to change the sender.",
-              "",
-              "INVARIANT: prepaid: == 0"
+              "Permissions given by the sender."
             ],
-            "type": "u64"
+            "type": {
+              "defined": "StreamPermissions"
+            }
           },
           {
-            "name": "isPaused",
+            "name": "arbiter",
             "docs": [
-              "If true, the stream is paused.",
-              "",
-              "INVARIANT: prepaid: == false"
+              "Arbiter of the stream. [`Pubkey::default()`] means no arbiter. See [`Stream::arbiter`]."
             ],
-            "type": "bool"
+            "type": "publicKey"
           },
           {
-            "name": "isPausedBySender",
+            "name": "requiresRecipientAcceptance",
             "docs": [
-              "If true, the stream is paused by sender.",
-              "",
-              "INVARIANT: prepaid: == false",
-              "INVARIANT: runtime: unbounded: !is_paused || !sender_can_pause => == false"
+              "If true, the stream only starts flowing once the recipient accepts it."
             ],
             "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamSchedule",
+      "docs": [
+        "Schedule and amounts of the streaming payments of a stream. See the fields of the same name on [`Stream`]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startsAt",
+            "docs": [
+              "Start time of the stream. A time in the past means the stream starts right away."
+            ],
+            "type": "u64"
           },
           {
-            "name": "senderCanPause",
+            "name": "endsAt",
             "docs": [
-              "True if a stream can be paused by the sender.",
-              "",
-              "INVARIANT: prepaid: false"
+              "End time of the stream. Can be 0 for non-prepaid streams to indicate no end time."
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "senderCanPauseAt",
+            "name": "initialAmount",
             "docs": [
-              "Time at which the sender is allowed to pause a stream.",
-              "",
-              "INVARIANT: prepaid: == 0"
+              "Amount available to the recipient once stream starts."
             ],
             "type": "u64"
           },
           {
-            "name": "recipientCanResumePauseBySender",
+            "name": "flowInterval",
             "docs": [
-              "True if a stream can be resumed by the recipient if it was paused by the sender.",
-              "",
-              "INVARIANT: prepaid: false"
+              "Interval in which `flow_rate` tokens are streamed. [`FLOW_RATE_SCALE`] makes `flow_rate` a fixed-point amount per",
+              "second. See [`FlowRate`]."
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "recipientCanResumePauseBySenderAt",
+            "name": "flowRate",
             "docs": [
-              "Time at which the recipient is allowed to resume a stream which was paused by the sender.",
-              "",
-              "INVARIANT: prepaid: == 0"
+              "Number of tokens to stream per interval."
             ],
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamPermissions",
+      "docs": [
+        "Permissions given by the sender of a stream. See the fields of the same name on [`Stream`]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "senderCanCancel",
+            "docs": [
+              "Permission of the sender to cancel a solvent stream."
+            ],
+            "type": {
+              "defined": "StreamPermission"
+            }
+          },
+          {
+            "name": "senderCanChangeSender",
+            "docs": [
+              "Permission of the sender to change the sender."
+            ],
+            "type": {
+              "defined": "StreamPermission"
+            }
+          },
+          {
+            "name": "senderCanPause",
+            "docs": [
+              "Permission of the sender to pause the stream."
+            ],
+            "type": {
+              "defined": "StreamPermission"
+            }
+          },
+          {
+            "name": "recipientCanResumePauseBySender",
+            "docs": [
+              "Permission of the recipient to resume a stream paused by the sender."
+            ],
+            "type": {
+              "defined": "StreamPermission"
+            }
           },
           {
             "name": "anyoneCanWithdrawForRecipient",
             "docs": [
-              "True if anyone can withdraw on behalf of the recipient. The amount will go in recipients' account."
+              "Permission of anyone to withdraw on behalf of the recipient."
+            ],
+            "type": {
+              "defined": "StreamPermission"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamPermission",
+      "docs": [
+        "A permission given on a stream, optionally unlocking and expiring at specific times."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "isAllowed",
+            "docs": [
+              "If true, the permission is given."
             ],
             "type": "bool"
           },
           {
-            "name": "anyoneCanWithdrawForRecipientAt",
+            "name": "allowedAt",
             "docs": [
-              "Time at which anyone can withdraw on behalf of the recipient."
+              "Time from which the permission is active. 0 or a time in the past means it is active right away."
             ],
             "type": "u64"
           },
           {
-            "name": "lastResumedAt",
+            "name": "expiresAt",
             "docs": [
-              "Time at which the stream was last resumed.",
-              "",
-              "INVARIANT: prepaid: == 0",
-              "INVARIANT: unbounded: (== 0 || >= starts_at) && (ends_at == 0 || < ends_at)"
+              "Time after which the permission is no longer active. 0 means it never expires."
             ],
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "WithdrawableView",
+      "docs": [
+        "Withdrawable amount of a stream. Returned by the `get_withdrawable` instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "accumulatedActiveTime",
+            "name": "at",
             "docs": [
-              "Total accumulated active (!is_paused) time since starts_at. This does not include (current_time -",
-              "last_resumed_at) time if the stream is not paused.",
-              "",
-              "INVARIANT: prepaid: == 0",
-              "INVARIANT: unbounded: == 0 || (current_time > starts_at && == current_time - starts_at - total_paused_time)"
+              "Time at which the figures were computed."
             ],
             "type": "u64"
           },
           {
-            "name": "totalWithdrawnAmount",
+            "name": "amountOwed",
             "docs": [
-              "Total amount withdrawn by the recipient.",
-              "",
-              "INVARIANT: runtime: prepaid: <= amount_owed && <= prepaid_amount_needed",
-              "INVARIANT: runtime: unbounded: <= amount_owed && <= total_topup_amount"
+              "Total amount owed to the recipient."
             ],
             "type": "u64"
           },
           {
-            "name": "lastWithdrawnAt",
+            "name": "totalWithdrawnAmount",
             "docs": [
-              "Last time at which recipient withdrew any amount."
+              "Total amount withdrawn by the recipient."
             ],
             "type": "u64"
           },
           {
-            "name": "lastWithdrawnAmount",
+            "name": "withdrawableAmount",
             "docs": [
-              "Last amount which recipient withdrew."
+              "Amount the recipient can withdraw. This doesn't include the recipient's share of the deposit which is only",
+              "available once an insolvent stream is cancelled."
             ],
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "SolvencyView",
+      "docs": [
+        "Solvency of a stream. Returned by the `get_solvency` instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "totalTopupAmount",
+            "name": "at",
             "docs": [
-              "Total topup amount added for the stream.",
-              "",
-              "INVARIANT: prepaid: == total_prepaid_amount",
-              "INVARIANT: unbounded: >= initial_amount + streaming_amount_owed"
+              "Time at which the figures were computed."
             ],
             "type": "u64"
           },
           {
-            "name": "lastTopupAt",
+            "name": "status",
             "docs": [
-              "Last time at which sender topped up the stream."
+              "Status of the stream."
             ],
-            "type": "u64"
+            "type": {
+              "defined": "StreamStatus"
+            }
           },
           {
-            "name": "lastTopupAmount",
+            "name": "isSolvent",
             "docs": [
-              "Last topup amount."
+              "True if the amount owed to the recipient is covered by the total topup amount."
             ],
-            "type": "u64"
+            "type": "bool"
           },
           {
-            "name": "depositNeeded",
+            "name": "isInInsolvencyGracePeriod",
             "docs": [
-              "Total deposit amount needed for the non-prepaid stream. These are needed in case the sender does not topup the",
-              "stream in time and the amount owed becomes > total topup amount. When that happens, anyone can cancel the",
-              "stream. The deposit amount will be distributed as a reward to whoever finds the insolvency and cancels the",
-              "stream.",
-              "",
-              "INVARIANT: prepaid: == 0",
-              "INVARIANT: unbounded: == DEPOSIT_AMOUNT_PERIOD_IN_SECS of streaming payments"
+              "True if the stream is insolvent but still within the insolvency grace period."
             ],
-            "type": "u64"
+            "type": "bool"
           },
           {
-            "name": "reserved",
+            "name": "insolventAt",
             "docs": [
-              "Extra space for program upgrades."
+              "Time from which the stream is insolvent. See [`Stream::insolvent_at`] for more information."
             ],
             "type": {
-              "array": [
-                "u64",
-                16
-              ]
+              "option": "u64"
             }
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "MaxTopupView",
+      "docs": [
+        "Maximum acceptable topup amount of a stream. Returned by the `get_max_topup` instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "seed",
+            "name": "at",
             "docs": [
-              "Seed of the stream PDA. It's upto the client how they choose the seed. Each tuple (seed, mint, name) corresponds",
-              "to a unique stream."
+              "Time at which the figures were computed."
             ],
             "type": "u64"
           },
           {
-            "name": "bump",
+            "name": "hasNoLimit",
             "docs": [
-              "The PDA bump."
+              "True if there is no limit on the topup amount."
             ],
-            "type": "u8"
+            "type": "bool"
           },
           {
-            "name": "name",
+            "name": "maxAcceptableTopupAmount",
             "docs": [
-              "Name of the stream. Should be unique for a particular set of (seed, mint).",
-              "",
-              "INVARIANT: Length <= 100 unicode chars or 400 bytes"
+              "Maximum acceptable topup amount. Only valid if `has_no_limit` is false."
             ],
-            "type": "string"
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamDerivation",
+      "docs": [
+        "Mode used to derive the address of a stream PDA account.",
+        "",
+        "The seeds of the stream PDA account are `[STREAM_ACCOUNT_SEED, seed, mint, name_seed, sender_seed,",
+        "recipient_seed]`. The sender and recipient seeds are empty if they are not part of the derivation, so global streams",
+        "keep their addresses."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Global"
+          },
+          {
+            "name": "Sender"
+          },
+          {
+            "name": "SenderAndRecipient"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CreateStreamParams",
+      "docs": [
+        "Parameters of a new stream passed to the `create_stream` instruction. Every version is a separate variant, so new",
+        "options can be added in a new version without breaking the callers of the older versions."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "V1",
+            "fields": [
+              {
+                "defined": "CreateStreamParamsV1"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamKind",
+      "docs": [
+        "Kind of a stream."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Prepaid"
+          },
+          {
+            "name": "NonPrepaid",
+            "fields": [
+              {
+                "name": "topup_amount",
+                "docs": [
+                  "Initial topup amount for the stream. Should be >= the initial amount + 2 * the deposit needed. See",
+                  "[`DEPOSIT_AMOUNT_PERIOD_IN_SECS`]."
+                ],
+                "type": "u64"
+              },
+              {
+                "name": "insolvency_grace_period",
+                "docs": [
+                  "Active streaming time after insolvency during which only the sender can cure the insolvency. Should be <=",
+                  "[`DEPOSIT_AMOUNT_PERIOD_IN_SECS`]."
+                ],
+                "type": "u64"
+              },
+              {
+                "name": "recipient_deposit_share_bps",
+                "docs": [
+                  "Share of the deposit (in basis points) given to the recipient if the stream is cancelled after becoming",
+                  "insolvent. The rest is given to whoever cancels the stream."
+                ],
+                "type": "u16"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamStatus",
+      "docs": [
+        "Status of a stream at a point in time. These match the states documented on the [`Stream`] struct."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Scheduled"
+          },
+          {
+            "name": "Streaming"
+          },
+          {
+            "name": "Paused"
+          },
+          {
+            "name": "Insolvent"
+          },
+          {
+            "name": "Ended"
+          },
+          {
+            "name": "Cancelled"
           }
         ]
       }
     }
   ],
-  "errors": [
+  "errors": [
+    {
+      "code": 6000,
+      "name": "EmptyRecipient",
+      "msg": "The stream recipient is empty. Should be a valid address"
+    },
+    {
+      "code": 6001,
+      "name": "SameSenderAndRecipient",
+      "msg": "The stream recipient is the same as the sender. Should be different addresses"
+    },
+    {
+      "code": 6002,
+      "name": "SameSenders",
+      "msg": "The stream sender is the same as the new sender. Should be different addresses"
+    },
+    {
+      "code": 6003,
+      "name": "SameRecipients",
+      "msg": "The stream recipient is the same as the new recipient. Should be different addresses"
+    },
+    {
+      "code": 6004,
+      "name": "InvalidNewSender",
+      "msg": "The new sender is invalid"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidSender",
+      "msg": "The sender is invalid"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidRecipient",
+      "msg": "The recipient is invalid"
+    },
+    {
+      "code": 6007,
+      "name": "StreamNameTooShort",
+      "msg": "The stream name is too short. Should be >= 2 chars"
+    },
+    {
+      "code": 6008,
+      "name": "StreamNameTooLong",
+      "msg": "The stream name is too long. Should be <= 100 chars"
+    },
+    {
+      "code": 6009,
+      "name": "ZeroFlowInterval",
+      "msg": "The flow interval is 0. Should be > 0"
+    },
+    {
+      "code": 6010,
+      "name": "InvalidEndsAt",
+      "msg": "The end time is either 0 with prepaid = true, in the past or < starts_at. Should be >= current_time and >= starts_at or if the stream is not prepaid, it can be 0"
+    },
+    {
+      "code": 6011,
+      "name": "ZeroLifetimeAmount",
+      "msg": "The stream will never lead to any payments. Either there should be a initial amount or flow rate and flow duration should be > 0"
+    },
+    {
+      "code": 6012,
+      "name": "ZeroAmount",
+      "msg": "The amount cannot be 0. Should be > 0"
+    },
+    {
+      "code": 6013,
+      "name": "PrepaidAmountNeededOutOfBounds",
+      "msg": "The prepaid amount needed by the stream is out of bounds"
+    },
+    {
+      "code": 6014,
+      "name": "DepositAmountNeededOutOfBounds",
+      "msg": "The deposit amount needed by the non-prepaid stream is out of bounds"
+    },
+    {
+      "code": 6015,
+      "name": "AmountLessThanAmountNeeded",
+      "msg": "The amount is less than the minimum initial amount needed"
+    },
+    {
+      "code": 6016,
+      "name": "UserUnauthorizedToWithdraw",
+      "msg": "The user is not allowed to withdraw. Should be the recipient of the stream"
+    },
+    {
+      "code": 6017,
+      "name": "WithdrawnAmountGreaterThanAmountOwed",
+      "msg": "The withdrawn amount by recipient is more than the amount owed. THIS SHOULD NOT HAVE HAPPENED!!!"
+    },
+    {
+      "code": 6018,
+      "name": "WithdrawAmountOutOfBounds",
+      "msg": "The total withdrawn amount by the recipient of the stream is out of bounds"
+    },
+    {
+      "code": 6019,
+      "name": "AmountAvailableToWithdrawOutOfBounds",
+      "msg": "The amount available to be withdrawn by the recipient of the stream is out of bounds"
+    },
+    {
+      "code": 6020,
+      "name": "CancellationRefundOutOfBounds",
+      "msg": "The cancellation refund amount to the sender of the stream is out of bounds"
+    },
+    {
+      "code": 6021,
+      "name": "TopupAmountOutOfBounds",
+      "msg": "The total topup amount by the sender of the stream is out of bounds"
+    },
+    {
+      "code": 6022,
+      "name": "TopupAmountMoreThanMaxAcceptable",
+      "msg": "The topup amount is more than what is needed by the stream"
+    },
+    {
+      "code": 6023,
+      "name": "SenderInsufficientFunds",
+      "msg": "The sender has insufficient balance in their token account"
+    },
+    {
+      "code": 6024,
+      "name": "EscrowInsufficientFunds",
+      "msg": "The token escrow account has insufficient balance. THIS SHOULD NOT HAVE HAPPENED!!!"
+    },
+    {
+      "code": 6025,
+      "name": "EscrowNotRentExempt",
+      "msg": "The token escrow account is not rent exempt"
+    },
+    {
+      "code": 6026,
+      "name": "StreamAlreadyCancelled",
+      "msg": "The stream has already been cancelled"
+    },
+    {
+      "code": 6027,
+      "name": "UserUnauthorizedToCancel",
+      "msg": "The user is not allowed to cancel. Should be the sender or the recipient of the stream"
+    },
+    {
+      "code": 6028,
+      "name": "SenderCannotCancel",
+      "msg": "The sender is not allowed to cancel permanently or at the moment"
+    },
+    {
+      "code": 6029,
+      "name": "StreamIsPrepaid",
+      "msg": "The stream is prepaid. Should be a non-prepaid stream"
+    },
+    {
+      "code": 6030,
+      "name": "StreamHasStopped",
+      "msg": "The stream has already stopped. Should be an unstopped stream"
+    },
+    {
+      "code": 6031,
+      "name": "StreamIsPaused",
+      "msg": "The stream is already paused. Should be a non-paused stream"
+    },
+    {
+      "code": 6032,
+      "name": "StreamIsNotPaused",
+      "msg": "The stream is not paused. Should be a paused stream"
+    },
+    {
+      "code": 6033,
+      "name": "StreamHasNoFlowPayments",
+      "msg": "The stream has no flow payments. Should be a stream stream with a positive flow rate and flow period"
+    },
+    {
+      "code": 6034,
+      "name": "SenderCannotChangeSender",
+      "msg": "The sender is not allowed to change sender of the stream permanently or at the moment"
+    },
+    {
+      "code": 6035,
+      "name": "SenderCannotPause",
+      "msg": "The sender is not allowed to pause stream permanently or at the moment"
+    },
+    {
+      "code": 6036,
+      "name": "RecipientCannotResumePauseBySender",
+      "msg": "The recipient is not allowed resume a stream paused by sender permanently or at the moment"
+    },
+    {
+      "code": 6037,
+      "name": "UserUnauthorizedToPause",
+      "msg": "The user is not allowed to pause. Should be the sender or the recipient of the stream"
+    },
+    {
+      "code": 6038,
+      "name": "UserUnauthorizedToResume",
+      "msg": "The user is not allowed to resume. Should be the sender or the recipient of the stream"
+    },
+    {
+      "code": 6039,
+      "name": "StreamNotEnded",
+      "msg": "The stream has not ended. Should have ended and nat been cancelled"
+    },
+    {
+      "code": 6040,
+      "name": "MintNotAllowed",
+      "msg": "The mint is not allowed by the mint registry. Streams cannot be created for this mint"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidNewAuthority",
+      "msg": "The new authority is invalid"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidInsolvencyGracePeriod",
+      "msg": "The insolvency grace period is more than the deposit amount period. Should be <= 8 hrs"
+    },
+    {
+      "code": 6043,
+      "name": "StreamInInsolvencyGracePeriod",
+      "msg": "The stream is insolvent but within the insolvency grace period. Only the sender can topup and only the sender or the recipient can cancel"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidRecipientDepositShare",
+      "msg": "The recipient deposit share is more than 100%. Should be <= 10000 basis points"
+    },
+    {
+      "code": 6045,
+      "name": "UserUnauthorizedToSettle",
+      "msg": "The user is not allowed to propose, cancel or accept a settlement. Should be the sender or the recipient of the stream and only the other party can accept a settlement"
+    },
+    {
+      "code": 6046,
+      "name": "SettlementNotProposed",
+      "msg": "No settlement has been proposed for the stream"
+    },
+    {
+      "code": 6047,
+      "name": "SettlementAmountMismatch",
+      "msg": "The settlement amount is different from the proposed settlement amount"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidSettlementAmount",
+      "msg": "The settlement amount is invalid. Should be >= total withdrawn amount and <= total amount paid by the sender"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidArbiter",
+      "msg": "The arbiter is invalid. Should be different from the sender and the recipient"
+    },
+    {
+      "code": 6050,
+      "name": "UserUnauthorizedToArbitrate",
+      "msg": "The user is not allowed to arbitrate. Should be the arbiter of the stream"
+    },
+    {
+      "code": 6051,
+      "name": "StreamIsFrozen",
+      "msg": "The stream is frozen by the arbiter. Should be a non-frozen stream"
+    },
+    {
+      "code": 6052,
+      "name": "StreamIsNotFrozen",
+      "msg": "The stream is not frozen. Should be a frozen stream"
+    },
+    {
+      "code": 6053,
+      "name": "StreamIsPendingAcceptance",
+      "msg": "The stream is pending recipient acceptance. Should be an accepted stream"
+    },
+    {
+      "code": 6054,
+      "name": "StreamIsNotPendingAcceptance",
+      "msg": "The stream is not pending recipient acceptance. Should be a stream pending recipient acceptance"
+    },
+    {
+      "code": 6055,
+      "name": "UserUnauthorizedToAccept",
+      "msg": "The user is not allowed to accept the stream. Should be the recipient of the stream"
+    },
+    {
+      "code": 6056,
+      "name": "SenderChangeNotProposed",
+      "msg": "No sender change has been proposed for the stream"
+    },
+    {
+      "code": 6057,
+      "name": "RecipientChangeNotProposed",
+      "msg": "No recipient change has been proposed for the stream"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidNewRecipient",
+      "msg": "The new recipient is invalid"
+    },
+    {
+      "code": 6059,
+      "name": "InvalidPermissionExpiry",
+      "msg": "The permission expiry time is invalid"
+    },
+    {
+      "code": 6060,
+      "name": "InvariantViolation",
+      "msg": "A stream invariant does not hold"
+    },
+    {
+      "code": 6061,
+      "name": "UserUnauthorizedToClose",
+      "msg": "The user is not allowed to close the stream. Should be the sender or the recipient of the stream"
+    },
+    {
+      "code": 6062,
+      "name": "StreamNotStopped",
+      "msg": "The stream has not stopped. Should have ended or been cancelled"
+    },
+    {
+      "code": 6063,
+      "name": "StreamHasFundsLeft",
+      "msg": "The stream still holds funds. Everything should have been withdrawn or refunded"
+    },
+    {
+      "code": 6064,
+      "name": "StreamNotMigrated",
+      "msg": "The stream is in the legacy account layout. It needs to be migrated before using this feature"
+    },
+    {
+      "code": 6065,
+      "name": "StreamAlreadyMigrated",
+      "msg": "The stream is already in the newest account layout"
+    },
+    {
+      "code": 6066,
+      "name": "UserUnauthorizedToConvert",
+      "msg": "The user is not allowed to convert the stream. Should be the sender or the recipient of the stream"
+    }
+  ]
+};
+
+export const IDL: Superstream = {
+  "version": "0.3.3",
+  "name": "superstream",
+  "docs": [
+    "Module for superstream cpi methods and other utilities."
+  ],
+  "instructions": [
+    {
+      "name": "createStream",
+      "docs": [
+        "Create a new stream. This is the same as `create_prepaid` and `create_non_prepaid`, but takes the options of",
+        "the stream as a single versioned struct, so new options don't change the arguments of the instruction.",
+        "",
+        "# Arguments",
+        "",
+        "* `derivation` - Mode used to derive the stream PDA address. Streams scoped to the sender can't be front-run by",
+        "someone else creating a stream with the same seed and name. See [`StreamDerivation`].",
+        "* `params` - Kind, schedule, permissions and other options of the stream. See [`CreateStreamParams`].",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is initialized by the program."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Stream sender wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. It might not have been initialized, in which case streams can be created for any",
+            "mint.",
+            ""
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.",
+            ""
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "derivation",
+          "type": {
+            "defined": "StreamDerivation"
+          }
+        },
+        {
+          "name": "params",
+          "type": {
+            "defined": "CreateStreamParams"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createPrepaid",
+      "docs": [
+        "Create a new prepaid stream. This is a thin wrapper around `create_stream` with [`StreamKind::Prepaid`] and the",
+        "defaults of the options added after it: the [`StreamDerivation::Global`] derivation, no permission expiry, no",
+        "arbiter and no recipient acceptance. Use `create_stream` for these options.",
+        "",
+        "# Arguments",
+        "",
+        "* `flow_interval` - Interval in which `flow_rate` tokens are streamed. Pass",
+        "[`FLOW_RATE_SCALE`](crate::state::FLOW_RATE_SCALE) to make `flow_rate` a fixed-point amount per second. See",
+        "[`FlowRate`](crate::state::FlowRate).",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is initialized by the program."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Stream sender wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. It might not have been initialized, in which case streams can be created for any",
+            "mint.",
+            ""
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.",
+            ""
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "startsAt",
+          "type": "u64"
+        },
+        {
+          "name": "endsAt",
+          "type": "u64"
+        },
+        {
+          "name": "initialAmount",
+          "type": "u64"
+        },
+        {
+          "name": "flowInterval",
+          "type": "u64"
+        },
+        {
+          "name": "flowRate",
+          "type": "u64"
+        },
+        {
+          "name": "senderCanCancel",
+          "type": "bool"
+        },
+        {
+          "name": "senderCanCancelAt",
+          "type": "u64"
+        },
+        {
+          "name": "senderCanChangeSender",
+          "type": "bool"
+        },
+        {
+          "name": "senderCanChangeSenderAt",
+          "type": "u64"
+        },
+        {
+          "name": "senderCanPause",
+          "type": "bool"
+        },
+        {
+          "name": "senderCanPauseAt",
+          "type": "u64"
+        },
+        {
+          "name": "recipientCanResumePauseBySender",
+          "type": "bool"
+        },
+        {
+          "name": "recipientCanResumePauseBySenderAt",
+          "type": "u64"
+        },
+        {
+          "name": "anyoneCanWithdrawForRecipient",
+          "type": "bool"
+        },
+        {
+          "name": "anyoneCanWithdrawForRecipientAt",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "createNonPrepaid",
+      "docs": [
+        "Create a new non-prepaid stream. This is a thin wrapper around `create_stream` with [`StreamKind::NonPrepaid`]",
+        "and the defaults of the options added after it: the [`StreamDerivation::Global`] derivation, no permission",
+        "expiry, no arbiter, no recipient acceptance, no insolvency grace period and the whole deposit going to whoever",
+        "cancels an insolvent stream. Use `create_stream` for these options.",
+        "",
+        "# Arguments",
+        "",
+        "* `flow_interval` - Interval in which `flow_rate` tokens are streamed. Pass",
+        "[`FLOW_RATE_SCALE`](crate::state::FLOW_RATE_SCALE) to make `flow_rate` a fixed-point amount per second. See",
+        "[`FlowRate`](crate::state::FlowRate).",
+        "* `topup_amount` - Initial topup amount for the stream. The topup amount should be >= minimum deposit required.",
+        "See [`DEPOSIT_AMOUNT_PERIOD_IN_SECS`](crate::state::DEPOSIT_AMOUNT_PERIOD_IN_SECS) for more information.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is initialized by the program."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Stream sender wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account. It might not have been initialized, in which case streams can be created for any",
+            "mint.",
+            ""
+          ]
+        },
+        {
+          "name": "allowedMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.",
+            ""
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account. It is created if it doesn't exist."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "startsAt",
+          "type": "u64"
+        },
+        {
+          "name": "endsAt",
+          "type": "u64"
+        },
+        {
+          "name": "initialAmount",
+          "type": "u64"
+        },
+        {
+          "name": "flowInterval",
+          "type": "u64"
+        },
+        {
+          "name": "flowRate",
+          "type": "u64"
+        },
+        {
+          "name": "senderCanCancel",
+          "type": "bool"
+        },
+        {
+          "name": "senderCanCancelAt",
+          "type": "u64"
+        },
+        {
+          "name": "senderCanChangeSender",
+          "type": "bool"
+        },
+        {
+          "name": "senderCanChangeSenderAt",
+          "type": "u64"
+        },
+        {
+          "name": "senderCanPause",
+          "type": "bool"
+        },
+        {
+          "name": "senderCanPauseAt",
+          "type": "u64"
+        },
+        {
+          "name": "recipientCanResumePauseBySender",
+          "type": "bool"
+        },
+        {
+          "name": "recipientCanResumePauseBySenderAt",
+          "type": "u64"
+        },
+        {
+          "name": "anyoneCanWithdrawForRecipient",
+          "type": "bool"
+        },
+        {
+          "name": "anyoneCanWithdrawForRecipientAt",
+          "type": "u64"
+        },
+        {
+          "name": "topupAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancel",
+      "docs": [
+        "Cancel a stream. If the stream is pending recipient acceptance, the sender can always cancel it to revoke it and",
+        "recover the full escrow, and the recipient can cancel it to refuse it.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the",
+            "insolvency grace period. After that, anyone can cancel."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "cancelV2",
+      "docs": [
+        "Same as [`cancel`](crate::superstream::cancel), but the seed, name and recipient are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the",
+            "insolvency grace period. After that, anyone can cancel."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawExcessTopupNonPrepaidEnded",
+      "docs": [
+        "Withdraw excess sender topup from a non-prepaid stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "withdrawExcessTopupNonPrepaidEndedV2",
+      "docs": [
+        "Same as",
+        "[`withdraw_excess_topup_non_prepaid_ended`](crate::superstream::withdraw_excess_topup_non_prepaid_ended), but",
+        "the seed and name are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "topupNonPrepaid",
+      "docs": [
+        "Topup a non-prepaid stream. Returns the topup amount accepted by the stream, which is the only amount",
+        "transferred to the escrow account.",
+        "",
+        "# Arguments",
+        "",
+        "* `topup_amount` - Topup amount for the stream. If it is more than the maximum acceptable topup amount, only the",
+        "maximum acceptable topup amount is accepted.",
+        "* `is_strict` - If true, the topup fails instead if the topup amount is more than the maximum acceptable topup",
+        "amount.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But",
+            "the refund when the stream gets cancelled will only go to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "topupAmount",
+          "type": "u64"
+        },
+        {
+          "name": "isStrict",
+          "type": "bool"
+        }
+      ],
+      "returns": "u64"
+    },
     {
-      "code": 6000,
-      "name": "EmptyRecipient",
-      "msg": "The stream recipient is empty. Should be a valid address"
+      "name": "topupNonPrepaidV2",
+      "docs": [
+        "Same as [`topup_non_prepaid`](crate::superstream::topup_non_prepaid), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But",
+            "the refund when the stream gets cancelled will only go to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the signer."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "topupAmount",
+          "type": "u64"
+        },
+        {
+          "name": "isStrict",
+          "type": "bool"
+        }
+      ],
+      "returns": "u64"
     },
     {
-      "code": 6001,
-      "name": "SameSenderAndRecipient",
-      "msg": "The stream recipient is the same as the sender. Should be different addresses"
+      "name": "changeSenderNonPrepaid",
+      "docs": [
+        "Propose to change sender of a non-prepaid stream. The sender changes only when the new sender accepts the change.",
+        "",
+        "# Arguments",
+        "",
+        "* `new_sender` - The new sender",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "newSender",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6002,
-      "name": "SameSenders",
-      "msg": "The stream sender is the same as the new sender. Should be different addresses"
+      "name": "changeSenderNonPrepaidV2",
+      "docs": [
+        "Same as [`change_sender_non_prepaid`](crate::superstream::change_sender_non_prepaid), but the seed and name are",
+        "read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newSender",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6003,
-      "name": "SameRecipients",
-      "msg": "The stream recipient is the same as the new recipient. Should be different addresses"
+      "name": "acceptSenderChangeNonPrepaid",
+      "docs": [
+        "Accept the proposed sender change of a non-prepaid stream. The signer needs to be the proposed new sender.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newSender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new sender wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream sender account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current sender.",
+            ""
+          ]
+        },
+        {
+          "name": "newSenderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6004,
-      "name": "InvalidNewSender",
-      "msg": "The new sender is invalid"
+      "name": "acceptSenderChangeNonPrepaidV2",
+      "docs": [
+        "Same as [`accept_sender_change_non_prepaid`](crate::superstream::accept_sender_change_non_prepaid), but the seed",
+        "and name are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newSender",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new sender wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream sender account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current sender.",
+            ""
+          ]
+        },
+        {
+          "name": "newSenderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new sender. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6005,
-      "name": "InvalidSender",
-      "msg": "The sender is invalid"
+      "name": "cancelSenderChangeNonPrepaid",
+      "docs": [
+        "Cancel the proposed sender change of a non-prepaid stream. The signer needs to be the current sender.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6006,
-      "name": "InvalidRecipient",
-      "msg": "The recipient is invalid"
+      "name": "cancelSenderChangeNonPrepaidV2",
+      "docs": [
+        "Same as [`cancel_sender_change_non_prepaid`](crate::superstream::cancel_sender_change_non_prepaid), but the seed",
+        "and name are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6007,
-      "name": "StreamNameTooShort",
-      "msg": "The stream name is too short. Should be >= 2 chars"
+      "name": "withdraw",
+      "docs": [
+        "Withdraw recipient funds from a stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6008,
-      "name": "StreamNameTooLong",
-      "msg": "The stream name is too long. Should be <= 100 chars"
+      "name": "withdrawV2",
+      "docs": [
+        "Same as [`withdraw`](crate::superstream::withdraw), but the seed, name and recipient are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6009,
-      "name": "ZeroFlowInterval",
-      "msg": "The flow interval is 0. Should be > 0"
+      "name": "withdrawAndChangeRecipient",
+      "docs": [
+        "Withdraw recipient funds from a stream and propose to change recipient of a stream. The recipient changes only",
+        "when the new recipient accepts the change. Fails if the stream is cancelled, including when the withdrawal",
+        "cancels an insolvent stream.",
+        "",
+        "# Arguments",
+        "",
+        "* `new_recipient` - The new recipient",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "newRecipient",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6010,
-      "name": "InvalidEndsAt",
-      "msg": "The end time is either 0 with prepaid = true, in the past or < starts_at. Should be >= current_time and >= starts_at or if the stream is not prepaid, it can be 0"
+      "name": "withdrawAndChangeRecipientV2",
+      "docs": [
+        "Same as [`withdraw_and_change_recipient`](crate::superstream::withdraw_and_change_recipient), but the seed, name",
+        "and recipient are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
+            "signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user",
+            "tries to send themselves as a recipient, the constraint for the recipient token account will fail."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "signerToken",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newRecipient",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6011,
-      "name": "ZeroLifetimeAmount",
-      "msg": "The stream will never lead to any payments. Either there should be a initial amount or flow rate and flow duration should be > 0"
+      "name": "acceptRecipientChange",
+      "docs": [
+        "Accept the proposed recipient change of a stream. The signer needs to be the proposed new recipient. Recipient",
+        "funds owed till now are withdrawn to the current recipient before the change. If the withdrawal cancels an",
+        "insolvent stream, the recipient is not changed and the reward for cancelling it goes to the current recipient.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newRecipient",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new recipient wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "currentRecipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the current recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "newRecipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6012,
-      "name": "ZeroAmount",
-      "msg": "The amount cannot be 0. Should be > 0"
+      "name": "acceptRecipientChangeV2",
+      "docs": [
+        "Same as [`accept_recipient_change`](crate::superstream::accept_recipient_change), but the seed, name and",
+        "recipient are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "newRecipient",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Proposed new recipient wallet. It pays for the extra space in its stream index account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "currentRecipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Current stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the current recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the current recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "newRecipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the new recipient. It is created if it doesn't exist.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6013,
-      "name": "PrepaidAmountNeededOutOfBounds",
-      "msg": "The prepaid amount needed by the stream is out of bounds"
+      "name": "cancelRecipientChange",
+      "docs": [
+        "Cancel the proposed recipient change of a stream. The signer needs to be the current recipient.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6014,
-      "name": "DepositAmountNeededOutOfBounds",
-      "msg": "The deposit amount needed by the non-prepaid stream is out of bounds"
+      "name": "cancelRecipientChangeV2",
+      "docs": [
+        "Same as [`cancel_recipient_change`](crate::superstream::cancel_recipient_change), but the seed and name are read",
+        "from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6015,
-      "name": "AmountLessThanAmountNeeded",
-      "msg": "The amount is less than the minimum initial amount needed"
+      "name": "pauseNonPrepaid",
+      "docs": [
+        "Pause a non-prepaid stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6016,
-      "name": "UserUnauthorizedToWithdraw",
-      "msg": "The user is not allowed to withdraw. Should be the recipient of the stream"
+      "name": "pauseNonPrepaidV2",
+      "docs": [
+        "Same as [`pause_non_prepaid`](crate::superstream::pause_non_prepaid), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6017,
-      "name": "WithdrawnAmountGreaterThanAmountOwed",
-      "msg": "The withdrawn amount by recipient is more than the amount owed. THIS SHOULD NOT HAVE HAPPENED!!!"
+      "name": "resumeNonPrepaid",
+      "docs": [
+        "Resume a non-prepaid stream.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if",
+            "the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender)."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6018,
-      "name": "WithdrawAmountOutOfBounds",
-      "msg": "The total withdrawn amount by the recipient of the stream is out of bounds"
+      "name": "resumeNonPrepaidV2",
+      "docs": [
+        "Same as [`resume_non_prepaid`](crate::superstream::resume_non_prepaid), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if",
+            "the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender)."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6019,
-      "name": "AmountAvailableToWithdrawOutOfBounds",
-      "msg": "The amount available to be withdrawn by the recipient of the stream is out of bounds"
+      "name": "accept",
+      "docs": [
+        "Accept a stream pending recipient acceptance. The flow starts at the time of acceptance if the stream was",
+        "scheduled to start earlier.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6020,
-      "name": "CancellationRefundOutOfBounds",
-      "msg": "The cancellation refund amount to the sender of the stream is out of bounds"
+      "name": "acceptV2",
+      "docs": [
+        "Same as [`accept`](crate::superstream::accept), but the seed and name are read from the stream account instead",
+        "of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Stream recipient wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6021,
-      "name": "TopupAmountOutOfBounds",
-      "msg": "The total topup amount by the sender of the stream is out of bounds"
+      "name": "proposeSettlement",
+      "docs": [
+        "Propose a settlement to terminate a stream. Either the sender or the recipient can propose a settlement, and it",
+        "replaces any previous proposal.",
+        "",
+        "# Arguments",
+        "",
+        "* `settlement_amount` - Total amount owed to the recipient on termination, including the amount already",
+        "withdrawn. Should be >= total withdrawn amount and <= total amount paid by the sender.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6022,
-      "name": "TopupAmountMoreThanMaxAcceptable",
-      "msg": "The topup amount is more than what is needed by the stream"
+      "name": "proposeSettlementV2",
+      "docs": [
+        "Same as [`propose_settlement`](crate::superstream::propose_settlement), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6023,
-      "name": "SenderInsufficientFunds",
-      "msg": "The sender has insufficient balance in their token account"
+      "name": "cancelSettlementProposal",
+      "docs": [
+        "Cancel the proposed settlement of a stream. The proposer can revoke it and the other party can reject it.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6024,
-      "name": "EscrowInsufficientFunds",
-      "msg": "The token escrow account has insufficient balance. THIS SHOULD NOT HAVE HAPPENED!!!"
+      "name": "cancelSettlementProposalV2",
+      "docs": [
+        "Same as [`cancel_settlement_proposal`](crate::superstream::cancel_settlement_proposal), but the seed and name",
+        "are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6025,
-      "name": "EscrowNotRentExempt",
-      "msg": "The token escrow account is not rent exempt"
+      "name": "terminateWithSettlement",
+      "docs": [
+        "Accept the proposed settlement of a stream and terminate it. The escrow is split according to the settlement and",
+        "the stream is marked cancelled.",
+        "",
+        "# Arguments",
+        "",
+        "* `settlement_amount` - The proposed settlement amount. Should be equal to the amount stored on the stream.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be the party which did not propose the settlement."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6026,
-      "name": "StreamAlreadyCancelled",
-      "msg": "The stream has already been cancelled"
+      "name": "terminateWithSettlementV2",
+      "docs": [
+        "Same as [`terminate_with_settlement`](crate::superstream::terminate_with_settlement), but the seed, name and",
+        "recipient are read from the stream account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be the party which did not propose the settlement."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "settlementAmount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6027,
-      "name": "UserUnauthorizedToCancel",
-      "msg": "The user is not allowed to cancel. Should be the sender or the recipient of the stream"
+      "name": "freeze",
+      "docs": [
+        "Freeze a stream in case of a dispute. Only the arbiter of the stream can freeze it. Withdrawals, cancellations,",
+        "settlements and refunds are halted till the stream is unfrozen or resolved.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6028,
-      "name": "SenderCannotCancel",
-      "msg": "The sender is not allowed to cancel permanently or at the moment"
+      "name": "freezeV2",
+      "docs": [
+        "Same as [`freeze`](crate::superstream::freeze), but the seed and name are read from the stream account instead",
+        "of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6029,
-      "name": "StreamIsPrepaid",
-      "msg": "The stream is prepaid. Should be a non-prepaid stream"
+      "name": "unfreeze",
+      "docs": [
+        "Unfreeze a frozen stream. Only the arbiter of the stream can unfreeze it.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6030,
-      "name": "StreamHasStopped",
-      "msg": "The stream has already stopped. Should be an unstopped stream"
+      "name": "unfreezeV2",
+      "docs": [
+        "Same as [`unfreeze`](crate::superstream::unfreeze), but the seed and name are read from the stream account",
+        "instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6031,
-      "name": "StreamIsPaused",
-      "msg": "The stream is already paused. Should be a non-paused stream"
+      "name": "resolve",
+      "docs": [
+        "Resolve the dispute of a frozen stream. Only the arbiter of the stream can resolve it. The escrow is split",
+        "according to the resolution and the stream is marked cancelled.",
+        "",
+        "# Arguments",
+        "",
+        "* `recipient_amount` - Total amount owed to the recipient, including the amount already withdrawn. Should be >=",
+        "total withdrawn amount and <= total amount paid by the sender. The rest is returned to the sender.",
+        "",
+        "For more information on the other arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        },
+        {
+          "name": "recipientAmount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6032,
-      "name": "StreamIsNotPaused",
-      "msg": "The stream is not paused. Should be a paused stream"
+      "name": "resolveV2",
+      "docs": [
+        "Same as [`resolve`](crate::superstream::resolve), but the seed, name and recipient are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "arbiter",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Arbiter wallet."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "recipientToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the recipient."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream."
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "recipientAmount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6033,
-      "name": "StreamHasNoFlowPayments",
-      "msg": "The stream has no flow payments. Should be a stream stream with a positive flow rate and flow period"
+      "name": "close",
+      "docs": [
+        "Close a stream which has stopped and has nothing left to withdraw or refund. The signer needs to be the sender",
+        "or the recipient. The stream is removed from the stream indexes, and the rent of the stream and escrow accounts",
+        "is returned to the sender. Any tokens sent to the escrow account directly are transferred to the sender.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is closed by the program and the rent is returned to the sender."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the recipient can close the stream."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream. This is closed by the program and the rent",
+            "is returned to the sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6034,
-      "name": "SenderCannotChangeSender",
-      "msg": "The sender is not allowed to change sender of the stream permanently or at the moment"
+      "name": "closeV2",
+      "docs": [
+        "Same as [`close`](crate::superstream::close), but the seed and name are read from the stream account instead of",
+        "being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account. This is closed by the program and the rent is returned to the sender."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Either the sender or the recipient can close the stream."
+          ]
+        },
+        {
+          "name": "sender",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream sender account.",
+            "",
+            "to the stream sender."
+          ]
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream recipient account. The rent freed from its stream index account is returned to it.",
+            "",
+            "lamports to the stream recipient."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "senderToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token account of the sender."
+          ]
+        },
+        {
+          "name": "escrowToken",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Associated token escrow account holding the funds for this stream. This is closed by the program and the rent",
+            "is returned to the sender."
+          ]
+        },
+        {
+          "name": "senderIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the sender.",
+            ""
+          ]
+        },
+        {
+          "name": "recipientIndex",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream index PDA account of the recipient.",
+            ""
+          ]
+        },
+        {
+          "name": "mintStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stats PDA account of the mint."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6035,
-      "name": "SenderCannotPause",
-      "msg": "The sender is not allowed to pause stream permanently or at the moment"
+      "name": "migrateStream",
+      "docs": [
+        "Migrate a stream in the legacy account layout to the newest layout. The stream account is reallocated and the",
+        "payer pays for the extra rent. Anyone can migrate a stream as it doesn't change the state of the stream.",
+        "",
+        "Streams in the legacy layout can be used with all the instructions, but the features introduced with the newest",
+        "layout, e.g. settlements or recipient changes, need the stream to be migrated first.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Payer of the extra rent of the stream account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": "u64"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
     },
     {
-      "code": 6036,
-      "name": "RecipientCannotResumePauseBySender",
-      "msg": "The recipient is not allowed resume a stream paused by sender permanently or at the moment"
+      "name": "migrateStreamV2",
+      "docs": [
+        "Same as [`migrate_stream`](crate::superstream::migrate_stream), but the seed and name are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Payer of the extra rent of the stream account."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6037,
-      "name": "UserUnauthorizedToPause",
-      "msg": "The user is not allowed to pause. Should be the sender or the recipient of the stream"
+      "name": "convertStreamToZeroCopy",
+      "docs": [
+        "Convert a stream to the zero-copy layout. The signer needs to be the sender or the recipient and pays the rent of",
+        "the extra space. Zero-copy streams can only be used with `withdraw_zero_copy`, `topup_non_prepaid_zero_copy` and",
+        "`cancel_zero_copy` and need to be converted back for everything else. See [`ZeroCopyStream`]."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account in any of the [`VersionedStream`] layouts."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient. They pay the rent of the extra space."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6038,
-      "name": "UserUnauthorizedToResume",
-      "msg": "The user is not allowed to resume. Should be the sender or the recipient of the stream"
+      "name": "convertStreamFromZeroCopy",
+      "docs": [
+        "Convert a zero-copy stream back to the newest stream layout. The signer needs to be the sender or the",
+        "recipient."
+      ],
+      "accounts": [
+        {
+          "name": "stream",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Zero-copy stream PDA account."
+          ]
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Signer wallet. Signer needs to be either the sender or the recipient."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Solana system program."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6039,
-      "name": "StreamNotEnded",
-      "msg": "The stream has not ended. Should have ended and nat been cancelled"
-    }
-  ]
-};
-
-export const IDL: Superstream = {
-  "version": "0.3.3",
-  "name": "superstream",
-  "docs": [
-    "Module for superstream cpi methods and other utilities."
-  ],
-  "instructions": [
-    {
-      "name": "createPrepaid",
+      "name": "withdrawZeroCopy",
       "docs": [
-        "Create a new prepaid stream.",
-        "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "Withdraw recipient funds from a zero-copy stream. See [`withdraw`](crate::superstream::withdraw)."
       ],
       "accounts": [
         {
@@ -1438,15 +9731,16 @@ export const IDL: Superstream = {
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Stream PDA account. This is initialized by the program."
+            "Zero-copy stream PDA account."
           ]
         },
         {
-          "name": "sender",
-          "isMut": true,
+          "name": "signer",
+          "isMut": false,
           "isSigner": true,
           "docs": [
-            "Stream sender wallet."
+            "Signer wallet. Anybody can call the withdraw method. The withdrawn amount always goes to the recipient of the",
+            "stream."
           ]
         },
         {
@@ -1458,124 +9752,62 @@ export const IDL: Superstream = {
           ]
         },
         {
-          "name": "senderToken",
+          "name": "signerToken",
           "isMut": true,
           "isSigner": false,
+          "isOptional": true,
           "docs": [
-            "Associated token account of the sender."
+            "Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels",
+            "an insolvent stream. If not provided, the whole deposit goes to the recipient."
           ]
         },
         {
-          "name": "escrowToken",
+          "name": "recipientToken",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token escrow account holding the funds for this stream."
+            "Associated token account of the recipient."
           ]
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "escrowToken",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "SPL token program."
+            "Associated token escrow account holding the funds for this stream."
           ]
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "mintStats",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "Solana system program."
+            "Stats PDA account of the mint."
           ]
-        }
-      ],
-      "args": [
-        {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
-        },
-        {
-          "name": "recipient",
-          "type": "publicKey"
-        },
-        {
-          "name": "startsAt",
-          "type": "u64"
-        },
-        {
-          "name": "endsAt",
-          "type": "u64"
-        },
-        {
-          "name": "initialAmount",
-          "type": "u64"
-        },
-        {
-          "name": "flowInterval",
-          "type": "u64"
-        },
-        {
-          "name": "flowRate",
-          "type": "u64"
-        },
-        {
-          "name": "senderCanCancel",
-          "type": "bool"
-        },
-        {
-          "name": "senderCanCancelAt",
-          "type": "u64"
-        },
-        {
-          "name": "senderCanChangeSender",
-          "type": "bool"
-        },
-        {
-          "name": "senderCanChangeSenderAt",
-          "type": "u64"
-        },
-        {
-          "name": "senderCanPause",
-          "type": "bool"
-        },
-        {
-          "name": "senderCanPauseAt",
-          "type": "u64"
-        },
-        {
-          "name": "recipientCanResumePauseBySender",
-          "type": "bool"
-        },
-        {
-          "name": "recipientCanResumePauseBySenderAt",
-          "type": "u64"
         },
         {
-          "name": "anyoneCanWithdrawForRecipient",
-          "type": "bool"
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
         },
         {
-          "name": "anyoneCanWithdrawForRecipientAt",
-          "type": "u64"
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "createNonPrepaid",
+      "name": "topupNonPrepaidZeroCopy",
       "docs": [
-        "Create a new non-prepaid stream.",
-        "",
-        "# Arguments",
-        "",
-        "* `topup_amount` - Initial topup amount for the stream. The topup amount should be >= minimum deposit required.",
-        "See [`DEPOSIT_AMOUNT_PERIOD_IN_SECS`](crate::state::DEPOSIT_AMOUNT_PERIOD_IN_SECS) for more information.",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "Topup a non-prepaid zero-copy stream. See [`topup_non_prepaid`](crate::superstream::topup_non_prepaid)."
       ],
       "accounts": [
         {
@@ -1583,15 +9815,16 @@ export const IDL: Superstream = {
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Stream PDA account. This is initialized by the program."
+            "Zero-copy stream PDA account."
           ]
         },
         {
-          "name": "sender",
-          "isMut": true,
+          "name": "signer",
+          "isMut": false,
           "isSigner": true,
           "docs": [
-            "Stream sender wallet."
+            "Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But",
+            "the refund when the stream gets cancelled will only go to the stream sender."
           ]
         },
         {
@@ -1603,11 +9836,11 @@ export const IDL: Superstream = {
           ]
         },
         {
-          "name": "senderToken",
+          "name": "signerToken",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token account of the sender."
+            "Associated token account of the signer."
           ]
         },
         {
@@ -1619,109 +9852,46 @@ export const IDL: Superstream = {
           ]
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "mintStats",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "SPL token program."
+            "Stats PDA account of the mint."
           ]
         },
         {
-          "name": "systemProgram",
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "Solana system program."
+            "SPL token program."
           ]
         }
       ],
       "args": [
         {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
-        },
-        {
-          "name": "recipient",
-          "type": "publicKey"
-        },
-        {
-          "name": "startsAt",
-          "type": "u64"
-        },
-        {
-          "name": "endsAt",
-          "type": "u64"
-        },
-        {
-          "name": "initialAmount",
-          "type": "u64"
-        },
-        {
-          "name": "flowInterval",
-          "type": "u64"
-        },
-        {
-          "name": "flowRate",
-          "type": "u64"
-        },
-        {
-          "name": "senderCanCancel",
-          "type": "bool"
-        },
-        {
-          "name": "senderCanCancelAt",
-          "type": "u64"
-        },
-        {
-          "name": "senderCanChangeSender",
-          "type": "bool"
-        },
-        {
-          "name": "senderCanChangeSenderAt",
-          "type": "u64"
-        },
-        {
-          "name": "senderCanPause",
-          "type": "bool"
-        },
-        {
-          "name": "senderCanPauseAt",
-          "type": "u64"
-        },
-        {
-          "name": "recipientCanResumePauseBySender",
-          "type": "bool"
-        },
-        {
-          "name": "recipientCanResumePauseBySenderAt",
+          "name": "topupAmount",
           "type": "u64"
         },
         {
-          "name": "anyoneCanWithdrawForRecipient",
+          "name": "isStrict",
           "type": "bool"
-        },
-        {
-          "name": "anyoneCanWithdrawForRecipientAt",
-          "type": "u64"
-        },
-        {
-          "name": "topupAmount",
-          "type": "u64"
         }
-      ]
+      ],
+      "returns": "u64"
     },
     {
-      "name": "cancel",
+      "name": "cancelZeroCopy",
       "docs": [
-        "Cancel a stream.",
-        "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "Cancel a zero-copy stream. See [`cancel`](crate::superstream::cancel)."
       ],
       "accounts": [
         {
@@ -1729,7 +9899,7 @@ export const IDL: Superstream = {
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Stream PDA account."
+            "Zero-copy stream PDA account."
           ]
         },
         {
@@ -1737,8 +9907,8 @@ export const IDL: Superstream = {
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent. After insolvency,",
-            "anyone can cancel."
+            "Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the",
+            "insolvency grace period. After that, anyone can cancel."
           ]
         },
         {
@@ -1792,211 +9962,260 @@ export const IDL: Superstream = {
           ]
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "mintStats",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "SPL token program."
+            "Stats PDA account of the mint."
           ]
-        }
-      ],
-      "args": [
-        {
-          "name": "seed",
-          "type": "u64"
         },
         {
-          "name": "name",
-          "type": "string"
+          "name": "globalStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Global stats PDA account."
+          ]
         },
         {
-          "name": "recipient",
-          "type": "publicKey"
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token program."
+          ]
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "withdrawExcessTopupNonPrepaidEnded",
+      "name": "initializeMintRegistry",
       "docs": [
-        "Withdraw excess sender topup from a non-prepaid stream.",
+        "Initialize the mint registry. Only the upgrade authority of the program can initialize the registry and they",
+        "become the authority of the registry.",
         "",
         "# Arguments",
         "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "For more information on the arguments, see fields of the [`MintRegistry`] struct."
       ],
       "accounts": [
         {
-          "name": "stream",
+          "name": "mintRegistry",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Stream PDA account."
+            "Mint registry PDA account. This is initialized by the program."
           ]
         },
         {
-          "name": "signer",
-          "isMut": false,
+          "name": "authority",
+          "isMut": true,
           "isSigner": true,
           "docs": [
-            "Signer wallet."
+            "Upgrade authority of the program. They become the authority of the mint registry."
           ]
         },
         {
-          "name": "sender",
+          "name": "program",
           "isMut": false,
           "isSigner": false,
-          "docs": [
-            "Stream sender account.",
-            "",
-            "to the stream sender."
+          "docs": [
+            "Superstream program."
           ]
         },
         {
-          "name": "mint",
+          "name": "programData",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "SPL token mint account."
+            "Program data account of the Superstream program."
           ]
         },
         {
-          "name": "senderToken",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false,
           "docs": [
-            "Associated token account of the sender."
+            "Solana system program."
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "escrowToken",
+          "name": "isEnabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setMintRegistryEnabled",
+      "docs": [
+        "Enable or disable the mint registry. When disabled, streams can be created for any mint.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`MintRegistry`] struct."
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token escrow account holding the funds for this stream."
+            "Mint registry PDA account."
           ]
         },
         {
-          "name": "tokenProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false,
+          "isSigner": true,
           "docs": [
-            "SPL token program."
+            "Authority of the mint registry."
           ]
         }
       ],
       "args": [
         {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
+          "name": "isEnabled",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "topupNonPrepaid",
+      "name": "changeMintRegistryAuthority",
       "docs": [
-        "Topup a non-prepaid stream.",
+        "Change the authority of the mint registry.",
         "",
         "# Arguments",
         "",
-        "* `topup_amount` - Topup amount for the stream. The topup amount should be <= maximum acceptable topup amount.",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "* `new_authority` - The new authority"
       ],
       "accounts": [
         {
-          "name": "stream",
+          "name": "mintRegistry",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Stream PDA account."
+            "Mint registry PDA account."
           ]
         },
         {
-          "name": "signer",
+          "name": "authority",
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "Signer wallet. Anyone can topup a stream. But the refund when the stream gets cancelled will only go to the",
-            "stream sender."
+            "Authority of the mint registry."
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "mint",
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "addAllowedMint",
+      "docs": [
+        "Add a mint to the mint registry."
+      ],
+      "accounts": [
+        {
+          "name": "mintRegistry",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "SPL token mint account."
+            "Mint registry PDA account."
           ]
         },
         {
-          "name": "signerToken",
+          "name": "allowedMint",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Associated token account of the signer."
+            "Allowed mint PDA account. This is initialized by the program."
           ]
         },
         {
-          "name": "escrowToken",
+          "name": "authority",
           "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Authority of the mint registry."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
           "isSigner": false,
           "docs": [
-            "Associated token escrow account holding the funds for this stream."
+            "SPL token mint account."
           ]
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "SPL token program."
+            "Solana system program."
           ]
         }
       ],
-      "args": [
+      "args": []
+    },
+    {
+      "name": "removeAllowedMint",
+      "docs": [
+        "Remove a mint from the mint registry. Existing streams of the mint are not affected."
+      ],
+      "accounts": [
         {
-          "name": "seed",
-          "type": "u64"
+          "name": "mintRegistry",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Mint registry PDA account."
+          ]
         },
         {
-          "name": "name",
-          "type": "string"
+          "name": "allowedMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Allowed mint PDA account. This is closed by the program and the rent is returned to the authority."
+          ]
         },
         {
-          "name": "topupAmount",
-          "type": "u64"
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Authority of the mint registry."
+          ]
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "changeSenderNonPrepaid",
+      "name": "getWithdrawable",
       "docs": [
-        "Change sender of a non-prepaid stream.",
+        "Get the withdrawable amount of a stream at the current time. The result is returned through the return data of",
+        "the transaction. Programs calling this through CPI can decode it using `get()` on the returned value.",
         "",
         "# Arguments",
         "",
-        "* `new_sender` - The new sender",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "For more information on the arguments, see fields of the [`Stream`] struct."
       ],
       "accounts": [
         {
           "name": "stream",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "docs": [
             "Stream PDA account."
           ]
         },
-        {
-          "name": "sender",
-          "isMut": false,
-          "isSigner": true
-        },
         {
           "name": "mint",
           "isMut": false,
@@ -2014,42 +10233,27 @@ export const IDL: Superstream = {
         {
           "name": "name",
           "type": "string"
-        },
-        {
-          "name": "newSender",
-          "type": "publicKey"
         }
-      ]
+      ],
+      "returns": {
+        "defined": "WithdrawableView"
+      }
     },
     {
-      "name": "withdraw",
+      "name": "getWithdrawableV2",
       "docs": [
-        "Withdraw recipient funds from a stream.",
-        "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
+        "Same as [`get_withdrawable`](crate::superstream::get_withdrawable), but the seed and name are read from the",
+        "stream account instead of being passed as arguments."
       ],
       "accounts": [
         {
           "name": "stream",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "docs": [
             "Stream PDA account."
           ]
         },
-        {
-          "name": "signer",
-          "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
-            "signer. Recipient is passed as an argument, based on which the stream PDA is accessed, so if a malicious user",
-            "tries to send themselves as a recipient, but a different stream account, the constraint for the stream account",
-            "will fail."
-          ]
-        },
         {
           "name": "mint",
           "isMut": false,
@@ -2057,29 +10261,38 @@ export const IDL: Superstream = {
           "docs": [
             "SPL token mint account."
           ]
-        },
-        {
-          "name": "recipientToken",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Associated token account of the recipient."
-          ]
-        },
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "WithdrawableView"
+      }
+    },
+    {
+      "name": "getSolvency",
+      "docs": [
+        "Get the solvency of a stream at the current time. The result is returned through the return data of the",
+        "transaction. Programs calling this through CPI can decode it using `get()` on the returned value.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
         {
-          "name": "escrowToken",
-          "isMut": true,
+          "name": "stream",
+          "isMut": false,
           "isSigner": false,
           "docs": [
-            "Associated token escrow account holding the funds for this stream."
+            "Stream PDA account."
           ]
         },
         {
-          "name": "tokenProgram",
+          "name": "mint",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "SPL token program."
+            "SPL token mint account."
           ]
         }
       ],
@@ -2091,44 +10304,27 @@ export const IDL: Superstream = {
         {
           "name": "name",
           "type": "string"
-        },
-        {
-          "name": "recipient",
-          "type": "publicKey"
         }
-      ]
+      ],
+      "returns": {
+        "defined": "SolvencyView"
+      }
     },
     {
-      "name": "withdrawAndChangeRecipient",
+      "name": "getSolvencyV2",
       "docs": [
-        "Withdraw recipient funds from a stream and change recipient of a stream.",
-        "",
-        "# Arguments",
-        "",
-        "* `new_recipient` - The new recipient",
-        "",
-        "For more information on the other arguments, see fields of the [`Stream`] struct."
+        "Same as [`get_solvency`](crate::superstream::get_solvency), but the seed and name are read from the stream",
+        "account instead of being passed as arguments."
       ],
       "accounts": [
         {
           "name": "stream",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "docs": [
             "Stream PDA account."
           ]
         },
-        {
-          "name": "signer",
-          "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the",
-            "signer. Recipient is passed as an argument, based on which the stream PDA is accessed, so if a malicious user",
-            "tries to send themselves as a recipient, but a different stream account, the constraint for the stream account",
-            "will fail."
-          ]
-        },
         {
           "name": "mint",
           "isMut": false,
@@ -2136,29 +10332,39 @@ export const IDL: Superstream = {
           "docs": [
             "SPL token mint account."
           ]
-        },
-        {
-          "name": "recipientToken",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Associated token account of the recipient."
-          ]
-        },
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "SolvencyView"
+      }
+    },
+    {
+      "name": "getMaxTopup",
+      "docs": [
+        "Get the maximum acceptable topup amount of a stream at the current time. The result is returned through the",
+        "return data of the transaction. Programs calling this through CPI can decode it using `get()` on the returned",
+        "value.",
+        "",
+        "# Arguments",
+        "",
+        "For more information on the arguments, see fields of the [`Stream`] struct."
+      ],
+      "accounts": [
         {
-          "name": "escrowToken",
-          "isMut": true,
+          "name": "stream",
+          "isMut": false,
           "isSigner": false,
           "docs": [
-            "Associated token escrow account holding the funds for this stream."
+            "Stream PDA account."
           ]
         },
         {
-          "name": "tokenProgram",
+          "name": "mint",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "SPL token program."
+            "SPL token mint account."
           ]
         }
       ],
@@ -2170,461 +10376,1560 @@ export const IDL: Superstream = {
         {
           "name": "name",
           "type": "string"
-        },
+        }
+      ],
+      "returns": {
+        "defined": "MaxTopupView"
+      }
+    },
+    {
+      "name": "getMaxTopupV2",
+      "docs": [
+        "Same as [`get_max_topup`](crate::superstream::get_max_topup), but the seed and name are read from the stream",
+        "account instead of being passed as arguments."
+      ],
+      "accounts": [
         {
-          "name": "recipient",
-          "type": "publicKey"
+          "name": "stream",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Stream PDA account."
+          ]
         },
         {
-          "name": "newRecipient",
-          "type": "publicKey"
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "SPL token mint account."
+          ]
         }
-      ]
+      ],
+      "args": [],
+      "returns": {
+        "defined": "MaxTopupView"
+      }
+    }
+  ],
+  "accounts": [
+    {
+      "name": "stream",
+      "docs": [
+        "A payment stream with support for SPL tokens, prepaid and limited upfront payment, unlimited lifetime, cliffs and",
+        "cancellations.",
+        "",
+        "Possible states of a stream:",
+        "- Not started",
+        "- Pending recipient acceptance",
+        "- Scheduled",
+        "- Cancelled before start",
+        "- Started but not stopped",
+        "- Streaming",
+        "- Paused",
+        "- Insolvent",
+        "- Stopped",
+        "- Cancelled after start",
+        "- Ended"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "isPrepaid",
+            "docs": [
+              "If true, the stream is prepaid - all the required amount needs to be deposited on creation. Prepaid streams",
+              "cannot have unlimited lifetime."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "mint",
+            "docs": [
+              "SPL token mint address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "sender",
+            "docs": [
+              "Sender address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "recipient",
+            "docs": [
+              "Recipient address."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "createdAt",
+            "docs": [
+              "Time at which the stream was created."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "startsAt",
+            "docs": [
+              "Start time of the stream.",
+              "",
+              "INVARIANT: >= created_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "endsAt",
+            "docs": [
+              "End time of the stream. If the stream is unbounded, this can be 0 to indicate no end time.",
+              "",
+              "INVARIANT: prepaid: >= starts_at",
+              "INVARIANT: unbounded: == 0 || >= starts_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "initialAmount",
+            "docs": [
+              "Amount available to the recipient once stream starts."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flowInterval",
+            "docs": [
+              "Flow interval is the interval in which flow payments are released. If == FLOW_RATE_SCALE, the flow rate is a",
+              "fixed-point amount per second. See [`FlowRate`]."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flowRate",
+            "docs": [
+              "Flow rate is the number of tokens to stream per interval."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isCancelled",
+            "docs": [
+              "If true, the stream has been cancelled."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isCancelledBeforeStart",
+            "docs": [
+              "If true, the stream has been cancelled before start.",
+              "",
+              "INVARIANT: !is_cancelled => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isCancelledBySender",
+            "docs": [
+              "If true, the stream has been cancelled by the sender.",
+              "",
+              "INVARIANT: !is_cancelled || !sender_can_cancel => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "cancelledAt",
+            "docs": [
+              "Time at which the stream was cancelled. If it is > 0, it means the stream has been cancelled and any funds in",
+              "the escrow account not available to be withdrawn by the recipient have been retrieved.",
+              "",
+              "INVARIANT: cancelled_at > 0 iff is_cancelled == true"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanCancel",
+            "docs": [
+              "True if a solvent stream can be cancelled by the sender."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanCancelAt",
+            "docs": [
+              "Time at which the sender is allowed to cancel a solvent stream. This can be in the future."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSender",
+            "docs": [
+              "True if the sender can change the sender of the stream who will do the upcoming topups.",
+              "",
+              "INVARIANT: prepaid: false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanChangeSenderAt",
+            "docs": [
+              "Time at which the sender is allowed to change the sender.",
+              "",
+              "INVARIANT: prepaid: == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isPaused",
+            "docs": [
+              "If true, the stream is paused.",
+              "",
+              "INVARIANT: prepaid: == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isPausedBySender",
+            "docs": [
+              "If true, the stream is paused by sender.",
+              "",
+              "INVARIANT: prepaid: == false",
+              "INVARIANT: runtime: unbounded: !is_paused || !sender_can_pause => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanPause",
+            "docs": [
+              "True if a stream can be paused by the sender.",
+              "",
+              "INVARIANT: prepaid: false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "senderCanPauseAt",
+            "docs": [
+              "Time at which the sender is allowed to pause a stream.",
+              "",
+              "INVARIANT: prepaid: == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySender",
+            "docs": [
+              "True if a stream can be resumed by the recipient if it was paused by the sender.",
+              "",
+              "INVARIANT: prepaid: false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderAt",
+            "docs": [
+              "Time at which the recipient is allowed to resume a stream which was paused by the sender.",
+              "",
+              "INVARIANT: prepaid: == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipient",
+            "docs": [
+              "True if anyone can withdraw on behalf of the recipient. The amount will go in recipients' account."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientAt",
+            "docs": [
+              "Time at which anyone can withdraw on behalf of the recipient."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastResumedAt",
+            "docs": [
+              "Time at which the stream was last resumed.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: (== 0 || >= starts_at) && (ends_at == 0 || < ends_at)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "accumulatedActiveTime",
+            "docs": [
+              "Total accumulated active (!is_paused) time since starts_at. This does not include (current_time -",
+              "last_resumed_at) time if the stream is not paused.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: == 0 || (current_time > starts_at && == current_time - starts_at - total_paused_time)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalWithdrawnAmount",
+            "docs": [
+              "Total amount withdrawn by the recipient.",
+              "",
+              "INVARIANT: runtime: prepaid: <= amount_owed && <= prepaid_amount_needed",
+              "INVARIANT: runtime: unbounded: <= amount_owed && <= total_topup_amount"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAt",
+            "docs": [
+              "Last time at which recipient withdrew any amount."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAmount",
+            "docs": [
+              "Last amount which recipient withdrew."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalTopupAmount",
+            "docs": [
+              "Total topup amount added for the stream.",
+              "",
+              "INVARIANT: prepaid: == total_prepaid_amount",
+              "INVARIANT: unbounded: >= initial_amount + streaming_amount_owed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAt",
+            "docs": [
+              "Last time at which sender topped up the stream."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAmount",
+            "docs": [
+              "Last topup amount."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "depositNeeded",
+            "docs": [
+              "Total deposit amount needed for the non-prepaid stream. These are needed in case the sender does not topup the",
+              "stream in time and the amount owed becomes > total topup amount. When that happens and the insolvency grace",
+              "period is over, anyone can cancel the stream. The deposit amount will be split between the recipient and",
+              "whoever finds the insolvency and cancels the stream as a reward.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: == DEPOSIT_AMOUNT_PERIOD_IN_SECS of streaming payments"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "version",
+            "docs": [
+              "Layout version of the stream account. It is at the same offset in all the layouts, so that the layout of an",
+              "account can be found before deserializing it. See [`VersionedStream`].",
+              "",
+              "INVARIANT: == LEGACY_STREAM_LAYOUT_VERSION || == STREAM_LAYOUT_VERSION"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "insolvencyGracePeriod",
+            "docs": [
+              "Active (!is_paused) streaming time after the stream becomes insolvent during which only the sender can cure the",
+              "insolvency by topping up. The flow keeps accruing as debt which is covered by the deposit. Only after this",
+              "period, the stream can be cancelled by anyone and the deposit is given as a reward.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: <= DEPOSIT_AMOUNT_PERIOD_IN_SECS"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipientDepositShareBps",
+            "docs": [
+              "Share of the deposit (in basis points) given to the recipient when an insolvent stream is cancelled. The rest",
+              "of the deposit is given as a reward to whoever cancels the stream.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: unbounded: <= BPS_DENOMINATOR"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "settlementAmount",
+            "docs": [
+              "Total amount owed to the recipient proposed by either the sender or the recipient to terminate the stream. If",
+              "the other party accepts the settlement, the stream is cancelled and the escrow is split accordingly.",
+              "",
+              "INVARIANT: !is_settlement_proposed && !is_settled => == 0"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isSettlementProposed",
+            "docs": [
+              "If true, a settlement has been proposed and is waiting to be accepted.",
+              "",
+              "INVARIANT: is_cancelled => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isSettlementProposedBySender",
+            "docs": [
+              "If true, the settlement has been proposed by the sender.",
+              "",
+              "INVARIANT: !is_settlement_proposed => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isSettled",
+            "docs": [
+              "If true, the stream has been terminated with a settlement accepted by both parties.",
+              "",
+              "INVARIANT: is_settled => is_cancelled && total_topup_amount == settlement_amount"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "arbiter",
+            "docs": [
+              "Arbiter address. The arbiter is a neutral third party who can freeze the stream in case of a dispute and later",
+              "resolve it by deciding how the escrow is split. If it is the default public key, the stream has no arbiter."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "isFrozen",
+            "docs": [
+              "If true, the stream has been frozen by the arbiter. Withdrawals, cancellations, settlements and refunds are",
+              "halted until the arbiter unfreezes or resolves the stream. The flow is not affected.",
+              "",
+              "INVARIANT: arbiter == Pubkey::default() || is_cancelled => == false"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isPendingAcceptance",
+            "docs": [
+              "If true, the stream is waiting for the recipient to accept it and the flow has not started. When the recipient",
+              "accepts, the schedule is shifted so that the stream effectively starts at the time of acceptance if it was",
+              "scheduled to start earlier. Before that, the sender can cancel the stream and recover the full escrow."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "pendingSender",
+            "docs": [
+              "Proposed new sender address. The sender changes only when the new sender accepts. If it is the default public",
+              "key, no sender change has been proposed.",
+              "",
+              "INVARIANT: prepaid: == Pubkey::default()"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingRecipient",
+            "docs": [
+              "Proposed new recipient address. The recipient changes only when the new recipient accepts. If it is the default",
+              "public key, no recipient change has been proposed."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "senderCanCancelExpiresAt",
+            "docs": [
+              "Time after which the sender is no longer allowed to cancel a solvent stream. If it is 0, the permission never",
+              "expires.",
+              "",
+              "INVARIANT: == 0 || > sender_can_cancel_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSenderExpiresAt",
+            "docs": [
+              "Time after which the sender is no longer allowed to change the sender. If it is 0, the permission never expires.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: == 0 || > sender_can_change_sender_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "senderCanPauseExpiresAt",
+            "docs": [
+              "Time after which the sender is no longer allowed to pause a stream. If it is 0, the permission never expires.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: == 0 || > sender_can_pause_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderExpiresAt",
+            "docs": [
+              "Time after which the recipient is no longer allowed to resume a stream which was paused by the sender. If it is",
+              "0, the permission never expires.",
+              "",
+              "INVARIANT: prepaid: == 0",
+              "INVARIANT: == 0 || > recipient_can_resume_pause_by_sender_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientExpiresAt",
+            "docs": [
+              "Time after which anyone is no longer allowed to withdraw on behalf of the recipient. If it is 0, the permission",
+              "never expires.",
+              "",
+              "INVARIANT: == 0 || > anyone_can_withdraw_for_recipient_at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "derivation",
+            "docs": [
+              "Mode used to derive the stream PDA address."
+            ],
+            "type": {
+              "defined": "StreamDerivation"
+            }
+          },
+          {
+            "name": "derivationSender",
+            "docs": [
+              "Sender at the time of creation if it is part of the stream PDA seeds. It doesn't change when the sender is",
+              "changed. If it is the default public key, the sender is not part of the seeds.",
+              "",
+              "INVARIANT: !derivation.includes_sender() => == Pubkey::default()"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "derivationRecipient",
+            "docs": [
+              "Recipient at the time of creation if it is part of the stream PDA seeds. It doesn't change when the recipient",
+              "is changed. If it is the default public key, the recipient is not part of the seeds.",
+              "",
+              "INVARIANT: !derivation.includes_recipient() => == Pubkey::default()"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "isCountedInMintStats",
+            "docs": [
+              "True if the stream is counted in the [`MintStats`] of its mint. Streams created before mint stats were",
+              "introduced are not counted."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "seed",
+            "docs": [
+              "Seed of the stream PDA. It's upto the client how they choose the seed. Each tuple (seed, mint, name) corresponds",
+              "to a unique stream for a particular derivation mode and derivation keys."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "name",
+            "docs": [
+              "Name of the stream. Should be unique for a particular set of (seed, mint). Names longer than 32 bytes are hashed",
+              "to derive the stream PDA address. See [`get_stream_name_seed`](crate::get_stream_name_seed).",
+              "",
+              "INVARIANT: Length <= 100 unicode chars or 400 bytes"
+            ],
+            "type": "string"
+          }
+        ]
+      }
     },
     {
-      "name": "pauseNonPrepaid",
+      "name": "zeroCopyStream",
       "docs": [
-        "Pause a non-prepaid stream.",
+        "A stream in the zero-copy layout. Zero-copy streams are accessed in place through an `AccountLoader` instead of",
+        "being deserialized and serialized again by every instruction. The name is stored in a fixed-size buffer, the booleans",
+        "are packed in [`Self::flags`] and the fields are ordered so that they are aligned without any padding.",
         "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
-      ],
-      "accounts": [
-        {
-          "name": "stream",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Stream PDA account."
-          ]
-        },
-        {
-          "name": "signer",
-          "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient."
-          ]
-        },
-        {
-          "name": "mint",
-          "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "SPL token mint account."
-          ]
-        }
+        "Streams are converted to and from this layout by the `convert_stream_to_zero_copy` and",
+        "`convert_stream_from_zero_copy` instructions. Zero-copy streams can only be used with `withdraw_zero_copy`,",
+        "`topup_non_prepaid_zero_copy` and `cancel_zero_copy`. They need to be converted back for everything else. See",
+        "[`Stream`] for the documentation of the fields."
       ],
-      "args": [
-        {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
-        }
-      ]
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "sender",
+            "type": "publicKey"
+          },
+          {
+            "name": "recipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "arbiter",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingSender",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingRecipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "derivationSender",
+            "type": "publicKey"
+          },
+          {
+            "name": "derivationRecipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "createdAt",
+            "type": "u64"
+          },
+          {
+            "name": "startsAt",
+            "type": "u64"
+          },
+          {
+            "name": "endsAt",
+            "type": "u64"
+          },
+          {
+            "name": "initialAmount",
+            "type": "u64"
+          },
+          {
+            "name": "flowInterval",
+            "type": "u64"
+          },
+          {
+            "name": "flowRate",
+            "type": "u64"
+          },
+          {
+            "name": "cancelledAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanCancelAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSenderAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanPauseAt",
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderAt",
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientAt",
+            "type": "u64"
+          },
+          {
+            "name": "lastResumedAt",
+            "type": "u64"
+          },
+          {
+            "name": "accumulatedActiveTime",
+            "type": "u64"
+          },
+          {
+            "name": "totalWithdrawnAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAt",
+            "type": "u64"
+          },
+          {
+            "name": "lastWithdrawnAmount",
+            "type": "u64"
+          },
+          {
+            "name": "totalTopupAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAt",
+            "type": "u64"
+          },
+          {
+            "name": "lastTopupAmount",
+            "type": "u64"
+          },
+          {
+            "name": "depositNeeded",
+            "type": "u64"
+          },
+          {
+            "name": "insolvencyGracePeriod",
+            "type": "u64"
+          },
+          {
+            "name": "settlementAmount",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanCancelExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanChangeSenderExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "senderCanPauseExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "recipientCanResumePauseBySenderExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "anyoneCanWithdrawForRecipientExpiresAt",
+            "type": "u64"
+          },
+          {
+            "name": "seed",
+            "type": "u64"
+          },
+          {
+            "name": "flags",
+            "docs": [
+              "Booleans of the stream packed as bit flags. See the `ZeroCopyStream::IS_*` and `ZeroCopyStream::*_CAN_*`",
+              "constants."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "recipientDepositShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "nameLength",
+            "docs": [
+              "Length of the name in bytes.",
+              "",
+              "INVARIANT: <= MAX_STREAM_NAME_BYTES"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "derivation",
+            "docs": [
+              "[`StreamDerivation`] as a `u8`."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "name",
+            "docs": [
+              "UTF-8 bytes of the name followed by zeroes."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                400
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "name": "resumeNonPrepaid",
+      "name": "mintRegistry",
       "docs": [
-        "Resume a non-prepaid stream.",
+        "Registry of SPL token mints for which streams can be created. There is only 1 registry per deployment of the",
+        "program.",
         "",
-        "# Arguments",
-        "",
-        "For more information on the arguments, see fields of the [`Stream`] struct."
-      ],
-      "accounts": [
-        {
-          "name": "stream",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Stream PDA account."
-          ]
-        },
-        {
-          "name": "signer",
-          "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if",
-            "the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender)."
-          ]
-        },
-        {
-          "name": "mint",
-          "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "SPL token mint account."
-          ]
-        }
+        "If the registry has not been initialized or is disabled, streams can be created for any mint, i.e. the deployment is",
+        "permissionless. If it is enabled, streams can only be created for mints which have an [`AllowedMint`] account."
       ],
-      "args": [
-        {
-          "name": "seed",
-          "type": "u64"
-        },
-        {
-          "name": "name",
-          "type": "string"
-        }
-      ]
-    }
-  ],
-  "accounts": [
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "docs": [
+              "Protocol authority address. Only the authority can manage the registry and the allowed mints."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "isEnabled",
+            "docs": [
+              "If true, the registry is enabled and streams can only be created for allowed mints."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
     {
-      "name": "stream",
+      "name": "allowedMint",
       "docs": [
-        "A payment stream with support for SPL tokens, prepaid and limited upfront payment, unlimited lifetime, cliffs and",
-        "cancellations.",
-        "",
-        "Possible states of a stream:",
-        "- Not started",
-        "- Scheduled",
-        "- Cancelled before start",
-        "- Started but not stopped",
-        "- Streaming",
-        "- Paused",
-        "- Stopped",
-        "- Cancelled after start",
-        "- Ended"
+        "An SPL token mint for which streams can be created when the [`MintRegistry`] is enabled."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "isPrepaid",
+            "name": "mint",
             "docs": [
-              "If true, the stream is prepaid - all the required amount needs to be deposited on creation. Prepaid streams",
-              "cannot have unlimited lifetime."
+              "SPL token mint address."
             ],
-            "type": "bool"
+            "type": "publicKey"
           },
           {
-            "name": "mint",
+            "name": "addedAt",
             "docs": [
-              "SPL token mint address."
+              "Time at which the mint was added to the registry."
             ],
-            "type": "publicKey"
+            "type": "u64"
           },
           {
-            "name": "sender",
+            "name": "bump",
             "docs": [
-              "Sender address."
+              "The PDA bump."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "streamIndex",
+      "docs": [
+        "Index of the streams of a user, either as the sender or as the recipient, so that wallets can list all the streams",
+        "of a user with a single account fetch. There are 2 index accounts per user - one for the streams sent by the user and",
+        "one for the streams received by the user.",
+        "",
+        "The index is updated when streams are created or closed and when their sender or recipient changes. Streams created",
+        "before the indexes were introduced are not indexed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "docs": [
+              "Address of the user whose streams are indexed."
             ],
             "type": "publicKey"
           },
           {
-            "name": "recipient",
+            "name": "bump",
             "docs": [
-              "Recipient address."
+              "The PDA bump."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "streams",
+            "docs": [
+              "Stream PDA addresses. The order is not maintained when streams are removed."
+            ],
+            "type": {
+              "vec": "publicKey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintStats",
+      "docs": [
+        "Figures of all the streams of an SPL token mint, so that dashboards can read protocol-wide figures with a single",
+        "account fetch instead of scanning all the stream accounts. There is 1 stats account per mint and it is created along",
+        "with the first stream of the mint.",
+        "",
+        "The stats are updated by every instruction which moves funds in or out of an escrow account or stops a stream.",
+        "Streams created before mint stats were introduced are not counted. Amounts never decrease and saturate instead of",
+        "overflowing, so that they can never block an instruction.",
+        "",
+        "INVARIANT: total_deposited_amount ==",
+        "total_withdrawn_amount + total_refunded_amount + total_rewarded_amount",
+        "+ sum of escrow amounts of the counted streams (excluding tokens sent to the escrows directly)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "docs": [
+              "SPL token mint address."
             ],
             "type": "publicKey"
           },
           {
-            "name": "createdAt",
+            "name": "streamCount",
             "docs": [
-              "Time at which the stream was created."
+              "Total number of streams created."
             ],
             "type": "u64"
           },
           {
-            "name": "startsAt",
+            "name": "activeStreamCount",
             "docs": [
-              "Start time of the stream.",
-              "",
-              "INVARIANT: >= created_at"
+              "Number of streams which have been neither cancelled nor closed. Streams which have ended are active till they",
+              "are closed."
             ],
             "type": "u64"
           },
           {
-            "name": "endsAt",
+            "name": "insolvencyCancellationCount",
             "docs": [
-              "End time of the stream. If the stream is unbounded, this can be 0 to indicate no end time.",
-              "",
-              "INVARIANT: prepaid: >= starts_at",
-              "INVARIANT: unbounded: == 0 || >= starts_at"
+              "Number of streams cancelled because they were insolvent and their insolvency grace period was over."
             ],
             "type": "u64"
           },
           {
-            "name": "initialAmount",
+            "name": "totalDepositedAmount",
             "docs": [
-              "Amount available to the recipient once stream starts."
+              "Total amount transferred to the escrow accounts by senders - prepaid amounts and topups."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "totalWithdrawnAmount",
+            "docs": [
+              "Total amount transferred from the escrow accounts to recipients."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "totalRefundedAmount",
+            "docs": [
+              "Total amount returned from the escrow accounts to senders."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "totalRewardedAmount",
+            "docs": [
+              "Total amount of deposits given as rewards to the accounts which cancelled insolvent streams."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Extra space for program upgrades."
+            ],
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "The PDA bump."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "globalStats",
+      "docs": [
+        "Figures of all the streams of all the mints, which are the sums of the figures of all the [`MintStats`] accounts.",
+        "There is a single global stats account and it is created along with the first stream.",
+        "",
+        "The global stats are updated with the same changes as the mint stats and count the same streams.",
+        "",
+        "INVARIANT: total_deposited_amount ==",
+        "total_withdrawn_amount + total_refunded_amount + total_rewarded_amount",
+        "+ sum of escrow amounts of the counted streams (excluding tokens sent to the escrows directly)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "streamCount",
+            "docs": [
+              "Total number of streams created."
             ],
             "type": "u64"
           },
           {
-            "name": "flowInterval",
+            "name": "activeStreamCount",
             "docs": [
-              "Flow interval is the interval in which flow payments are released."
+              "Number of streams which have been neither cancelled nor closed. Streams which have ended are active till they",
+              "are closed."
             ],
             "type": "u64"
           },
           {
-            "name": "flowRate",
+            "name": "insolvencyCancellationCount",
             "docs": [
-              "Flow rate is the number of tokens to stream per interval."
+              "Number of streams cancelled because they were insolvent and their insolvency grace period was over."
             ],
             "type": "u64"
           },
           {
-            "name": "isCancelled",
+            "name": "totalDepositedAmount",
             "docs": [
-              "If true, the stream has been cancelled."
+              "Total amount transferred to the escrow accounts by senders - prepaid amounts and topups."
             ],
-            "type": "bool"
+            "type": "u128"
           },
           {
-            "name": "isCancelledBeforeStart",
+            "name": "totalWithdrawnAmount",
             "docs": [
-              "If true, the stream has been cancelled before start.",
-              "",
-              "INVARIANT: !is_cancelled => == false"
+              "Total amount transferred from the escrow accounts to recipients."
             ],
-            "type": "bool"
+            "type": "u128"
           },
           {
-            "name": "isCancelledBySender",
+            "name": "totalRefundedAmount",
             "docs": [
-              "If true, the stream has been cancelled by the sender.",
-              "",
-              "INVARIANT: !is_cancelled || !sender_can_cancel => == false"
+              "Total amount returned from the escrow accounts to senders."
             ],
-            "type": "bool"
+            "type": "u128"
           },
           {
-            "name": "cancelledAt",
+            "name": "totalRewardedAmount",
             "docs": [
-              "Time at which the stream was cancelled. If it is > 0, it means the stream has been cancelled and any funds in",
-              "the escrow account not available to be withdrawn by the recipient have been retrieved.",
-              "",
-              "INVARIANT: cancelled_at > 0 iff is_cancelled == true"
+              "Total amount of deposits given as rewards to the accounts which cancelled insolvent streams."
             ],
-            "type": "u64"
+            "type": "u128"
           },
           {
-            "name": "senderCanCancel",
+            "name": "reserved",
             "docs": [
-              "True if a solvent stream can be cancelled by the sender."
+              "Extra space for program upgrades."
             ],
-            "type": "bool"
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
           },
           {
-            "name": "senderCanCancelAt",
+            "name": "bump",
             "docs": [
-              "Time at which the sender is allowed to cancel a solvent stream."
+              "The PDA bump."
             ],
-            "type": "u64"
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "CreateStreamParamsV1",
+      "docs": [
+        "Version 1 of [`CreateStreamParams`]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "docs": [
+              "Kind of the stream along with the options specific to the kind."
+            ],
+            "type": {
+              "defined": "StreamKind"
+            }
           },
           {
-            "name": "senderCanChangeSender",
+            "name": "schedule",
             "docs": [
-              "True if the sender can change the sender of the stream who will do the upcoming topups.",
-              "",
-              "INVARIANT: prepaid: false"
+              "Schedule and amounts of the streaming payments."
+            ],
+            "type": {
+              "defined": "StreamSchedule"
+            }
+          },
+          {
+            "name": "permissions",
+            "docs": [
+              "Permissions given by the sender."
+            ],
+            "type": {
+              "defined": "StreamPermissions"
+            }
+          },
+          {
+            "name": "arbiter",
+            "docs": [
+              "Arbiter of the stream. [`Pubkey::default()`] means no arbiter. See [`Stream::arbiter`]."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "requiresRecipientAcceptance",
+            "docs": [
+              "If true, the stream only starts flowing once the recipient accepts it."
             ],
             "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamSchedule",
+      "docs": [
+        "Schedule and amounts of the streaming payments of a stream. See the fields of the same name on [`Stream`]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startsAt",
+            "docs": [
+              "Start time of the stream. A time in the past means the stream starts right away."
+            ],
+            "type": "u64"
           },
           {
-            "name": "senderCanChangeSenderAt",
+            "name": "endsAt",
             "docs": [
-              "Time at which the sender is allowed to change the sender.",
-              "",
-              "INVARIANT: prepaid: == 0"
+              "End time of the stream. Can be 0 for non-prepaid streams to indicate no end time."
             ],
             "type": "u64"
           },
           {
-            "name": "isPaused",
+            "name": "initialAmount",
             "docs": [
-              "If true, the stream is paused.",
-              "",
-              "INVARIANT: prepaid: == false"
+              "Amount available to the recipient once stream starts."
             ],
-            "type": "bool"
+            "type": "u64"
+          },
+          {
+            "name": "flowInterval",
+            "docs": [
+              "Interval in which `flow_rate` tokens are streamed. [`FLOW_RATE_SCALE`] makes `flow_rate` a fixed-point amount per",
+              "second. See [`FlowRate`]."
+            ],
+            "type": "u64"
           },
           {
-            "name": "isPausedBySender",
+            "name": "flowRate",
+            "docs": [
+              "Number of tokens to stream per interval."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamPermissions",
+      "docs": [
+        "Permissions given by the sender of a stream. See the fields of the same name on [`Stream`]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "senderCanCancel",
             "docs": [
-              "If true, the stream is paused by sender.",
-              "",
-              "INVARIANT: prepaid: == false",
-              "INVARIANT: runtime: unbounded: !is_paused || !sender_can_pause => == false"
+              "Permission of the sender to cancel a solvent stream."
             ],
-            "type": "bool"
+            "type": {
+              "defined": "StreamPermission"
+            }
           },
           {
-            "name": "senderCanPause",
+            "name": "senderCanChangeSender",
             "docs": [
-              "True if a stream can be paused by the sender.",
-              "",
-              "INVARIANT: prepaid: false"
+              "Permission of the sender to change the sender."
             ],
-            "type": "bool"
+            "type": {
+              "defined": "StreamPermission"
+            }
           },
           {
-            "name": "senderCanPauseAt",
+            "name": "senderCanPause",
             "docs": [
-              "Time at which the sender is allowed to pause a stream.",
-              "",
-              "INVARIANT: prepaid: == 0"
+              "Permission of the sender to pause the stream."
             ],
-            "type": "u64"
+            "type": {
+              "defined": "StreamPermission"
+            }
           },
           {
             "name": "recipientCanResumePauseBySender",
             "docs": [
-              "True if a stream can be resumed by the recipient if it was paused by the sender.",
-              "",
-              "INVARIANT: prepaid: false"
+              "Permission of the recipient to resume a stream paused by the sender."
             ],
-            "type": "bool"
+            "type": {
+              "defined": "StreamPermission"
+            }
           },
           {
-            "name": "recipientCanResumePauseBySenderAt",
+            "name": "anyoneCanWithdrawForRecipient",
             "docs": [
-              "Time at which the recipient is allowed to resume a stream which was paused by the sender.",
-              "",
-              "INVARIANT: prepaid: == 0"
+              "Permission of anyone to withdraw on behalf of the recipient."
             ],
-            "type": "u64"
-          },
+            "type": {
+              "defined": "StreamPermission"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamPermission",
+      "docs": [
+        "A permission given on a stream, optionally unlocking and expiring at specific times."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "anyoneCanWithdrawForRecipient",
+            "name": "isAllowed",
             "docs": [
-              "True if anyone can withdraw on behalf of the recipient. The amount will go in recipients' account."
+              "If true, the permission is given."
             ],
             "type": "bool"
           },
           {
-            "name": "anyoneCanWithdrawForRecipientAt",
+            "name": "allowedAt",
             "docs": [
-              "Time at which anyone can withdraw on behalf of the recipient."
+              "Time from which the permission is active. 0 or a time in the past means it is active right away."
             ],
             "type": "u64"
           },
           {
-            "name": "lastResumedAt",
+            "name": "expiresAt",
             "docs": [
-              "Time at which the stream was last resumed.",
-              "",
-              "INVARIANT: prepaid: == 0",
-              "INVARIANT: unbounded: (== 0 || >= starts_at) && (ends_at == 0 || < ends_at)"
+              "Time after which the permission is no longer active. 0 means it never expires."
             ],
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "WithdrawableView",
+      "docs": [
+        "Withdrawable amount of a stream. Returned by the `get_withdrawable` instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "accumulatedActiveTime",
+            "name": "at",
             "docs": [
-              "Total accumulated active (!is_paused) time since starts_at. This does not include (current_time -",
-              "last_resumed_at) time if the stream is not paused.",
-              "",
-              "INVARIANT: prepaid: == 0",
-              "INVARIANT: unbounded: == 0 || (current_time > starts_at && == current_time - starts_at - total_paused_time)"
+              "Time at which the figures were computed."
             ],
             "type": "u64"
           },
           {
-            "name": "totalWithdrawnAmount",
+            "name": "amountOwed",
             "docs": [
-              "Total amount withdrawn by the recipient.",
-              "",
-              "INVARIANT: runtime: prepaid: <= amount_owed && <= prepaid_amount_needed",
-              "INVARIANT: runtime: unbounded: <= amount_owed && <= total_topup_amount"
+              "Total amount owed to the recipient."
             ],
             "type": "u64"
           },
           {
-            "name": "lastWithdrawnAt",
+            "name": "totalWithdrawnAmount",
             "docs": [
-              "Last time at which recipient withdrew any amount."
+              "Total amount withdrawn by the recipient."
             ],
             "type": "u64"
           },
           {
-            "name": "lastWithdrawnAmount",
+            "name": "withdrawableAmount",
             "docs": [
-              "Last amount which recipient withdrew."
+              "Amount the recipient can withdraw. This doesn't include the recipient's share of the deposit which is only",
+              "available once an insolvent stream is cancelled."
             ],
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "SolvencyView",
+      "docs": [
+        "Solvency of a stream. Returned by the `get_solvency` instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "totalTopupAmount",
+            "name": "at",
             "docs": [
-              "Total topup amount added for the stream.",
-              "",
-              "INVARIANT: prepaid: == total_prepaid_amount",
-              "INVARIANT: unbounded: >= initial_amount + streaming_amount_owed"
+              "Time at which the figures were computed."
             ],
             "type": "u64"
           },
           {
-            "name": "lastTopupAt",
+            "name": "status",
             "docs": [
-              "Last time at which sender topped up the stream."
+              "Status of the stream."
             ],
-            "type": "u64"
+            "type": {
+              "defined": "StreamStatus"
+            }
           },
           {
-            "name": "lastTopupAmount",
+            "name": "isSolvent",
             "docs": [
-              "Last topup amount."
+              "True if the amount owed to the recipient is covered by the total topup amount."
             ],
-            "type": "u64"
+            "type": "bool"
           },
           {
-            "name": "depositNeeded",
+            "name": "isInInsolvencyGracePeriod",
             "docs": [
-              "Total deposit amount needed for the non-prepaid stream. These are needed in case the sender does not topup the",
-              "stream in time and the amount owed becomes > total topup amount. When that happens, anyone can cancel the",
-              "stream. The deposit amount will be distributed as a reward to whoever finds the insolvency and cancels the",
-              "stream.",
-              "",
-              "INVARIANT: prepaid: == 0",
-              "INVARIANT: unbounded: == DEPOSIT_AMOUNT_PERIOD_IN_SECS of streaming payments"
+              "True if the stream is insolvent but still within the insolvency grace period."
             ],
-            "type": "u64"
+            "type": "bool"
           },
           {
-            "name": "reserved",
+            "name": "insolventAt",
             "docs": [
-              "Extra space for program upgrades."
+              "Time from which the stream is insolvent. See [`Stream::insolvent_at`] for more information."
             ],
             "type": {
-              "array": [
-                "u64",
-                16
-              ]
+              "option": "u64"
             }
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "MaxTopupView",
+      "docs": [
+        "Maximum acceptable topup amount of a stream. Returned by the `get_max_topup` instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "seed",
+            "name": "at",
             "docs": [
-              "Seed of the stream PDA. It's upto the client how they choose the seed. Each tuple (seed, mint, name) corresponds",
-              "to a unique stream."
+              "Time at which the figures were computed."
             ],
             "type": "u64"
           },
           {
-            "name": "bump",
+            "name": "hasNoLimit",
             "docs": [
-              "The PDA bump."
+              "True if there is no limit on the topup amount."
             ],
-            "type": "u8"
+            "type": "bool"
           },
           {
-            "name": "name",
+            "name": "maxAcceptableTopupAmount",
             "docs": [
-              "Name of the stream. Should be unique for a particular set of (seed, mint).",
-              "",
-              "INVARIANT: Length <= 100 unicode chars or 400 bytes"
+              "Maximum acceptable topup amount. Only valid if `has_no_limit` is false."
             ],
-            "type": "string"
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamDerivation",
+      "docs": [
+        "Mode used to derive the address of a stream PDA account.",
+        "",
+        "The seeds of the stream PDA account are `[STREAM_ACCOUNT_SEED, seed, mint, name_seed, sender_seed,",
+        "recipient_seed]`. The sender and recipient seeds are empty if they are not part of the derivation, so global streams",
+        "keep their addresses."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Global"
+          },
+          {
+            "name": "Sender"
+          },
+          {
+            "name": "SenderAndRecipient"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CreateStreamParams",
+      "docs": [
+        "Parameters of a new stream passed to the `create_stream` instruction. Every version is a separate variant, so new",
+        "options can be added in a new version without breaking the callers of the older versions."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "V1",
+            "fields": [
+              {
+                "defined": "CreateStreamParamsV1"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamKind",
+      "docs": [
+        "Kind of a stream."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Prepaid"
+          },
+          {
+            "name": "NonPrepaid",
+            "fields": [
+              {
+                "name": "topup_amount",
+                "docs": [
+                  "Initial topup amount for the stream. Should be >= the initial amount + 2 * the deposit needed. See",
+                  "[`DEPOSIT_AMOUNT_PERIOD_IN_SECS`]."
+                ],
+                "type": "u64"
+              },
+              {
+                "name": "insolvency_grace_period",
+                "docs": [
+                  "Active streaming time after insolvency during which only the sender can cure the insolvency. Should be <=",
+                  "[`DEPOSIT_AMOUNT_PERIOD_IN_SECS`]."
+                ],
+                "type": "u64"
+              },
+              {
+                "name": "recipient_deposit_share_bps",
+                "docs": [
+                  "Share of the deposit (in basis points) given to the recipient if the stream is cancelled after becoming",
+                  "insolvent. The rest is given to whoever cancels the stream."
+                ],
+                "type": "u16"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "StreamStatus",
+      "docs": [
+        "Status of a stream at a point in time. These match the states documented on the [`Stream`] struct."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Scheduled"
+          },
+          {
+            "name": "Streaming"
+          },
+          {
+            "name": "Paused"
+          },
+          {
+            "name": "Insolvent"
+          },
+          {
+            "name": "Ended"
+          },
+          {
+            "name": "Cancelled"
           }
         ]
       }
@@ -2830,6 +12135,141 @@ export const IDL: Superstream = {
       "code": 6039,
       "name": "StreamNotEnded",
       "msg": "The stream has not ended. Should have ended and nat been cancelled"
+    },
+    {
+      "code": 6040,
+      "name": "MintNotAllowed",
+      "msg": "The mint is not allowed by the mint registry. Streams cannot be created for this mint"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidNewAuthority",
+      "msg": "The new authority is invalid"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidInsolvencyGracePeriod",
+      "msg": "The insolvency grace period is more than the deposit amount period. Should be <= 8 hrs"
+    },
+    {
+      "code": 6043,
+      "name": "StreamInInsolvencyGracePeriod",
+      "msg": "The stream is insolvent but within the insolvency grace period. Only the sender can topup and only the sender or the recipient can cancel"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidRecipientDepositShare",
+      "msg": "The recipient deposit share is more than 100%. Should be <= 10000 basis points"
+    },
+    {
+      "code": 6045,
+      "name": "UserUnauthorizedToSettle",
+      "msg": "The user is not allowed to propose, cancel or accept a settlement. Should be the sender or the recipient of the stream and only the other party can accept a settlement"
+    },
+    {
+      "code": 6046,
+      "name": "SettlementNotProposed",
+      "msg": "No settlement has been proposed for the stream"
+    },
+    {
+      "code": 6047,
+      "name": "SettlementAmountMismatch",
+      "msg": "The settlement amount is different from the proposed settlement amount"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidSettlementAmount",
+      "msg": "The settlement amount is invalid. Should be >= total withdrawn amount and <= total amount paid by the sender"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidArbiter",
+      "msg": "The arbiter is invalid. Should be different from the sender and the recipient"
+    },
+    {
+      "code": 6050,
+      "name": "UserUnauthorizedToArbitrate",
+      "msg": "The user is not allowed to arbitrate. Should be the arbiter of the stream"
+    },
+    {
+      "code": 6051,
+      "name": "StreamIsFrozen",
+      "msg": "The stream is frozen by the arbiter. Should be a non-frozen stream"
+    },
+    {
+      "code": 6052,
+      "name": "StreamIsNotFrozen",
+      "msg": "The stream is not frozen. Should be a frozen stream"
+    },
+    {
+      "code": 6053,
+      "name": "StreamIsPendingAcceptance",
+      "msg": "The stream is pending recipient acceptance. Should be an accepted stream"
+    },
+    {
+      "code": 6054,
+      "name": "StreamIsNotPendingAcceptance",
+      "msg": "The stream is not pending recipient acceptance. Should be a stream pending recipient acceptance"
+    },
+    {
+      "code": 6055,
+      "name": "UserUnauthorizedToAccept",
+      "msg": "The user is not allowed to accept the stream. Should be the recipient of the stream"
+    },
+    {
+      "code": 6056,
+      "name": "SenderChangeNotProposed",
+      "msg": "No sender change has been proposed for the stream"
+    },
+    {
+      "code": 6057,
+      "name": "RecipientChangeNotProposed",
+      "msg": "No recipient change has been proposed for the stream"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidNewRecipient",
+      "msg": "The new recipient is invalid"
+    },
+    {
+      "code": 6059,
+      "name": "InvalidPermissionExpiry",
+      "msg": "The permission expiry time is invalid"
+    },
+    {
+      "code": 6060,
+      "name": "InvariantViolation",
+      "msg": "A stream invariant does not hold"
+    },
+    {
+      "code": 6061,
+      "name": "UserUnauthorizedToClose",
+      "msg": "The user is not allowed to close the stream. Should be the sender or the recipient of the stream"
+    },
+    {
+      "code": 6062,
+      "name": "StreamNotStopped",
+      "msg": "The stream has not stopped. Should have ended or been cancelled"
+    },
+    {
+      "code": 6063,
+      "name": "StreamHasFundsLeft",
+      "msg": "The stream still holds funds. Everything should have been withdrawn or refunded"
+    },
+    {
+      "code": 6064,
+      "name": "StreamNotMigrated",
+      "msg": "The stream is in the legacy account layout. It needs to be migrated before using this feature"
+    },
+    {
+      "code": 6065,
+      "name": "StreamAlreadyMigrated",
+      "msg": "The stream is already in the newest account layout"
+    },
+    {
+      "code": 6066,
+      "name": "UserUnauthorizedToConvert",
+      "msg": "The user is not allowed to convert the stream. Should be the sender or the recipient of the stream"
     }
   ]
 };
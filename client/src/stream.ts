@@ -238,6 +238,26 @@ export class Stream {
    */
   readonly depositNeeded: BN
 
+  /**
+   * Sender proposed by the current sender. The sender changes only when the pending sender accepts the change.
+   * `web3.PublicKey.default` if no change has been proposed.
+   */
+  readonly pendingSender: web3.PublicKey
+  /**
+   * Recipient proposed by the current recipient. The recipient changes only when the pending recipient accepts the
+   * change. `web3.PublicKey.default` if no change has been proposed.
+   */
+  readonly pendingRecipient: web3.PublicKey
+
+  /**
+   * Sender used to derive the stream PDA. `web3.PublicKey.default` if the sender isn't part of the PDA seeds.
+   */
+  readonly derivationSender: web3.PublicKey
+  /**
+   * Recipient used to derive the stream PDA. `web3.PublicKey.default` if the recipient isn't part of the PDA seeds.
+   */
+  readonly derivationRecipient: web3.PublicKey
+
   /**
    * Seed of the stream PDA. It's upto the client how they choose the seed. Each tuple (seed, mint, name) corresponds
    * to a unique stream.
@@ -301,6 +321,10 @@ export class Stream {
           lastTopupAt: BN
           lastTopupAmount: BN
           depositNeeded: BN
+          pendingSender: web3.PublicKey
+          pendingRecipient: web3.PublicKey
+          derivationSender: web3.PublicKey
+          derivationRecipient: web3.PublicKey
           seed: BN
           bump: number
         }
@@ -343,6 +367,10 @@ export class Stream {
     this.lastTopupAt = other.lastTopupAt
     this.lastTopupAmount = other.lastTopupAmount
     this.depositNeeded = other.depositNeeded
+    this.pendingSender = other.pendingSender
+    this.pendingRecipient = other.pendingRecipient
+    this.derivationSender = other.derivationSender
+    this.derivationRecipient = other.derivationRecipient
     this.seed = other.seed
     this.bump = other.bump
     this.name = other.name
@@ -412,7 +440,13 @@ export class Stream {
    * @returns The stream public key
    */
   readonly getStreamPublicKey = (): [web3.PublicKey, number] => {
-    return this.clientInternal.getStreamPublicKey(this.seed, this.mint, this.name)
+    return this.clientInternal.getStreamPublicKey(
+      this.seed,
+      this.mint,
+      this.name,
+      this.derivationSender.equals(web3.PublicKey.default) ? undefined : this.derivationSender,
+      this.derivationRecipient.equals(web3.PublicKey.default) ? undefined : this.derivationRecipient,
+    )
   }
 
   /**
@@ -859,6 +893,44 @@ export class Stream {
     await this.clientInternal.changeSenderNonPrepaidStream(this, newSender)
   }
 
+  /**
+   * Validate if the wallet can accept the sender change of the non-prepaid stream at the given on-chain time.
+   *
+   * Conditions when validation fails:
+   * - Stream is prepaid
+   * - Wallet isn't the pending sender of the stream
+   * - Stream has already stopped
+   *
+   * @param at The on-chain time
+   *
+   * @throws An error is thrown if the sender change cannot be accepted
+   */
+  readonly validateAcceptSenderChangeNonPrepaid = (at: BN) => {
+    if (this.isPrepaid) {
+      throw new Error('Sender of prepaid streams cannot be changed')
+    } else if (this.pendingSender.equals(web3.PublicKey.default)) {
+      throw new Error('No sender change has been proposed')
+    } else if (!this.pendingSender.equals(this.clientInternal.getWalletPublicKey())) {
+      throw new Error('Only the pending sender can accept the sender change')
+    } else if (this.hasStopped(at)) {
+      throw new Error('Stream has already stopped. Cannot change sender of a stopped stream')
+    }
+  }
+
+  /**
+   * Accept the sender change of the non-prepaid stream and validate at the given on-chain time. The wallet becomes the
+   * sender of the stream.
+   *
+   * @param at The on-chain time
+   *
+   * @throws An error is thrown if the sender change cannot be accepted or a user wallet wasn't provided to the
+   *         Superstream client or there is a Solana RPC issue
+   */
+  readonly acceptSenderChangeNonPrepaid = async (at: BN) => {
+    this.validateAcceptSenderChangeNonPrepaid(at)
+    await this.clientInternal.acceptSenderChangeNonPrepaidStream(this)
+  }
+
   /**
    * Validate if the stream's recipient funds can be withdrawn at the given on-chain time.
    *
@@ -948,6 +1020,37 @@ export class Stream {
     await this.clientInternal.withdrawFromStreamAndChangeRecipient(this, newRecipient)
   }
 
+  /**
+   * Validate if the wallet can accept the recipient change of the stream.
+   *
+   * Conditions when validation fails:
+   * - Stream has already been cancelled
+   * - Wallet isn't the pending recipient of the stream
+   *
+   * @throws An error is thrown if the recipient change cannot be accepted
+   */
+  readonly validateAcceptRecipientChange = () => {
+    if (this.isCancelled) {
+      throw new Error('The stream has already been cancelled')
+    } else if (this.pendingRecipient.equals(web3.PublicKey.default)) {
+      throw new Error('No recipient change has been proposed')
+    } else if (!this.pendingRecipient.equals(this.clientInternal.getWalletPublicKey())) {
+      throw new Error('Only the pending recipient can accept the recipient change')
+    }
+  }
+
+  /**
+   * Accept the recipient change of the stream. Everything owed till now is withdrawn to the current recipient and the
+   * wallet becomes the recipient of the stream.
+   *
+   * @throws An error is thrown if the recipient change cannot be accepted or a user wallet wasn't provided to the
+   *         Superstream client or there is a Solana RPC issue
+   */
+  readonly acceptRecipientChange = async () => {
+    this.validateAcceptRecipientChange()
+    await this.clientInternal.acceptRecipientChangeStream(this)
+  }
+
   /**
    * Validate if the stream can be paused at the given on-chain time.
    *
@@ -1060,6 +1163,38 @@ export class Stream {
     await this.clientInternal.resumeNonPrepaidStream(this)
   }
 
+  /**
+   * Validate if the stream can be closed at the given on-chain time.
+   *
+   * Conditions when validation fails:
+   * - Wallet doesn't belong to the sender or the recipient of the stream
+   * - Stream has not been cancelled and has not stopped
+   *
+   * @param at The on-chain time
+   *
+   * @throws An error is thrown if the stream cannot be closed
+   */
+  readonly validateClose = (at: BN) => {
+    if (!this.isSender() && !this.isRecipient()) {
+      throw new Error('Only the sender or the recipient can close the stream')
+    } else if (!this.isCancelled && !this.hasStopped(at)) {
+      throw new Error('Stream has not stopped. Only cancelled or stopped streams can be closed')
+    }
+  }
+
+  /**
+   * Close the stream and validate at the given on-chain time. The rent is returned to the sender.
+   *
+   * @param at The on-chain time
+   *
+   * @throws An error is thrown if the stream cannot be closed or a user wallet wasn't provided to the Superstream
+   *         client or there is a Solana RPC issue
+   */
+  readonly close = async (at: BN) => {
+    this.validateClose(at)
+    await this.clientInternal.closeStream(this)
+  }
+
   /**
    * Get or create an associated token account for the stream's mint for the given owner.
    *
@@ -0,0 +1,202 @@
+//! Module for fetching and deserializing Superstream accounts.
+
+use anchor_lang::{error::ErrorCode, prelude::Pubkey, AccountDeserialize, Discriminator};
+use solana_account_decoder::UiDataSliceConfig;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use superstream::{
+    state::{GlobalStats, MintStats, Stream, StreamIndex, VersionedStream, ZeroCopyStream},
+    ID,
+};
+
+use crate::{
+    error::ClientError,
+    filters::{StreamFilters, StreamLayout},
+    pda::{get_global_stats_address, get_mint_stats_address, get_recipient_index_address, get_sender_index_address},
+};
+
+/// Deserialize a stream from raw account data. The account discriminator is checked. Streams in the legacy account
+/// layout are returned in the newest layout with `version` set to
+/// [`LEGACY_STREAM_LAYOUT_VERSION`](superstream::state::LEGACY_STREAM_LAYOUT_VERSION). Zero-copy streams are returned
+/// in the newest layout too.
+pub fn deserialize_stream(data: &[u8]) -> Result<Stream, ClientError> {
+    if is_zero_copy_stream(data) {
+        return deserialize_zero_copy_stream(data);
+    }
+
+    let mut data = data;
+    Ok(VersionedStream::try_deserialize(&mut data)?.into_inner())
+}
+
+/// Check if raw account data is a stream in the zero-copy layout.
+pub fn is_zero_copy_stream(data: &[u8]) -> bool {
+    data.starts_with(&ZeroCopyStream::DISCRIMINATOR)
+}
+
+fn deserialize_zero_copy_stream(data: &[u8]) -> Result<Stream, ClientError> {
+    // The account data fetched over RPC is not necessarily aligned, so the stream is copied out instead of being read
+    // in place.
+    let data = data
+        .get(ZeroCopyStream::DISCRIMINATOR.len()..ZeroCopyStream::LENGTH)
+        .ok_or_else(|| anchor_lang::error::Error::from(ErrorCode::AccountDidNotDeserialize))?;
+    let stream: ZeroCopyStream = bytemuck::pod_read_unaligned(data);
+    Ok(stream.to_stream()?)
+}
+
+/// Fetch and deserialize a stream.
+pub fn fetch_stream(rpc_client: &RpcClient, address: &Pubkey) -> Result<Stream, ClientError> {
+    let data = rpc_client.get_account_data(address)?;
+    deserialize_stream(&data)
+}
+
+/// Fetch and deserialize all the streams matching the filters, along with their addresses. Streams in both layouts are
+/// returned unless the filters specify the layout.
+pub fn fetch_streams(rpc_client: &RpcClient, filters: &StreamFilters) -> Result<Vec<(Pubkey, Stream)>, ClientError> {
+    let mut streams = Vec::new();
+    if filters.layout != Some(StreamLayout::ZeroCopy) {
+        for (address, data) in fetch_program_accounts(rpc_client, filters.to_rpc_filters(), None)? {
+            streams.push((address, deserialize_stream(&data)?));
+        }
+    }
+    if filters.layout != Some(StreamLayout::Regular) {
+        streams.extend(fetch_zero_copy_streams(rpc_client, filters)?);
+    }
+    Ok(streams)
+}
+
+/// Fetch the addresses of all the streams matching the filters. The account data is only fetched for zero-copy streams
+/// since they can only be filtered by most fields after being deserialized.
+pub fn fetch_stream_addresses(rpc_client: &RpcClient, filters: &StreamFilters) -> Result<Vec<Pubkey>, ClientError> {
+    let mut addresses = Vec::new();
+    if filters.layout != Some(StreamLayout::ZeroCopy) {
+        let data_slice = UiDataSliceConfig { offset: 0, length: 0 };
+        let accounts = fetch_program_accounts(rpc_client, filters.to_rpc_filters(), Some(data_slice))?;
+        addresses.extend(accounts.into_iter().map(|(address, _)| address));
+    }
+    if filters.layout != Some(StreamLayout::Regular) {
+        let streams = fetch_zero_copy_streams(rpc_client, filters)?;
+        addresses.extend(streams.into_iter().map(|(address, _)| address));
+    }
+    Ok(addresses)
+}
+
+fn fetch_zero_copy_streams(
+    rpc_client: &RpcClient,
+    filters: &StreamFilters,
+) -> Result<Vec<(Pubkey, Stream)>, ClientError> {
+    let mut streams = Vec::new();
+    for (address, data) in fetch_program_accounts(rpc_client, filters.to_zero_copy_rpc_filters(), None)? {
+        let stream = deserialize_stream(&data)?;
+        if filters.matches(&stream) {
+            streams.push((address, stream));
+        }
+    }
+    Ok(streams)
+}
+
+fn fetch_program_accounts(
+    rpc_client: &RpcClient,
+    filters: Vec<RpcFilterType>,
+    data_slice: Option<UiDataSliceConfig>,
+) -> Result<Vec<(Pubkey, Vec<u8>)>, ClientError> {
+    let accounts = rpc_client.get_program_accounts_with_config(
+        &ID,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                data_slice,
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+    Ok(accounts
+        .into_iter()
+        .map(|(address, account)| (address, account.data))
+        .collect())
+}
+
+/// Fetch and deserialize a stream index. Returns `None` if the index account doesn't exist.
+pub fn fetch_stream_index(rpc_client: &RpcClient, address: &Pubkey) -> Result<Option<StreamIndex>, ClientError> {
+    let account = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())?
+        .value;
+    account
+        .map(|account| {
+            let mut data = &account.data[..];
+            Ok(StreamIndex::try_deserialize(&mut data)?)
+        })
+        .transpose()
+}
+
+/// Fetch the addresses of the streams sent by a sender using its stream index, with a single account fetch. Streams
+/// created before stream indexes were introduced are not included.
+pub fn fetch_sent_stream_addresses(rpc_client: &RpcClient, sender: &Pubkey) -> Result<Vec<Pubkey>, ClientError> {
+    let index = fetch_stream_index(rpc_client, &get_sender_index_address(sender).0)?;
+    Ok(index.map(|index| index.streams).unwrap_or_default())
+}
+
+/// Fetch the addresses of the streams received by a recipient using its stream index, with a single account fetch.
+/// Streams created before stream indexes were introduced are not included.
+pub fn fetch_received_stream_addresses(rpc_client: &RpcClient, recipient: &Pubkey) -> Result<Vec<Pubkey>, ClientError> {
+    let index = fetch_stream_index(rpc_client, &get_recipient_index_address(recipient).0)?;
+    Ok(index.map(|index| index.streams).unwrap_or_default())
+}
+
+/// Fetch and deserialize the stats of a mint with a single account fetch. Returns `None` if no stream has been created
+/// for the mint since mint stats were introduced.
+pub fn fetch_mint_stats(rpc_client: &RpcClient, mint: &Pubkey) -> Result<Option<MintStats>, ClientError> {
+    let account = rpc_client
+        .get_account_with_commitment(&get_mint_stats_address(mint).0, rpc_client.commitment())?
+        .value;
+    account
+        .map(|account| {
+            let mut data = &account.data[..];
+            Ok(MintStats::try_deserialize(&mut data)?)
+        })
+        .transpose()
+}
+
+/// Fetch and deserialize the stats of all the streams of all the mints with a single account fetch. Returns `None` if no
+/// stream has been created since the global stats were introduced.
+pub fn fetch_global_stats(rpc_client: &RpcClient) -> Result<Option<GlobalStats>, ClientError> {
+    let account = rpc_client
+        .get_account_with_commitment(&get_global_stats_address().0, rpc_client.commitment())?
+        .value;
+    account
+        .map(|account| {
+            let mut data = &account.data[..];
+            Ok(GlobalStats::try_deserialize(&mut data)?)
+        })
+        .transpose()
+}
+
+/// Fetch and deserialize the stats of all the mints. There is only 1 stats account per mint, so this is much cheaper
+/// than fetching all the streams to compute protocol-wide figures.
+pub fn fetch_all_mint_stats(rpc_client: &RpcClient) -> Result<Vec<MintStats>, ClientError> {
+    let accounts = rpc_client.get_program_accounts_with_config(
+        &ID,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                MintStats::discriminator().to_vec(),
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+    accounts
+        .into_iter()
+        .map(|(_, account)| {
+            let mut data = &account.data[..];
+            Ok(MintStats::try_deserialize(&mut data)?)
+        })
+        .collect()
+}
@@ -0,0 +1,26 @@
+//! Module for superstream client errors.
+
+use thiserror::Error;
+
+/// Errors returned by the client. The underlying errors are boxed to keep the results small.
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// The RPC request failed.
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    /// The account data could not be deserialized.
+    #[error("Account could not be deserialized: {0}")]
+    Deserialize(Box<anchor_lang::error::Error>),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(err: anchor_lang::error::Error) -> Self {
+        Self::Deserialize(Box::new(err))
+    }
+}
@@ -0,0 +1,174 @@
+//! Module for filtering streams when fetching all the streams.
+
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use superstream::state::{Stream, ZeroCopyStream, MAX_STREAM_NAME_BYTES};
+
+const DISCRIMINATOR_LENGTH: usize = 8;
+const BOOL_LENGTH: usize = 1;
+const U64_LENGTH: usize = 8;
+const PUBLIC_KEY_LENGTH: usize = 32;
+const STRING_LENGTH_PREFIX: usize = 4;
+
+/// Offset of `is_prepaid` in the stream account data.
+pub const IS_PREPAID_OFFSET: usize = DISCRIMINATOR_LENGTH;
+/// Offset of `mint` in the stream account data.
+pub const MINT_OFFSET: usize = IS_PREPAID_OFFSET + BOOL_LENGTH;
+/// Offset of `sender` in the stream account data.
+pub const SENDER_OFFSET: usize = MINT_OFFSET + PUBLIC_KEY_LENGTH;
+/// Offset of `recipient` in the stream account data.
+pub const RECIPIENT_OFFSET: usize = SENDER_OFFSET + PUBLIC_KEY_LENGTH;
+/// Offset of `is_cancelled` in the stream account data. It comes after `recipient`, `created_at`, `starts_at`,
+/// `ends_at`, `initial_amount`, `flow_interval` and `flow_rate`.
+pub const IS_CANCELLED_OFFSET: usize = RECIPIENT_OFFSET + PUBLIC_KEY_LENGTH + 6 * U64_LENGTH;
+/// Offset of `is_cancelled_before_start` in the stream account data.
+pub const IS_CANCELLED_BEFORE_START_OFFSET: usize = IS_CANCELLED_OFFSET + BOOL_LENGTH;
+/// Offset of `is_cancelled_by_sender` in the stream account data.
+pub const IS_CANCELLED_BY_SENDER_OFFSET: usize = IS_CANCELLED_BEFORE_START_OFFSET + BOOL_LENGTH;
+/// Offset of `is_paused` in the stream account data. It comes after `is_cancelled_by_sender`, `cancelled_at`,
+/// `sender_can_cancel`, `sender_can_cancel_at`, `sender_can_change_sender` and `sender_can_change_sender_at`.
+pub const IS_PAUSED_OFFSET: usize = IS_CANCELLED_BY_SENDER_OFFSET + BOOL_LENGTH + 3 * U64_LENGTH + 2 * BOOL_LENGTH;
+/// Offset of `is_paused_by_sender` in the stream account data.
+pub const IS_PAUSED_BY_SENDER_OFFSET: usize = IS_PAUSED_OFFSET + BOOL_LENGTH;
+/// Offset of `name` in the stream account data, excluding the string length prefix. The name is stored last, so streams
+/// in the legacy account layout are not matched by name until they are migrated.
+pub const NAME_OFFSET: usize = Stream::BASE_LENGTH + STRING_LENGTH_PREFIX;
+
+/// Offset of `mint` in the zero-copy stream account data.
+pub const ZERO_COPY_MINT_OFFSET: usize = DISCRIMINATOR_LENGTH;
+/// Offset of `sender` in the zero-copy stream account data.
+pub const ZERO_COPY_SENDER_OFFSET: usize = ZERO_COPY_MINT_OFFSET + PUBLIC_KEY_LENGTH;
+/// Offset of `recipient` in the zero-copy stream account data.
+pub const ZERO_COPY_RECIPIENT_OFFSET: usize = ZERO_COPY_SENDER_OFFSET + PUBLIC_KEY_LENGTH;
+/// Offset of `name` in the zero-copy stream account data. The fixed-size name buffer is stored last.
+pub const ZERO_COPY_NAME_OFFSET: usize = ZeroCopyStream::LENGTH - MAX_STREAM_NAME_BYTES;
+
+/// Account layout of a stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StreamLayout {
+    /// The Borsh layout, including the legacy layout. See
+    /// [`VersionedStream`](superstream::state::VersionedStream).
+    Regular,
+    /// The zero-copy layout. See [`ZeroCopyStream`].
+    ZeroCopy,
+}
+
+/// Stream filters that can be used to filter streams when fetching all streams.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamFilters {
+    /// Filter by stream type - prepaid or unbounded.
+    pub is_prepaid: Option<bool>,
+    /// Filter by the stream mint.
+    pub mint: Option<Pubkey>,
+    /// Filter by the stream sender.
+    pub sender: Option<Pubkey>,
+    /// Filter by the stream recipient.
+    pub recipient: Option<Pubkey>,
+    /// Filter by the stream cancellation status.
+    pub is_cancelled: Option<bool>,
+    /// Filter by the stream cancellation before start status.
+    pub is_cancelled_before_start: Option<bool>,
+    /// Filter by the stream cancellation by sender status.
+    pub is_cancelled_by_sender: Option<bool>,
+    /// Filter by the stream paused status.
+    pub is_paused: Option<bool>,
+    /// Filter by the stream paused by sender status.
+    pub is_paused_by_sender: Option<bool>,
+    /// Filter by the stream name.
+    pub name: Option<String>,
+    /// Filter by the stream account layout.
+    pub layout: Option<StreamLayout>,
+}
+
+impl StreamFilters {
+    /// Convert the filters to RPC filters for streams in the regular layout. A filter on the stream account
+    /// discriminator is always included so that only stream accounts are returned.
+    pub fn to_rpc_filters(&self) -> Vec<RpcFilterType> {
+        let mut filters = vec![memcmp(0, Stream::discriminator().to_vec())];
+
+        let bool_filters = [
+            (IS_PREPAID_OFFSET, self.is_prepaid),
+            (IS_CANCELLED_OFFSET, self.is_cancelled),
+            (IS_CANCELLED_BEFORE_START_OFFSET, self.is_cancelled_before_start),
+            (IS_CANCELLED_BY_SENDER_OFFSET, self.is_cancelled_by_sender),
+            (IS_PAUSED_OFFSET, self.is_paused),
+            (IS_PAUSED_BY_SENDER_OFFSET, self.is_paused_by_sender),
+        ];
+        for (offset, value) in bool_filters {
+            if let Some(value) = value {
+                filters.push(memcmp(offset, vec![value as u8]));
+            }
+        }
+
+        let public_key_filters = [
+            (MINT_OFFSET, self.mint),
+            (SENDER_OFFSET, self.sender),
+            (RECIPIENT_OFFSET, self.recipient),
+        ];
+        for (offset, value) in public_key_filters {
+            if let Some(value) = value {
+                filters.push(memcmp(offset, value.to_bytes().to_vec()));
+            }
+        }
+
+        if let Some(name) = &self.name {
+            filters.push(memcmp(NAME_OFFSET, name.as_bytes().to_vec()));
+        }
+        filters
+    }
+
+    /// Convert the filters to RPC filters for streams in the zero-copy layout. A filter on the zero-copy stream account
+    /// discriminator is always included. The booleans of zero-copy streams are packed in bit flags which RPC filters
+    /// can't match, so the fetched streams need to be filtered with [`Self::matches`] too.
+    pub fn to_zero_copy_rpc_filters(&self) -> Vec<RpcFilterType> {
+        let mut filters = vec![memcmp(0, ZeroCopyStream::discriminator().to_vec())];
+
+        let public_key_filters = [
+            (ZERO_COPY_MINT_OFFSET, self.mint),
+            (ZERO_COPY_SENDER_OFFSET, self.sender),
+            (ZERO_COPY_RECIPIENT_OFFSET, self.recipient),
+        ];
+        for (offset, value) in public_key_filters {
+            if let Some(value) = value {
+                filters.push(memcmp(offset, value.to_bytes().to_vec()));
+            }
+        }
+
+        if let Some(name) = &self.name {
+            filters.push(memcmp(ZERO_COPY_NAME_OFFSET, name.as_bytes().to_vec()));
+        }
+        filters
+    }
+
+    /// Check if a stream matches the filters, except for the layout. Like the RPC filters, the name filter matches
+    /// the start of the stream name.
+    pub fn matches(&self, stream: &Stream) -> bool {
+        let bool_filters = [
+            (self.is_prepaid, stream.is_prepaid),
+            (self.is_cancelled, stream.is_cancelled),
+            (self.is_cancelled_before_start, stream.is_cancelled_before_start),
+            (self.is_cancelled_by_sender, stream.is_cancelled_by_sender),
+            (self.is_paused, stream.is_paused),
+            (self.is_paused_by_sender, stream.is_paused_by_sender),
+        ];
+        let public_key_filters = [
+            (self.mint, stream.mint),
+            (self.sender, stream.sender),
+            (self.recipient, stream.recipient),
+        ];
+        bool_filters
+            .into_iter()
+            .all(|(filter, value)| filter.is_none() || filter == Some(value))
+            && public_key_filters
+                .into_iter()
+                .all(|(filter, value)| filter.is_none() || filter == Some(value))
+            && match &self.name {
+                Some(name) => stream.name.starts_with(name.as_str()),
+                None => true,
+            }
+    }
+}
+
+fn memcmp(offset: usize, bytes: Vec<u8>) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, bytes))
+}
@@ -0,0 +1,649 @@
+//! Module for building Superstream instructions. There is a builder for every instruction of the program. All the
+//! accounts are derived from the arguments, using associated token accounts for the token accounts. Builders for
+//! instructions on an existing stream use the `_v2` variants, which read the seed and name from the stream account.
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::token;
+use superstream::{accounts, instruction as data, state::StreamDerivation, ID};
+
+use crate::pda::{
+    get_allowed_mint_address, get_global_stats_address, get_mint_registry_address, get_mint_stats_address,
+    get_program_data_address, get_recipient_index_address, get_sender_index_address, get_stream_address,
+    get_token_address, StreamId,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn create_accounts(
+    sender: &Pubkey,
+    mint: &Pubkey,
+    seed: u64,
+    name: &str,
+    recipient: &Pubkey,
+    derivation: StreamDerivation,
+) -> accounts::CreateStream {
+    let stream = get_stream_address(seed, mint, name, derivation, sender, recipient).0;
+    accounts::CreateStream {
+        stream,
+        sender: *sender,
+        mint: *mint,
+        sender_token: get_token_address(sender, mint),
+        escrow_token: get_token_address(&stream, mint),
+        mint_registry: get_mint_registry_address().0,
+        allowed_mint: get_allowed_mint_address(mint).0,
+        sender_index: get_sender_index_address(sender).0,
+        recipient_index: get_recipient_index_address(recipient).0,
+        mint_stats: get_mint_stats_address(mint).0,
+        global_stats: get_global_stats_address().0,
+        token_program: token::ID,
+        system_program: system_program::ID,
+    }
+}
+
+fn create_global_accounts(
+    sender: &Pubkey,
+    mint: &Pubkey,
+    seed: u64,
+    name: &str,
+    recipient: &Pubkey,
+) -> accounts::Create {
+    let accounts::CreateStream {
+        stream,
+        sender,
+        mint,
+        sender_token,
+        escrow_token,
+        mint_registry,
+        allowed_mint,
+        sender_index,
+        recipient_index,
+        mint_stats,
+        global_stats,
+        token_program,
+        system_program,
+    } = create_accounts(sender, mint, seed, name, recipient, StreamDerivation::Global);
+    accounts::Create {
+        stream,
+        sender,
+        mint,
+        sender_token,
+        escrow_token,
+        mint_registry,
+        allowed_mint,
+        sender_index,
+        recipient_index,
+        mint_stats,
+        global_stats,
+        token_program,
+        system_program,
+    }
+}
+
+/// Build a `create_stream` instruction. The stream address is derived from the seed, name, recipient and derivation
+/// mode in the arguments.
+pub fn create_stream(sender: &Pubkey, mint: &Pubkey, args: data::CreateStream) -> Instruction {
+    build(
+        create_accounts(sender, mint, args.seed, &args.name, &args.recipient, args.derivation),
+        args,
+    )
+}
+
+/// Build a `create_prepaid` instruction. The stream address is derived from the seed, name and recipient in the
+/// arguments, using the [`StreamDerivation::Global`] derivation mode.
+pub fn create_prepaid(sender: &Pubkey, mint: &Pubkey, args: data::CreatePrepaid) -> Instruction {
+    build(
+        create_global_accounts(sender, mint, args.seed, &args.name, &args.recipient),
+        args,
+    )
+}
+
+/// Build a `create_non_prepaid` instruction. The stream address is derived from the seed, name and recipient in the
+/// arguments, using the [`StreamDerivation::Global`] derivation mode.
+pub fn create_non_prepaid(sender: &Pubkey, mint: &Pubkey, args: data::CreateNonPrepaid) -> Instruction {
+    build(
+        create_global_accounts(sender, mint, args.seed, &args.name, &args.recipient),
+        args,
+    )
+}
+
+/// Build a `cancel_v2` instruction.
+pub fn cancel(stream: &StreamId, signer: &Pubkey, sender: &Pubkey, recipient: &Pubkey) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::Cancel {
+            stream: stream.address(),
+            signer: *signer,
+            sender: *sender,
+            mint: *mint,
+            signer_token: get_token_address(signer, mint),
+            sender_token: get_token_address(sender, mint),
+            recipient_token: get_token_address(recipient, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::CancelV2 {},
+    )
+}
+
+/// Build a `withdraw_excess_topup_non_prepaid_ended_v2` instruction.
+pub fn withdraw_excess_topup_non_prepaid_ended(stream: &StreamId, signer: &Pubkey, sender: &Pubkey) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::WithdrawExcessTopupNonPrepaidEnded {
+            stream: stream.address(),
+            signer: *signer,
+            sender: *sender,
+            mint: *mint,
+            sender_token: get_token_address(sender, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::WithdrawExcessTopupNonPrepaidEndedV2 {},
+    )
+}
+
+/// Build a `topup_non_prepaid_v2` instruction.
+pub fn topup_non_prepaid(stream: &StreamId, signer: &Pubkey, topup_amount: u64, is_strict: bool) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::TopupNonPrepaid {
+            stream: stream.address(),
+            signer: *signer,
+            mint: *mint,
+            signer_token: get_token_address(signer, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::TopupNonPrepaidV2 {
+            topup_amount,
+            is_strict,
+        },
+    )
+}
+
+/// Build a `change_sender_non_prepaid_v2` instruction.
+pub fn change_sender_non_prepaid(stream: &StreamId, sender: &Pubkey, new_sender: &Pubkey) -> Instruction {
+    build(
+        accounts::ChangeSenderNonPrepaid {
+            stream: stream.address(),
+            sender: *sender,
+            mint: stream.mint,
+        },
+        data::ChangeSenderNonPrepaidV2 {
+            new_sender: *new_sender,
+        },
+    )
+}
+
+/// Build a `accept_sender_change_non_prepaid_v2` instruction.
+pub fn accept_sender_change_non_prepaid(stream: &StreamId, new_sender: &Pubkey, sender: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptSenderChangeNonPrepaid {
+            stream: stream.address(),
+            new_sender: *new_sender,
+            mint: stream.mint,
+            sender: *sender,
+            sender_index: get_sender_index_address(sender).0,
+            new_sender_index: get_sender_index_address(new_sender).0,
+            system_program: system_program::ID,
+        },
+        data::AcceptSenderChangeNonPrepaidV2 {},
+    )
+}
+
+/// Build a `cancel_sender_change_non_prepaid_v2` instruction.
+pub fn cancel_sender_change_non_prepaid(stream: &StreamId, sender: &Pubkey) -> Instruction {
+    build(
+        accounts::ChangeSenderNonPrepaid {
+            stream: stream.address(),
+            sender: *sender,
+            mint: stream.mint,
+        },
+        data::CancelSenderChangeNonPrepaidV2 {},
+    )
+}
+
+fn withdraw_accounts(
+    stream: &StreamId,
+    signer: &Pubkey,
+    recipient: &Pubkey,
+    send_reward_to_signer: bool,
+) -> accounts::WithdrawAndChangeRecipient {
+    let mint = &stream.mint;
+    accounts::WithdrawAndChangeRecipient {
+        stream: stream.address(),
+        signer: *signer,
+        mint: *mint,
+        signer_token: send_reward_to_signer.then(|| get_token_address(signer, mint)),
+        recipient_token: get_token_address(recipient, mint),
+        escrow_token: stream.escrow_token_address(),
+        mint_stats: get_mint_stats_address(mint).0,
+        global_stats: get_global_stats_address().0,
+        token_program: token::ID,
+    }
+}
+
+/// Build a `withdraw_v2` instruction. If `send_reward_to_signer` is true, any reward for cancelling an insolvent stream is
+/// sent to the signer's token account instead of the recipient's.
+pub fn withdraw(stream: &StreamId, signer: &Pubkey, recipient: &Pubkey, send_reward_to_signer: bool) -> Instruction {
+    build(
+        withdraw_accounts(stream, signer, recipient, send_reward_to_signer),
+        data::WithdrawV2 {},
+    )
+}
+
+/// Build a `withdraw_and_change_recipient_v2` instruction. See [`withdraw`] for `send_reward_to_signer`.
+pub fn withdraw_and_change_recipient(
+    stream: &StreamId,
+    signer: &Pubkey,
+    recipient: &Pubkey,
+    new_recipient: &Pubkey,
+    send_reward_to_signer: bool,
+) -> Instruction {
+    build(
+        withdraw_accounts(stream, signer, recipient, send_reward_to_signer),
+        data::WithdrawAndChangeRecipientV2 {
+            new_recipient: *new_recipient,
+        },
+    )
+}
+
+/// Build a `accept_recipient_change_v2` instruction.
+pub fn accept_recipient_change(stream: &StreamId, new_recipient: &Pubkey, recipient: &Pubkey) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::AcceptRecipientChange {
+            stream: stream.address(),
+            new_recipient: *new_recipient,
+            mint: *mint,
+            current_recipient: *recipient,
+            recipient_token: get_token_address(recipient, mint),
+            escrow_token: stream.escrow_token_address(),
+            recipient_index: get_recipient_index_address(recipient).0,
+            new_recipient_index: get_recipient_index_address(new_recipient).0,
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        data::AcceptRecipientChangeV2 {},
+    )
+}
+
+/// Build a `cancel_recipient_change_v2` instruction.
+pub fn cancel_recipient_change(stream: &StreamId, recipient: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelRecipientChange {
+            stream: stream.address(),
+            recipient: *recipient,
+            mint: stream.mint,
+        },
+        data::CancelRecipientChangeV2 {},
+    )
+}
+
+/// Build a `pause_non_prepaid_v2` instruction.
+pub fn pause_non_prepaid(stream: &StreamId, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::PauseNonPrepaid {
+            stream: stream.address(),
+            signer: *signer,
+            mint: stream.mint,
+        },
+        data::PauseNonPrepaidV2 {},
+    )
+}
+
+/// Build a `resume_non_prepaid_v2` instruction.
+pub fn resume_non_prepaid(stream: &StreamId, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::ResumeNonPrepaid {
+            stream: stream.address(),
+            signer: *signer,
+            mint: stream.mint,
+        },
+        data::ResumeNonPrepaidV2 {},
+    )
+}
+
+/// Build a `accept_v2` instruction.
+pub fn accept(stream: &StreamId, recipient: &Pubkey) -> Instruction {
+    build(
+        accounts::Accept {
+            stream: stream.address(),
+            recipient: *recipient,
+            mint: stream.mint,
+        },
+        data::AcceptV2 {},
+    )
+}
+
+/// Build a `propose_settlement_v2` instruction.
+pub fn propose_settlement(stream: &StreamId, signer: &Pubkey, settlement_amount: u64) -> Instruction {
+    build(
+        accounts::ProposeSettlement {
+            stream: stream.address(),
+            signer: *signer,
+            mint: stream.mint,
+        },
+        data::ProposeSettlementV2 { settlement_amount },
+    )
+}
+
+/// Build a `cancel_settlement_proposal_v2` instruction.
+pub fn cancel_settlement_proposal(stream: &StreamId, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeSettlement {
+            stream: stream.address(),
+            signer: *signer,
+            mint: stream.mint,
+        },
+        data::CancelSettlementProposalV2 {},
+    )
+}
+
+/// Build a `terminate_with_settlement_v2` instruction.
+pub fn terminate_with_settlement(
+    stream: &StreamId,
+    signer: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    settlement_amount: u64,
+) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::TerminateWithSettlement {
+            stream: stream.address(),
+            signer: *signer,
+            sender: *sender,
+            mint: *mint,
+            sender_token: get_token_address(sender, mint),
+            recipient_token: get_token_address(recipient, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::TerminateWithSettlementV2 { settlement_amount },
+    )
+}
+
+/// Build a `close_v2` instruction.
+pub fn close(stream: &StreamId, signer: &Pubkey, sender: &Pubkey, recipient: &Pubkey) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::Close {
+            stream: stream.address(),
+            signer: *signer,
+            sender: *sender,
+            recipient: *recipient,
+            mint: *mint,
+            sender_token: get_token_address(sender, mint),
+            escrow_token: stream.escrow_token_address(),
+            sender_index: get_sender_index_address(sender).0,
+            recipient_index: get_recipient_index_address(recipient).0,
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::CloseV2 {},
+    )
+}
+
+/// Build a `migrate_stream_v2` instruction.
+pub fn migrate_stream(stream: &StreamId, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateStream {
+            stream: stream.address(),
+            payer: *payer,
+            mint: stream.mint,
+            system_program: system_program::ID,
+        },
+        data::MigrateStreamV2 {},
+    )
+}
+
+/// Build a `convert_stream_to_zero_copy` instruction.
+pub fn convert_stream_to_zero_copy(stream: &StreamId, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::ConvertStreamToZeroCopy {
+            stream: stream.address(),
+            signer: *signer,
+            system_program: system_program::ID,
+        },
+        data::ConvertStreamToZeroCopy {},
+    )
+}
+
+/// Build a `convert_stream_from_zero_copy` instruction.
+pub fn convert_stream_from_zero_copy(stream: &StreamId, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::ConvertStreamFromZeroCopy {
+            stream: stream.address(),
+            signer: *signer,
+            system_program: system_program::ID,
+        },
+        data::ConvertStreamFromZeroCopy {},
+    )
+}
+
+/// Build a `withdraw_zero_copy` instruction. See [`withdraw`] for `send_reward_to_signer`.
+pub fn withdraw_zero_copy(
+    stream: &StreamId,
+    signer: &Pubkey,
+    recipient: &Pubkey,
+    send_reward_to_signer: bool,
+) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::WithdrawZeroCopy {
+            stream: stream.address(),
+            signer: *signer,
+            mint: *mint,
+            signer_token: send_reward_to_signer.then(|| get_token_address(signer, mint)),
+            recipient_token: get_token_address(recipient, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::WithdrawZeroCopy {},
+    )
+}
+
+/// Build a `cancel_zero_copy` instruction.
+pub fn cancel_zero_copy(stream: &StreamId, signer: &Pubkey, sender: &Pubkey, recipient: &Pubkey) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::CancelZeroCopy {
+            stream: stream.address(),
+            signer: *signer,
+            sender: *sender,
+            mint: *mint,
+            signer_token: get_token_address(signer, mint),
+            sender_token: get_token_address(sender, mint),
+            recipient_token: get_token_address(recipient, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::CancelZeroCopy {},
+    )
+}
+
+/// Build a `topup_non_prepaid_zero_copy` instruction.
+pub fn topup_non_prepaid_zero_copy(
+    stream: &StreamId,
+    signer: &Pubkey,
+    topup_amount: u64,
+    is_strict: bool,
+) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::TopupNonPrepaidZeroCopy {
+            stream: stream.address(),
+            signer: *signer,
+            mint: *mint,
+            signer_token: get_token_address(signer, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::TopupNonPrepaidZeroCopy {
+            topup_amount,
+            is_strict,
+        },
+    )
+}
+
+/// Build a `freeze_v2` instruction.
+pub fn freeze(stream: &StreamId, arbiter: &Pubkey) -> Instruction {
+    build(
+        accounts::Freeze {
+            stream: stream.address(),
+            arbiter: *arbiter,
+            mint: stream.mint,
+        },
+        data::FreezeV2 {},
+    )
+}
+
+/// Build a `unfreeze_v2` instruction.
+pub fn unfreeze(stream: &StreamId, arbiter: &Pubkey) -> Instruction {
+    build(
+        accounts::Freeze {
+            stream: stream.address(),
+            arbiter: *arbiter,
+            mint: stream.mint,
+        },
+        data::UnfreezeV2 {},
+    )
+}
+
+/// Build a `resolve_v2` instruction.
+pub fn resolve(
+    stream: &StreamId,
+    arbiter: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    recipient_amount: u64,
+) -> Instruction {
+    let mint = &stream.mint;
+    build(
+        accounts::Resolve {
+            stream: stream.address(),
+            arbiter: *arbiter,
+            sender: *sender,
+            mint: *mint,
+            sender_token: get_token_address(sender, mint),
+            recipient_token: get_token_address(recipient, mint),
+            escrow_token: stream.escrow_token_address(),
+            mint_stats: get_mint_stats_address(mint).0,
+            global_stats: get_global_stats_address().0,
+            token_program: token::ID,
+        },
+        data::ResolveV2 { recipient_amount },
+    )
+}
+
+/// Build an `initialize_mint_registry` instruction. The authority needs to be the upgrade authority of the program.
+pub fn initialize_mint_registry(authority: &Pubkey, is_enabled: bool) -> Instruction {
+    build(
+        accounts::InitializeMintRegistry {
+            mint_registry: get_mint_registry_address().0,
+            authority: *authority,
+            program: ID,
+            program_data: get_program_data_address(),
+            system_program: system_program::ID,
+        },
+        data::InitializeMintRegistry { is_enabled },
+    )
+}
+
+/// Build a `set_mint_registry_enabled` instruction.
+pub fn set_mint_registry_enabled(authority: &Pubkey, is_enabled: bool) -> Instruction {
+    build(
+        accounts::UpdateMintRegistry {
+            mint_registry: get_mint_registry_address().0,
+            authority: *authority,
+        },
+        data::SetMintRegistryEnabled { is_enabled },
+    )
+}
+
+/// Build a `change_mint_registry_authority` instruction.
+pub fn change_mint_registry_authority(authority: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::UpdateMintRegistry {
+            mint_registry: get_mint_registry_address().0,
+            authority: *authority,
+        },
+        data::ChangeMintRegistryAuthority {
+            new_authority: *new_authority,
+        },
+    )
+}
+
+/// Build an `add_allowed_mint` instruction.
+pub fn add_allowed_mint(authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::AddAllowedMint {
+            mint_registry: get_mint_registry_address().0,
+            allowed_mint: get_allowed_mint_address(mint).0,
+            authority: *authority,
+            mint: *mint,
+            system_program: system_program::ID,
+        },
+        data::AddAllowedMint {},
+    )
+}
+
+/// Build a `remove_allowed_mint` instruction.
+pub fn remove_allowed_mint(authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveAllowedMint {
+            mint_registry: get_mint_registry_address().0,
+            allowed_mint: get_allowed_mint_address(mint).0,
+            authority: *authority,
+        },
+        data::RemoveAllowedMint {},
+    )
+}
+
+fn view_accounts(stream: &StreamId) -> accounts::ViewStream {
+    accounts::ViewStream {
+        stream: stream.address(),
+        mint: stream.mint,
+    }
+}
+
+/// Build a `get_withdrawable_v2` instruction. The result is returned through the return data of the transaction.
+pub fn get_withdrawable(stream: &StreamId) -> Instruction {
+    build(view_accounts(stream), data::GetWithdrawableV2 {})
+}
+
+/// Build a `get_solvency_v2` instruction. The result is returned through the return data of the transaction.
+pub fn get_solvency(stream: &StreamId) -> Instruction {
+    build(view_accounts(stream), data::GetSolvencyV2 {})
+}
+
+/// Build a `get_max_topup_v2` instruction. The result is returned through the return data of the transaction.
+pub fn get_max_topup(stream: &StreamId) -> Instruction {
+    build(view_accounts(stream), data::GetMaxTopupV2 {})
+}
@@ -0,0 +1,21 @@
+//! Rust client for [Superstream](https://superstream.finance/) payment streams.
+//!
+//! It provides:
+//! - PDA and escrow address derivation - see [`pda`]
+//! - Instruction builders for every Superstream instruction - see [`instruction`]
+//! - Fetching and deserializing stream accounts - see [`account`]
+//! - Filters to fetch only some of the streams - see [`filters`]
+
+pub mod account;
+pub mod error;
+pub mod filters;
+pub mod instruction;
+pub mod pda;
+
+pub use superstream::{self, state::Stream, ID as PROGRAM_ID};
+
+pub use crate::{
+    error::ClientError,
+    filters::{StreamFilters, StreamLayout},
+    pda::StreamId,
+};
@@ -0,0 +1,166 @@
+//! Module for deriving Superstream account addresses.
+
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+use anchor_spl::associated_token::get_associated_token_address;
+use superstream::{
+    get_stream_name_seed,
+    state::{Stream, StreamDerivation},
+    ALLOWED_MINT_ACCOUNT_SEED, GLOBAL_STATS_ACCOUNT_SEED, ID, MINT_REGISTRY_ACCOUNT_SEED, MINT_STATS_ACCOUNT_SEED,
+    RECIPIENT_INDEX_ACCOUNT_SEED, SENDER_INDEX_ACCOUNT_SEED, STREAM_ACCOUNT_SEED,
+};
+
+/// Identifier of a stream. Each tuple (seed, mint, name) corresponds to a unique stream for a particular derivation
+/// mode and derivation keys.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StreamId {
+    /// Seed of the stream PDA.
+    pub seed: u64,
+    /// SPL token mint address.
+    pub mint: Pubkey,
+    /// Name of the stream.
+    pub name: String,
+    /// Mode used to derive the stream PDA address.
+    pub derivation: StreamDerivation,
+    /// Sender at the time of creation if it is part of the stream PDA seeds.
+    pub derivation_sender: Pubkey,
+    /// Recipient at the time of creation if it is part of the stream PDA seeds.
+    pub derivation_recipient: Pubkey,
+}
+
+impl StreamId {
+    /// Create a new identifier of a stream with a global address.
+    pub fn new(seed: u64, mint: Pubkey, name: impl Into<String>) -> Self {
+        Self {
+            seed,
+            mint,
+            name: name.into(),
+            derivation: StreamDerivation::Global,
+            derivation_sender: Pubkey::default(),
+            derivation_recipient: Pubkey::default(),
+        }
+    }
+
+    /// Create a new identifier of a stream whose address is scoped to the sender.
+    pub fn sender_scoped(seed: u64, mint: Pubkey, name: impl Into<String>, sender: Pubkey) -> Self {
+        Self {
+            derivation: StreamDerivation::Sender,
+            derivation_sender: sender,
+            ..Self::new(seed, mint, name)
+        }
+    }
+
+    /// Create a new identifier of a stream whose address is scoped to the sender and the recipient.
+    pub fn sender_and_recipient_scoped(
+        seed: u64,
+        mint: Pubkey,
+        name: impl Into<String>,
+        sender: Pubkey,
+        recipient: Pubkey,
+    ) -> Self {
+        Self {
+            derivation: StreamDerivation::SenderAndRecipient,
+            derivation_sender: sender,
+            derivation_recipient: recipient,
+            ..Self::new(seed, mint, name)
+        }
+    }
+
+    /// Get the stream PDA address.
+    pub fn address(&self) -> Pubkey {
+        get_stream_address(
+            self.seed,
+            &self.mint,
+            &self.name,
+            self.derivation,
+            &self.derivation_sender,
+            &self.derivation_recipient,
+        )
+        .0
+    }
+
+    /// Get the associated token escrow account address holding the funds for the stream.
+    pub fn escrow_token_address(&self) -> Pubkey {
+        get_escrow_token_address(&self.address(), &self.mint)
+    }
+}
+
+impl From<&Stream> for StreamId {
+    fn from(stream: &Stream) -> Self {
+        Self {
+            seed: stream.seed,
+            mint: stream.mint,
+            name: stream.name.clone(),
+            derivation: stream.derivation,
+            derivation_sender: stream.derivation_sender,
+            derivation_recipient: stream.derivation_recipient,
+        }
+    }
+}
+
+/// Get the stream PDA address and bump. Names longer than 32 bytes are hashed, see [`get_stream_name_seed`]. The sender
+/// and recipient are only part of the seeds if the derivation mode includes them, see [`StreamDerivation`].
+pub fn get_stream_address(
+    seed: u64,
+    mint: &Pubkey,
+    name: &str,
+    derivation: StreamDerivation,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            STREAM_ACCOUNT_SEED,
+            seed.to_le_bytes().as_ref(),
+            mint.as_ref(),
+            get_stream_name_seed(name).as_ref(),
+            derivation.sender_seed(sender),
+            derivation.recipient_seed(recipient),
+        ],
+        &ID,
+    )
+}
+
+/// Get the associated token escrow account address holding the funds for a stream.
+pub fn get_escrow_token_address(stream: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(stream, mint)
+}
+
+/// Get the associated token account address of a wallet.
+pub fn get_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(wallet, mint)
+}
+
+/// Get the PDA address and bump of the stream index of a sender.
+pub fn get_sender_index_address(sender: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SENDER_INDEX_ACCOUNT_SEED, sender.as_ref()], &ID)
+}
+
+/// Get the PDA address and bump of the stream index of a recipient.
+pub fn get_recipient_index_address(recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECIPIENT_INDEX_ACCOUNT_SEED, recipient.as_ref()], &ID)
+}
+
+/// Get the PDA address and bump of the stats of a mint.
+pub fn get_mint_stats_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_STATS_ACCOUNT_SEED, mint.as_ref()], &ID)
+}
+
+/// Get the global stats PDA address and bump.
+pub fn get_global_stats_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_STATS_ACCOUNT_SEED], &ID)
+}
+
+/// Get the mint registry PDA address and bump.
+pub fn get_mint_registry_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_REGISTRY_ACCOUNT_SEED], &ID)
+}
+
+/// Get the allowed mint PDA address and bump.
+pub fn get_allowed_mint_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWED_MINT_ACCOUNT_SEED, mint.as_ref()], &ID)
+}
+
+/// Get the program data account address of the Superstream program.
+pub fn get_program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
@@ -0,0 +1,73 @@
+//! Module for the keeper command line arguments.
+
+use std::{net::SocketAddr, path::PathBuf};
+
+use clap::Parser;
+use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
+use url::Url;
+
+/// Keeper that cancels insolvent Superstream streams as soon as anyone is allowed to, collecting the cancellation
+/// rewards.
+#[derive(Parser, Debug)]
+#[clap(name = "superstream-keeper", version)]
+pub struct Args {
+    /// JSON RPC URL of the cluster.
+    #[clap(short, long, env = "SUPERSTREAM_KEEPER_URL", default_value = "http://127.0.0.1:8899")]
+    pub url: String,
+    /// Websocket URL of the cluster. Derived from the JSON RPC URL if not provided.
+    #[clap(long, env = "SUPERSTREAM_KEEPER_WS_URL")]
+    pub ws_url: Option<String>,
+    /// Path to the keypair that signs and pays for the cancellations. Defaults to the Solana CLI keypair.
+    #[clap(short, long, env = "SUPERSTREAM_KEEPER_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+    /// Commitment level used for fetching and subscribing to streams.
+    #[clap(long, env = "SUPERSTREAM_KEEPER_COMMITMENT", default_value = "confirmed")]
+    pub commitment: CommitmentLevel,
+    /// Address to serve the Prometheus metrics on.
+    #[clap(long, env = "SUPERSTREAM_KEEPER_METRICS_ADDR", default_value = "127.0.0.1:9464")]
+    pub metrics_addr: SocketAddr,
+    /// Interval in seconds after which all the streams are fetched again, in case any account update was missed.
+    #[clap(long, env = "SUPERSTREAM_KEEPER_RESYNC_INTERVAL", default_value_t = 300)]
+    pub resync_interval: u64,
+}
+
+impl Args {
+    /// Get the websocket URL of the cluster.
+    pub fn ws_url(&self) -> String {
+        self.ws_url.clone().unwrap_or_else(|| get_websocket_url(&self.url))
+    }
+
+    /// Get the path to the keypair.
+    pub fn keypair_path(&self) -> PathBuf {
+        self.keypair.clone().unwrap_or_else(|| {
+            let mut path = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+            path.extend([".config", "solana", "id.json"]);
+            path
+        })
+    }
+
+    /// Get the commitment config.
+    pub fn commitment_config(&self) -> CommitmentConfig {
+        CommitmentConfig {
+            commitment: self.commitment,
+        }
+    }
+}
+
+/// Derive the websocket URL from the JSON RPC URL the same way the Solana CLI does - the scheme is changed to `ws` or
+/// `wss` and the port, if any, is incremented.
+fn get_websocket_url(url: &str) -> String {
+    let mut ws_url = match Url::parse(url) {
+        Ok(ws_url) => ws_url,
+        Err(_) => return url.to_string(),
+    };
+    let scheme = if ws_url.scheme() == "https" { "wss" } else { "ws" };
+    if ws_url.set_scheme(scheme).is_err() {
+        return url.to_string();
+    }
+    if let Some(port) = ws_url.port() {
+        // A valid URL always has a valid port, so this can't fail.
+        let _ = ws_url.set_port(Some(port.wrapping_add(1)));
+    }
+    ws_url.to_string()
+}
@@ -0,0 +1,44 @@
+//! Module for estimating the cluster time.
+
+use std::time::{Duration, Instant};
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::from_account, clock::Clock, sysvar};
+
+use crate::error::KeeperError;
+
+/// Cluster time estimated from the clock sysvar. The program checks solvency against the cluster's unix timestamp, which
+/// can drift from the local time, so cancellations are scheduled against this instead.
+#[derive(Clone, Copy, Debug)]
+pub struct ClusterClock {
+    unix_timestamp: u64,
+    fetched_at: Instant,
+}
+
+impl ClusterClock {
+    /// Fetch the clock sysvar.
+    pub fn fetch(rpc_client: &RpcClient) -> Result<Self, KeeperError> {
+        let account = rpc_client.get_account(&sysvar::clock::ID)?;
+        let clock: Clock = from_account(&account).ok_or(KeeperError::InvalidClock)?;
+        Ok(Self {
+            unix_timestamp: clock.unix_timestamp.max(0) as u64,
+            fetched_at: Instant::now(),
+        })
+    }
+
+    /// Get the time elapsed since the clock sysvar was fetched.
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+
+    /// Get the current cluster time.
+    pub fn now(&self) -> u64 {
+        self.unix_timestamp + self.fetched_at.elapsed().as_secs()
+    }
+
+    /// Get the duration until the cluster time is `at`. Returns zero if `at` is in the past.
+    pub fn duration_until(&self, at: u64) -> Duration {
+        let target = self.fetched_at + Duration::from_secs(at.saturating_sub(self.unix_timestamp));
+        target.saturating_duration_since(Instant::now())
+    }
+}
@@ -0,0 +1,45 @@
+//! Module for keeper errors.
+
+use solana_pubsub_client::pubsub_client::PubsubClientError;
+use thiserror::Error;
+
+/// Errors returned by the keeper. The underlying RPC errors are boxed to keep the results small.
+#[derive(Error, Debug)]
+pub enum KeeperError {
+    /// The keypair file could not be read.
+    #[error("Keypair could not be read: {0}")]
+    Keypair(String),
+    /// The RPC request failed.
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    /// The program account subscription failed.
+    #[error("Subscription failed: {0}")]
+    Pubsub(Box<PubsubClientError>),
+    /// The program account subscription was closed by the server.
+    #[error("Subscription closed")]
+    SubscriptionClosed,
+    /// The streams could not be fetched.
+    #[error(transparent)]
+    Client(#[from] superstream_client::ClientError),
+    /// The clock sysvar could not be deserialized.
+    #[error("Clock sysvar could not be deserialized")]
+    InvalidClock,
+    /// The metrics could not be registered.
+    #[error("Metrics could not be registered: {0}")]
+    Metrics(#[from] prometheus::Error),
+    /// The metrics server could not be started.
+    #[error("Metrics server could not be started: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<solana_client::client_error::ClientError> for KeeperError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
+
+impl From<PubsubClientError> for KeeperError {
+    fn from(err: PubsubClientError) -> Self {
+        Self::Pubsub(Box::new(err))
+    }
+}
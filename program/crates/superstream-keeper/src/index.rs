@@ -0,0 +1,255 @@
+//! Module for the in-memory solvency index.
+
+use std::collections::{BTreeSet, HashMap};
+
+use solana_sdk::pubkey::Pubkey;
+use superstream_client::Stream;
+
+/// A stream tracked by the index.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    /// The latest known state of the stream.
+    pub stream: Stream,
+    /// Time from which anyone can cancel the stream for being insolvent. `0` if the stream can already be cancelled.
+    pub cancellable_at: u64,
+    /// Time at which the keeper should next try to cancel the stream. It is later than `cancellable_at` if a
+    /// cancellation was already sent or failed.
+    pub due_at: u64,
+    /// Number of cancellation attempts since the stream was last updated.
+    pub attempts: u32,
+}
+
+/// In-memory index of streams ordered by the time from which they can be cancelled for being insolvent. Only streams
+/// that will become cancellable without any further change to them are tracked.
+#[derive(Debug, Default)]
+pub struct SolvencyIndex {
+    entries: HashMap<Pubkey, Entry>,
+    queue: BTreeSet<(u64, Pubkey)>,
+}
+
+impl SolvencyIndex {
+    /// Get the number of tracked streams.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if a stream is tracked.
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.entries.contains_key(address)
+    }
+
+    /// Get a tracked stream.
+    pub fn get(&self, address: &Pubkey) -> Option<&Entry> {
+        self.entries.get(address)
+    }
+
+    /// Insert or update a stream. The stream is removed if it can't become cancellable in its current state. Pending
+    /// retries are kept if the update doesn't change when the stream becomes cancellable.
+    pub fn upsert(&mut self, address: Pubkey, stream: Stream) {
+        let cancellable_at = match stream.state().insolvent_cancellable_at() {
+            Some(cancellable_at) => cancellable_at,
+            None => {
+                self.remove(&address);
+                return;
+            },
+        };
+
+        match self.entries.get_mut(&address) {
+            Some(entry) if entry.cancellable_at == cancellable_at => {
+                entry.stream = stream;
+            },
+            _ => {
+                self.remove(&address);
+                self.queue.insert((cancellable_at, address));
+                self.entries.insert(
+                    address,
+                    Entry {
+                        stream,
+                        cancellable_at,
+                        due_at: cancellable_at,
+                        attempts: 0,
+                    },
+                );
+            },
+        }
+    }
+
+    /// Remove a stream. Returns the removed entry, if the stream was tracked.
+    pub fn remove(&mut self, address: &Pubkey) -> Option<Entry> {
+        let entry = self.entries.remove(address)?;
+        self.queue.remove(&(entry.due_at, *address));
+        Some(entry)
+    }
+
+    /// Replace all the tracked streams. Pending retries are kept for the streams that are unchanged.
+    pub fn replace(&mut self, streams: Vec<(Pubkey, Stream)>) {
+        let mut previous = std::mem::take(self);
+        for (address, stream) in streams {
+            if let Some(entry) = previous.remove(&address) {
+                self.queue.insert((entry.due_at, address));
+                self.entries.insert(address, entry);
+            }
+            self.upsert(address, stream);
+        }
+    }
+
+    /// Get the time at which the next stream is due.
+    pub fn next_due_at(&self) -> Option<u64> {
+        self.queue.iter().next().map(|(due_at, _)| *due_at)
+    }
+
+    /// Get the addresses of all the streams due at time `at`.
+    pub fn due(&self, at: u64) -> Vec<Pubkey> {
+        self.queue
+            .iter()
+            .take_while(|(due_at, _)| *due_at <= at)
+            .map(|(_, address)| *address)
+            .collect()
+    }
+
+    /// Record a cancellation attempt and make the stream due again at time `at`.
+    pub fn reschedule(&mut self, address: &Pubkey, at: u64) {
+        if let Some(entry) = self.entries.get_mut(address) {
+            self.queue.remove(&(entry.due_at, *address));
+            self.queue.insert((at, *address));
+            entry.due_at = at;
+            entry.attempts = entry.attempts.saturating_add(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AccountDeserialize;
+
+    use super::*;
+
+    const STARTS_AT: u64 = 1_000;
+
+    /// Create a non-prepaid stream streaming 10 tokens per sec, which can be cancelled for being insolvent at
+    /// `STARTS_AT + total_topup_amount / 10 + 1`.
+    fn new_stream(total_topup_amount: u64) -> Stream {
+        let data = vec![0; Stream::space("")];
+        let mut stream = Stream::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        stream.starts_at = STARTS_AT;
+        stream.flow_interval = 1;
+        stream.flow_rate = 10;
+        stream.total_topup_amount = total_topup_amount;
+        stream
+    }
+
+    fn cancelled_stream() -> Stream {
+        let mut stream = new_stream(0);
+        stream.is_cancelled = true;
+        stream.cancelled_at = STARTS_AT;
+        stream
+    }
+
+    #[test]
+    fn upsert_orders_streams_by_cancellable_at() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut index = SolvencyIndex::default();
+        index.upsert(a, new_stream(1_000));
+        index.upsert(b, new_stream(100));
+        index.upsert(c, cancelled_stream());
+
+        assert_eq!(index.len(), 2);
+        assert!(!index.contains(&c));
+        assert_eq!(index.get(&a).unwrap().cancellable_at, STARTS_AT + 101);
+        assert_eq!(index.next_due_at(), Some(STARTS_AT + 11));
+        assert_eq!(index.due(STARTS_AT + 10), vec![]);
+        assert_eq!(index.due(STARTS_AT + 11), vec![b]);
+        assert_eq!(index.due(STARTS_AT + 101), vec![b, a]);
+    }
+
+    #[test]
+    fn upsert_removes_streams_that_cannot_become_cancellable() {
+        let a = Pubkey::new_unique();
+        let mut index = SolvencyIndex::default();
+        index.upsert(a, new_stream(100));
+        index.upsert(a, cancelled_stream());
+
+        assert_eq!(index.len(), 0);
+        assert_eq!(index.next_due_at(), None);
+        assert_eq!(index.due(u64::MAX), vec![]);
+    }
+
+    #[test]
+    fn upsert_keeps_retries_if_cancellable_at_is_unchanged() {
+        let a = Pubkey::new_unique();
+        let mut index = SolvencyIndex::default();
+        index.upsert(a, new_stream(100));
+        index.reschedule(&a, STARTS_AT + 100);
+
+        let mut stream = new_stream(100);
+        stream.total_withdrawn_amount = 50;
+        index.upsert(a, stream.clone());
+        let entry = index.get(&a).unwrap();
+        assert_eq!(entry.stream, stream);
+        assert_eq!(
+            (entry.cancellable_at, entry.due_at, entry.attempts),
+            (STARTS_AT + 11, STARTS_AT + 100, 1)
+        );
+
+        // A topup changes when the stream becomes cancellable, so the retries start over.
+        index.upsert(a, new_stream(200));
+        let entry = index.get(&a).unwrap();
+        assert_eq!(
+            (entry.cancellable_at, entry.due_at, entry.attempts),
+            (STARTS_AT + 21, STARTS_AT + 21, 0)
+        );
+        assert_eq!(index.due(u64::MAX), vec![a]);
+    }
+
+    #[test]
+    fn reschedule_changes_order() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut index = SolvencyIndex::default();
+        index.upsert(a, new_stream(100));
+        index.upsert(b, new_stream(1_000));
+
+        index.reschedule(&a, STARTS_AT + 200);
+        index.reschedule(&a, STARTS_AT + 300);
+        let entry = index.get(&a).unwrap();
+        assert_eq!(
+            (entry.cancellable_at, entry.due_at, entry.attempts),
+            (STARTS_AT + 11, STARTS_AT + 300, 2)
+        );
+        assert_eq!(index.next_due_at(), Some(STARTS_AT + 101));
+        assert_eq!(index.due(STARTS_AT + 299), vec![b]);
+        assert_eq!(index.due(STARTS_AT + 300), vec![b, a]);
+
+        // Untracked streams are ignored.
+        index.reschedule(&Pubkey::new_unique(), STARTS_AT);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.next_due_at(), Some(STARTS_AT + 101));
+    }
+
+    #[test]
+    fn replace_keeps_retries_of_unchanged_streams() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut index = SolvencyIndex::default();
+        index.upsert(a, new_stream(100));
+        index.upsert(b, new_stream(1_000));
+        index.reschedule(&a, STARTS_AT + 200);
+
+        index.replace(vec![(a, new_stream(100)), (c, new_stream(500))]);
+        assert_eq!(index.len(), 2);
+        assert!(!index.contains(&b));
+        let entry = index.get(&a).unwrap();
+        assert_eq!((entry.due_at, entry.attempts), (STARTS_AT + 200, 1));
+        assert_eq!(index.due(u64::MAX), vec![c, a]);
+    }
+
+    #[test]
+    fn remove_returns_entry() {
+        let a = Pubkey::new_unique();
+        let mut index = SolvencyIndex::default();
+        index.upsert(a, new_stream(100));
+        index.reschedule(&a, STARTS_AT + 200);
+
+        assert_eq!(index.remove(&a).unwrap().due_at, STARTS_AT + 200);
+        assert!(index.remove(&a).is_none());
+        assert_eq!(index.next_due_at(), None);
+    }
+}
@@ -0,0 +1,521 @@
+//! Module for the keeper loop.
+
+use std::{
+    collections::{HashMap, HashSet},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{select, Receiver};
+use log::{debug, error, info, warn};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::RpcFilterType,
+    rpc_response::{Response, RpcKeyedAccount},
+};
+use solana_pubsub_client::pubsub_client::{ProgramSubscription, PubsubClient, PubsubProgramClientSubscription};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use superstream_client::{
+    account::{deserialize_stream, fetch_streams, is_zero_copy_stream},
+    instruction::{cancel, cancel_zero_copy},
+    Stream, StreamFilters, StreamId, StreamLayout, PROGRAM_ID,
+};
+
+use crate::{clock::ClusterClock, error::KeeperError, index::SolvencyIndex, metrics::Metrics};
+
+/// Maximum time to wait for an account update before checking the clock, resync and due streams again.
+const MAX_WAIT: Duration = Duration::from_secs(5);
+/// Time after which the clock sysvar is fetched again.
+const CLOCK_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Time to wait before subscribing again after the subscription fails.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// Time in seconds to wait for a sent cancellation to show up in an account update before sending it again.
+const CONFIRMATION_TIMEOUT_SECS: u64 = 30;
+/// Maximum time in seconds to wait before retrying a failed cancellation.
+const MAX_RETRY_DELAY_SECS: u64 = 60;
+
+/// Keeper that tracks all the non-prepaid streams and cancels them as soon as they can be cancelled for being insolvent.
+pub struct Keeper {
+    rpc_client: RpcClient,
+    ws_url: String,
+    payer: Keypair,
+    metrics: Metrics,
+    resync_interval: Duration,
+    index: SolvencyIndex,
+    /// Sent cancellations that haven't shown up in an account update yet, with the time the stream became cancellable.
+    pending: HashMap<Pubkey, u64>,
+    /// Tracked streams in the zero-copy layout. They are cancelled with `cancel_zero_copy` instead of `cancel`.
+    zero_copy_streams: HashSet<Pubkey>,
+}
+
+impl Keeper {
+    /// Create a new keeper.
+    pub fn new(
+        rpc_client: RpcClient,
+        ws_url: String,
+        payer: Keypair,
+        metrics: Metrics,
+        resync_interval: Duration,
+    ) -> Self {
+        Self {
+            rpc_client,
+            ws_url,
+            payer,
+            metrics,
+            resync_interval,
+            index: SolvencyIndex::default(),
+            pending: HashMap::new(),
+            zero_copy_streams: HashSet::new(),
+        }
+    }
+
+    /// Run the keeper forever. Subscription and RPC errors are logged and the keeper subscribes again after a while.
+    pub fn run(&mut self) -> ! {
+        info!("Starting keeper [payer={}]", self.payer.pubkey());
+        loop {
+            if let Err(err) = self.run_subscription() {
+                self.metrics.errors.inc();
+                error!("Keeper failed, retrying in sometime: {err}");
+                thread::sleep(RECONNECT_DELAY);
+            }
+        }
+    }
+
+    fn run_subscription(&mut self) -> Result<(), KeeperError> {
+        let filters = StreamFilters {
+            is_prepaid: Some(false),
+            ..StreamFilters::default()
+        };
+        // Subscribe before syncing so that no update between the two is missed. Zero-copy streams can't be filtered by
+        // `is_prepaid` over RPC, so prepaid ones are received too and dropped by the index.
+        let (mut subscription, receiver) = self.subscribe(filters.to_rpc_filters())?;
+        let (mut zero_copy_subscription, zero_copy_receiver) = self.subscribe(filters.to_zero_copy_rpc_filters())?;
+        info!("Subscribed to stream updates [url={}]", self.ws_url);
+
+        let result = self.process(&receiver, &zero_copy_receiver);
+        unsubscribe(&mut subscription);
+        unsubscribe(&mut zero_copy_subscription);
+        result
+    }
+
+    fn subscribe(&self, filters: Vec<RpcFilterType>) -> Result<ProgramSubscription, KeeperError> {
+        Ok(PubsubClient::program_subscribe(
+            &self.ws_url,
+            &PROGRAM_ID,
+            Some(RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(self.rpc_client.commitment()),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            }),
+        )?)
+    }
+
+    fn process(
+        &mut self,
+        receiver: &Receiver<Response<RpcKeyedAccount>>,
+        zero_copy_receiver: &Receiver<Response<RpcKeyedAccount>>,
+    ) -> Result<(), KeeperError> {
+        let mut clock = self.refresh_clock()?;
+        self.sync()?;
+        let mut synced_at = Instant::now();
+
+        loop {
+            if clock.age() >= CLOCK_REFRESH_INTERVAL {
+                clock = self.refresh_clock()?;
+            }
+            if synced_at.elapsed() >= self.resync_interval {
+                self.sync()?;
+                synced_at = Instant::now();
+            }
+
+            self.cancel_due(&clock);
+            self.update_index_metrics();
+
+            let timeout = self
+                .index
+                .next_due_at()
+                .map_or(MAX_WAIT, |due_at| clock.duration_until(due_at).min(MAX_WAIT));
+            let response = select! {
+                recv(receiver) -> response => Some(response),
+                recv(zero_copy_receiver) -> response => Some(response),
+                default(timeout) => None,
+            };
+            match response {
+                Some(Ok(response)) => self.handle_update(response.value, &clock),
+                Some(Err(_)) => return Err(KeeperError::SubscriptionClosed),
+                None => {},
+            }
+        }
+    }
+
+    fn refresh_clock(&self) -> Result<ClusterClock, KeeperError> {
+        let clock = ClusterClock::fetch(&self.rpc_client)?;
+        self.metrics.cluster_time.set(clock.now() as i64);
+        Ok(clock)
+    }
+
+    /// Fetch all the active non-prepaid streams and rebuild the index from them.
+    fn sync(&mut self) -> Result<(), KeeperError> {
+        let filters = StreamFilters {
+            is_prepaid: Some(false),
+            is_cancelled: Some(false),
+            ..StreamFilters::default()
+        };
+        let mut streams = fetch_streams(
+            &self.rpc_client,
+            &StreamFilters {
+                layout: Some(StreamLayout::Regular),
+                ..filters.clone()
+            },
+        )?;
+        let zero_copy_streams = fetch_streams(
+            &self.rpc_client,
+            &StreamFilters {
+                layout: Some(StreamLayout::ZeroCopy),
+                ..filters
+            },
+        )?;
+        self.zero_copy_streams = zero_copy_streams.iter().map(|(address, _)| *address).collect();
+        streams.extend(zero_copy_streams);
+        let count = streams.len();
+        self.index.replace(streams);
+        // Sent cancellations that are no longer tracked have either gone through or the stream was closed.
+        self.pending.retain(|address, _| self.index.contains(address));
+        self.metrics.syncs.inc();
+        info!("Synced streams [active={count}, tracked={}]", self.index.len());
+        Ok(())
+    }
+
+    fn handle_update(&mut self, keyed_account: RpcKeyedAccount, clock: &ClusterClock) {
+        self.metrics.account_updates.inc();
+        let address = match keyed_account.pubkey.parse::<Pubkey>() {
+            Ok(address) => address,
+            Err(err) => {
+                warn!("Invalid stream address [address={}]: {err}", keyed_account.pubkey);
+                return;
+            },
+        };
+
+        let account: Option<Account> = keyed_account.account.decode();
+        let data = match account {
+            Some(account) if account.lamports > 0 && !account.data.is_empty() => account.data,
+            _ => {
+                // The account was closed.
+                self.index.remove(&address);
+                self.pending.remove(&address);
+                self.zero_copy_streams.remove(&address);
+                return;
+            },
+        };
+        let stream = match deserialize_stream(&data) {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!("Unable to deserialize stream [address={address}]: {err}");
+                return;
+            },
+        };
+        // Streams can be converted between the layouts at any time.
+        if is_zero_copy_stream(&data) {
+            self.zero_copy_streams.insert(address);
+        } else {
+            self.zero_copy_streams.remove(&address);
+        }
+
+        if stream.is_cancelled {
+            if let Some(cancellable_at) = self.pending.remove(&address) {
+                self.metrics.cancellations_confirmed.inc();
+                self.metrics
+                    .cancellation_delay
+                    .observe(stream.cancelled_at.saturating_sub(cancellable_at) as f64);
+                info!("Cancellation confirmed [address={address}]");
+            }
+        }
+        self.index.upsert(address, stream);
+        if let Some(entry) = self.index.get(&address) {
+            debug!(
+                "Tracking stream [address={address}, cancellable_at={}, now={}]",
+                entry.cancellable_at,
+                clock.now()
+            );
+        }
+    }
+
+    fn cancel_due(&mut self, clock: &ClusterClock) {
+        let now = clock.now();
+        for address in self.index.due(now) {
+            let entry = match self.index.get(&address) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let cancellable_at = entry.cancellable_at;
+            let attempts = entry.attempts;
+
+            info!("Found insolvent stream [address={address}, attempt={}]", attempts + 1);
+            match self.send_cancel(&address, &entry.stream) {
+                Ok(signature) => {
+                    info!("Sent cancellation [address={address}, signature={signature}]");
+                    self.metrics.cancellations_sent.inc();
+                    // If the exact time the stream became cancellable is not known, measure the delay from now.
+                    let cancellable_at = if cancellable_at == 0 { now } else { cancellable_at };
+                    self.pending.entry(address).or_insert(cancellable_at);
+                    self.index.reschedule(&address, now + CONFIRMATION_TIMEOUT_SECS);
+                },
+                Err(err) => {
+                    // The cluster clock might be slightly behind the estimate, so retry soon at first.
+                    warn!("Unable to cancel insolvent stream [address={address}]: {err}");
+                    self.metrics.cancellations_failed.inc();
+                    let delay = 1u64.checked_shl(attempts).unwrap_or(u64::MAX).min(MAX_RETRY_DELAY_SECS);
+                    self.index.reschedule(&address, now + delay);
+                },
+            }
+        }
+    }
+
+    fn send_cancel(&self, address: &Pubkey, stream: &Stream) -> Result<Signature, KeeperError> {
+        let payer = self.payer.pubkey();
+        let instructions = cancel_instructions(&payer, stream, self.zero_copy_streams.contains(address));
+        let blockhash = self.rpc_client.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer), &[&self.payer], blockhash);
+        Ok(self.rpc_client.send_transaction(&transaction)?)
+    }
+
+    fn update_index_metrics(&self) {
+        self.metrics.tracked_streams.set(self.index.len() as i64);
+        self.metrics
+            .next_due_at
+            .set(self.index.next_due_at().unwrap_or_default() as i64);
+    }
+}
+
+/// Build the instructions to cancel an insolvent stream. All the token accounts need to exist, so they are created
+/// first if needed.
+fn cancel_instructions(payer: &Pubkey, stream: &Stream, is_zero_copy: bool) -> Vec<Instruction> {
+    let mint = &stream.mint;
+    let mut instructions: Vec<Instruction> = [payer, &stream.sender, &stream.recipient]
+        .into_iter()
+        .map(|owner| create_associated_token_account_idempotent(payer, owner, mint, &anchor_spl::token::ID))
+        .collect();
+    let cancel = if is_zero_copy { cancel_zero_copy } else { cancel };
+    instructions.push(cancel(
+        &StreamId::from(stream),
+        payer,
+        &stream.sender,
+        &stream.recipient,
+    ));
+    instructions
+}
+
+fn unsubscribe(subscription: &mut PubsubProgramClientSubscription) {
+    if let Err(err) = subscription.send_unsubscribe() {
+        debug!("Unable to unsubscribe: {err}");
+    }
+    let _ = subscription.shutdown();
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AccountDeserialize;
+    use anchor_spl::token::spl_token;
+    use solana_sdk::{commitment_config::CommitmentConfig, program_pack::Pack, system_instruction};
+    use spl_associated_token_account::instruction::create_associated_token_account;
+    use superstream_client::{
+        instruction::{convert_stream_to_zero_copy, create_non_prepaid},
+        pda::get_token_address,
+        superstream::{instruction as data, state::DEPOSIT_AMOUNT_PERIOD_IN_SECS},
+    };
+
+    use super::*;
+
+    const LOCAL_URL: &str = "http://127.0.0.1:8899";
+    const LOCAL_WS_URL: &str = "ws://127.0.0.1:8900";
+    /// Error code of `StreamError::UserUnauthorizedToCancel`.
+    const USER_UNAUTHORIZED_TO_CANCEL: &str = "custom program error: 0x178b";
+
+    fn new_stream() -> Stream {
+        let data = vec![0; Stream::space("stream")];
+        let mut stream = Stream::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        stream.seed = 1;
+        stream.name = "stream".to_string();
+        stream.mint = Pubkey::new_unique();
+        stream.sender = Pubkey::new_unique();
+        stream.recipient = Pubkey::new_unique();
+        stream
+    }
+
+    #[test]
+    fn cancel_instructions_create_token_accounts_first() {
+        let payer = Pubkey::new_unique();
+        let stream = new_stream();
+        let instructions = cancel_instructions(&payer, &stream, false);
+
+        assert_eq!(instructions.len(), 4);
+        for (instruction, owner) in instructions.iter().zip([payer, stream.sender, stream.recipient]) {
+            assert_eq!(
+                *instruction,
+                create_associated_token_account_idempotent(&payer, &owner, &stream.mint, &anchor_spl::token::ID)
+            );
+        }
+        assert_eq!(
+            instructions[3],
+            cancel(&StreamId::from(&stream), &payer, &stream.sender, &stream.recipient)
+        );
+    }
+
+    #[test]
+    fn cancel_instructions_use_cancel_zero_copy_for_zero_copy_streams() {
+        let payer = Pubkey::new_unique();
+        let stream = new_stream();
+        let instructions = cancel_instructions(&payer, &stream, true);
+
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[..3], cancel_instructions(&payer, &stream, false)[..3]);
+        assert_eq!(
+            instructions[3],
+            cancel_zero_copy(&StreamId::from(&stream), &payer, &stream.sender, &stream.recipient)
+        );
+    }
+
+    fn airdrop(rpc_client: &RpcClient, address: &Pubkey) {
+        let signature = rpc_client.request_airdrop(address, 10_000_000_000).unwrap();
+        while !rpc_client.confirm_transaction(&signature).unwrap() {
+            thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    fn send(rpc_client: &RpcClient, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) {
+        let blockhash = rpc_client.get_latest_blockhash().unwrap();
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+        transaction.sign(&[&[payer], signers].concat(), blockhash);
+        rpc_client.send_and_confirm_transaction(&transaction).unwrap();
+    }
+
+    /// Create a non-prepaid stream with a new mint and track it with a keeper. The stream is still solvent, so the
+    /// program accepts all the accounts of the cancellation and rejects only the signer.
+    ///
+    /// Run with `cargo test -p superstream-keeper -- --ignored` against a local validator with the program deployed, as
+    /// described in the README. The mint registry must not be enabled.
+    #[test]
+    #[ignore = "needs a local validator with the program deployed"]
+    fn tracks_streams_on_local_validator() {
+        let rpc_client = RpcClient::new_with_commitment(LOCAL_URL.to_string(), CommitmentConfig::confirmed());
+        let sender = Keypair::new();
+        let mint = Keypair::new();
+        airdrop(&rpc_client, &sender.pubkey());
+
+        let mint_rent = rpc_client
+            .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+            .unwrap();
+        let topup_amount = 2 * DEPOSIT_AMOUNT_PERIOD_IN_SECS;
+        send(
+            &rpc_client,
+            &[
+                system_instruction::create_account(
+                    &sender.pubkey(),
+                    &mint.pubkey(),
+                    mint_rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &sender.pubkey(), None, 6)
+                    .unwrap(),
+                create_associated_token_account(&sender.pubkey(), &sender.pubkey(), &mint.pubkey(), &spl_token::ID),
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &get_token_address(&sender.pubkey(), &mint.pubkey()),
+                    &sender.pubkey(),
+                    &[],
+                    topup_amount,
+                )
+                .unwrap(),
+            ],
+            &sender,
+            &[&mint],
+        );
+
+        // 1 token per sec with the minimum topup amount, so the stream becomes insolvent after the deposit period.
+        let stream_id = StreamId::new(0, mint.pubkey(), "keeper");
+        send(
+            &rpc_client,
+            &[create_non_prepaid(
+                &sender.pubkey(),
+                &mint.pubkey(),
+                data::CreateNonPrepaid {
+                    seed: 0,
+                    name: "keeper".to_string(),
+                    recipient: Pubkey::new_unique(),
+                    starts_at: 0,
+                    ends_at: 0,
+                    initial_amount: 0,
+                    flow_interval: 1,
+                    flow_rate: 1,
+                    sender_can_cancel: true,
+                    sender_can_cancel_at: 0,
+                    sender_can_change_sender: false,
+                    sender_can_change_sender_at: 0,
+                    sender_can_pause: false,
+                    sender_can_pause_at: 0,
+                    recipient_can_resume_pause_by_sender: false,
+                    recipient_can_resume_pause_by_sender_at: 0,
+                    anyone_can_withdraw_for_recipient: false,
+                    anyone_can_withdraw_for_recipient_at: 0,
+                    topup_amount,
+                },
+            )],
+            &sender,
+            &[],
+        );
+
+        let payer = Keypair::new();
+        airdrop(&rpc_client, &payer.pubkey());
+        let metrics = Metrics::new().unwrap();
+        let mut keeper = Keeper::new(
+            rpc_client,
+            LOCAL_WS_URL.to_string(),
+            payer,
+            metrics,
+            Duration::from_secs(60),
+        );
+        let address = stream_id.address();
+        keeper.sync().unwrap();
+        let entry = keeper.index.get(&address).unwrap();
+        assert_eq!(
+            entry.cancellable_at,
+            entry.stream.starts_at + DEPOSIT_AMOUNT_PERIOD_IN_SECS + 1
+        );
+        assert!(!keeper.zero_copy_streams.contains(&address));
+        let err = keeper.send_cancel(&address, &entry.stream).unwrap_err();
+        assert!(err.to_string().contains(USER_UNAUTHORIZED_TO_CANCEL), "{err}");
+
+        // The stream is tracked in the zero-copy layout too and cancelled with `cancel_zero_copy`.
+        send(
+            &keeper.rpc_client,
+            &[convert_stream_to_zero_copy(&stream_id, &sender.pubkey())],
+            &sender,
+            &[],
+        );
+        keeper.sync().unwrap();
+        let entry = keeper.index.get(&address).unwrap();
+        assert_eq!(
+            entry.cancellable_at,
+            entry.stream.starts_at + DEPOSIT_AMOUNT_PERIOD_IN_SECS + 1
+        );
+        assert!(keeper.zero_copy_streams.contains(&address));
+        let err = keeper.send_cancel(&address, &entry.stream).unwrap_err();
+        assert!(err.to_string().contains(USER_UNAUTHORIZED_TO_CANCEL), "{err}");
+    }
+}
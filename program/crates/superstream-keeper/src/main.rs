@@ -0,0 +1,50 @@
+//! Keeper for [Superstream](https://superstream.finance/) payment streams.
+//!
+//! It subscribes to all the non-prepaid stream accounts, keeps an in-memory index of when each of them can be cancelled
+//! for being insolvent and cancels them at that time, collecting the cancellation rewards. Metrics are served in the
+//! Prometheus text format.
+
+mod args;
+mod clock;
+mod error;
+mod index;
+mod keeper;
+mod metrics;
+
+use std::{process, time::Duration};
+
+use clap::Parser;
+use log::error;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::signature::read_keypair_file;
+
+use crate::{args::Args, error::KeeperError, keeper::Keeper, metrics::Metrics};
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    if let Err(err) = run(args) {
+        error!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), KeeperError> {
+    let keypair_path = args.keypair_path();
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| KeeperError::Keypair(format!("{}: {err}", keypair_path.display())))?;
+
+    let metrics = Metrics::new()?;
+    metrics.serve(args.metrics_addr)?;
+
+    let rpc_client = RpcClient::new_with_commitment(args.url.clone(), args.commitment_config());
+    Keeper::new(
+        rpc_client,
+        args.ws_url(),
+        payer,
+        metrics,
+        Duration::from_secs(args.resync_interval),
+    )
+    .run()
+}
@@ -0,0 +1,129 @@
+//! Module for the Prometheus metrics of the keeper.
+
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+};
+
+use log::{info, warn};
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounter, IntGauge, Registry, TextEncoder};
+
+/// Prometheus metrics of the keeper.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    /// Number of streams tracked by the solvency index.
+    pub tracked_streams: IntGauge,
+    /// Time at which the next stream becomes cancellable, or `0` if no stream is tracked.
+    pub next_due_at: IntGauge,
+    /// Latest estimated cluster time.
+    pub cluster_time: IntGauge,
+    /// Number of stream account updates received.
+    pub account_updates: IntCounter,
+    /// Number of full syncs of the streams.
+    pub syncs: IntCounter,
+    /// Number of subscription, sync or RPC errors.
+    pub errors: IntCounter,
+    /// Number of cancellation transactions sent.
+    pub cancellations_sent: IntCounter,
+    /// Number of cancellation transactions that failed to send.
+    pub cancellations_failed: IntCounter,
+    /// Number of sent cancellations confirmed through account updates.
+    pub cancellations_confirmed: IntCounter,
+    /// Delay between a stream becoming cancellable and its cancellation being confirmed.
+    pub cancellation_delay: Histogram,
+}
+
+impl Metrics {
+    /// Create and register all the metrics.
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new_custom(Some("superstream_keeper".to_string()), None)?;
+        let metrics = Self {
+            tracked_streams: IntGauge::new("tracked_streams", "Number of streams tracked by the solvency index")?,
+            next_due_at: IntGauge::new("next_due_at", "Time at which the next stream becomes cancellable")?,
+            cluster_time: IntGauge::new("cluster_time", "Latest estimated cluster time")?,
+            account_updates: IntCounter::new("account_updates_total", "Number of stream account updates received")?,
+            syncs: IntCounter::new("syncs_total", "Number of full syncs of the streams")?,
+            errors: IntCounter::new("errors_total", "Number of subscription, sync or RPC errors")?,
+            cancellations_sent: IntCounter::new(
+                "cancellations_sent_total",
+                "Number of cancellation transactions sent",
+            )?,
+            cancellations_failed: IntCounter::new(
+                "cancellations_failed_total",
+                "Number of cancellation transactions that failed to send",
+            )?,
+            cancellations_confirmed: IntCounter::new(
+                "cancellations_confirmed_total",
+                "Number of sent cancellations confirmed through account updates",
+            )?,
+            cancellation_delay: Histogram::with_opts(
+                HistogramOpts::new(
+                    "cancellation_delay_seconds",
+                    "Delay between a stream becoming cancellable and its cancellation being confirmed",
+                )
+                .buckets(vec![1.0, 2.0, 5.0, 10.0, 30.0, 60.0, 300.0]),
+            )?,
+            registry,
+        };
+
+        metrics.registry.register(Box::new(metrics.tracked_streams.clone()))?;
+        metrics.registry.register(Box::new(metrics.next_due_at.clone()))?;
+        metrics.registry.register(Box::new(metrics.cluster_time.clone()))?;
+        metrics.registry.register(Box::new(metrics.account_updates.clone()))?;
+        metrics.registry.register(Box::new(metrics.syncs.clone()))?;
+        metrics.registry.register(Box::new(metrics.errors.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.cancellations_sent.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.cancellations_failed.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.cancellations_confirmed.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.cancellation_delay.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Serve the metrics in the Prometheus text format on a background thread. Every request gets the metrics
+    /// regardless of its path.
+    pub fn serve(&self, addr: SocketAddr) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        info!("Serving metrics [addr={}]", listener.local_addr()?);
+
+        let registry = self.registry.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if let Err(err) = stream.and_then(|stream| respond(stream, &registry)) {
+                    warn!("Unable to serve metrics: {err}");
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+fn respond(mut stream: TcpStream, registry: &Registry) -> std::io::Result<()> {
+    // The request itself is not needed. Read whatever has been sent so that the client doesn't see a reset connection.
+    let mut request = [0u8; 1024];
+    let _ = stream.read(&mut request)?;
+
+    let encoder = TextEncoder::new();
+    let mut body = Vec::new();
+    encoder
+        .encode(&registry.gather(), &mut body)
+        .map_err(std::io::Error::other)?;
+
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        encoder.format_type(),
+        body.len()
+    )?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
@@ -0,0 +1,174 @@
+//! Module for superstream math errors.
+
+use thiserror::Error;
+
+/// Errors returned by the stream math. Every variant has a counterpart with the same name in the on-chain program's
+/// `StreamError`.
+#[derive(Error, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// The stream sender is the same as the new sender. Should be different addresses.
+    #[error("The stream sender is the same as the new sender. Should be different addresses")]
+    SameSenders,
+    /// The stream recipient is the same as the new recipient. Should be different addresses.
+    #[error("The stream recipient is the same as the new recipient. Should be different addresses")]
+    SameRecipients,
+    /// The new sender is invalid.
+    #[error("The new sender is invalid")]
+    InvalidNewSender,
+    /// The sender is invalid.
+    #[error("The sender is invalid")]
+    InvalidSender,
+    /// The recipient is invalid.
+    #[error("The recipient is invalid")]
+    InvalidRecipient,
+    /// The end time is either 0 with prepaid = true, in the past or < starts_at. Should be >= current_time and
+    /// >= starts_at or if the stream is not prepaid, it can be 0.
+    #[error("The end time is either 0 with prepaid = true, in the past or < starts_at. Should be >= current_time and >= starts_at or if the stream is not prepaid, it can be 0")]
+    InvalidEndsAt,
+    /// The stream will never lead to any payments. Either there should be a initial amount or flow rate and flow
+    /// duration should be > 0.
+    #[error("The stream will never lead to any payments. Either there should be a initial amount or flow rate and flow duration should be > 0")]
+    ZeroLifetimeAmount,
+    /// The amount cannot be 0. Should be > 0.
+    #[error("The amount cannot be 0. Should be > 0")]
+    ZeroAmount,
+    /// The prepaid amount needed by the stream is out of bounds.
+    #[error("The prepaid amount needed by the stream is out of bounds")]
+    PrepaidAmountNeededOutOfBounds,
+    /// The deposit amount needed by the non-prepaid stream is out of bounds.
+    #[error("The deposit amount needed by the non-prepaid stream is out of bounds")]
+    DepositAmountNeededOutOfBounds,
+    /// The amount is less than the minimum initial amount needed.
+    #[error("The amount is less than the minimum initial amount needed")]
+    AmountLessThanAmountNeeded,
+    /// The user is not allowed to withdraw. Should be the recipient of the stream.
+    #[error("The user is not allowed to withdraw. Should be the recipient of the stream")]
+    UserUnauthorizedToWithdraw,
+    /// The withdrawn amount by recipient is more than the amount owed. THIS SHOULD NOT HAVE HAPPENED!!!
+    #[error("The withdrawn amount by recipient is more than the amount owed. THIS SHOULD NOT HAVE HAPPENED!!!")]
+    WithdrawnAmountGreaterThanAmountOwed,
+    /// The total withdrawn amount by the recipient of the stream is out of bounds.
+    #[error("The total withdrawn amount by the recipient of the stream is out of bounds")]
+    WithdrawAmountOutOfBounds,
+    /// The amount available to be withdrawn by the recipient of the stream is out of bounds.
+    #[error("The amount available to be withdrawn by the recipient of the stream is out of bounds")]
+    AmountAvailableToWithdrawOutOfBounds,
+    /// The cancellation refund amount to the sender of the stream is out of bounds.
+    #[error("The cancellation refund amount to the sender of the stream is out of bounds")]
+    CancellationRefundOutOfBounds,
+    /// The total topup amount by the sender of the stream is out of bounds.
+    #[error("The total topup amount by the sender of the stream is out of bounds")]
+    TopupAmountOutOfBounds,
+    /// The topup amount is more than what is needed by the stream.
+    #[error("The topup amount is more than what is needed by the stream")]
+    TopupAmountMoreThanMaxAcceptable,
+    /// The stream has already been cancelled.
+    #[error("The stream has already been cancelled")]
+    StreamAlreadyCancelled,
+    /// The user is not allowed to cancel. Should be the sender or the recipient of the stream.
+    #[error("The user is not allowed to cancel. Should be the sender or the recipient of the stream")]
+    UserUnauthorizedToCancel,
+    /// The sender is not allowed to cancel permanently or at the moment.
+    #[error("The sender is not allowed to cancel permanently or at the moment")]
+    SenderCannotCancel,
+    /// The stream is prepaid. Should be a non-prepaid stream.
+    #[error("The stream is prepaid. Should be a non-prepaid stream")]
+    StreamIsPrepaid,
+    /// The stream has already stopped. Should be an unstopped stream.
+    #[error("The stream has already stopped. Should be an unstopped stream")]
+    StreamHasStopped,
+    /// The stream is already paused. Should be a non-paused stream.
+    #[error("The stream is already paused. Should be a non-paused stream")]
+    StreamIsPaused,
+    /// The stream is not paused. Should be a paused stream.
+    #[error("The stream is not paused. Should be a paused stream")]
+    StreamIsNotPaused,
+    /// The stream has no flow payments. Should be a stream stream with a positive flow rate and flow period.
+    #[error("The stream has no flow payments. Should be a stream stream with a positive flow rate and flow period")]
+    StreamHasNoFlowPayments,
+    /// The sender is not allowed to change sender of the stream permanently or at the moment.
+    #[error("The sender is not allowed to change sender of the stream permanently or at the moment")]
+    SenderCannotChangeSender,
+    /// The sender is not allowed to pause stream permanently or at the moment.
+    #[error("The sender is not allowed to pause stream permanently or at the moment")]
+    SenderCannotPause,
+    /// The recipient is not allowed resume a stream paused by sender permanently or at the moment.
+    #[error("The recipient is not allowed resume a stream paused by sender permanently or at the moment")]
+    RecipientCannotResumePauseBySender,
+    /// The user is not allowed to pause. Should be the sender or the recipient of the stream.
+    #[error("The user is not allowed to pause. Should be the sender or the recipient of the stream")]
+    UserUnauthorizedToPause,
+    /// The user is not allowed to resume. Should be the sender or the recipient of the stream.
+    #[error("The user is not allowed to resume. Should be the sender or the recipient of the stream")]
+    UserUnauthorizedToResume,
+    /// The stream has not ended. Should have ended and nat been cancelled.
+    #[error("The stream has not ended. Should have ended and nat been cancelled")]
+    StreamNotEnded,
+    /// The insolvency grace period is more than the deposit amount period. Should be <= 8 hrs.
+    #[error("The insolvency grace period is more than the deposit amount period. Should be <= 8 hrs")]
+    InvalidInsolvencyGracePeriod,
+    /// The stream is insolvent but within the insolvency grace period. Only the sender can topup and only the sender or
+    /// the recipient can cancel.
+    #[error("The stream is insolvent but within the insolvency grace period. Only the sender can topup and only the sender or the recipient can cancel")]
+    StreamInInsolvencyGracePeriod,
+    /// The recipient deposit share is more than 100%. Should be <= 10000 basis points.
+    #[error("The recipient deposit share is more than 100%. Should be <= 10000 basis points")]
+    InvalidRecipientDepositShare,
+    /// The user is not allowed to propose, cancel or accept a settlement. Should be the sender or the recipient of the
+    /// stream and only the other party can accept a settlement.
+    #[error("The user is not allowed to propose, cancel or accept a settlement. Should be the sender or the recipient of the stream and only the other party can accept a settlement")]
+    UserUnauthorizedToSettle,
+    /// No settlement has been proposed for the stream.
+    #[error("No settlement has been proposed for the stream")]
+    SettlementNotProposed,
+    /// The settlement amount is different from the proposed settlement amount.
+    #[error("The settlement amount is different from the proposed settlement amount")]
+    SettlementAmountMismatch,
+    /// The settlement amount is invalid. Should be >= total withdrawn amount and <= total amount paid by the sender.
+    #[error(
+        "The settlement amount is invalid. Should be >= total withdrawn amount and <= total amount paid by the sender"
+    )]
+    InvalidSettlementAmount,
+    /// The user is not allowed to arbitrate. Should be the arbiter of the stream.
+    #[error("The user is not allowed to arbitrate. Should be the arbiter of the stream")]
+    UserUnauthorizedToArbitrate,
+    /// The stream is frozen by the arbiter. Should be a non-frozen stream.
+    #[error("The stream is frozen by the arbiter. Should be a non-frozen stream")]
+    StreamIsFrozen,
+    /// The stream is not frozen. Should be a frozen stream.
+    #[error("The stream is not frozen. Should be a frozen stream")]
+    StreamIsNotFrozen,
+    /// The stream is pending recipient acceptance. Should be an accepted stream.
+    #[error("The stream is pending recipient acceptance. Should be an accepted stream")]
+    StreamIsPendingAcceptance,
+    /// The stream is not pending recipient acceptance. Should be a stream pending recipient acceptance.
+    #[error("The stream is not pending recipient acceptance. Should be a stream pending recipient acceptance")]
+    StreamIsNotPendingAcceptance,
+    /// The user is not allowed to accept the stream. Should be the recipient of the stream.
+    #[error("The user is not allowed to accept the stream. Should be the recipient of the stream")]
+    UserUnauthorizedToAccept,
+    /// No sender change has been proposed for the stream.
+    #[error("No sender change has been proposed for the stream")]
+    SenderChangeNotProposed,
+    /// No recipient change has been proposed for the stream.
+    #[error("No recipient change has been proposed for the stream")]
+    RecipientChangeNotProposed,
+    /// The new recipient is invalid.
+    #[error("The new recipient is invalid")]
+    InvalidNewRecipient,
+    /// The permission expiry time is invalid. It should be 0 or after the time at which the permission is given.
+    #[error("The permission expiry time is invalid")]
+    InvalidPermissionExpiry,
+    /// A documented stream invariant does not hold. This indicates a bug in the stream accounting.
+    #[error("A stream invariant does not hold")]
+    InvariantViolation,
+    /// The user is not allowed to close the stream. Should be the sender or the recipient of the stream.
+    #[error("The user is not allowed to close the stream. Should be the sender or the recipient of the stream")]
+    UserUnauthorizedToClose,
+    /// The stream has not stopped. Should have ended or been cancelled.
+    #[error("The stream has not stopped. Should have ended or been cancelled")]
+    StreamNotStopped,
+    /// The stream still holds funds. Everything should have been withdrawn or refunded.
+    #[error("The stream still holds funds. Everything should have been withdrawn or refunded")]
+    StreamHasFundsLeft,
+}
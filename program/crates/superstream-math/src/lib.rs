@@ -0,0 +1,36 @@
+//! Stream accounting for [Superstream](https://superstream.finance/) payment streams.
+//!
+//! This crate has no dependency on Anchor. Time is always passed explicitly and users are identified by their public
+//! keys, so the same math that runs on-chain can be used from Rust services, tests and other programs.
+
+/// Return the error if the condition is false.
+macro_rules! require {
+    ($cond:expr, $err:expr $(,)?) => {
+        if !($cond) {
+            return Err($err);
+        }
+    };
+}
+
+pub mod error;
+pub mod stream;
+
+pub use crate::{
+    error::MathError,
+    stream::{get_permission_times, CancelTransferParams, FlowRate, StreamState, StreamStatus, WithdrawTransferParams},
+};
+
+/// Result type of the stream math.
+pub type Result<T> = std::result::Result<T, MathError>;
+
+/// Deposit amount period (in seconds) for a non-prepaid stream. If a non-prepaid stream has unlimited lifetime or
+/// lifetime >= DEPOSIT_AMOUNT_PERIOD_IN_SECS, a security deposit is taken from the sender which would not be returned
+/// in case the stream becomes insolvent. This is done to make sure users keep topping up their streams on time.
+pub const DEPOSIT_AMOUNT_PERIOD_IN_SECS: u64 = 8 * 60 * 60; // 8 hrs
+
+/// Denominator for amounts expressed in basis points.
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Scale of fixed-point flow rates. A fixed-point flow rate is the amount streamed per second multiplied by
+/// FLOW_RATE_SCALE, and is stored as `flow_rate` with `flow_interval == FLOW_RATE_SCALE`. See [`FlowRate`].
+pub const FLOW_RATE_SCALE: u64 = 1_000_000_000;
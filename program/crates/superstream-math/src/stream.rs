@@ -0,0 +1,2262 @@
+//! Module for superstream stream accounting.
+
+use std::cmp::min;
+
+use solana_program::pubkey::Pubkey;
+
+use crate::{error::MathError, Result, BPS_DENOMINATOR, DEPOSIT_AMOUNT_PERIOD_IN_SECS, FLOW_RATE_SCALE};
+
+/// Accounting state of a payment stream. This has the same fields as the on-chain `Stream` account except the PDA
+/// seed, bump and the name, so that all the stream math can be run off-chain with an explicit time.
+///
+/// Possible states of a stream:
+/// - Not started
+///     - Pending recipient acceptance
+///     - Scheduled
+///     - Cancelled before start
+/// - Started but not stopped
+///     - Streaming
+///     - Paused
+///     - Insolvent
+/// - Stopped
+///     - Cancelled after start
+///     - Ended
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamState {
+    /// If true, the stream is prepaid - all the required amount needs to be deposited on creation. Prepaid streams
+    /// cannot have unlimited lifetime.
+    pub is_prepaid: bool,
+
+    /// SPL token mint address.
+    pub mint: Pubkey,
+    /// Sender address.
+    pub sender: Pubkey,
+    /// Recipient address.
+    pub recipient: Pubkey,
+
+    /// Time at which the stream was created.
+    pub created_at: u64,
+    /// Start time of the stream.
+    ///
+    /// INVARIANT: >= created_at
+    pub starts_at: u64,
+    /// End time of the stream. If the stream is unbounded, this can be 0 to indicate no end time.
+    ///
+    /// INVARIANT: prepaid: >= starts_at
+    /// INVARIANT: unbounded: == 0 || >= starts_at
+    pub ends_at: u64,
+
+    /// Amount available to the recipient once stream starts.
+    pub initial_amount: u64,
+    /// Flow interval is the interval in which flow payments are released. If == FLOW_RATE_SCALE, the flow rate is a
+    /// fixed-point amount per second. See [`FlowRate`].
+    pub flow_interval: u64,
+    /// Flow rate is the number of tokens to stream per interval.
+    pub flow_rate: u64,
+
+    /// If true, the stream has been cancelled.
+    pub is_cancelled: bool,
+    /// If true, the stream has been cancelled before start.
+    ///
+    /// INVARIANT: !is_cancelled => == false
+    pub is_cancelled_before_start: bool,
+    /// If true, the stream has been cancelled by the sender.
+    ///
+    /// INVARIANT: !is_cancelled => == false
+    pub is_cancelled_by_sender: bool,
+
+    /// Time at which the stream was cancelled. If it is > 0, it means the stream has been cancelled and any funds in
+    /// the escrow account not available to be withdrawn by the recipient have been retrieved.
+    ///
+    /// INVARIANT: cancelled_at > 0 iff is_cancelled == true
+    pub cancelled_at: u64,
+
+    /// True if a solvent stream can be cancelled by the sender.
+    pub sender_can_cancel: bool,
+    /// Time at which the sender is allowed to cancel a solvent stream. This can be in the future.
+    pub sender_can_cancel_at: u64,
+
+    /// True if the sender can change the sender of the stream who will do the upcoming topups.
+    ///
+    /// INVARIANT: prepaid: false
+    pub sender_can_change_sender: bool,
+    /// Time at which the sender is allowed to change the sender.
+    ///
+    /// INVARIANT: prepaid: == 0
+    pub sender_can_change_sender_at: u64,
+
+    /// If true, the stream is paused.
+    ///
+    /// INVARIANT: prepaid: == false
+    pub is_paused: bool,
+    /// If true, the stream is paused by sender.
+    ///
+    /// INVARIANT: prepaid: == false
+    /// INVARIANT: runtime: unbounded: !is_paused || !sender_can_pause => == false
+    pub is_paused_by_sender: bool,
+
+    /// True if a stream can be paused by the sender.
+    ///
+    /// INVARIANT: prepaid: false
+    pub sender_can_pause: bool,
+    /// Time at which the sender is allowed to pause a stream.
+    ///
+    /// INVARIANT: prepaid: == 0
+    pub sender_can_pause_at: u64,
+
+    /// True if a stream can be resumed by the recipient if it was paused by the sender.
+    ///
+    /// INVARIANT: prepaid: false
+    pub recipient_can_resume_pause_by_sender: bool,
+    /// Time at which the recipient is allowed to resume a stream which was paused by the sender.
+    ///
+    /// INVARIANT: prepaid: == 0
+    pub recipient_can_resume_pause_by_sender_at: u64,
+
+    /// True if anyone can withdraw on behalf of the recipient. The amount will go in recipients' account.
+    pub anyone_can_withdraw_for_recipient: bool,
+    /// Time at which anyone can withdraw on behalf of the recipient.
+    pub anyone_can_withdraw_for_recipient_at: u64,
+
+    /// Time at which the stream was last resumed.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: (== 0 || >= starts_at) && (ends_at == 0 || <= ends_at)
+    pub last_resumed_at: u64,
+    /// Total accumulated active (!is_paused) time since starts_at. This does not include (current_time -
+    /// last_resumed_at) time if the stream is not paused.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: == 0 || (current_time > starts_at && == current_time - starts_at - total_paused_time)
+    /// INVARIANT: runtime: unbounded: <= current_time - starts_at
+    pub accumulated_active_time: u64,
+
+    /// Total amount withdrawn by the recipient.
+    ///
+    /// INVARIANT: runtime: !is_cancelled => <= amount_owed && <= total_topup_amount
+    /// INVARIANT: is_cancelled => == total_topup_amount
+    pub total_withdrawn_amount: u64,
+    /// Last time at which recipient withdrew any amount.
+    pub last_withdrawn_at: u64,
+    /// Last amount which recipient withdrew.
+    pub last_withdrawn_amount: u64,
+
+    /// Total topup amount added for the stream.
+    ///
+    /// INVARIANT: prepaid: !is_cancelled => == prepaid_amount_needed
+    /// INVARIANT: unbounded: !is_cancelled => >= initial_amount
+    /// INVARIANT: is_cancelled => == total amount owed to the recipient
+    pub total_topup_amount: u64,
+    /// Last time at which sender topped up the stream.
+    pub last_topup_at: u64,
+    /// Last topup amount.
+    pub last_topup_amount: u64,
+
+    /// Total deposit amount needed for the non-prepaid stream. These are needed in case the sender does not topup the
+    /// stream in time and the amount owed becomes > total topup amount. When that happens and the insolvency grace
+    /// period is over, anyone can cancel the stream. The deposit amount will be split between the recipient and
+    /// whoever finds the insolvency and cancels the stream as a reward.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: == 0 || == DEPOSIT_AMOUNT_PERIOD_IN_SECS of streaming payments
+    /// INVARIANT: is_cancelled => == 0
+    pub deposit_needed: u64,
+    /// Active (!is_paused) streaming time after the stream becomes insolvent during which only the sender can cure the
+    /// insolvency by topping up. The flow keeps accruing as debt which is covered by the deposit. Only after this
+    /// period, the stream can be cancelled by anyone and the deposit is given as a reward.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: <= DEPOSIT_AMOUNT_PERIOD_IN_SECS
+    pub insolvency_grace_period: u64,
+    /// Share of the deposit (in basis points) given to the recipient when an insolvent stream is cancelled. The rest
+    /// of the deposit is given as a reward to whoever cancels the stream.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: <= BPS_DENOMINATOR
+    pub recipient_deposit_share_bps: u16,
+
+    /// Total amount owed to the recipient proposed by either the sender or the recipient to terminate the stream. If
+    /// the other party accepts the settlement, the stream is cancelled and the escrow is split accordingly.
+    ///
+    /// INVARIANT: !is_settlement_proposed && !is_settled => == 0
+    pub settlement_amount: u64,
+    /// If true, a settlement has been proposed and is waiting to be accepted.
+    ///
+    /// INVARIANT: is_cancelled => == false
+    pub is_settlement_proposed: bool,
+    /// If true, the settlement has been proposed by the sender.
+    ///
+    /// INVARIANT: !is_settlement_proposed => == false
+    pub is_settlement_proposed_by_sender: bool,
+    /// If true, the stream has been terminated with a settlement accepted by both parties.
+    ///
+    /// INVARIANT: is_settled => is_cancelled && total_topup_amount == settlement_amount
+    pub is_settled: bool,
+
+    /// Arbiter address. The arbiter is a neutral third party who can freeze the stream in case of a dispute and later
+    /// resolve it by deciding how the escrow is split. If it is the default public key, the stream has no arbiter.
+    pub arbiter: Pubkey,
+    /// If true, the stream has been frozen by the arbiter. Withdrawals, cancellations, settlements and refunds are
+    /// halted until the arbiter unfreezes or resolves the stream. The flow is not affected.
+    ///
+    /// INVARIANT: arbiter == Pubkey::default() || is_cancelled => == false
+    pub is_frozen: bool,
+
+    /// If true, the stream is waiting for the recipient to accept it and the flow has not started. When the recipient
+    /// accepts, the schedule is shifted so that the stream effectively starts at the time of acceptance if it was
+    /// scheduled to start earlier. Before that, the sender can cancel the stream and recover the full escrow.
+    pub is_pending_acceptance: bool,
+
+    /// Proposed new sender address. The sender changes only when the new sender accepts. If it is the default public
+    /// key, no sender change has been proposed.
+    ///
+    /// INVARIANT: prepaid: == Pubkey::default()
+    pub pending_sender: Pubkey,
+    /// Proposed new recipient address. The recipient changes only when the new recipient accepts. If it is the default
+    /// public key, no recipient change has been proposed.
+    pub pending_recipient: Pubkey,
+
+    /// Time after which the sender is no longer allowed to cancel a solvent stream. If it is 0, the permission never
+    /// expires.
+    ///
+    /// INVARIANT: == 0 || > sender_can_cancel_at
+    pub sender_can_cancel_expires_at: u64,
+    /// Time after which the sender is no longer allowed to change the sender. If it is 0, the permission never expires.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: == 0 || > sender_can_change_sender_at
+    pub sender_can_change_sender_expires_at: u64,
+    /// Time after which the sender is no longer allowed to pause a stream. If it is 0, the permission never expires.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: == 0 || > sender_can_pause_at
+    pub sender_can_pause_expires_at: u64,
+    /// Time after which the recipient is no longer allowed to resume a stream which was paused by the sender. If it is
+    /// 0, the permission never expires.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: == 0 || > recipient_can_resume_pause_by_sender_at
+    pub recipient_can_resume_pause_by_sender_expires_at: u64,
+    /// Time after which anyone is no longer allowed to withdraw on behalf of the recipient. If it is 0, the permission
+    /// never expires.
+    ///
+    /// INVARIANT: == 0 || > anyone_can_withdraw_for_recipient_at
+    pub anyone_can_withdraw_for_recipient_expires_at: u64,
+}
+
+impl StreamState {
+    // --- Utility functions --- BEGIN ---
+
+    pub fn has_flow_payments(&self) -> bool {
+        self.flow_rate > 0 && (self.ends_at == 0 || self.ends_at > self.starts_at)
+    }
+
+    /// Get the flow rate of the stream.
+    pub fn get_flow_rate(&self) -> FlowRate {
+        FlowRate::new(self.flow_interval, self.flow_rate)
+    }
+
+    /// Get the amount streamed in `active_time` secs, i.e. `active_time * flow_rate / flow_interval`. The product is
+    /// computed in u128 so that `None` is returned only if the final amount does not fit in u64.
+    ///
+    /// The amount is always rounded down and is computed from the total active time instead of being accumulated per
+    /// interval, so the rounding error is < 1 base unit and never grows over the lifetime of the stream. The prepaid
+    /// amount, the deposit, the max acceptable topup and the amount owed all go through this function, so e.g. the
+    /// amount owed to the recipient of a prepaid stream at its end is exactly the prepaid amount and nothing is left in
+    /// the escrow once the stream ends or is cancelled.
+    fn get_flow_amount(&self, active_time: u64) -> Option<u64> {
+        u64::try_from(active_time as u128 * self.flow_rate as u128 / self.flow_interval as u128).ok()
+    }
+
+    /// Calculate the amount of prepaid needed for a prepaid stream. This is called when creating the stream.
+    pub fn get_prepaid_amount_needed(&self) -> Result<u64> {
+        if !self.is_prepaid || self.ends_at == 0 {
+            Ok(0)
+        } else if !self.has_flow_payments() {
+            Ok(self.initial_amount)
+        } else {
+            self.initial_amount
+                .checked_add(
+                    self.get_flow_amount(self.ends_at - self.starts_at)
+                        .ok_or(MathError::PrepaidAmountNeededOutOfBounds)?,
+                )
+                .ok_or(MathError::PrepaidAmountNeededOutOfBounds)
+        }
+    }
+
+    /// Calculate the amount of deposit needed for the streaming payments excluding the initial amount. This is called
+    /// when creating the stream.
+    pub fn get_deposit_needed(&self) -> Result<u64> {
+        Ok(if self.is_prepaid || !self.has_flow_payments() {
+            0
+        } else {
+            let deposit_period = if self.ends_at == 0 {
+                DEPOSIT_AMOUNT_PERIOD_IN_SECS
+            } else {
+                min(DEPOSIT_AMOUNT_PERIOD_IN_SECS, self.ends_at - self.starts_at)
+            };
+            let deposit_needed = self
+                .get_flow_amount(deposit_period)
+                .ok_or(MathError::DepositAmountNeededOutOfBounds)?;
+
+            if deposit_needed >= 10 {
+                deposit_needed
+            } else {
+                deposit_needed + 1
+            }
+        })
+    }
+
+    /// Returns true if the sender is allowed to cancel a solvent stream at time `at`.
+    pub fn sender_can_cancel_now(&self, at: u64) -> bool {
+        is_permission_active(
+            self.sender_can_cancel,
+            self.sender_can_cancel_at,
+            self.sender_can_cancel_expires_at,
+            at,
+        )
+    }
+
+    /// Returns true if the sender is allowed to change the sender at time `at`.
+    pub fn sender_can_change_sender_now(&self, at: u64) -> bool {
+        is_permission_active(
+            self.sender_can_change_sender,
+            self.sender_can_change_sender_at,
+            self.sender_can_change_sender_expires_at,
+            at,
+        )
+    }
+
+    /// Returns true if the sender is allowed to pause the stream at time `at`.
+    pub fn sender_can_pause_now(&self, at: u64) -> bool {
+        is_permission_active(
+            self.sender_can_pause,
+            self.sender_can_pause_at,
+            self.sender_can_pause_expires_at,
+            at,
+        )
+    }
+
+    /// Returns true if the recipient is allowed to resume a stream paused by the sender at time `at`.
+    pub fn recipient_can_resume_pause_by_sender_now(&self, at: u64) -> bool {
+        is_permission_active(
+            self.recipient_can_resume_pause_by_sender,
+            self.recipient_can_resume_pause_by_sender_at,
+            self.recipient_can_resume_pause_by_sender_expires_at,
+            at,
+        )
+    }
+
+    /// Returns true if anyone is allowed to withdraw on behalf of the recipient at time `at`.
+    pub fn anyone_can_withdraw_for_recipient_now(&self, at: u64) -> bool {
+        is_permission_active(
+            self.anyone_can_withdraw_for_recipient,
+            self.anyone_can_withdraw_for_recipient_at,
+            self.anyone_can_withdraw_for_recipient_expires_at,
+            at,
+        )
+    }
+
+    pub fn get_stops_at(&self) -> u64 {
+        let cancelled_at = self.cancelled_at;
+        let ends_at = self.ends_at;
+        if cancelled_at == 0 {
+            ends_at
+        } else if ends_at == 0 {
+            cancelled_at
+        } else {
+            min(ends_at, cancelled_at)
+        }
+    }
+
+    /// Check if the stream has stopped.
+    pub fn has_stopped(&self, at: u64) -> bool {
+        let stops_at = self.get_stops_at();
+        stops_at > 0 && at > stops_at
+    }
+
+    fn min_with_stopped_at(&self, at: u64) -> u64 {
+        let stops_at = self.get_stops_at();
+        if stops_at > 0 && at > stops_at {
+            // If the stream has been stopped for some reason - either ending or being cancelled - make at = stopped_at
+            // if the stream stopped before at. This will make sure, the amount is calculated only till the time the
+            // stream was active.
+            stops_at
+        } else {
+            at
+        }
+    }
+
+    // INVARIANT: (stops_at == 0 || at <= stops_at) && at >= self.starts_at && self.has_flow_payments()
+    fn unsafe_get_active_time_after_start(&self, at: u64) -> Result<u64> {
+        Ok(if self.is_paused {
+            // INVARIANT: The stream is paused => accumulated time is the total time.
+            self.accumulated_active_time
+        } else if self.last_resumed_at == 0 {
+            // INVARIANT: The stream is not paused and was never resumed => stream was never paused.
+            at - self.starts_at
+        } else {
+            // SAFETY: INVARIANT: last_resumed_at != 0 =>
+            //     last_resumed_at >= starts_at && (ends_at == 0 || < ends_at) =>
+            //     last_resumed_at will never be > ends_at if ends_at > 0
+            (at - self.last_resumed_at)
+                .checked_add(self.accumulated_active_time)
+                .ok_or(MathError::AmountAvailableToWithdrawOutOfBounds)?
+        })
+    }
+
+    /// Get the maximum acceptable topup amount.
+    pub fn get_max_acceptable_topup_amount(&self, at: u64) -> Result<(bool, u64)> {
+        Ok(if self.is_prepaid || !self.has_flow_payments() {
+            (false, 0)
+        } else {
+            // Streams: non-prepaid, with flow payments.
+            let stops_at = self.get_stops_at();
+            if stops_at == 0 {
+                // Streams: non-prepaid, with flow payments, non-cancelled with no end time.
+                (true, 0)
+            } else if stops_at < self.starts_at {
+                // Streams: non-prepaid, with flow payments, stopped before start.
+                (false, 0)
+            } else {
+                // Streams: non-prepaid, with flow payments, cancelled after start time and/or with set end time after
+                // start time.
+                let total_possible_active_time = if at < self.starts_at {
+                    // Streams: non-prepaid, with flow payments, not-started, with set end time after start time. The
+                    // stream cannot be cancelled before current time.
+                    self.ends_at - self.starts_at
+                } else {
+                    // Streams: non-prepaid, with flow payments, started, cancelled after start time and/or with set end
+                    // time after start time.
+                    if stops_at <= at {
+                        // Streams: non-prepaid, with flow payments, started, stopped after start time.
+                        self.unsafe_get_active_time_after_start(stops_at)?
+                    } else {
+                        // Streams: non-prepaid, with flow payments, started, not already stopped => with set end time
+                        // after start time in the future.
+                        self.unsafe_get_active_time_after_start(at)?
+                            .checked_add(stops_at - at)
+                            .ok_or(MathError::TopupAmountOutOfBounds)?
+                    }
+                };
+
+                let total_possible_topup = if total_possible_active_time == 0 {
+                    self.initial_amount
+                } else {
+                    self.initial_amount
+                        .checked_add(
+                            self.get_flow_amount(total_possible_active_time)
+                                .ok_or(MathError::TopupAmountOutOfBounds)?,
+                        )
+                        .ok_or(MathError::TopupAmountOutOfBounds)?
+                };
+
+                (false, total_possible_topup.saturating_sub(self.total_topup_amount))
+            }
+        })
+    }
+
+    /// Get the total amount owed to the recipient.
+    pub fn get_amount_owed(&self, at: u64) -> Result<u64> {
+        if self.is_settled {
+            // The stream has been terminated with a settlement. The settlement amount replaces whatever had accrued.
+            return Ok(self.settlement_amount);
+        }
+        if self.is_pending_acceptance {
+            // The flow starts only after the recipient accepts the stream.
+            return Ok(0);
+        }
+
+        let at = self.min_with_stopped_at(at);
+
+        Ok(if at < self.starts_at {
+            0
+        } else if !self.has_flow_payments() {
+            self.initial_amount
+        } else {
+            let active_time = self.unsafe_get_active_time_after_start(at)?;
+            if active_time == 0 {
+                self.initial_amount
+            } else {
+                self.initial_amount
+                    .checked_add(
+                        self.get_flow_amount(active_time)
+                            .ok_or(MathError::AmountAvailableToWithdrawOutOfBounds)?,
+                    )
+                    .ok_or(MathError::AmountAvailableToWithdrawOutOfBounds)?
+            }
+        })
+    }
+
+    /// Get the active time after start at which the amount owed becomes more than the total topup amount. Returns
+    /// `None` if the stream can never become insolvent with the current total topup amount.
+    fn get_insolvent_active_time(&self) -> Option<u64> {
+        if self.is_prepaid || !self.has_flow_payments() {
+            None
+        } else if self.total_topup_amount < self.initial_amount {
+            Some(0)
+        } else {
+            // The smallest active time for which `active_time * flow_rate / flow_interval` is more than the streaming
+            // amount topped up.
+            let active_time = ((self.total_topup_amount - self.initial_amount) as u128 + 1)
+                * self.flow_interval as u128
+                + (self.flow_rate - 1) as u128;
+            u64::try_from(active_time / self.flow_rate as u128).ok()
+        }
+    }
+
+    /// Check if the stream is solvent, i.e. the amount owed to the recipient is covered by the total topup amount.
+    pub fn is_solvent(&self, at: u64) -> Result<bool> {
+        Ok(self.get_amount_owed(at)? <= self.total_topup_amount)
+    }
+
+    /// Get the time from which the stream is insolvent if the stream is not topped up, paused or resumed. Returns `None`
+    /// if the stream will not become insolvent before it stops or while it is paused.
+    ///
+    /// If the stream became insolvent before it was last paused or resumed, the exact time is not recorded and
+    /// `Some(0)` is returned.
+    pub fn insolvent_at(&self) -> Option<u64> {
+        if self.is_settled || self.is_pending_acceptance {
+            return None;
+        }
+
+        let insolvent_active_time = self.get_insolvent_active_time()?;
+        let insolvent_at = if self.is_paused {
+            if insolvent_active_time > self.accumulated_active_time {
+                // A paused stream doesn't accrue anything, so it can only become insolvent after being resumed.
+                return None;
+            }
+            0
+        } else if self.last_resumed_at == 0 {
+            // The stream was never paused.
+            self.starts_at.checked_add(insolvent_active_time)?
+        } else if insolvent_active_time >= self.accumulated_active_time {
+            self.last_resumed_at
+                .checked_add(insolvent_active_time - self.accumulated_active_time)?
+        } else {
+            0
+        };
+
+        let stops_at = self.get_stops_at();
+        if stops_at > 0 && insolvent_at > stops_at {
+            None
+        } else {
+            Some(insolvent_at)
+        }
+    }
+
+    /// Get the time from which anyone can cancel the stream for being insolvent, i.e. the stream is insolvent and the
+    /// insolvency grace period is over, if the stream is not topped up, paused or resumed. Returns `None` if that will
+    /// not happen while the stream is in its current state.
+    ///
+    /// `Some(0)` is returned if the stream can already be cancelled but the exact time is not recorded.
+    pub fn insolvent_cancellable_at(&self) -> Option<u64> {
+        if self.is_cancelled || self.is_frozen {
+            return None;
+        }
+
+        let insolvent_at = self.insolvent_at()?;
+        let grace_period_ends_at = if self.insolvency_grace_period == 0 {
+            Some(insolvent_at)
+        } else {
+            let cancellable_active_time = self
+                .get_insolvent_active_time()?
+                .checked_add(self.insolvency_grace_period)?;
+            if self.is_paused {
+                // The grace period doesn't run out while the stream is paused.
+                (cancellable_active_time <= self.accumulated_active_time).then_some(0)
+            } else if self.last_resumed_at == 0 {
+                self.starts_at.checked_add(cancellable_active_time)
+            } else if cancellable_active_time >= self.accumulated_active_time {
+                self.last_resumed_at
+                    .checked_add(cancellable_active_time - self.accumulated_active_time)
+            } else {
+                Some(0)
+            }
+        };
+
+        // Once the stream has stopped, the grace period is considered over.
+        let stops_at = self.get_stops_at();
+        let stopped_at = if stops_at > 0 { stops_at.checked_add(1) } else { None };
+        match (grace_period_ends_at, stopped_at) {
+            (Some(grace_period_ends_at), Some(stopped_at)) => Some(grace_period_ends_at.min(stopped_at)),
+            (grace_period_ends_at, stopped_at) => grace_period_ends_at.or(stopped_at),
+        }
+    }
+
+    /// Get the status of the stream at time `at`. See [`StreamStatus`] for more information.
+    pub fn status(&self, at: u64) -> Result<StreamStatus> {
+        Ok(if self.is_cancelled {
+            StreamStatus::Cancelled
+        } else if self.is_pending_acceptance || at < self.starts_at {
+            StreamStatus::Scheduled
+        } else if self.has_stopped(at) {
+            StreamStatus::Ended
+        } else if !self.is_solvent(at)? {
+            StreamStatus::Insolvent
+        } else if self.is_paused {
+            StreamStatus::Paused
+        } else {
+            StreamStatus::Streaming
+        })
+    }
+
+    /// Check if the stream is insolvent but still within the insolvency grace period. The grace period is measured in
+    /// active streaming time, so it doesn't run out while the stream is paused. Once the stream has stopped, it can no
+    /// longer be topped up, so the grace period is considered over.
+    pub fn is_in_insolvency_grace_period(&self, at: u64) -> Result<bool> {
+        if self.insolvency_grace_period == 0
+            || self.is_pending_acceptance
+            || at < self.starts_at
+            || self.has_stopped(at)
+        {
+            return Ok(false);
+        }
+
+        Ok(match self.get_insolvent_active_time() {
+            None => false,
+            Some(insolvent_active_time) => {
+                let active_time = self.unsafe_get_active_time_after_start(at)?;
+                active_time >= insolvent_active_time
+                    && active_time - insolvent_active_time < self.insolvency_grace_period
+            },
+        })
+    }
+
+    fn mark_cancelled(&mut self, at: u64, signer: Pubkey) {
+        self.is_cancelled = true;
+        self.is_cancelled_before_start = at < self.starts_at || self.is_pending_acceptance;
+        self.is_cancelled_by_sender = signer == self.sender;
+        self.cancelled_at = at;
+        self.settlement_amount = 0;
+        self.is_settlement_proposed = false;
+        self.is_settlement_proposed_by_sender = false;
+    }
+
+    /// Forfeit the deposit of an insolvent stream that is being cancelled. The recipient's share of the deposit is added
+    /// to the total topup amount so that it can be withdrawn by the recipient. Returns the rest of the deposit which is
+    /// the reward for whoever cancels the stream.
+    fn forfeit_deposit(&mut self) -> Result<u64> {
+        let deposit_needed = self.deposit_needed;
+        // SAFETY: recipient_deposit_share_bps <= BPS_DENOMINATOR => the share is <= deposit_needed.
+        let recipient_deposit_share =
+            (deposit_needed as u128 * self.recipient_deposit_share_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+
+        self.total_topup_amount = self
+            .total_topup_amount
+            .checked_add(recipient_deposit_share)
+            .ok_or(MathError::TopupAmountOutOfBounds)?;
+        self.deposit_needed = 0;
+        Ok(deposit_needed - recipient_deposit_share)
+    }
+
+    fn require_arbiter(&self, signer: Pubkey) -> Result<()> {
+        require!(
+            self.arbiter != Pubkey::default() && signer == self.arbiter,
+            MathError::UserUnauthorizedToArbitrate,
+        );
+        Ok(())
+    }
+
+    fn validate_settlement_amount(&self, settlement_amount: u64) -> Result<()> {
+        let total_paid_amount = self
+            .total_topup_amount
+            .checked_add(self.deposit_needed)
+            .ok_or(MathError::CancellationRefundOutOfBounds)?;
+        require!(
+            settlement_amount >= self.total_withdrawn_amount && settlement_amount <= total_paid_amount,
+            MathError::InvalidSettlementAmount,
+        );
+        Ok(())
+    }
+
+    /// Cancel the stream and split the escrow so that the recipient is owed exactly the settlement amount in total.
+    ///
+    /// INVARIANT: validate_settlement_amount(settlement_amount) is ok
+    fn settle(&mut self, at: u64, signer: Pubkey, settlement_amount: u64) -> Result<CancelTransferParams> {
+        self.mark_cancelled(at, signer);
+
+        // Return anything the sender paid - topup or deposit that is not part of the settlement.
+        let transfer_amount_sender = self
+            .total_topup_amount
+            .checked_add(self.deposit_needed)
+            .ok_or(MathError::CancellationRefundOutOfBounds)?
+            - settlement_amount;
+
+        self.total_topup_amount = settlement_amount;
+        self.deposit_needed = 0;
+        self.settlement_amount = settlement_amount;
+        self.is_settled = true;
+
+        let transfer_amount_recipient = settlement_amount - self.total_withdrawn_amount;
+        self.add_withdrawn_amount(at, transfer_amount_recipient)?;
+
+        Ok(CancelTransferParams {
+            transfer_amount_sender,
+            transfer_amount_signer: 0,
+            transfer_amount_recipient,
+            is_insolvency_cancellation: false,
+        })
+    }
+
+    fn add_topup_amount(&mut self, at: u64, latest_topup_amount: u64) -> Result<()> {
+        self.total_topup_amount = self
+            .total_topup_amount
+            .checked_add(latest_topup_amount)
+            .ok_or(MathError::TopupAmountOutOfBounds)?;
+        self.last_topup_at = at;
+        self.last_topup_amount = latest_topup_amount;
+        Ok(())
+    }
+
+    fn add_withdrawn_amount(&mut self, at: u64, latest_withdrawn_amount: u64) -> Result<()> {
+        if latest_withdrawn_amount == 0 {
+            return Ok(());
+        }
+
+        self.total_withdrawn_amount = self
+            .total_withdrawn_amount
+            .checked_add(latest_withdrawn_amount)
+            .ok_or(MathError::WithdrawAmountOutOfBounds)?;
+        self.last_withdrawn_at = at;
+        self.last_withdrawn_amount = latest_withdrawn_amount;
+        Ok(())
+    }
+
+    // --- Utility functions --- END ---
+
+    // --- Instruction functions --- BEGIN ---
+
+    /// Initialize a prepaid stream.
+    pub fn initialize_prepaid(&mut self, at: u64) -> Result<u64> {
+        let prepaid_amount_needed = self.get_prepaid_amount_needed()?;
+        require!(prepaid_amount_needed > 0, MathError::ZeroLifetimeAmount);
+        self.add_topup_amount(at, prepaid_amount_needed)?;
+        Ok(prepaid_amount_needed)
+    }
+
+    /// Initialize a non-prepaid stream.
+    pub fn initialize_non_prepaid(
+        &mut self,
+        at: u64,
+        topup_amount: u64,
+        insolvency_grace_period: u64,
+        recipient_deposit_share_bps: u16,
+    ) -> Result<()> {
+        require!(topup_amount > 0, MathError::ZeroAmount);
+        require!(
+            insolvency_grace_period <= DEPOSIT_AMOUNT_PERIOD_IN_SECS,
+            MathError::InvalidInsolvencyGracePeriod,
+        );
+        require!(
+            recipient_deposit_share_bps <= BPS_DENOMINATOR,
+            MathError::InvalidRecipientDepositShare,
+        );
+        self.insolvency_grace_period = insolvency_grace_period;
+        self.recipient_deposit_share_bps = recipient_deposit_share_bps;
+
+        // Amount needed = initial_amount + 2 * deposit_amount.
+        //
+        // We are doing 2 times deposit amount, because if it was just once, the stream would become insolvent
+        // immediately.
+        let amount_needed = self
+            .initial_amount
+            .checked_add(
+                self.deposit_needed
+                    .checked_mul(2)
+                    .ok_or(MathError::DepositAmountNeededOutOfBounds)?,
+            )
+            .ok_or(MathError::DepositAmountNeededOutOfBounds)?;
+        require!(topup_amount >= amount_needed, MathError::AmountLessThanAmountNeeded);
+        self.add_topup_amount(at, topup_amount - self.deposit_needed)
+    }
+
+    pub fn cancel(&mut self, at: u64, signer: Pubkey, recipient: Pubkey) -> Result<CancelTransferParams> {
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+        require!(recipient == self.recipient, MathError::InvalidRecipient);
+
+        // The stream is marked cancelled only once the signer is known to be allowed to cancel, so that the state is
+        // left untouched on error. The amount owed at `at` is the same before and after marking it cancelled at `at`.
+        let total_topup_amount = self.total_topup_amount;
+        let amount_owed = self.get_amount_owed(at)?;
+        let is_insolvent = total_topup_amount < amount_owed;
+        if is_insolvent && !self.is_in_insolvency_grace_period(at)? {
+            // The stream is insolvent and the grace period is over. Anyone can cancel.
+            // The deposit is split between the recipient and the signer as a reward. The remaining topup amount along
+            // with the recipient's share of the deposit can be withdrawn by the recipient.
+            self.mark_cancelled(at, signer);
+            let transfer_amount_signer = self.forfeit_deposit()?;
+            let total_topup_amount = self.total_topup_amount;
+            let transfer_amount_recipient = total_topup_amount.saturating_sub(self.total_withdrawn_amount);
+            self.add_withdrawn_amount(at, transfer_amount_recipient)?;
+
+            Ok(CancelTransferParams {
+                transfer_amount_sender: 0,
+                transfer_amount_signer,
+                transfer_amount_recipient,
+                is_insolvency_cancellation: true,
+            })
+        } else {
+            // The stream is still solvent or within the insolvency grace period. Only the sender and recipient can
+            // cancel.
+            require!(
+                !is_insolvent || signer == self.sender || signer == self.recipient,
+                MathError::StreamInInsolvencyGracePeriod,
+            );
+            require!(
+                signer == self.sender || signer == self.recipient,
+                MathError::UserUnauthorizedToCancel,
+            );
+            require!(
+                signer != self.sender || self.is_pending_acceptance || self.sender_can_cancel_now(at),
+                MathError::SenderCannotCancel,
+            );
+            self.mark_cancelled(at, signer);
+
+            // Return anything the sender paid - topup or deposit that is not owed to the recipient. The stream has been
+            // cancelled and stopped, so the deposit is no longer needed. If the stream is within the insolvency grace
+            // period, the deposit first covers the debt owed to the recipient.
+            let total_paid_amount = total_topup_amount
+                .checked_add(self.deposit_needed)
+                .ok_or(MathError::CancellationRefundOutOfBounds)?;
+            let amount_owed = min(amount_owed, total_paid_amount);
+            let transfer_amount_sender = total_paid_amount - amount_owed;
+
+            self.total_topup_amount = amount_owed;
+            self.deposit_needed = 0;
+
+            let transfer_amount_recipient = amount_owed.saturating_sub(self.total_withdrawn_amount);
+            self.add_withdrawn_amount(at, transfer_amount_recipient)?;
+
+            Ok(CancelTransferParams {
+                transfer_amount_sender,
+                transfer_amount_signer: 0,
+                transfer_amount_recipient,
+                is_insolvency_cancellation: false,
+            })
+        }
+    }
+
+    pub fn withdraw_excess_topup_non_prepaid_ended(&mut self, at: u64) -> Result<u64> {
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+        require!(self.ends_at > 0 && self.ends_at < at, MathError::StreamNotEnded);
+
+        let total_topup_amount = self.total_topup_amount;
+        let amount_owed = self.get_amount_owed(at)?;
+        Ok(if total_topup_amount < amount_owed {
+            // The stream is insolvent. Nothing to do.
+            0
+        } else {
+            let deposit_needed = self.deposit_needed;
+
+            self.total_topup_amount = amount_owed;
+            self.deposit_needed = 0;
+
+            total_topup_amount
+                .checked_add(deposit_needed)
+                .ok_or(MathError::CancellationRefundOutOfBounds)?
+                - amount_owed
+        })
+    }
+
+    pub fn topup_non_prepaid(&mut self, at: u64, signer: Pubkey, topup_amount: u64, is_strict: bool) -> Result<u64> {
+        require!(topup_amount > 0, MathError::ZeroAmount);
+        require!(!self.is_prepaid, MathError::StreamIsPrepaid);
+        require!(self.has_flow_payments(), MathError::StreamHasNoFlowPayments);
+        require!(!self.has_stopped(at), MathError::StreamHasStopped);
+        require!(
+            signer == self.sender || !self.is_in_insolvency_grace_period(at)?,
+            MathError::StreamInInsolvencyGracePeriod,
+        );
+
+        // Any amount above the maximum acceptable topup would be stuck in the escrow till the stream ends, so only
+        // accept the amount the stream can use.
+        let (no_limit, max_acceptable_topup) = self.get_max_acceptable_topup_amount(at)?;
+        let accepted_topup_amount = if no_limit || topup_amount <= max_acceptable_topup {
+            topup_amount
+        } else {
+            require!(!is_strict, MathError::TopupAmountMoreThanMaxAcceptable);
+            max_acceptable_topup
+        };
+        require!(accepted_topup_amount > 0, MathError::TopupAmountMoreThanMaxAcceptable,);
+
+        self.add_topup_amount(at, accepted_topup_amount)?;
+        Ok(accepted_topup_amount)
+    }
+
+    pub fn change_sender_non_prepaid(&mut self, at: u64, sender: Pubkey, new_sender: Pubkey) -> Result<()> {
+        require!(!self.is_prepaid, MathError::StreamIsPrepaid);
+        require!(sender == self.sender, MathError::InvalidSender);
+        require!(new_sender != Pubkey::default(), MathError::InvalidNewSender);
+        require!(new_sender != self.sender, MathError::SameSenders);
+        require!(
+            self.sender_can_change_sender_now(at),
+            MathError::SenderCannotChangeSender
+        );
+        require!(!self.has_stopped(at), MathError::StreamHasStopped);
+
+        // The sender changes only when the new sender accepts. A new proposal replaces any previous proposal.
+        self.pending_sender = new_sender;
+        Ok(())
+    }
+
+    pub fn accept_sender_change_non_prepaid(&mut self, at: u64, new_sender: Pubkey) -> Result<()> {
+        require!(!self.is_prepaid, MathError::StreamIsPrepaid);
+        require!(
+            self.pending_sender != Pubkey::default(),
+            MathError::SenderChangeNotProposed
+        );
+        require!(new_sender == self.pending_sender, MathError::InvalidNewSender);
+        require!(
+            self.sender_can_change_sender_now(at),
+            MathError::SenderCannotChangeSender
+        );
+        require!(!self.has_stopped(at), MathError::StreamHasStopped);
+
+        self.sender = self.pending_sender;
+        self.pending_sender = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn cancel_sender_change_non_prepaid(&mut self, sender: Pubkey) -> Result<()> {
+        require!(!self.is_prepaid, MathError::StreamIsPrepaid);
+        require!(sender == self.sender, MathError::InvalidSender);
+        require!(
+            self.pending_sender != Pubkey::default(),
+            MathError::SenderChangeNotProposed
+        );
+
+        self.pending_sender = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn withdraw_and_change_recipient(
+        &mut self,
+        at: u64,
+        signer: Pubkey,
+        recipient: Pubkey,
+        new_recipient: Pubkey,
+    ) -> Result<WithdrawTransferParams> {
+        require!(recipient == self.recipient, MathError::InvalidRecipient);
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+        require!(
+            signer == self.recipient || self.anyone_can_withdraw_for_recipient_now(at),
+            MathError::UserUnauthorizedToWithdraw,
+        );
+
+        let params = self.withdraw_amount_owed(at, signer)?;
+        if new_recipient != Pubkey::default() {
+            // A cancelled stream cannot change its recipient, including a stream which has just been cancelled by this
+            // withdrawal for being insolvent.
+            require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+            // Only the recipient can change the recipient. The recipient changes only when the new recipient accepts. A
+            // new proposal replaces any previous proposal.
+            require!(signer == self.recipient, MathError::UserUnauthorizedToWithdraw);
+            require!(new_recipient != self.recipient, MathError::SameRecipients);
+            self.pending_recipient = new_recipient;
+        }
+
+        Ok(params)
+    }
+
+    pub fn accept_recipient_change(
+        &mut self,
+        at: u64,
+        new_recipient: Pubkey,
+        recipient: Pubkey,
+    ) -> Result<WithdrawTransferParams> {
+        require!(recipient == self.recipient, MathError::InvalidRecipient);
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+        require!(
+            self.pending_recipient != Pubkey::default(),
+            MathError::RecipientChangeNotProposed,
+        );
+        require!(new_recipient == self.pending_recipient, MathError::InvalidNewRecipient,);
+
+        // Everything owed till now belongs to the current recipient, so withdraw it before changing the recipient. If
+        // the withdrawal cancels an insolvent stream, the current recipient is the one cancelling it, so the new
+        // recipient cannot earn the reward.
+        let params = self.withdraw_amount_owed(at, self.recipient)?;
+        if self.is_cancelled {
+            // The stream has just been cancelled for being insolvent, so there is nothing left to hand over.
+            self.pending_recipient = Pubkey::default();
+            return Ok(params);
+        }
+
+        self.recipient = self.pending_recipient;
+        self.pending_recipient = Pubkey::default();
+        Ok(params)
+    }
+
+    pub fn cancel_recipient_change(&mut self, recipient: Pubkey) -> Result<()> {
+        require!(recipient == self.recipient, MathError::InvalidRecipient);
+        require!(
+            self.pending_recipient != Pubkey::default(),
+            MathError::RecipientChangeNotProposed,
+        );
+
+        self.pending_recipient = Pubkey::default();
+        Ok(())
+    }
+
+    fn withdraw_amount_owed(&mut self, at: u64, signer: Pubkey) -> Result<WithdrawTransferParams> {
+        let total_topup_amount = self.total_topup_amount;
+        let mut amount_owed = self.get_amount_owed(at)?;
+        let mut transfer_amount_signer = 0;
+        let mut is_insolvency_cancellation = false;
+        if amount_owed > total_topup_amount {
+            // The stream is insolvent. Cancel the stream if not already cancelled and the grace period is over.
+            // Recipient is owed the whole topup amount and if the stream is not cancelled yet, also their share of the
+            // deposit amount. The rest of the deposit is given as a reward to the signer. Within the grace period, the
+            // recipient can only withdraw the whole topup amount.
+            amount_owed = if self.is_cancelled || self.is_in_insolvency_grace_period(at)? {
+                total_topup_amount
+            } else {
+                self.mark_cancelled(at, signer);
+                is_insolvency_cancellation = true;
+                transfer_amount_signer = self.forfeit_deposit()?;
+                self.total_topup_amount
+            }
+        }
+
+        require!(
+            amount_owed >= self.total_withdrawn_amount,
+            MathError::WithdrawnAmountGreaterThanAmountOwed,
+        );
+        let amount_available_to_withdraw = amount_owed - self.total_withdrawn_amount;
+        self.add_withdrawn_amount(at, amount_available_to_withdraw)?;
+
+        Ok(WithdrawTransferParams {
+            transfer_amount_signer,
+            transfer_amount_recipient: amount_available_to_withdraw,
+            is_insolvency_cancellation,
+        })
+    }
+
+    pub fn pause_non_prepaid(&mut self, at: u64, signer: Pubkey) -> Result<()> {
+        require!(!self.is_prepaid, MathError::StreamIsPrepaid);
+        require!(!self.is_pending_acceptance, MathError::StreamIsPendingAcceptance);
+        require!(!self.is_paused, MathError::StreamIsPaused);
+        require!(self.has_flow_payments(), MathError::StreamHasNoFlowPayments);
+
+        let is_sender = signer == self.sender;
+        let is_recipient = signer == self.recipient;
+        require!(is_sender || is_recipient, MathError::UserUnauthorizedToPause);
+        require!(
+            is_recipient || self.sender_can_pause_now(at),
+            MathError::SenderCannotPause
+        );
+
+        require!(!self.has_stopped(at), MathError::StreamHasStopped);
+
+        // Update accumulated_active_time if there has been any flow till `at`.
+        if at > self.starts_at {
+            self.accumulated_active_time = self.unsafe_get_active_time_after_start(at)?;
+        }
+
+        self.is_paused = true;
+        self.is_paused_by_sender = is_sender;
+
+        Ok(())
+    }
+
+    pub fn resume_non_prepaid(&mut self, at: u64, signer: Pubkey) -> Result<()> {
+        require!(!self.is_prepaid, MathError::StreamIsPrepaid);
+        require!(!self.is_pending_acceptance, MathError::StreamIsPendingAcceptance);
+        require!(self.is_paused, MathError::StreamIsNotPaused);
+
+        let is_sender = signer == self.sender;
+        let is_recipient = signer == self.recipient;
+        require!(is_sender || is_recipient, MathError::UserUnauthorizedToResume);
+        require!(
+            is_sender || !self.is_paused_by_sender || self.recipient_can_resume_pause_by_sender_now(at),
+            MathError::RecipientCannotResumePauseBySender
+        );
+
+        require!(!self.has_stopped(at), MathError::StreamHasStopped);
+
+        self.is_paused = false;
+        self.is_paused_by_sender = false;
+
+        // Update last_resumed_at if there has been any flow till `at`.
+        if at > self.starts_at {
+            self.last_resumed_at = at;
+        }
+
+        Ok(())
+    }
+
+    pub fn accept(&mut self, at: u64, signer: Pubkey) -> Result<()> {
+        require!(self.is_pending_acceptance, MathError::StreamIsNotPendingAcceptance);
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        require!(signer == self.recipient, MathError::UserUnauthorizedToAccept);
+        require!(!self.has_stopped(at), MathError::StreamHasStopped);
+
+        if at > self.starts_at {
+            // Shift the schedule so that the stream starts now and has the same duration as initially scheduled. This
+            // keeps the prepaid amount and the deposit in sync with the streaming payments.
+            let delay = at - self.starts_at;
+            self.starts_at = at;
+            if self.ends_at > 0 {
+                self.ends_at = self.ends_at.checked_add(delay).ok_or(MathError::InvalidEndsAt)?;
+            }
+        }
+
+        self.is_pending_acceptance = false;
+        Ok(())
+    }
+
+    pub fn propose_settlement(&mut self, signer: Pubkey, settlement_amount: u64) -> Result<()> {
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+
+        let is_sender = signer == self.sender;
+        let is_recipient = signer == self.recipient;
+        require!(is_sender || is_recipient, MathError::UserUnauthorizedToSettle);
+        self.validate_settlement_amount(settlement_amount)?;
+
+        // A new proposal by either party replaces any previous proposal.
+        self.settlement_amount = settlement_amount;
+        self.is_settlement_proposed = true;
+        self.is_settlement_proposed_by_sender = is_sender;
+        Ok(())
+    }
+
+    pub fn cancel_settlement_proposal(&mut self, signer: Pubkey) -> Result<()> {
+        require!(self.is_settlement_proposed, MathError::SettlementNotProposed);
+
+        // The proposer can revoke the proposal and the other party can reject it.
+        require!(
+            signer == self.sender || signer == self.recipient,
+            MathError::UserUnauthorizedToSettle,
+        );
+
+        self.settlement_amount = 0;
+        self.is_settlement_proposed = false;
+        self.is_settlement_proposed_by_sender = false;
+        Ok(())
+    }
+
+    pub fn terminate_with_settlement(
+        &mut self,
+        at: u64,
+        signer: Pubkey,
+        recipient: Pubkey,
+        settlement_amount: u64,
+    ) -> Result<CancelTransferParams> {
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        // A frozen stream can only be settled by the arbiter through `resolve`.
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+        require!(recipient == self.recipient, MathError::InvalidRecipient);
+        require!(self.is_settlement_proposed, MathError::SettlementNotProposed);
+        // The settlement amount is passed again so that the accepting party cannot be front-run by a new proposal.
+        require!(
+            settlement_amount == self.settlement_amount,
+            MathError::SettlementAmountMismatch,
+        );
+
+        // Only the party which did not propose the settlement can accept it.
+        require!(
+            if self.is_settlement_proposed_by_sender {
+                signer == self.recipient
+            } else {
+                signer == self.sender
+            },
+            MathError::UserUnauthorizedToSettle,
+        );
+        self.validate_settlement_amount(settlement_amount)?;
+        self.settle(at, signer, settlement_amount)
+    }
+
+    pub fn freeze(&mut self, signer: Pubkey) -> Result<()> {
+        self.require_arbiter(signer)?;
+        require!(!self.is_cancelled, MathError::StreamAlreadyCancelled);
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+
+        self.is_frozen = true;
+        Ok(())
+    }
+
+    pub fn unfreeze(&mut self, signer: Pubkey) -> Result<()> {
+        self.require_arbiter(signer)?;
+        require!(self.is_frozen, MathError::StreamIsNotFrozen);
+
+        self.is_frozen = false;
+        Ok(())
+    }
+
+    pub fn resolve(
+        &mut self,
+        at: u64,
+        signer: Pubkey,
+        recipient: Pubkey,
+        recipient_amount: u64,
+    ) -> Result<CancelTransferParams> {
+        self.require_arbiter(signer)?;
+        require!(recipient == self.recipient, MathError::InvalidRecipient);
+        require!(self.is_frozen, MathError::StreamIsNotFrozen);
+        self.validate_settlement_amount(recipient_amount)?;
+        self.is_frozen = false;
+        self.settle(at, signer, recipient_amount)
+    }
+
+    /// Check that the stream can be closed by the signer at time `at`. A stream can be closed by the sender or the
+    /// recipient once it has stopped and everything paid by the sender has been withdrawn, refunded or given as a reward.
+    pub fn close(&self, at: u64, signer: Pubkey) -> Result<()> {
+        require!(
+            signer == self.sender || signer == self.recipient,
+            MathError::UserUnauthorizedToClose
+        );
+        require!(!self.is_frozen, MathError::StreamIsFrozen);
+        require!(self.is_cancelled || self.has_stopped(at), MathError::StreamNotStopped);
+        require!(self.get_escrow_amount_needed()? == 0, MathError::StreamHasFundsLeft);
+        Ok(())
+    }
+    // --- Instruction functions --- END ---
+
+    // --- Invariant functions --- BEGIN ---
+
+    /// Get the amount the escrow account should hold for the stream - everything the sender paid that has not been
+    /// withdrawn by the recipient, refunded to the sender or given as a reward.
+    pub fn get_escrow_amount_needed(&self) -> Result<u64> {
+        self.total_topup_amount
+            .checked_add(self.deposit_needed)
+            .and_then(|total_paid_amount| total_paid_amount.checked_sub(self.total_withdrawn_amount))
+            .ok_or(MathError::InvariantViolation)
+    }
+
+    /// Check all the invariants documented on the fields of [`StreamState`] at time `at`. Invariants that depend on
+    /// the time are checked only for the state at time `at`.
+    pub fn check_invariants(&self, at: u64) -> Result<()> {
+        let is_unbounded = !self.is_prepaid;
+
+        require!(self.starts_at >= self.created_at, MathError::InvariantViolation);
+        require!(
+            self.ends_at >= self.starts_at || (is_unbounded && self.ends_at == 0),
+            MathError::InvariantViolation
+        );
+
+        require!(
+            self.is_cancelled || (!self.is_cancelled_before_start && !self.is_cancelled_by_sender),
+            MathError::InvariantViolation
+        );
+        require!(
+            (self.cancelled_at > 0) == self.is_cancelled,
+            MathError::InvariantViolation
+        );
+
+        if self.is_prepaid {
+            require!(
+                !self.sender_can_change_sender
+                    && self.sender_can_change_sender_at == 0
+                    && self.sender_can_change_sender_expires_at == 0
+                    && !self.is_paused
+                    && !self.is_paused_by_sender
+                    && !self.sender_can_pause
+                    && self.sender_can_pause_at == 0
+                    && self.sender_can_pause_expires_at == 0
+                    && !self.recipient_can_resume_pause_by_sender
+                    && self.recipient_can_resume_pause_by_sender_at == 0
+                    && self.recipient_can_resume_pause_by_sender_expires_at == 0
+                    && self.last_resumed_at == 0
+                    && self.accumulated_active_time == 0
+                    && self.deposit_needed == 0
+                    && self.insolvency_grace_period == 0
+                    && self.recipient_deposit_share_bps == 0
+                    && self.pending_sender == Pubkey::default(),
+                MathError::InvariantViolation
+            );
+        } else {
+            require!(
+                (self.is_paused && self.sender_can_pause) || !self.is_paused_by_sender,
+                MathError::InvariantViolation
+            );
+            require!(
+                self.last_resumed_at == 0
+                    || (self.last_resumed_at >= self.starts_at
+                        && (self.ends_at == 0 || self.last_resumed_at <= self.ends_at)),
+                MathError::InvariantViolation
+            );
+            require!(
+                self.accumulated_active_time <= at.saturating_sub(self.starts_at),
+                MathError::InvariantViolation
+            );
+            require!(
+                self.deposit_needed == 0 || self.deposit_needed == self.get_deposit_needed()?,
+                MathError::InvariantViolation
+            );
+            require!(
+                self.insolvency_grace_period <= DEPOSIT_AMOUNT_PERIOD_IN_SECS
+                    && self.recipient_deposit_share_bps <= BPS_DENOMINATOR,
+                MathError::InvariantViolation
+            );
+        }
+
+        if self.is_cancelled {
+            // Everything owed to the recipient has been transferred and the rest has been refunded or given as a reward.
+            require!(
+                self.deposit_needed == 0 && self.total_withdrawn_amount == self.total_topup_amount,
+                MathError::InvariantViolation
+            );
+        } else {
+            require!(
+                self.total_withdrawn_amount <= self.get_amount_owed(at)?
+                    && self.total_withdrawn_amount <= self.total_topup_amount,
+                MathError::InvariantViolation
+            );
+            if self.is_prepaid {
+                require!(
+                    self.total_topup_amount == self.get_prepaid_amount_needed()?,
+                    MathError::InvariantViolation
+                );
+                // No dust is left in the escrow once the recipient withdraws at the end of the stream.
+                require!(
+                    self.is_pending_acceptance || self.get_amount_owed(self.ends_at)? == self.total_topup_amount,
+                    MathError::InvariantViolation
+                );
+            } else {
+                require!(
+                    self.total_topup_amount >= self.initial_amount,
+                    MathError::InvariantViolation
+                );
+            }
+        }
+
+        require!(
+            self.is_settlement_proposed || self.is_settled || self.settlement_amount == 0,
+            MathError::InvariantViolation
+        );
+        require!(
+            !self.is_cancelled || !self.is_settlement_proposed,
+            MathError::InvariantViolation
+        );
+        require!(
+            self.is_settlement_proposed || !self.is_settlement_proposed_by_sender,
+            MathError::InvariantViolation
+        );
+        require!(
+            !self.is_settled || (self.is_cancelled && self.total_topup_amount == self.settlement_amount),
+            MathError::InvariantViolation
+        );
+        require!(
+            !self.is_frozen || (self.arbiter != Pubkey::default() && !self.is_cancelled),
+            MathError::InvariantViolation
+        );
+
+        require!(
+            self.sender_can_cancel_expires_at == 0 || self.sender_can_cancel_expires_at > self.sender_can_cancel_at,
+            MathError::InvariantViolation
+        );
+        require!(
+            self.sender_can_change_sender_expires_at == 0
+                || self.sender_can_change_sender_expires_at > self.sender_can_change_sender_at,
+            MathError::InvariantViolation
+        );
+        require!(
+            self.sender_can_pause_expires_at == 0 || self.sender_can_pause_expires_at > self.sender_can_pause_at,
+            MathError::InvariantViolation
+        );
+        require!(
+            self.recipient_can_resume_pause_by_sender_expires_at == 0
+                || self.recipient_can_resume_pause_by_sender_expires_at > self.recipient_can_resume_pause_by_sender_at,
+            MathError::InvariantViolation
+        );
+        require!(
+            self.anyone_can_withdraw_for_recipient_expires_at == 0
+                || self.anyone_can_withdraw_for_recipient_expires_at > self.anyone_can_withdraw_for_recipient_at,
+            MathError::InvariantViolation
+        );
+
+        // The escrow amount needed is well defined.
+        self.get_escrow_amount_needed()?;
+        Ok(())
+    }
+
+    // --- Invariant functions --- END ---
+}
+
+/// Record of funds to be transferred once a stream is cancelled.
+pub struct CancelTransferParams {
+    /// Transfer fund amount to the stream sender.
+    pub transfer_amount_sender: u64,
+    /// Transfer fund amount to the signer.
+    pub transfer_amount_signer: u64,
+    /// Transfer fund amount to the stream recipient.
+    pub transfer_amount_recipient: u64,
+    /// True if the stream was cancelled because it was insolvent and the insolvency grace period was over.
+    pub is_insolvency_cancellation: bool,
+}
+
+/// Record of funds to be transferred once the recipient funds of a stream are withdrawn.
+pub struct WithdrawTransferParams {
+    /// Transfer fund amount to the signer.
+    pub transfer_amount_signer: u64,
+    /// Transfer fund amount to the stream recipient.
+    pub transfer_amount_recipient: u64,
+    /// True if the withdrawal cancelled the stream because it was insolvent and the insolvency grace period was over.
+    pub is_insolvency_cancellation: bool,
+}
+
+/// Flow rate of a stream. Both variants are stored in the `flow_interval` and `flow_rate` fields of the stream, and the
+/// amount streamed in `t` active secs is `t * flow_rate / flow_interval` rounded down in both cases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowRate {
+    /// `flow_rate` tokens are streamed every `flow_interval` secs.
+    PerInterval { flow_interval: u64, flow_rate: u64 },
+    /// Fixed-point amount streamed per second, scaled by [`FLOW_RATE_SCALE`]. This can express rates which are not a
+    /// whole number of base units per interval, e.g. 1000 USDC per 30-day month is 385.802469135 base units per second.
+    ScaledPerSecond(u64),
+}
+
+impl FlowRate {
+    /// Get the flow rate stored as `flow_interval` and `flow_rate` on a stream.
+    pub fn new(flow_interval: u64, flow_rate: u64) -> Self {
+        if flow_interval == FLOW_RATE_SCALE {
+            FlowRate::ScaledPerSecond(flow_rate)
+        } else {
+            FlowRate::PerInterval {
+                flow_interval,
+                flow_rate,
+            }
+        }
+    }
+
+    /// Get the fixed-point flow rate that streams `amount` tokens every `period` secs. The rate is rounded down, so at
+    /// most `amount` tokens are streamed per period. Returns `None` if the period is 0 or the rate does not fit in u64.
+    pub fn scaled_from_amount_per_period(amount: u64, period: u64) -> Option<Self> {
+        if period == 0 {
+            return None;
+        }
+        u64::try_from(amount as u128 * FLOW_RATE_SCALE as u128 / period as u128)
+            .ok()
+            .map(FlowRate::ScaledPerSecond)
+    }
+
+    /// Get the `flow_interval` to store on a stream.
+    pub fn flow_interval(&self) -> u64 {
+        match *self {
+            FlowRate::PerInterval { flow_interval, .. } => flow_interval,
+            FlowRate::ScaledPerSecond(_) => FLOW_RATE_SCALE,
+        }
+    }
+
+    /// Get the `flow_rate` to store on a stream.
+    pub fn flow_rate(&self) -> u64 {
+        match *self {
+            FlowRate::PerInterval { flow_rate, .. } => flow_rate,
+            FlowRate::ScaledPerSecond(flow_rate) => flow_rate,
+        }
+    }
+}
+
+/// Status of a stream at a point in time. These match the states documented on the [`StreamState`] struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamStatus {
+    /// The stream has not started yet or is pending recipient acceptance.
+    Scheduled,
+    /// The stream has started, is not paused and is solvent.
+    Streaming,
+    /// The stream has started, is paused and is solvent.
+    Paused,
+    /// The stream has started and the amount owed to the recipient is more than the total topup amount. A paused stream
+    /// can be insolvent too.
+    Insolvent,
+    /// The stream has reached its end time.
+    Ended,
+    /// The stream has been cancelled, either before or after start.
+    Cancelled,
+}
+
+/// Returns the unlock and expiry times of a permission to store on a stream. Both are 0 if the permission is not given.
+pub fn get_permission_times(is_allowed: bool, allowed_at: u64, expires_at: u64) -> Result<(u64, u64)> {
+    if !is_allowed {
+        return Ok((0, 0));
+    }
+
+    require!(
+        expires_at == 0 || expires_at > allowed_at,
+        MathError::InvalidPermissionExpiry,
+    );
+    Ok((allowed_at, expires_at))
+}
+
+/// Returns true if a permission is given, has unlocked and has not expired at time `at`. An expiry time of 0 means the
+/// permission never expires.
+fn is_permission_active(is_allowed: bool, allowed_at: u64, expires_at: u64, at: u64) -> bool {
+    is_allowed && allowed_at <= at && (expires_at == 0 || at < expires_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CREATED_AT: u64 = 1_000;
+    const STARTS_AT: u64 = 2_000;
+    const INITIAL_AMOUNT: u64 = 100;
+    /// Tokens streamed per second with a flow interval of 1 sec.
+    const FLOW_RATE: u64 = 10;
+    /// Deposit needed by a non-prepaid stream with a lifetime of at least DEPOSIT_AMOUNT_PERIOD_IN_SECS.
+    const DEPOSIT: u64 = DEPOSIT_AMOUNT_PERIOD_IN_SECS * FLOW_RATE;
+    /// Time at which a non-prepaid stream created with `non_prepaid_stream` becomes insolvent if it is never paused,
+    /// i.e. the first second for which the streamed amount is more than DEPOSIT.
+    const INSOLVENT_AT: u64 = STARTS_AT + DEPOSIT / FLOW_RATE + 1;
+
+    fn sender() -> Pubkey {
+        Pubkey::new_from_array([1; 32])
+    }
+
+    fn recipient() -> Pubkey {
+        Pubkey::new_from_array([2; 32])
+    }
+
+    fn arbiter() -> Pubkey {
+        Pubkey::new_from_array([3; 32])
+    }
+
+    fn other() -> Pubkey {
+        Pubkey::new_from_array([4; 32])
+    }
+
+    fn new_stream(is_prepaid: bool, ends_at: u64, flow_interval: u64, flow_rate: u64) -> StreamState {
+        let mut stream = StreamState {
+            is_prepaid,
+            mint: Pubkey::new_from_array([5; 32]),
+            sender: sender(),
+            recipient: recipient(),
+            arbiter: arbiter(),
+            created_at: CREATED_AT,
+            starts_at: STARTS_AT,
+            ends_at,
+            initial_amount: INITIAL_AMOUNT,
+            flow_interval,
+            flow_rate,
+            sender_can_cancel: true,
+            sender_can_pause: !is_prepaid,
+            recipient_can_resume_pause_by_sender: !is_prepaid,
+            ..Default::default()
+        };
+        stream.deposit_needed = stream.get_deposit_needed().unwrap();
+        stream
+    }
+
+    /// Create a non-prepaid stream streaming FLOW_RATE tokens per sec, topped up with the minimum amount needed.
+    fn non_prepaid_stream(ends_at: u64, insolvency_grace_period: u64, recipient_deposit_share_bps: u16) -> StreamState {
+        let mut stream = new_stream(false, ends_at, 1, FLOW_RATE);
+        let topup_amount = INITIAL_AMOUNT + 2 * stream.deposit_needed;
+        stream
+            .initialize_non_prepaid(
+                CREATED_AT,
+                topup_amount,
+                insolvency_grace_period,
+                recipient_deposit_share_bps,
+            )
+            .unwrap();
+        stream
+    }
+
+    fn prepaid_stream(ends_at: u64, flow_interval: u64, flow_rate: u64) -> StreamState {
+        let mut stream = new_stream(true, ends_at, flow_interval, flow_rate);
+        stream.initialize_prepaid(CREATED_AT).unwrap();
+        stream
+    }
+
+    fn withdraw(stream: &mut StreamState, at: u64) -> u64 {
+        stream
+            .withdraw_and_change_recipient(at, recipient(), recipient(), Pubkey::default())
+            .unwrap()
+            .transfer_amount_recipient
+    }
+
+    #[test]
+    fn amount_owed_excludes_paused_time() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        assert_eq!(stream.total_topup_amount, INITIAL_AMOUNT + DEPOSIT);
+        assert_eq!(stream.get_amount_owed(CREATED_AT).unwrap(), 0);
+        assert_eq!(stream.get_amount_owed(STARTS_AT).unwrap(), INITIAL_AMOUNT);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 10).unwrap(), 200);
+
+        stream.pause_non_prepaid(STARTS_AT + 10, sender()).unwrap();
+        assert_eq!(stream.accumulated_active_time, 10);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 1_000).unwrap(), 200);
+        assert_eq!(stream.status(STARTS_AT + 1_000).unwrap(), StreamStatus::Paused);
+
+        stream.resume_non_prepaid(STARTS_AT + 100, recipient()).unwrap();
+        assert_eq!(stream.last_resumed_at, STARTS_AT + 100);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 110).unwrap(), 300);
+
+        stream.pause_non_prepaid(STARTS_AT + 120, recipient()).unwrap();
+        assert_eq!(stream.accumulated_active_time, 30);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 500).unwrap(), 400);
+
+        stream.resume_non_prepaid(STARTS_AT + 200, sender()).unwrap();
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 250).unwrap(), 900);
+        assert_eq!(stream.status(STARTS_AT + 250).unwrap(), StreamStatus::Streaming);
+        stream.check_invariants(STARTS_AT + 250).unwrap();
+    }
+
+    #[test]
+    fn pause_before_start_does_not_change_amount_owed() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        stream.pause_non_prepaid(CREATED_AT, sender()).unwrap();
+        stream.resume_non_prepaid(STARTS_AT - 1, sender()).unwrap();
+        assert_eq!(stream.accumulated_active_time, 0);
+        assert_eq!(stream.last_resumed_at, 0);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 10).unwrap(), 200);
+    }
+
+    #[test]
+    fn insolvent_at_follows_pauses() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        assert_eq!(stream.insolvent_at(), Some(INSOLVENT_AT));
+        assert!(stream.is_solvent(INSOLVENT_AT - 1).unwrap());
+        assert!(!stream.is_solvent(INSOLVENT_AT).unwrap());
+        assert_eq!(stream.status(INSOLVENT_AT).unwrap(), StreamStatus::Insolvent);
+
+        // A paused stream doesn't accrue anything.
+        stream.pause_non_prepaid(STARTS_AT + 10, sender()).unwrap();
+        assert_eq!(stream.insolvent_at(), None);
+
+        // The paused time is added to the insolvency time.
+        stream.resume_non_prepaid(STARTS_AT + 100, sender()).unwrap();
+        assert_eq!(stream.insolvent_at(), Some(INSOLVENT_AT + 90));
+        assert!(stream.is_solvent(INSOLVENT_AT + 89).unwrap());
+        assert!(!stream.is_solvent(INSOLVENT_AT + 90).unwrap());
+
+        // The exact time is not recorded once the stream is paused or resumed after becoming insolvent.
+        stream.pause_non_prepaid(INSOLVENT_AT + 1_000, sender()).unwrap();
+        assert_eq!(stream.insolvent_at(), Some(0));
+        stream.resume_non_prepaid(INSOLVENT_AT + 2_000, sender()).unwrap();
+        assert_eq!(stream.insolvent_at(), Some(0));
+    }
+
+    #[test]
+    fn insolvent_at_is_none_when_stream_cannot_become_insolvent() {
+        // The deposit period is capped by the lifetime, so the minimum topup covers the whole stream.
+        let stream = non_prepaid_stream(STARTS_AT + 1_000, 0, 0);
+        assert_eq!(stream.insolvent_at(), None);
+        assert_eq!(stream.insolvent_cancellable_at(), None);
+
+        let stream = prepaid_stream(STARTS_AT + 1_000, 1, FLOW_RATE);
+        assert_eq!(stream.insolvent_at(), None);
+
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        stream.is_pending_acceptance = true;
+        assert_eq!(stream.insolvent_at(), None);
+    }
+
+    #[test]
+    fn insolvent_cancellable_at_adds_grace_period() {
+        let stream = non_prepaid_stream(0, 0, 0);
+        assert_eq!(stream.insolvent_cancellable_at(), Some(INSOLVENT_AT));
+
+        let mut stream = non_prepaid_stream(0, 3_600, 0);
+        assert_eq!(stream.insolvent_at(), Some(INSOLVENT_AT));
+        assert_eq!(stream.insolvent_cancellable_at(), Some(INSOLVENT_AT + 3_600));
+        assert!(!stream.is_in_insolvency_grace_period(INSOLVENT_AT - 1).unwrap());
+        assert!(stream.is_in_insolvency_grace_period(INSOLVENT_AT).unwrap());
+        assert!(stream.is_in_insolvency_grace_period(INSOLVENT_AT + 3_599).unwrap());
+        assert!(!stream.is_in_insolvency_grace_period(INSOLVENT_AT + 3_600).unwrap());
+
+        // The grace period doesn't run out while the stream is paused.
+        stream.pause_non_prepaid(INSOLVENT_AT + 100, sender()).unwrap();
+        assert_eq!(stream.insolvent_cancellable_at(), None);
+        assert!(stream.is_in_insolvency_grace_period(INSOLVENT_AT + 10_000).unwrap());
+        stream.resume_non_prepaid(INSOLVENT_AT + 1_000, sender()).unwrap();
+        assert_eq!(stream.insolvent_cancellable_at(), Some(INSOLVENT_AT + 4_500));
+
+        stream.is_frozen = true;
+        assert_eq!(stream.insolvent_cancellable_at(), None);
+    }
+
+    #[test]
+    fn insolvent_cancellable_at_is_capped_by_end() {
+        // The stream ends 1000 secs after becoming insolvent, before the grace period is over.
+        let ends_at = INSOLVENT_AT + 1_000;
+        let stream = non_prepaid_stream(ends_at, 3_600, 0);
+        assert_eq!(stream.insolvent_at(), Some(INSOLVENT_AT));
+        assert_eq!(stream.insolvent_cancellable_at(), Some(ends_at + 1));
+        assert!(stream.is_in_insolvency_grace_period(ends_at).unwrap());
+        assert!(!stream.is_in_insolvency_grace_period(ends_at + 1).unwrap());
+    }
+
+    #[test]
+    fn cancel_solvent_stream() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        assert_eq!(
+            stream.cancel(STARTS_AT + 100, other(), recipient()).err(),
+            Some(MathError::UserUnauthorizedToCancel)
+        );
+        assert_eq!(
+            stream.cancel(STARTS_AT + 100, sender(), other()).err(),
+            Some(MathError::InvalidRecipient)
+        );
+
+        let params = stream.cancel(STARTS_AT + 100, sender(), recipient()).unwrap();
+        assert_eq!(params.transfer_amount_recipient, 1_100);
+        assert_eq!(params.transfer_amount_sender, INITIAL_AMOUNT + 2 * DEPOSIT - 1_100);
+        assert_eq!(params.transfer_amount_signer, 0);
+        assert!(!params.is_insolvency_cancellation);
+        assert!(stream.is_cancelled_by_sender && !stream.is_cancelled_before_start);
+        assert_eq!(stream.get_escrow_amount_needed().unwrap(), 0);
+        stream.check_invariants(STARTS_AT + 100).unwrap();
+
+        assert_eq!(
+            stream.cancel(STARTS_AT + 200, sender(), recipient()).err(),
+            Some(MathError::StreamAlreadyCancelled)
+        );
+    }
+
+    #[test]
+    fn cancel_before_start_refunds_everything() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        let params = stream.cancel(CREATED_AT + 10, sender(), recipient()).unwrap();
+        assert_eq!(params.transfer_amount_sender, INITIAL_AMOUNT + 2 * DEPOSIT);
+        assert_eq!(params.transfer_amount_recipient, 0);
+        assert!(stream.is_cancelled_before_start);
+        stream.check_invariants(CREATED_AT + 10).unwrap();
+    }
+
+    #[test]
+    fn cancel_respects_sender_permission() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        stream.sender_can_cancel = false;
+        assert_eq!(
+            stream.cancel(STARTS_AT + 100, sender(), recipient()).err(),
+            Some(MathError::SenderCannotCancel)
+        );
+        stream.cancel(STARTS_AT + 100, recipient(), recipient()).unwrap();
+        assert!(!stream.is_cancelled_by_sender);
+
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        stream.is_frozen = true;
+        assert_eq!(
+            stream.cancel(STARTS_AT + 100, sender(), recipient()).err(),
+            Some(MathError::StreamIsFrozen)
+        );
+    }
+
+    #[test]
+    fn cancel_within_grace_period_covers_debt_with_deposit() {
+        let mut stream = non_prepaid_stream(0, 3_600, 2_500);
+        let at = INSOLVENT_AT + 1_000;
+        assert_eq!(
+            stream.cancel(at, other(), recipient()).err(),
+            Some(MathError::StreamInInsolvencyGracePeriod)
+        );
+
+        let amount_owed = stream.get_amount_owed(at).unwrap();
+        assert_eq!(amount_owed, INITIAL_AMOUNT + (at - STARTS_AT) * FLOW_RATE);
+        let params = stream.cancel(at, sender(), recipient()).unwrap();
+        assert_eq!(params.transfer_amount_recipient, amount_owed);
+        assert_eq!(
+            params.transfer_amount_sender,
+            INITIAL_AMOUNT + 2 * DEPOSIT - amount_owed
+        );
+        assert_eq!(params.transfer_amount_signer, 0);
+        assert!(!params.is_insolvency_cancellation);
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn cancel_after_grace_period_splits_deposit() {
+        let mut stream = non_prepaid_stream(0, 3_600, 2_500);
+        assert_eq!(withdraw(&mut stream, STARTS_AT + 1_000), 10_100);
+
+        let at = INSOLVENT_AT + 3_600;
+        let params = stream.cancel(at, other(), recipient()).unwrap();
+        let recipient_deposit_share = DEPOSIT / 4;
+        assert!(params.is_insolvency_cancellation);
+        assert_eq!(params.transfer_amount_sender, 0);
+        assert_eq!(params.transfer_amount_signer, DEPOSIT - recipient_deposit_share);
+        assert_eq!(
+            params.transfer_amount_recipient,
+            INITIAL_AMOUNT + DEPOSIT + recipient_deposit_share - 10_100
+        );
+        assert!(!stream.is_cancelled_by_sender);
+        assert_eq!(stream.deposit_needed, 0);
+        assert_eq!(stream.get_escrow_amount_needed().unwrap(), 0);
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn cancel_without_grace_period_splits_deposit() {
+        let mut stream = non_prepaid_stream(0, 0, BPS_DENOMINATOR);
+        let params = stream.cancel(INSOLVENT_AT, other(), recipient()).unwrap();
+        assert!(params.is_insolvency_cancellation);
+        assert_eq!(params.transfer_amount_signer, 0);
+        assert_eq!(params.transfer_amount_recipient, INITIAL_AMOUNT + 2 * DEPOSIT);
+    }
+
+    #[test]
+    fn terminate_with_settlement() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        assert_eq!(withdraw(&mut stream, STARTS_AT + 1_000), 10_100);
+        let total_paid_amount = INITIAL_AMOUNT + 2 * DEPOSIT;
+
+        assert_eq!(
+            stream.propose_settlement(other(), 50_000),
+            Err(MathError::UserUnauthorizedToSettle)
+        );
+        assert_eq!(
+            stream.propose_settlement(sender(), 10_099),
+            Err(MathError::InvalidSettlementAmount)
+        );
+        assert_eq!(
+            stream.propose_settlement(sender(), total_paid_amount + 1),
+            Err(MathError::InvalidSettlementAmount)
+        );
+        stream.propose_settlement(sender(), 50_000).unwrap();
+        assert!(stream.is_settlement_proposed_by_sender);
+
+        let at = STARTS_AT + 2_000;
+        assert_eq!(
+            stream
+                .terminate_with_settlement(at, sender(), recipient(), 50_000)
+                .err(),
+            Some(MathError::UserUnauthorizedToSettle)
+        );
+        assert_eq!(
+            stream
+                .terminate_with_settlement(at, recipient(), recipient(), 40_000)
+                .err(),
+            Some(MathError::SettlementAmountMismatch)
+        );
+
+        let params = stream
+            .terminate_with_settlement(at, recipient(), recipient(), 50_000)
+            .unwrap();
+        assert_eq!(params.transfer_amount_sender, total_paid_amount - 50_000);
+        assert_eq!(params.transfer_amount_recipient, 50_000 - 10_100);
+        assert_eq!(params.transfer_amount_signer, 0);
+        assert!(stream.is_settled);
+        assert_eq!(stream.get_amount_owed(at + 100_000).unwrap(), 50_000);
+        assert_eq!(stream.insolvent_at(), None);
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn cancel_settlement_proposal() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        stream.propose_settlement(recipient(), 50_000).unwrap();
+        assert_eq!(
+            stream.cancel_settlement_proposal(other()),
+            Err(MathError::UserUnauthorizedToSettle)
+        );
+        stream.cancel_settlement_proposal(sender()).unwrap();
+        assert!(!stream.is_settlement_proposed);
+        assert_eq!(stream.settlement_amount, 0);
+        assert_eq!(
+            stream
+                .terminate_with_settlement(STARTS_AT, sender(), recipient(), 0)
+                .err(),
+            Some(MathError::SettlementNotProposed)
+        );
+    }
+
+    #[test]
+    fn accepting_recipient_change_of_insolvent_stream_keeps_recipient() {
+        let mut stream = non_prepaid_stream(0, 0, 2_500);
+        let withdrawn_amount = stream
+            .withdraw_and_change_recipient(STARTS_AT + 10, recipient(), recipient(), other())
+            .unwrap()
+            .transfer_amount_recipient;
+        assert_eq!(stream.pending_recipient, other());
+
+        let at = INSOLVENT_AT + 10;
+        let params = stream.accept_recipient_change(at, other(), recipient()).unwrap();
+        assert!(params.is_insolvency_cancellation);
+        assert!(stream.is_cancelled && !stream.is_cancelled_by_sender);
+        assert_eq!(stream.recipient, recipient());
+        assert_eq!(stream.pending_recipient, Pubkey::default());
+        // Everything paid by the sender goes to the current recipient, including the reward for cancelling.
+        assert_eq!(
+            withdrawn_amount + params.transfer_amount_recipient + params.transfer_amount_signer,
+            INITIAL_AMOUNT + 2 * DEPOSIT
+        );
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn changing_recipient_fails_if_withdrawal_cancels_stream() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        let at = INSOLVENT_AT + 10;
+        assert_eq!(
+            stream
+                .withdraw_and_change_recipient(at, recipient(), recipient(), other())
+                .err(),
+            Some(MathError::StreamAlreadyCancelled)
+        );
+
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        let params = stream
+            .withdraw_and_change_recipient(at, recipient(), recipient(), Pubkey::default())
+            .unwrap();
+        assert!(params.is_insolvency_cancellation);
+        assert_eq!(
+            stream
+                .withdraw_and_change_recipient(at, recipient(), recipient(), other())
+                .err(),
+            Some(MathError::StreamAlreadyCancelled)
+        );
+        assert_eq!(stream.pending_recipient, Pubkey::default());
+    }
+
+    #[test]
+    fn settlement_is_blocked_while_frozen() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        let at = STARTS_AT + 5_000;
+        stream.propose_settlement(recipient(), 50_000).unwrap();
+        stream.freeze(arbiter()).unwrap();
+
+        assert_eq!(
+            stream.propose_settlement(sender(), 60_000),
+            Err(MathError::StreamIsFrozen)
+        );
+        assert_eq!(
+            stream
+                .terminate_with_settlement(at, sender(), recipient(), 50_000)
+                .err(),
+            Some(MathError::StreamIsFrozen)
+        );
+        assert!(!stream.is_cancelled && stream.is_frozen);
+        assert_eq!(stream.settlement_amount, 50_000);
+
+        stream.unfreeze(arbiter()).unwrap();
+        stream
+            .terminate_with_settlement(at, sender(), recipient(), 50_000)
+            .unwrap();
+        assert!(stream.is_settled && !stream.is_frozen);
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn resolve_frozen_stream() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        let at = STARTS_AT + 5_000;
+        let total_paid_amount = INITIAL_AMOUNT + 2 * DEPOSIT;
+        assert_eq!(
+            stream.resolve(at, arbiter(), recipient(), 0).err(),
+            Some(MathError::StreamIsNotFrozen)
+        );
+        assert_eq!(stream.freeze(sender()), Err(MathError::UserUnauthorizedToArbitrate));
+        stream.freeze(arbiter()).unwrap();
+
+        assert_eq!(
+            stream
+                .withdraw_and_change_recipient(at, recipient(), recipient(), Pubkey::default())
+                .err(),
+            Some(MathError::StreamIsFrozen)
+        );
+        assert_eq!(
+            stream.resolve(at, sender(), recipient(), 0).err(),
+            Some(MathError::UserUnauthorizedToArbitrate)
+        );
+        assert_eq!(
+            stream.resolve(at, arbiter(), recipient(), total_paid_amount + 1).err(),
+            Some(MathError::InvalidSettlementAmount)
+        );
+
+        let params = stream.resolve(at, arbiter(), recipient(), 200_000).unwrap();
+        assert_eq!(params.transfer_amount_sender, total_paid_amount - 200_000);
+        assert_eq!(params.transfer_amount_recipient, 200_000);
+        assert!(!stream.is_frozen && stream.is_settled && !stream.is_cancelled_by_sender);
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn accept_shifts_schedule() {
+        let ends_at = STARTS_AT + 1_000;
+        let mut stream = new_stream(true, ends_at, 1, FLOW_RATE);
+        stream.is_pending_acceptance = true;
+        stream.initialize_prepaid(CREATED_AT).unwrap();
+        assert_eq!(stream.total_topup_amount, 10_100);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 500).unwrap(), 0);
+        assert_eq!(stream.status(STARTS_AT + 500).unwrap(), StreamStatus::Scheduled);
+
+        assert_eq!(
+            stream.accept(STARTS_AT + 500, sender()),
+            Err(MathError::UserUnauthorizedToAccept)
+        );
+        stream.accept(STARTS_AT + 500, recipient()).unwrap();
+        assert_eq!(stream.starts_at, STARTS_AT + 500);
+        assert_eq!(stream.ends_at, ends_at + 500);
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 1_000).unwrap(), 5_100);
+        assert_eq!(
+            stream.get_amount_owed(stream.ends_at).unwrap(),
+            stream.total_topup_amount
+        );
+        stream.check_invariants(STARTS_AT + 1_000).unwrap();
+
+        assert_eq!(
+            stream.accept(STARTS_AT + 600, recipient()),
+            Err(MathError::StreamIsNotPendingAcceptance)
+        );
+    }
+
+    #[test]
+    fn accept_before_start_keeps_schedule() {
+        let mut stream = non_prepaid_stream(STARTS_AT + 1_000, 0, 0);
+        stream.is_pending_acceptance = true;
+        stream.accept(CREATED_AT + 10, recipient()).unwrap();
+        assert_eq!(stream.starts_at, STARTS_AT);
+        assert_eq!(stream.ends_at, STARTS_AT + 1_000);
+
+        let mut stream = non_prepaid_stream(STARTS_AT + 1_000, 0, 0);
+        stream.is_pending_acceptance = true;
+        assert_eq!(
+            stream.accept(STARTS_AT + 1_001, recipient()),
+            Err(MathError::StreamHasStopped)
+        );
+    }
+
+    #[test]
+    fn cancel_pending_stream() {
+        let mut stream = non_prepaid_stream(0, 0, 0);
+        stream.is_pending_acceptance = true;
+        stream.sender_can_cancel = false;
+
+        // The sender can always cancel a stream which has not been accepted.
+        let params = stream.cancel(STARTS_AT + 100, sender(), recipient()).unwrap();
+        assert_eq!(params.transfer_amount_sender, INITIAL_AMOUNT + 2 * DEPOSIT);
+        assert!(stream.is_cancelled_before_start);
+        assert_eq!(
+            stream.accept(STARTS_AT + 100, recipient()),
+            Err(MathError::StreamAlreadyCancelled)
+        );
+    }
+
+    #[test]
+    fn topup_is_clamped_to_max_acceptable() {
+        let ends_at = STARTS_AT + 30_000;
+        let mut stream = non_prepaid_stream(ends_at, 0, 0);
+        let max_acceptable_topup = INITIAL_AMOUNT + 30_000 * FLOW_RATE - stream.total_topup_amount;
+        assert_eq!(
+            stream.get_max_acceptable_topup_amount(CREATED_AT).unwrap(),
+            (false, max_acceptable_topup)
+        );
+        assert_eq!(
+            stream.get_max_acceptable_topup_amount(STARTS_AT + 100).unwrap(),
+            (false, max_acceptable_topup)
+        );
+
+        let total_topup_amount = stream.total_topup_amount;
+        assert_eq!(
+            stream.topup_non_prepaid(STARTS_AT, other(), max_acceptable_topup + 1, true),
+            Err(MathError::TopupAmountMoreThanMaxAcceptable)
+        );
+        assert_eq!(stream.total_topup_amount, total_topup_amount);
+
+        assert_eq!(
+            stream.topup_non_prepaid(STARTS_AT, other(), max_acceptable_topup + 1, false),
+            Ok(max_acceptable_topup)
+        );
+        assert_eq!(stream.last_topup_amount, max_acceptable_topup);
+        assert_eq!(stream.insolvent_at(), None);
+        assert_eq!(
+            stream.topup_non_prepaid(STARTS_AT, other(), 1, false),
+            Err(MathError::TopupAmountMoreThanMaxAcceptable)
+        );
+        assert_eq!(
+            stream.topup_non_prepaid(ends_at + 1, sender(), 1, false),
+            Err(MathError::StreamHasStopped)
+        );
+    }
+
+    #[test]
+    fn topup_exact_max_acceptable_is_strict() {
+        let mut stream = non_prepaid_stream(STARTS_AT + 30_000, 0, 0);
+        let (_, max_acceptable_topup) = stream.get_max_acceptable_topup_amount(STARTS_AT).unwrap();
+        assert_eq!(
+            stream.topup_non_prepaid(STARTS_AT, sender(), max_acceptable_topup, true),
+            Ok(max_acceptable_topup)
+        );
+
+        // Paused time reduces the amount the stream can use.
+        let mut stream = non_prepaid_stream(STARTS_AT + 30_000, 0, 0);
+        stream.pause_non_prepaid(STARTS_AT + 1_000, sender()).unwrap();
+        assert_eq!(
+            stream.get_max_acceptable_topup_amount(STARTS_AT + 5_000).unwrap(),
+            (false, 0)
+        );
+    }
+
+    #[test]
+    fn topup_unbounded_stream() {
+        let mut stream = non_prepaid_stream(0, 3_600, 0);
+        assert_eq!(stream.get_max_acceptable_topup_amount(STARTS_AT).unwrap(), (true, 0));
+        assert_eq!(
+            stream.topup_non_prepaid(STARTS_AT, other(), 0, false),
+            Err(MathError::ZeroAmount)
+        );
+
+        // Only the sender can topup within the grace period.
+        assert_eq!(
+            stream.topup_non_prepaid(INSOLVENT_AT, other(), 1_000, true),
+            Err(MathError::StreamInInsolvencyGracePeriod)
+        );
+        assert_eq!(stream.topup_non_prepaid(INSOLVENT_AT, sender(), 1_000, true), Ok(1_000));
+        assert_eq!(stream.insolvent_at(), Some(INSOLVENT_AT + 100));
+        assert_eq!(
+            stream.topup_non_prepaid(INSOLVENT_AT, other(), 1_000_000_000, true),
+            Ok(1_000_000_000)
+        );
+
+        let mut stream = prepaid_stream(STARTS_AT + 1_000, 1, FLOW_RATE);
+        assert_eq!(
+            stream.topup_non_prepaid(STARTS_AT, sender(), 1, false),
+            Err(MathError::StreamIsPrepaid)
+        );
+    }
+
+    const MONTH: u64 = 30 * 24 * 60 * 60;
+
+    /// Fixed-point flow rate streaming 1000 tokens with 6 decimals every 30 days, which is not a whole number of base
+    /// units per second.
+    fn monthly_flow_rate() -> FlowRate {
+        FlowRate::scaled_from_amount_per_period(1_000_000_000, MONTH).unwrap()
+    }
+
+    #[test]
+    fn scaled_prepaid_stream_owes_prepaid_amount_at_end() {
+        let flow_rate = monthly_flow_rate();
+        assert_eq!(flow_rate, FlowRate::ScaledPerSecond(385_802_469_135));
+
+        let ends_at = STARTS_AT + MONTH;
+        let mut stream = prepaid_stream(ends_at, flow_rate.flow_interval(), flow_rate.flow_rate());
+        assert_eq!(stream.get_flow_rate(), flow_rate);
+        assert_eq!(stream.total_topup_amount, INITIAL_AMOUNT + 999_999_999);
+        assert_eq!(
+            stream.get_amount_owed(ends_at - 1).unwrap(),
+            INITIAL_AMOUNT + 999_999_614
+        );
+        assert_eq!(stream.get_amount_owed(ends_at).unwrap(), stream.total_topup_amount);
+        assert_eq!(
+            stream.get_amount_owed(ends_at + MONTH).unwrap(),
+            stream.total_topup_amount
+        );
+
+        // Withdrawing in between doesn't accumulate any rounding error.
+        let mut withdrawn_amount = 0;
+        for at in [
+            STARTS_AT + 1,
+            STARTS_AT + 3,
+            STARTS_AT + 1_000_001,
+            ends_at - 1,
+            ends_at + 1,
+        ] {
+            withdrawn_amount += withdraw(&mut stream, at);
+            assert_eq!(withdrawn_amount, stream.get_amount_owed(at).unwrap());
+        }
+        assert_eq!(withdrawn_amount, stream.total_topup_amount);
+        assert_eq!(stream.get_escrow_amount_needed().unwrap(), 0);
+        stream.check_invariants(ends_at + 1).unwrap();
+    }
+
+    #[test]
+    fn scaled_prepaid_stream_cancel_mid_stream() {
+        let flow_rate = monthly_flow_rate();
+        let mut stream = prepaid_stream(STARTS_AT + MONTH, flow_rate.flow_interval(), flow_rate.flow_rate());
+        let prepaid_amount = stream.total_topup_amount;
+        assert_eq!(withdraw(&mut stream, STARTS_AT + 1), INITIAL_AMOUNT + 385);
+
+        let at = STARTS_AT + 1_000_001;
+        let amount_owed = stream.get_amount_owed(at).unwrap();
+        assert_eq!(amount_owed, INITIAL_AMOUNT + 385_802_854);
+        let params = stream.cancel(at, sender(), recipient()).unwrap();
+        assert_eq!(params.transfer_amount_recipient, amount_owed - INITIAL_AMOUNT - 385);
+        assert_eq!(params.transfer_amount_sender, prepaid_amount - amount_owed);
+        assert_eq!(stream.get_escrow_amount_needed().unwrap(), 0);
+        stream.check_invariants(at).unwrap();
+    }
+
+    #[test]
+    fn scaled_stream_becomes_insolvent_at_ceil_boundary() {
+        // Exact division: 57_602 tokens are streamed in exactly 28_801 secs at 2 tokens per sec.
+        let mut stream = new_stream(false, 0, FLOW_RATE_SCALE, 2 * FLOW_RATE_SCALE);
+        let topup_amount = INITIAL_AMOUNT + 2 * stream.deposit_needed + 1;
+        stream.initialize_non_prepaid(CREATED_AT, topup_amount, 0, 0).unwrap();
+        assert_eq!(stream.total_topup_amount, INITIAL_AMOUNT + 57_601);
+        assert_eq!(stream.insolvent_at(), Some(STARTS_AT + 28_801));
+
+        let flow_rates = [
+            1,
+            7,
+            FLOW_RATE_SCALE - 1,
+            FLOW_RATE_SCALE + 1,
+            3 * FLOW_RATE_SCALE / 2,
+            2 * FLOW_RATE_SCALE,
+            monthly_flow_rate().flow_rate(),
+        ];
+        for flow_rate in flow_rates {
+            for extra_topup_amount in [0, 1, 2, 3, 999] {
+                let mut stream = new_stream(false, 0, FLOW_RATE_SCALE, flow_rate);
+                let topup_amount = INITIAL_AMOUNT + 2 * stream.deposit_needed + extra_topup_amount;
+                stream.initialize_non_prepaid(CREATED_AT, topup_amount, 0, 0).unwrap();
+
+                let insolvent_at = stream.insolvent_at().unwrap();
+                assert!(insolvent_at > STARTS_AT);
+                assert!(stream.is_solvent(insolvent_at - 1).unwrap(), "flow rate {flow_rate}");
+                assert!(!stream.is_solvent(insolvent_at).unwrap(), "flow rate {flow_rate}");
+            }
+        }
+    }
+
+    /// Fixed-point flow rate for which `active_time * flow_rate` overflows u64 after 1000 secs while the amount streamed
+    /// fits in u64 for much longer.
+    const LARGE_FLOW_RATE: u64 = u64::MAX / 1_000;
+
+    #[test]
+    fn large_flow_rate_amounts_fit_when_product_overflows() {
+        assert!(10_000u64.checked_mul(LARGE_FLOW_RATE).is_none());
+
+        let ends_at = STARTS_AT + 10_000;
+        let stream = prepaid_stream(ends_at, FLOW_RATE_SCALE, LARGE_FLOW_RATE);
+        assert_eq!(stream.total_topup_amount, INITIAL_AMOUNT + 184_467_440_737);
+        assert_eq!(stream.get_amount_owed(ends_at).unwrap(), stream.total_topup_amount);
+        stream.check_invariants(ends_at).unwrap();
+
+        let mut stream = new_stream(false, STARTS_AT + 100_000, FLOW_RATE_SCALE, LARGE_FLOW_RATE);
+        assert_eq!(stream.deposit_needed, 531_266_229_322);
+        let topup_amount = INITIAL_AMOUNT + 2 * stream.deposit_needed;
+        stream.initialize_non_prepaid(CREATED_AT, topup_amount, 0, 0).unwrap();
+        assert_eq!(
+            stream.get_max_acceptable_topup_amount(STARTS_AT).unwrap(),
+            (false, INITIAL_AMOUNT + 1_844_674_407_370 - stream.total_topup_amount)
+        );
+
+        // The insolvency time is computed in u128 too.
+        let insolvent_at = stream.insolvent_at().unwrap();
+        assert_eq!(insolvent_at, STARTS_AT + 28_801);
+        assert!(stream.is_solvent(insolvent_at - 1).unwrap());
+        assert!(!stream.is_solvent(insolvent_at).unwrap());
+    }
+
+    #[test]
+    fn amounts_which_do_not_fit_are_errors() {
+        let mut stream = StreamState {
+            starts_at: STARTS_AT,
+            flow_interval: 1,
+            flow_rate: u64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(stream.get_amount_owed(STARTS_AT + 1).unwrap(), u64::MAX);
+        assert_eq!(
+            stream.get_amount_owed(STARTS_AT + 2),
+            Err(MathError::AmountAvailableToWithdrawOutOfBounds)
+        );
+        assert_eq!(
+            stream.get_deposit_needed(),
+            Err(MathError::DepositAmountNeededOutOfBounds)
+        );
+
+        stream.is_prepaid = true;
+        stream.ends_at = STARTS_AT + 2;
+        assert_eq!(
+            stream.get_prepaid_amount_needed(),
+            Err(MathError::PrepaidAmountNeededOutOfBounds)
+        );
+    }
+}
@@ -1,6 +1,10 @@
+// Every instruction returns `anchor_lang::error::Error`, which is larger than clippy's limit for error variants.
+#![allow(clippy::result_large_err)]
+
 use anchor_lang::prelude::*;
 
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use superstream::state::{CreateStreamParams, StreamDerivation};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -8,7 +12,38 @@ declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 pub mod superstream_cpi_example {
     use super::*;
 
+    /// Create a new stream.
+    pub fn create_stream(
+        ctx: Context<Create>,
+        seed: u64,
+        name: String,
+        recipient: Pubkey,
+        derivation: StreamDerivation,
+        params: CreateStreamParams,
+    ) -> Result<()> {
+        let cpi_program = ctx.accounts.superstream_program.to_account_info();
+        let cpi_accounts = superstream::cpi::accounts::CreateStream {
+            stream: ctx.accounts.stream.to_account_info(),
+            sender: ctx.accounts.sender.to_account_info(),
+            mint: ctx.accounts.sender.to_account_info(),
+            sender_token: ctx.accounts.sender_token.to_account_info(),
+            escrow_token: ctx.accounts.escrow_token.to_account_info(),
+            mint_registry: ctx.accounts.mint_registry.to_account_info(),
+            allowed_mint: ctx.accounts.allowed_mint.to_account_info(),
+            sender_index: ctx.accounts.sender_index.to_account_info(),
+            recipient_index: ctx.accounts.recipient_index.to_account_info(),
+            mint_stats: ctx.accounts.mint_stats.to_account_info(),
+            global_stats: ctx.accounts.global_stats.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        superstream::cpi::create_stream(cpi_ctx, seed, name, recipient, derivation, params)
+    }
+
     /// Create a new prepaid stream.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_prepaid(
         ctx: Context<Create>,
         seed: u64,
@@ -37,6 +72,12 @@ pub mod superstream_cpi_example {
             mint: ctx.accounts.sender.to_account_info(),
             sender_token: ctx.accounts.sender_token.to_account_info(),
             escrow_token: ctx.accounts.escrow_token.to_account_info(),
+            mint_registry: ctx.accounts.mint_registry.to_account_info(),
+            allowed_mint: ctx.accounts.allowed_mint.to_account_info(),
+            sender_index: ctx.accounts.sender_index.to_account_info(),
+            recipient_index: ctx.accounts.recipient_index.to_account_info(),
+            mint_stats: ctx.accounts.mint_stats.to_account_info(),
+            global_stats: ctx.accounts.global_stats.to_account_info(),
             token_program: ctx.accounts.token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
         };
@@ -66,6 +107,7 @@ pub mod superstream_cpi_example {
     }
 
     /// Create a new non-prepaid stream.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_non_prepaid(
         ctx: Context<Create>,
         seed: u64,
@@ -95,6 +137,12 @@ pub mod superstream_cpi_example {
             mint: ctx.accounts.sender.to_account_info(),
             sender_token: ctx.accounts.sender_token.to_account_info(),
             escrow_token: ctx.accounts.escrow_token.to_account_info(),
+            mint_registry: ctx.accounts.mint_registry.to_account_info(),
+            allowed_mint: ctx.accounts.allowed_mint.to_account_info(),
+            sender_index: ctx.accounts.sender_index.to_account_info(),
+            recipient_index: ctx.accounts.recipient_index.to_account_info(),
+            mint_stats: ctx.accounts.mint_stats.to_account_info(),
+            global_stats: ctx.accounts.global_stats.to_account_info(),
             token_program: ctx.accounts.token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
         };
@@ -136,12 +184,40 @@ pub mod superstream_cpi_example {
             sender_token: ctx.accounts.sender_token.to_account_info(),
             recipient_token: ctx.accounts.recipient_token.to_account_info(),
             escrow_token: ctx.accounts.escrow_token.to_account_info(),
+            mint_stats: ctx.accounts.mint_stats.to_account_info(),
+            global_stats: ctx.accounts.global_stats.to_account_info(),
             token_program: ctx.accounts.token_program.to_account_info(),
         };
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
         superstream::cpi::cancel(cpi_ctx, seed, name, recipient)
     }
+
+    /// Get the amount the recipient can withdraw from a stream right now.
+    pub fn get_withdrawable_amount(ctx: Context<ViewStream>, seed: u64, name: String) -> Result<u64> {
+        let cpi_program = ctx.accounts.superstream_program.to_account_info();
+        let cpi_accounts = superstream::cpi::accounts::ViewStream {
+            stream: ctx.accounts.stream.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        let withdrawable = superstream::cpi::get_withdrawable(cpi_ctx, seed, name)?.get();
+        Ok(withdrawable.withdrawable_amount)
+    }
+
+    /// Check if a stream is solvent right now.
+    pub fn is_solvent(ctx: Context<ViewStream>, seed: u64, name: String) -> Result<bool> {
+        let cpi_program = ctx.accounts.superstream_program.to_account_info();
+        let cpi_accounts = superstream::cpi::accounts::ViewStream {
+            stream: ctx.accounts.stream.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        let solvency = superstream::cpi::get_solvency(cpi_ctx, seed, name)?.get();
+        Ok(solvency.is_solvent)
+    }
 }
 
 /// Accounts struct for creating a new stream.
@@ -149,7 +225,7 @@ pub mod superstream_cpi_example {
 pub struct Create<'info> {
     /// Stream PDA account. This is initialized by the program.
     #[account(mut)]
-    pub stream: Box<Account<'info, superstream::state::Stream>>,
+    pub stream: Box<Account<'info, superstream::state::VersionedStream>>,
 
     /// Stream sender wallet.
     #[account(mut)]
@@ -174,6 +250,35 @@ pub struct Create<'info> {
     )]
     pub escrow_token: Box<Account<'info, TokenAccount>>,
 
+    /// Mint registry PDA account.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    pub mint_registry: UncheckedAccount<'info>,
+    /// Allowed mint PDA account.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    pub allowed_mint: UncheckedAccount<'info>,
+    /// Stream index PDA account of the sender.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    #[account(mut)]
+    pub sender_index: UncheckedAccount<'info>,
+    /// Stream index PDA account of the recipient.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    #[account(mut)]
+    pub recipient_index: UncheckedAccount<'info>,
+    /// Stats PDA account of the mint.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    #[account(mut)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    #[account(mut)]
+    pub global_stats: UncheckedAccount<'info>,
+
     /// SPL token program.
     pub token_program: Program<'info, Token>,
     /// Solana system program.
@@ -189,7 +294,7 @@ pub struct Create<'info> {
 pub struct Cancel<'info> {
     /// Stream PDA account.
     #[account(mut)]
-    pub stream: Box<Account<'info, superstream::state::Stream>>,
+    pub stream: Box<Account<'info, superstream::state::VersionedStream>>,
 
     /// Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent.
     /// After insolvency, anyone can cancel.
@@ -236,6 +341,16 @@ pub struct Cancel<'info> {
             && escrow_token.owner == stream.key(),
     )]
     pub escrow_token: Box<Account<'info, TokenAccount>>,
+    /// Stats PDA account of the mint.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    #[account(mut)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    ///
+    /// CHECK: Superstream program verifies the address.
+    #[account(mut)]
+    pub global_stats: UncheckedAccount<'info>,
 
     /// SPL token program.
     pub token_program: Program<'info, Token>,
@@ -243,3 +358,16 @@ pub struct Cancel<'info> {
     /// Superstream program.
     pub superstream_program: Program<'info, superstream::program::Superstream>,
 }
+
+/// Accounts struct for reading figures of a stream.
+#[derive(Accounts)]
+pub struct ViewStream<'info> {
+    /// Stream PDA account.
+    pub stream: Box<Account<'info, superstream::state::VersionedStream>>,
+
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Superstream program.
+    pub superstream_program: Program<'info, superstream::program::Superstream>,
+}
@@ -1,6 +1,7 @@
 //! Module for superstream error handling.
 
 use anchor_lang::prelude::*;
+use superstream_math::MathError;
 
 /// Enumeration of possible stream errors.
 #[error_code]
@@ -127,4 +128,150 @@ pub enum StreamError {
     /// The stream has not ended. Should have ended and nat been cancelled.
     #[msg("The stream has not ended. Should have ended and nat been cancelled")]
     StreamNotEnded,
+    /// The mint is not allowed by the mint registry. Streams cannot be created for this mint.
+    #[msg("The mint is not allowed by the mint registry. Streams cannot be created for this mint")]
+    MintNotAllowed,
+    /// The new authority is invalid.
+    #[msg("The new authority is invalid")]
+    InvalidNewAuthority,
+    /// The insolvency grace period is more than the deposit amount period. Should be <= 8 hrs.
+    #[msg("The insolvency grace period is more than the deposit amount period. Should be <= 8 hrs")]
+    InvalidInsolvencyGracePeriod,
+    /// The stream is insolvent but within the insolvency grace period. Only the sender can topup and only the sender or
+    /// the recipient can cancel.
+    #[msg("The stream is insolvent but within the insolvency grace period. Only the sender can topup and only the sender or the recipient can cancel")]
+    StreamInInsolvencyGracePeriod,
+    /// The recipient deposit share is more than 100%. Should be <= 10000 basis points.
+    #[msg("The recipient deposit share is more than 100%. Should be <= 10000 basis points")]
+    InvalidRecipientDepositShare,
+    /// The user is not allowed to propose, cancel or accept a settlement. Should be the sender or the recipient of the
+    /// stream and only the other party can accept a settlement.
+    #[msg("The user is not allowed to propose, cancel or accept a settlement. Should be the sender or the recipient of the stream and only the other party can accept a settlement")]
+    UserUnauthorizedToSettle,
+    /// No settlement has been proposed for the stream.
+    #[msg("No settlement has been proposed for the stream")]
+    SettlementNotProposed,
+    /// The settlement amount is different from the proposed settlement amount.
+    #[msg("The settlement amount is different from the proposed settlement amount")]
+    SettlementAmountMismatch,
+    /// The settlement amount is invalid. Should be >= total withdrawn amount and <= total amount paid by the sender.
+    #[msg(
+        "The settlement amount is invalid. Should be >= total withdrawn amount and <= total amount paid by the sender"
+    )]
+    InvalidSettlementAmount,
+    /// The arbiter is invalid. Should be different from the sender and the recipient.
+    #[msg("The arbiter is invalid. Should be different from the sender and the recipient")]
+    InvalidArbiter,
+    /// The user is not allowed to arbitrate. Should be the arbiter of the stream.
+    #[msg("The user is not allowed to arbitrate. Should be the arbiter of the stream")]
+    UserUnauthorizedToArbitrate,
+    /// The stream is frozen by the arbiter. Should be a non-frozen stream.
+    #[msg("The stream is frozen by the arbiter. Should be a non-frozen stream")]
+    StreamIsFrozen,
+    /// The stream is not frozen. Should be a frozen stream.
+    #[msg("The stream is not frozen. Should be a frozen stream")]
+    StreamIsNotFrozen,
+    /// The stream is pending recipient acceptance. Should be an accepted stream.
+    #[msg("The stream is pending recipient acceptance. Should be an accepted stream")]
+    StreamIsPendingAcceptance,
+    /// The stream is not pending recipient acceptance. Should be a stream pending recipient acceptance.
+    #[msg("The stream is not pending recipient acceptance. Should be a stream pending recipient acceptance")]
+    StreamIsNotPendingAcceptance,
+    /// The user is not allowed to accept the stream. Should be the recipient of the stream.
+    #[msg("The user is not allowed to accept the stream. Should be the recipient of the stream")]
+    UserUnauthorizedToAccept,
+    /// No sender change has been proposed for the stream.
+    #[msg("No sender change has been proposed for the stream")]
+    SenderChangeNotProposed,
+    /// No recipient change has been proposed for the stream.
+    #[msg("No recipient change has been proposed for the stream")]
+    RecipientChangeNotProposed,
+    /// The new recipient is invalid.
+    #[msg("The new recipient is invalid")]
+    InvalidNewRecipient,
+    /// The permission expiry time is invalid. It should be 0 or after the time at which the permission is given.
+    #[msg("The permission expiry time is invalid")]
+    InvalidPermissionExpiry,
+    /// A documented stream invariant or the escrow conservation does not hold. Only checked when the program is built
+    /// with the `invariant-checks` feature.
+    #[msg("A stream invariant does not hold")]
+    InvariantViolation,
+    /// The user is not allowed to close the stream. Should be the sender or the recipient of the stream.
+    #[msg("The user is not allowed to close the stream. Should be the sender or the recipient of the stream")]
+    UserUnauthorizedToClose,
+    /// The stream has not stopped. Should have ended or been cancelled.
+    #[msg("The stream has not stopped. Should have ended or been cancelled")]
+    StreamNotStopped,
+    /// The stream still holds funds. Everything should have been withdrawn or refunded.
+    #[msg("The stream still holds funds. Everything should have been withdrawn or refunded")]
+    StreamHasFundsLeft,
+    /// The stream is in the legacy account layout. It needs to be migrated before using this feature.
+    #[msg("The stream is in the legacy account layout. It needs to be migrated before using this feature")]
+    StreamNotMigrated,
+    /// The stream is already in the newest account layout.
+    #[msg("The stream is already in the newest account layout")]
+    StreamAlreadyMigrated,
+    /// The user is not allowed to convert the stream. Should be the sender or the recipient of the stream.
+    #[msg("The user is not allowed to convert the stream. Should be the sender or the recipient of the stream")]
+    UserUnauthorizedToConvert,
+}
+
+impl From<MathError> for StreamError {
+    fn from(err: MathError) -> Self {
+        match err {
+            MathError::SameSenders => StreamError::SameSenders,
+            MathError::SameRecipients => StreamError::SameRecipients,
+            MathError::InvalidNewSender => StreamError::InvalidNewSender,
+            MathError::InvalidSender => StreamError::InvalidSender,
+            MathError::InvalidRecipient => StreamError::InvalidRecipient,
+            MathError::InvalidEndsAt => StreamError::InvalidEndsAt,
+            MathError::ZeroLifetimeAmount => StreamError::ZeroLifetimeAmount,
+            MathError::ZeroAmount => StreamError::ZeroAmount,
+            MathError::PrepaidAmountNeededOutOfBounds => StreamError::PrepaidAmountNeededOutOfBounds,
+            MathError::DepositAmountNeededOutOfBounds => StreamError::DepositAmountNeededOutOfBounds,
+            MathError::AmountLessThanAmountNeeded => StreamError::AmountLessThanAmountNeeded,
+            MathError::UserUnauthorizedToWithdraw => StreamError::UserUnauthorizedToWithdraw,
+            MathError::WithdrawnAmountGreaterThanAmountOwed => StreamError::WithdrawnAmountGreaterThanAmountOwed,
+            MathError::WithdrawAmountOutOfBounds => StreamError::WithdrawAmountOutOfBounds,
+            MathError::AmountAvailableToWithdrawOutOfBounds => StreamError::AmountAvailableToWithdrawOutOfBounds,
+            MathError::CancellationRefundOutOfBounds => StreamError::CancellationRefundOutOfBounds,
+            MathError::TopupAmountOutOfBounds => StreamError::TopupAmountOutOfBounds,
+            MathError::TopupAmountMoreThanMaxAcceptable => StreamError::TopupAmountMoreThanMaxAcceptable,
+            MathError::StreamAlreadyCancelled => StreamError::StreamAlreadyCancelled,
+            MathError::UserUnauthorizedToCancel => StreamError::UserUnauthorizedToCancel,
+            MathError::SenderCannotCancel => StreamError::SenderCannotCancel,
+            MathError::StreamIsPrepaid => StreamError::StreamIsPrepaid,
+            MathError::StreamHasStopped => StreamError::StreamHasStopped,
+            MathError::StreamIsPaused => StreamError::StreamIsPaused,
+            MathError::StreamIsNotPaused => StreamError::StreamIsNotPaused,
+            MathError::StreamHasNoFlowPayments => StreamError::StreamHasNoFlowPayments,
+            MathError::SenderCannotChangeSender => StreamError::SenderCannotChangeSender,
+            MathError::SenderCannotPause => StreamError::SenderCannotPause,
+            MathError::RecipientCannotResumePauseBySender => StreamError::RecipientCannotResumePauseBySender,
+            MathError::UserUnauthorizedToPause => StreamError::UserUnauthorizedToPause,
+            MathError::UserUnauthorizedToResume => StreamError::UserUnauthorizedToResume,
+            MathError::StreamNotEnded => StreamError::StreamNotEnded,
+            MathError::InvalidInsolvencyGracePeriod => StreamError::InvalidInsolvencyGracePeriod,
+            MathError::StreamInInsolvencyGracePeriod => StreamError::StreamInInsolvencyGracePeriod,
+            MathError::InvalidRecipientDepositShare => StreamError::InvalidRecipientDepositShare,
+            MathError::UserUnauthorizedToSettle => StreamError::UserUnauthorizedToSettle,
+            MathError::SettlementNotProposed => StreamError::SettlementNotProposed,
+            MathError::SettlementAmountMismatch => StreamError::SettlementAmountMismatch,
+            MathError::InvalidSettlementAmount => StreamError::InvalidSettlementAmount,
+            MathError::UserUnauthorizedToArbitrate => StreamError::UserUnauthorizedToArbitrate,
+            MathError::StreamIsFrozen => StreamError::StreamIsFrozen,
+            MathError::StreamIsNotFrozen => StreamError::StreamIsNotFrozen,
+            MathError::StreamIsPendingAcceptance => StreamError::StreamIsPendingAcceptance,
+            MathError::StreamIsNotPendingAcceptance => StreamError::StreamIsNotPendingAcceptance,
+            MathError::UserUnauthorizedToAccept => StreamError::UserUnauthorizedToAccept,
+            MathError::SenderChangeNotProposed => StreamError::SenderChangeNotProposed,
+            MathError::RecipientChangeNotProposed => StreamError::RecipientChangeNotProposed,
+            MathError::InvalidNewRecipient => StreamError::InvalidNewRecipient,
+            MathError::InvalidPermissionExpiry => StreamError::InvalidPermissionExpiry,
+            MathError::InvariantViolation => StreamError::InvariantViolation,
+            MathError::UserUnauthorizedToClose => StreamError::UserUnauthorizedToClose,
+            MathError::StreamNotStopped => StreamError::StreamNotStopped,
+            MathError::StreamHasFundsLeft => StreamError::StreamHasFundsLeft,
+        }
+    }
 }
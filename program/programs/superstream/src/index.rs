@@ -0,0 +1,80 @@
+//! Module for maintaining the stream index accounts of the users. See [`StreamIndex`].
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::StreamIndex,
+    utils::{
+        create_program_account, is_program_account_initialized, load_program_account, resize_program_account,
+        store_program_account,
+    },
+};
+
+/// Add a stream to a stream index account, creating the index account if it doesn't exist. The payer pays the rent of
+/// the extra space.
+pub(crate) fn add_to_index<'info>(
+    index: &UncheckedAccount<'info>,
+    index_seed: &[u8],
+    owner: Pubkey,
+    bump: u8,
+    stream: Pubkey,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let index = index.to_account_info();
+    let mut stream_index = if is_program_account_initialized(&index) {
+        load_program_account::<StreamIndex>(&index)?
+    } else {
+        create_program_account(
+            &index,
+            StreamIndex::space(0),
+            &[index_seed, owner.as_ref(), &[bump]],
+            payer,
+            system_program,
+        )?;
+        StreamIndex {
+            owner,
+            bump,
+            streams: Vec::new(),
+        }
+    };
+    if !stream_index.add(stream) {
+        return Ok(());
+    }
+
+    resize_program_account(
+        &index,
+        StreamIndex::space(stream_index.streams.len()),
+        payer,
+        system_program,
+    )?;
+    store_program_account(&index, &stream_index)
+}
+
+/// Remove a stream from a stream index account. The rent of the freed space is returned to the owner of the index. This
+/// is a no-op if the index account doesn't exist or doesn't have the stream, i.e. for streams created before indexes
+/// were introduced.
+pub(crate) fn remove_from_index<'info>(
+    index: &UncheckedAccount<'info>,
+    stream: &Pubkey,
+    owner: &AccountInfo<'info>,
+) -> Result<()> {
+    let index = index.to_account_info();
+    if !is_program_account_initialized(&index) {
+        return Ok(());
+    }
+    let mut stream_index = load_program_account::<StreamIndex>(&index)?;
+    require_keys_eq!(stream_index.owner, owner.key());
+    if !stream_index.remove(stream) {
+        return Ok(());
+    }
+
+    let space = StreamIndex::space(stream_index.streams.len());
+    index.realloc(space, false)?;
+    store_program_account(&index, &stream_index)?;
+
+    let excess_lamports = index.lamports().saturating_sub(Rent::get()?.minimum_balance(space));
+    **index.try_borrow_mut_lamports()? -= excess_lamports;
+    **owner.try_borrow_mut_lamports()? += excess_lamports;
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! Runtime checks of the documented stream invariants. The checks run at the end of every instruction that changes a
+//! stream when the program is built with the `invariant-checks` feature, and are no-ops otherwise.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[cfg(feature = "invariant-checks")]
+use superstream_math::StreamState;
+
+use crate::state::{VersionedStream, ZeroCopyStream};
+#[cfg(feature = "invariant-checks")]
+use crate::{
+    error::StreamError,
+    state::{StreamDerivation, LEGACY_STREAM_LAYOUT_VERSION, MAX_STREAM_NAME_LENGTH, STREAM_LAYOUT_VERSION},
+    utils::get_current_timestamp,
+};
+
+/// Check the stream invariants and the conservation of the escrow amount. If the instruction doesn't have the escrow
+/// account, the amount the escrow needs to hold should not have changed.
+#[cfg(feature = "invariant-checks")]
+pub(crate) fn check_invariants<'info>(
+    stream: &Account<'info, VersionedStream>,
+    escrow_token: Option<&mut Account<'info, TokenAccount>>,
+) -> Result<()> {
+    require!(
+        stream.version == LEGACY_STREAM_LAYOUT_VERSION || stream.version == STREAM_LAYOUT_VERSION,
+        StreamError::InvariantViolation
+    );
+    require!(
+        stream.name.chars().count() <= MAX_STREAM_NAME_LENGTH,
+        StreamError::InvariantViolation
+    );
+    check_derivation(
+        stream.derivation,
+        &stream.derivation_sender,
+        &stream.derivation_recipient,
+    )?;
+
+    // The stream account data is only written when the instruction exits, so it still holds the stream as it was
+    // before the instruction. The data is zeroed if the stream is being created.
+    let previous_stream = {
+        let account_info = stream.to_account_info();
+        let data = account_info.try_borrow_data()?;
+        VersionedStream::try_deserialize_unchecked(&mut &data[..])?
+    };
+    check_state(&stream.state(), &previous_stream.state(), escrow_token)
+}
+
+/// Check the stream invariants and the conservation of the escrow amount. This is a no-op as the program is built
+/// without the `invariant-checks` feature.
+#[cfg(not(feature = "invariant-checks"))]
+#[inline(always)]
+pub(crate) fn check_invariants<'info>(
+    _stream: &Account<'info, VersionedStream>,
+    _escrow_token: Option<&mut Account<'info, TokenAccount>>,
+) -> Result<()> {
+    Ok(())
+}
+
+/// Accounting state of a zero-copy stream taken before the instruction changes it. Zero-copy streams are changed in
+/// place, so unlike the other streams, the state from before the instruction can't be read from the account data at
+/// the end. The snapshot is empty if the program is built without the `invariant-checks` feature.
+pub(crate) struct StreamSnapshot {
+    #[cfg(feature = "invariant-checks")]
+    state: StreamState,
+}
+
+impl StreamSnapshot {
+    #[cfg_attr(not(feature = "invariant-checks"), allow(unused_variables))]
+    #[inline(always)]
+    pub(crate) fn new(stream: &ZeroCopyStream) -> Self {
+        Self {
+            #[cfg(feature = "invariant-checks")]
+            state: stream.state(),
+        }
+    }
+}
+
+/// Check the invariants of a zero-copy stream and the conservation of the escrow amount. See [`check_invariants`].
+#[cfg(feature = "invariant-checks")]
+pub(crate) fn check_zero_copy_invariants<'info>(
+    stream: &AccountLoader<'info, ZeroCopyStream>,
+    snapshot: StreamSnapshot,
+    escrow_token: Option<&mut Account<'info, TokenAccount>>,
+) -> Result<()> {
+    let stream = stream.load()?;
+    require!(
+        stream.name()?.chars().count() <= MAX_STREAM_NAME_LENGTH,
+        StreamError::InvariantViolation
+    );
+    check_derivation(
+        stream.derivation(),
+        &stream.derivation_sender,
+        &stream.derivation_recipient,
+    )?;
+    check_state(&stream.state(), &snapshot.state, escrow_token)
+}
+
+/// Check the invariants of a zero-copy stream and the conservation of the escrow amount. This is a no-op as the program
+/// is built without the `invariant-checks` feature.
+#[cfg(not(feature = "invariant-checks"))]
+#[inline(always)]
+pub(crate) fn check_zero_copy_invariants<'info>(
+    _stream: &AccountLoader<'info, ZeroCopyStream>,
+    _snapshot: StreamSnapshot,
+    _escrow_token: Option<&mut Account<'info, TokenAccount>>,
+) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(feature = "invariant-checks")]
+fn check_derivation(
+    derivation: StreamDerivation,
+    derivation_sender: &Pubkey,
+    derivation_recipient: &Pubkey,
+) -> Result<()> {
+    require!(
+        (derivation.includes_sender() || *derivation_sender == Pubkey::default())
+            && (derivation.includes_recipient() || *derivation_recipient == Pubkey::default()),
+        StreamError::InvariantViolation
+    );
+    Ok(())
+}
+
+#[cfg(feature = "invariant-checks")]
+fn check_state<'info>(
+    state: &StreamState,
+    previous_state: &StreamState,
+    escrow_token: Option<&mut Account<'info, TokenAccount>>,
+) -> Result<()> {
+    let at = get_current_timestamp()?;
+    state.check_invariants(at).map_err(invariant_error)?;
+    let escrow_amount_needed = state.get_escrow_amount_needed().map_err(invariant_error)?;
+    let previous_escrow_amount_needed = previous_state.get_escrow_amount_needed().map_err(invariant_error)?;
+
+    match escrow_token {
+        Some(escrow_token) => {
+            // The escrow account is not reloaded after the token transfers, so it still holds the amount from before
+            // the instruction.
+            let previous_escrow_amount = escrow_token.amount;
+            escrow_token.reload()?;
+
+            // Anyone can send tokens to the escrow account, so it can hold more than needed. But every token
+            // transferred by the instruction needs to be accounted for by the stream.
+            require!(
+                escrow_token.amount >= escrow_amount_needed,
+                StreamError::InvariantViolation
+            );
+            require!(
+                escrow_token.amount as i128 - previous_escrow_amount as i128
+                    == escrow_amount_needed as i128 - previous_escrow_amount_needed as i128,
+                StreamError::InvariantViolation
+            );
+        },
+        None => require!(
+            escrow_amount_needed == previous_escrow_amount_needed,
+            StreamError::InvariantViolation
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "invariant-checks")]
+fn invariant_error(err: superstream_math::MathError) -> Error {
+    error!(StreamError::from(err))
+}
@@ -34,6 +34,8 @@
 //!             sender_token: ctx.accounts.sender_token.to_account_info(),
 //!             recipient_token: ctx.accounts.recipient_token.to_account_info(),
 //!             escrow_token: ctx.accounts.escrow_token.to_account_info(),
+//!             mint_stats: ctx.accounts.mint_stats.to_account_info(),
+//!             global_stats: ctx.accounts.global_stats.to_account_info(),
 //!             token_program: ctx.accounts.token_program.to_account_info(),
 //!         };
 //!         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
@@ -71,6 +73,12 @@
 //!     /// Associated token escrow account holding the funds for this stream.
 //!     #[account(mut)]
 //!     pub escrow_token: Box<Account<'info, TokenAccount>>,
+//!     /// Stats PDA account of the mint.
+//!     #[account(mut)]
+//!     pub mint_stats: AccountInfo<'info>,
+//!     /// Global stats PDA account.
+//!     #[account(mut)]
+//!     pub global_stats: AccountInfo<'info>,
 //!
 //!     /// SPL token program.
 //!     pub token_program: Program<'info, Token>,
@@ -82,38 +90,116 @@
 //! // ... other stuff
 //! ```
 
+// Every instruction returns `anchor_lang::error::Error`, which is larger than clippy's limit for error variants, and
+// the CPI wrappers generated for `create_prepaid` and `create_non_prepaid` take all their arguments positionally.
+#![allow(clippy::result_large_err, clippy::too_many_arguments)]
+
+mod index;
+mod invariants;
+mod stats;
 mod transfer;
 mod utils;
+mod zero_copy;
 
 pub mod error;
 pub mod state;
 
-use anchor_lang::prelude::*;
+use std::borrow::Cow;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{hash::hash, pubkey::MAX_SEED_LEN},
+};
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{
     error::StreamError,
-    state::Stream,
-    transfer::{transfer_from_escrow, transfer_to_escrow},
-    utils::is_token_account_rent_exempt,
+    index::{add_to_index, remove_from_index},
+    invariants::{check_invariants, check_zero_copy_invariants, StreamSnapshot},
+    state::{
+        AllowedMint, ConvertibleStream, ConvertibleZeroCopyStream, CreateStreamParams, CreateStreamParamsV1,
+        MaxTopupView, MintRegistry, SolvencyView, Stream, StreamDerivation, StreamKind, StreamPermission,
+        StreamPermissions, StreamSchedule, VersionedStream, WithdrawableView, ZeroCopyStream, STREAM_LAYOUT_VERSION,
+    },
+    stats::{record_creation, record_global_creation, update_stats},
+    transfer::{close_escrow, transfer_from_escrow, transfer_from_zero_copy_escrow, transfer_to_escrow},
+    utils::{get_current_timestamp, is_token_account_rent_exempt},
+    zero_copy::{convert_from_zero_copy, convert_to_zero_copy},
 };
 
 declare_id!("4WLNkJ6RKt54sv85iTgJPLgoaxfrxAasZWBxAPLUfuVG");
 
 /// PDA account seed to create new stream PDA accounts.
 pub const STREAM_ACCOUNT_SEED: &[u8] = b"stream";
+/// Maximum length in bytes of a stream name that is used as is as a seed of the stream PDA account. Longer names are
+/// hashed. See [`get_stream_name_seed`].
+pub const MAX_STREAM_NAME_SEED_LENGTH: usize = MAX_SEED_LEN;
+/// PDA account seed to create the stream index PDA accounts of the senders.
+pub const SENDER_INDEX_ACCOUNT_SEED: &[u8] = b"sender_index";
+/// PDA account seed to create the stream index PDA accounts of the recipients.
+pub const RECIPIENT_INDEX_ACCOUNT_SEED: &[u8] = b"recipient_index";
+/// PDA account seed to create the stats PDA accounts of the mints.
+pub const MINT_STATS_ACCOUNT_SEED: &[u8] = b"mint_stats";
+/// PDA account seed to create the global stats PDA account.
+pub const GLOBAL_STATS_ACCOUNT_SEED: &[u8] = b"global_stats";
+/// PDA account seed to create the mint registry PDA account.
+pub const MINT_REGISTRY_ACCOUNT_SEED: &[u8] = b"mint_registry";
+/// PDA account seed to create new allowed mint PDA accounts.
+pub const ALLOWED_MINT_ACCOUNT_SEED: &[u8] = b"allowed_mint";
+
+/// Get the name seed of the stream PDA account.
+///
+/// - v1: names upto [`MAX_STREAM_NAME_SEED_LENGTH`] bytes are used as is. All the streams created before longer names
+///   were supported have such names, so they keep their addresses.
+/// - v2: longer names are hashed using SHA-256, since a PDA seed can be at most 32 bytes.
+pub fn get_stream_name_seed(name: &str) -> Cow<'_, [u8]> {
+    let name = name.as_bytes();
+    if name.len() <= MAX_STREAM_NAME_SEED_LENGTH {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(hash(name).to_bytes().to_vec())
+    }
+}
 
+// The generated handlers of `create_prepaid` and `create_non_prepaid` take all their arguments positionally.
 #[program]
 pub mod superstream {
     //! Module for superstream cpi methods and other utilities.
 
     use super::*;
 
-    /// Create a new prepaid stream.
+    /// Create a new stream. This is the same as `create_prepaid` and `create_non_prepaid`, but takes the options of
+    /// the stream as a single versioned struct, so new options don't change the arguments of the instruction.
     ///
     /// # Arguments
     ///
-    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    /// * `derivation` - Mode used to derive the stream PDA address. Streams scoped to the sender can't be front-run by
+    ///   someone else creating a stream with the same seed and name. See [`StreamDerivation`].
+    /// * `params` - Kind, schedule, permissions and other options of the stream. See [`CreateStreamParams`].
+    ///
+    /// For more information on the other arguments, see fields of the [`Stream`] struct.
+    pub fn create_stream(
+        mut ctx: Context<CreateStream>,
+        seed: u64,
+        name: String,
+        recipient: Pubkey,
+        derivation: StreamDerivation,
+        params: CreateStreamParams,
+    ) -> Result<()> {
+        create(&mut ctx, seed, name, recipient, derivation, params)
+    }
+
+    /// Create a new prepaid stream. This is a thin wrapper around `create_stream` with [`StreamKind::Prepaid`] and the
+    /// defaults of the options added after it: the [`StreamDerivation::Global`] derivation, no permission expiry, no
+    /// arbiter and no recipient acceptance. Use `create_stream` for these options.
+    ///
+    /// # Arguments
+    ///
+    /// * `flow_interval` - Interval in which `flow_rate` tokens are streamed. Pass
+    ///   [`FLOW_RATE_SCALE`](crate::state::FLOW_RATE_SCALE) to make `flow_rate` a fixed-point amount per second. See
+    ///   [`FlowRate`](crate::state::FlowRate).
+    ///
+    /// For more information on the other arguments, see fields of the [`Stream`] struct.
     pub fn create_prepaid(
         mut ctx: Context<Create>,
         seed: u64,
@@ -135,38 +221,57 @@ pub mod superstream {
         anyone_can_withdraw_for_recipient: bool,
         anyone_can_withdraw_for_recipient_at: u64,
     ) -> Result<()> {
+        let params = CreateStreamParamsV1 {
+            kind: StreamKind::Prepaid,
+            schedule: StreamSchedule {
+                starts_at,
+                ends_at,
+                initial_amount,
+                flow_interval,
+                flow_rate,
+            },
+            permissions: StreamPermissions {
+                sender_can_cancel: StreamPermission::new(sender_can_cancel, sender_can_cancel_at, 0),
+                sender_can_change_sender: StreamPermission::new(
+                    sender_can_change_sender,
+                    sender_can_change_sender_at,
+                    0,
+                ),
+                sender_can_pause: StreamPermission::new(sender_can_pause, sender_can_pause_at, 0),
+                recipient_can_resume_pause_by_sender: StreamPermission::new(
+                    recipient_can_resume_pause_by_sender,
+                    recipient_can_resume_pause_by_sender_at,
+                    0,
+                ),
+                anyone_can_withdraw_for_recipient: StreamPermission::new(
+                    anyone_can_withdraw_for_recipient,
+                    anyone_can_withdraw_for_recipient_at,
+                    0,
+                ),
+            },
+            arbiter: Pubkey::default(),
+            requires_recipient_acceptance: false,
+        };
         create(
             &mut ctx,
-            true,
-            recipient,
-            name,
-            starts_at,
-            ends_at,
-            initial_amount,
-            flow_interval,
-            flow_rate,
-            sender_can_cancel,
-            sender_can_cancel_at,
-            sender_can_change_sender,
-            sender_can_change_sender_at,
-            sender_can_pause,
-            sender_can_pause_at,
-            recipient_can_resume_pause_by_sender,
-            recipient_can_resume_pause_by_sender_at,
-            anyone_can_withdraw_for_recipient,
-            anyone_can_withdraw_for_recipient_at,
             seed,
-        )?;
-
-        let stream = &mut ctx.accounts.stream;
-        let prepaid_amount_needed = stream.initialize_prepaid()?;
-        ctx.accounts.transfer_to_escrow(prepaid_amount_needed)
+            name,
+            recipient,
+            StreamDerivation::Global,
+            CreateStreamParams::V1(params),
+        )
     }
 
-    /// Create a new non-prepaid stream.
+    /// Create a new non-prepaid stream. This is a thin wrapper around `create_stream` with [`StreamKind::NonPrepaid`]
+    /// and the defaults of the options added after it: the [`StreamDerivation::Global`] derivation, no permission
+    /// expiry, no arbiter, no recipient acceptance, no insolvency grace period and the whole deposit going to whoever
+    /// cancels an insolvent stream. Use `create_stream` for these options.
     ///
     /// # Arguments
     ///
+    /// * `flow_interval` - Interval in which `flow_rate` tokens are streamed. Pass
+    ///   [`FLOW_RATE_SCALE`](crate::state::FLOW_RATE_SCALE) to make `flow_rate` a fixed-point amount per second. See
+    ///   [`FlowRate`](crate::state::FlowRate).
     /// * `topup_amount` - Initial topup amount for the stream. The topup amount should be >= minimum deposit required.
     ///   See [`DEPOSIT_AMOUNT_PERIOD_IN_SECS`](crate::state::DEPOSIT_AMOUNT_PERIOD_IN_SECS) for more information.
     ///
@@ -193,52 +298,83 @@ pub mod superstream {
         anyone_can_withdraw_for_recipient_at: u64,
         topup_amount: u64,
     ) -> Result<()> {
+        let params = CreateStreamParamsV1 {
+            kind: StreamKind::NonPrepaid {
+                topup_amount,
+                insolvency_grace_period: 0,
+                recipient_deposit_share_bps: 0,
+            },
+            schedule: StreamSchedule {
+                starts_at,
+                ends_at,
+                initial_amount,
+                flow_interval,
+                flow_rate,
+            },
+            permissions: StreamPermissions {
+                sender_can_cancel: StreamPermission::new(sender_can_cancel, sender_can_cancel_at, 0),
+                sender_can_change_sender: StreamPermission::new(
+                    sender_can_change_sender,
+                    sender_can_change_sender_at,
+                    0,
+                ),
+                sender_can_pause: StreamPermission::new(sender_can_pause, sender_can_pause_at, 0),
+                recipient_can_resume_pause_by_sender: StreamPermission::new(
+                    recipient_can_resume_pause_by_sender,
+                    recipient_can_resume_pause_by_sender_at,
+                    0,
+                ),
+                anyone_can_withdraw_for_recipient: StreamPermission::new(
+                    anyone_can_withdraw_for_recipient,
+                    anyone_can_withdraw_for_recipient_at,
+                    0,
+                ),
+            },
+            arbiter: Pubkey::default(),
+            requires_recipient_acceptance: false,
+        };
         create(
             &mut ctx,
-            false,
-            recipient,
-            name,
-            starts_at,
-            ends_at,
-            initial_amount,
-            flow_interval,
-            flow_rate,
-            sender_can_cancel,
-            sender_can_cancel_at,
-            sender_can_change_sender,
-            sender_can_change_sender_at,
-            sender_can_pause,
-            sender_can_pause_at,
-            recipient_can_resume_pause_by_sender,
-            recipient_can_resume_pause_by_sender_at,
-            anyone_can_withdraw_for_recipient,
-            anyone_can_withdraw_for_recipient_at,
             seed,
-        )?;
-
-        let stream = &mut ctx.accounts.stream;
-        stream.initialize_non_prepaid(topup_amount)?;
-        ctx.accounts.transfer_to_escrow(topup_amount)
+            name,
+            recipient,
+            StreamDerivation::Global,
+            CreateStreamParams::V1(params),
+        )
     }
 
-    /// Cancel a stream.
+    /// Cancel a stream. If the stream is pending recipient acceptance, the sender can always cancel it to revoke it and
+    /// recover the full escrow, and the recipient can cancel it to refuse it.
     ///
     /// # Arguments
     ///
     /// For more information on the arguments, see fields of the [`Stream`] struct.
     pub fn cancel(ctx: Context<Cancel>, seed: u64, name: String, recipient: Pubkey) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        require_keys_eq!(recipient, ctx.accounts.stream.recipient, StreamError::InvalidRecipient);
+        cancel_v2(ctx)
+    }
+
+    /// Same as [`cancel`](crate::superstream::cancel), but the seed, name and recipient are read from the stream
+    /// account instead of being passed as arguments.
+    pub fn cancel_v2(ctx: Context<Cancel>) -> Result<()> {
+        let recipient = ctx.accounts.stream.recipient;
         let stream = &mut ctx.accounts.stream;
         let stream_key = stream.to_account_info().key;
-        let bump = stream.bump;
         let params = stream.cancel(*stream_key, &ctx.accounts.signer, recipient)?;
         ctx.accounts
-            .transfer_from_escrow_to_sender(seed, &name, bump, params.transfer_amount_sender)?;
+            .transfer_from_escrow_to_sender(params.transfer_amount_sender)?;
         ctx.accounts
-            .transfer_from_escrow_to_signer(seed, &name, bump, params.transfer_amount_signer)?;
+            .transfer_from_escrow_to_signer(params.transfer_amount_signer)?;
         ctx.accounts
-            .transfer_from_escrow_to_recipient(seed, &name, bump, params.transfer_amount_recipient)?;
-
-        Ok(())
+            .transfer_from_escrow_to_recipient(params.transfer_amount_recipient)?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_cancellation(&params),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))
     }
 
     /// Withdraw excess sender topup from a non-prepaid stream.
@@ -251,34 +387,67 @@ pub mod superstream {
         seed: u64,
         name: String,
     ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        withdraw_excess_topup_non_prepaid_ended_v2(ctx)
+    }
+
+    /// Same as
+    /// [`withdraw_excess_topup_non_prepaid_ended`](crate::superstream::withdraw_excess_topup_non_prepaid_ended), but
+    /// the seed and name are read from the stream account instead of being passed as arguments.
+    pub fn withdraw_excess_topup_non_prepaid_ended_v2(ctx: Context<WithdrawExcessTopupNonPrepaidEnded>) -> Result<()> {
         let stream = &mut ctx.accounts.stream;
         let amount = stream.withdraw_excess_topup_non_prepaid_ended()?;
         if amount > 0 {
-            let bump = stream.bump;
-            ctx.accounts.transfer_from_escrow(seed, &name, bump, amount)?;
+            ctx.accounts.transfer_from_escrow(amount)?;
         }
-        Ok(())
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_refund(amount),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))
     }
 
-    /// Topup a non-prepaid stream.
+    /// Topup a non-prepaid stream. Returns the topup amount accepted by the stream, which is the only amount
+    /// transferred to the escrow account.
     ///
     /// # Arguments
     ///
-    /// * `topup_amount` - Topup amount for the stream. The topup amount should be <= maximum acceptable topup amount.
+    /// * `topup_amount` - Topup amount for the stream. If it is more than the maximum acceptable topup amount, only the
+    ///   maximum acceptable topup amount is accepted.
+    /// * `is_strict` - If true, the topup fails instead if the topup amount is more than the maximum acceptable topup
+    ///   amount.
     ///
     /// For more information on the other arguments, see fields of the [`Stream`] struct.
     pub fn topup_non_prepaid(
         ctx: Context<TopupNonPrepaid>,
-        _seed: u64,
-        _name: String,
+        seed: u64,
+        name: String,
         topup_amount: u64,
-    ) -> Result<()> {
+        is_strict: bool,
+    ) -> Result<u64> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        topup_non_prepaid_v2(ctx, topup_amount, is_strict)
+    }
+
+    /// Same as [`topup_non_prepaid`](crate::superstream::topup_non_prepaid), but the seed and name are read from the
+    /// stream account instead of being passed as arguments.
+    pub fn topup_non_prepaid_v2(ctx: Context<TopupNonPrepaid>, topup_amount: u64, is_strict: bool) -> Result<u64> {
         let stream = &mut ctx.accounts.stream;
-        stream.topup_non_prepaid(topup_amount)?;
-        ctx.accounts.transfer_to_escrow(topup_amount)
+        let accepted_topup_amount = stream.topup_non_prepaid(&ctx.accounts.signer, topup_amount, is_strict)?;
+        ctx.accounts.transfer_to_escrow(accepted_topup_amount)?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_deposit(accepted_topup_amount),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))?;
+        Ok(accepted_topup_amount)
     }
 
-    /// Change sender of a non-prepaid stream.
+    /// Propose to change sender of a non-prepaid stream. The sender changes only when the new sender accepts the change.
     ///
     /// # Arguments
     ///
@@ -287,12 +456,66 @@ pub mod superstream {
     /// For more information on the other arguments, see fields of the [`Stream`] struct.
     pub fn change_sender_non_prepaid(
         ctx: Context<ChangeSenderNonPrepaid>,
-        _seed: u64,
-        _name: String,
+        seed: u64,
+        name: String,
         new_sender: Pubkey,
     ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        change_sender_non_prepaid_v2(ctx, new_sender)
+    }
+
+    /// Same as [`change_sender_non_prepaid`](crate::superstream::change_sender_non_prepaid), but the seed and name are
+    /// read from the stream account instead of being passed as arguments.
+    pub fn change_sender_non_prepaid_v2(ctx: Context<ChangeSenderNonPrepaid>, new_sender: Pubkey) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.change_sender_non_prepaid(&ctx.accounts.sender, new_sender)?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
+
+    /// Accept the proposed sender change of a non-prepaid stream. The signer needs to be the proposed new sender.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn accept_sender_change_non_prepaid(
+        ctx: Context<AcceptSenderChangeNonPrepaid>,
+        seed: u64,
+        name: String,
+    ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        accept_sender_change_non_prepaid_v2(ctx)
+    }
+
+    /// Same as [`accept_sender_change_non_prepaid`](crate::superstream::accept_sender_change_non_prepaid), but the seed
+    /// and name are read from the stream account instead of being passed as arguments.
+    pub fn accept_sender_change_non_prepaid_v2(ctx: Context<AcceptSenderChangeNonPrepaid>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.accept_sender_change_non_prepaid(&ctx.accounts.new_sender)?;
+        ctx.accounts
+            .move_between_indexes(*ctx.bumps.get("new_sender_index").unwrap())?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
+
+    /// Cancel the proposed sender change of a non-prepaid stream. The signer needs to be the current sender.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn cancel_sender_change_non_prepaid(
+        ctx: Context<ChangeSenderNonPrepaid>,
+        seed: u64,
+        name: String,
+    ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        cancel_sender_change_non_prepaid_v2(ctx)
+    }
+
+    /// Same as [`cancel_sender_change_non_prepaid`](crate::superstream::cancel_sender_change_non_prepaid), but the seed
+    /// and name are read from the stream account instead of being passed as arguments.
+    pub fn cancel_sender_change_non_prepaid_v2(ctx: Context<ChangeSenderNonPrepaid>) -> Result<()> {
         let stream = &mut ctx.accounts.stream;
-        stream.change_sender_non_prepaid(&ctx.accounts.sender, new_sender)
+        stream.cancel_sender_change_non_prepaid(&ctx.accounts.sender)?;
+        check_invariants(&ctx.accounts.stream, None)
     }
 
     /// Withdraw recipient funds from a stream.
@@ -306,10 +529,20 @@ pub mod superstream {
         name: String,
         recipient: Pubkey,
     ) -> Result<()> {
-        withdraw_and_change_recipient(ctx, seed, name, recipient, Pubkey::default())
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        require_keys_eq!(recipient, ctx.accounts.stream.recipient, StreamError::InvalidRecipient);
+        withdraw_v2(ctx)
+    }
+
+    /// Same as [`withdraw`](crate::superstream::withdraw), but the seed, name and recipient are read from the stream
+    /// account instead of being passed as arguments.
+    pub fn withdraw_v2(ctx: Context<WithdrawAndChangeRecipient>) -> Result<()> {
+        withdraw_and_change_recipient_v2(ctx, Pubkey::default())
     }
 
-    /// Withdraw recipient funds from a stream and change recipient of a stream.
+    /// Withdraw recipient funds from a stream and propose to change recipient of a stream. The recipient changes only
+    /// when the new recipient accepts the change. Fails if the stream is cancelled, including when the withdrawal
+    /// cancels an insolvent stream.
     ///
     /// # Arguments
     ///
@@ -323,12 +556,104 @@ pub mod superstream {
         recipient: Pubkey,
         new_recipient: Pubkey,
     ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        require_keys_eq!(recipient, ctx.accounts.stream.recipient, StreamError::InvalidRecipient);
+        withdraw_and_change_recipient_v2(ctx, new_recipient)
+    }
+
+    /// Same as [`withdraw_and_change_recipient`](crate::superstream::withdraw_and_change_recipient), but the seed, name
+    /// and recipient are read from the stream account instead of being passed as arguments.
+    pub fn withdraw_and_change_recipient_v2(
+        ctx: Context<WithdrawAndChangeRecipient>,
+        new_recipient: Pubkey,
+    ) -> Result<()> {
+        let recipient = ctx.accounts.stream.recipient;
         let stream = &mut ctx.accounts.stream;
-        let amount_available_to_withdraw =
-            stream.withdraw_and_change_recipient(&ctx.accounts.signer, recipient, new_recipient)?;
-        let bump = stream.bump;
-        ctx.accounts
-            .transfer_from_escrow(seed, &name, bump, amount_available_to_withdraw)
+        let params = stream.withdraw_and_change_recipient(&ctx.accounts.signer, recipient, new_recipient)?;
+        let (recipient_amount, signer_amount) = if ctx.accounts.signer_token.is_some() {
+            ctx.accounts
+                .transfer_from_escrow_to_signer(params.transfer_amount_signer)?;
+            ctx.accounts.transfer_from_escrow(params.transfer_amount_recipient)?;
+            (params.transfer_amount_recipient, params.transfer_amount_signer)
+        } else {
+            // The signer has not provided a token account to receive their reward, so the whole amount goes to the
+            // recipient.
+            let amount = params
+                .transfer_amount_recipient
+                .checked_add(params.transfer_amount_signer)
+                .ok_or(error!(StreamError::WithdrawAmountOutOfBounds))?;
+            ctx.accounts.transfer_from_escrow(amount)?;
+            (amount, 0)
+        };
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_withdrawal(recipient_amount, signer_amount, params.is_insolvency_cancellation),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))
+    }
+
+    /// Accept the proposed recipient change of a stream. The signer needs to be the proposed new recipient. Recipient
+    /// funds owed till now are withdrawn to the current recipient before the change. If the withdrawal cancels an
+    /// insolvent stream, the recipient is not changed and the reward for cancelling it goes to the current recipient.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn accept_recipient_change(
+        ctx: Context<AcceptRecipientChange>,
+        seed: u64,
+        name: String,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        require_keys_eq!(recipient, ctx.accounts.stream.recipient, StreamError::InvalidRecipient);
+        accept_recipient_change_v2(ctx)
+    }
+
+    /// Same as [`accept_recipient_change`](crate::superstream::accept_recipient_change), but the seed, name and
+    /// recipient are read from the stream account instead of being passed as arguments.
+    pub fn accept_recipient_change_v2(ctx: Context<AcceptRecipientChange>) -> Result<()> {
+        let recipient = ctx.accounts.stream.recipient;
+        let stream = &mut ctx.accounts.stream;
+        let params = stream.accept_recipient_change(&ctx.accounts.new_recipient, recipient)?;
+        // The current recipient is the one cancelling an insolvent stream, so any reward belongs to them too.
+        let amount = params
+            .transfer_amount_recipient
+            .checked_add(params.transfer_amount_signer)
+            .ok_or(error!(StreamError::WithdrawAmountOutOfBounds))?;
+        ctx.accounts.transfer_from_escrow(amount)?;
+        // The recipient is left unchanged if the withdrawal has cancelled an insolvent stream.
+        if ctx.accounts.stream.recipient != recipient {
+            ctx.accounts
+                .move_between_indexes(*ctx.bumps.get("new_recipient_index").unwrap())?;
+        }
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_withdrawal(amount, 0, params.is_insolvency_cancellation),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))
+    }
+
+    /// Cancel the proposed recipient change of a stream. The signer needs to be the current recipient.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn cancel_recipient_change(ctx: Context<CancelRecipientChange>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        cancel_recipient_change_v2(ctx)
+    }
+
+    /// Same as [`cancel_recipient_change`](crate::superstream::cancel_recipient_change), but the seed and name are read
+    /// from the stream account instead of being passed as arguments.
+    pub fn cancel_recipient_change_v2(ctx: Context<CancelRecipientChange>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.cancel_recipient_change(&ctx.accounts.recipient)?;
+        check_invariants(&ctx.accounts.stream, None)
     }
 
     /// Pause a non-prepaid stream.
@@ -336,9 +661,17 @@ pub mod superstream {
     /// # Arguments
     ///
     /// For more information on the arguments, see fields of the [`Stream`] struct.
-    pub fn pause_non_prepaid(ctx: Context<PauseNonPrepaid>, _seed: u64, _name: String) -> Result<()> {
+    pub fn pause_non_prepaid(ctx: Context<PauseNonPrepaid>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        pause_non_prepaid_v2(ctx)
+    }
+
+    /// Same as [`pause_non_prepaid`](crate::superstream::pause_non_prepaid), but the seed and name are read from the
+    /// stream account instead of being passed as arguments.
+    pub fn pause_non_prepaid_v2(ctx: Context<PauseNonPrepaid>) -> Result<()> {
         let stream = &mut ctx.accounts.stream;
-        stream.pause_non_prepaid(&ctx.accounts.signer)
+        stream.pause_non_prepaid(&ctx.accounts.signer)?;
+        check_invariants(&ctx.accounts.stream, None)
     }
 
     /// Resume a non-prepaid stream.
@@ -346,167 +679,1293 @@ pub mod superstream {
     /// # Arguments
     ///
     /// For more information on the arguments, see fields of the [`Stream`] struct.
-    pub fn resume_non_prepaid(ctx: Context<ResumeNonPrepaid>, _seed: u64, _name: String) -> Result<()> {
+    pub fn resume_non_prepaid(ctx: Context<ResumeNonPrepaid>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        resume_non_prepaid_v2(ctx)
+    }
+
+    /// Same as [`resume_non_prepaid`](crate::superstream::resume_non_prepaid), but the seed and name are read from the
+    /// stream account instead of being passed as arguments.
+    pub fn resume_non_prepaid_v2(ctx: Context<ResumeNonPrepaid>) -> Result<()> {
         let stream = &mut ctx.accounts.stream;
-        stream.resume_non_prepaid(&ctx.accounts.signer)
+        stream.resume_non_prepaid(&ctx.accounts.signer)?;
+        check_invariants(&ctx.accounts.stream, None)
     }
-}
 
-pub(crate) fn create(
-    ctx: &mut Context<Create>,
-    is_prepaid: bool,
-    recipient: Pubkey,
-    name: String,
-    starts_at: u64,
-    ends_at: u64,
-    initial_amount: u64,
-    flow_interval: u64,
-    flow_rate: u64,
-    sender_can_cancel: bool,
-    sender_can_cancel_at: u64,
-    sender_can_change_sender: bool,
-    sender_can_change_sender_at: u64,
-    sender_can_pause: bool,
-    sender_can_pause_at: u64,
-    recipient_can_resume_pause_by_sender: bool,
-    recipient_can_resume_pause_by_sender_at: u64,
-    anyone_can_withdraw_for_recipient: bool,
-    anyone_can_withdraw_for_recipient_at: u64,
-    seed: u64,
-) -> Result<()> {
-    let escrow_token_account = &ctx.accounts.escrow_token;
-    require!(
-        is_token_account_rent_exempt(escrow_token_account)?,
-        StreamError::EscrowNotRentExempt,
-    );
+    /// Accept a stream pending recipient acceptance. The flow starts at the time of acceptance if the stream was
+    /// scheduled to start earlier.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn accept(ctx: Context<Accept>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        accept_v2(ctx)
+    }
 
-    let stream = &mut ctx.accounts.stream;
-    stream.initialize(
-        is_prepaid,
-        ctx.accounts.mint.key(),
-        ctx.accounts.sender.key(),
-        recipient,
-        name,
-        starts_at,
-        ends_at,
-        initial_amount,
-        flow_interval,
-        flow_rate,
-        sender_can_cancel,
-        sender_can_cancel_at,
-        sender_can_change_sender,
-        sender_can_change_sender_at,
-        sender_can_pause,
-        sender_can_pause_at,
-        recipient_can_resume_pause_by_sender,
-        recipient_can_resume_pause_by_sender_at,
-        anyone_can_withdraw_for_recipient,
-        anyone_can_withdraw_for_recipient_at,
-        seed,
-        *ctx.bumps.get("stream").unwrap(),
-    )
-}
+    /// Same as [`accept`](crate::superstream::accept), but the seed and name are read from the stream account instead
+    /// of being passed as arguments.
+    pub fn accept_v2(ctx: Context<Accept>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.accept(&ctx.accounts.recipient)?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
 
-/// Accounts struct for creating a new stream.
-#[derive(Accounts)]
-#[instruction(seed: u64, name: String)]
-pub struct Create<'info> {
-    /// Stream PDA account. This is initialized by the program.
-    #[account(
-        init,
-        seeds = [
-            STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
-            mint.key().as_ref(),
-            name.as_bytes(),
-        ],
-        payer = sender,
-        space = Stream::space(&name),
-        bump,
-    )]
-    pub stream: Account<'info, Stream>,
+    /// Propose a settlement to terminate a stream. Either the sender or the recipient can propose a settlement, and it
+    /// replaces any previous proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `settlement_amount` - Total amount owed to the recipient on termination, including the amount already
+    ///   withdrawn. Should be >= total withdrawn amount and <= total amount paid by the sender.
+    ///
+    /// For more information on the other arguments, see fields of the [`Stream`] struct.
+    pub fn propose_settlement(
+        ctx: Context<ProposeSettlement>,
+        seed: u64,
+        name: String,
+        settlement_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        propose_settlement_v2(ctx, settlement_amount)
+    }
 
-    /// Stream sender wallet.
-    #[account(mut)]
-    pub sender: Signer<'info>,
-    /// SPL token mint account.
-    pub mint: Box<Account<'info, Mint>>,
+    /// Same as [`propose_settlement`](crate::superstream::propose_settlement), but the seed and name are read from the
+    /// stream account instead of being passed as arguments.
+    pub fn propose_settlement_v2(ctx: Context<ProposeSettlement>, settlement_amount: u64) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.propose_settlement(&ctx.accounts.signer, settlement_amount)?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
 
-    /// Associated token account of the sender.
-    #[account(
-        mut,
-        constraint =
-            sender_token.mint == mint.key()
-            && sender_token.owner == sender.key(),
-    )]
-    pub sender_token: Box<Account<'info, TokenAccount>>,
-    /// Associated token escrow account holding the funds for this stream.
-    #[account(
-        mut,
-        constraint =
-            escrow_token.mint == mint.key()
-            && escrow_token.owner == stream.key(),
-    )]
-    pub escrow_token: Box<Account<'info, TokenAccount>>,
+    /// Cancel the proposed settlement of a stream. The proposer can revoke it and the other party can reject it.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn cancel_settlement_proposal(ctx: Context<ProposeSettlement>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        cancel_settlement_proposal_v2(ctx)
+    }
 
-    /// SPL token program.
-    pub token_program: Program<'info, Token>,
-    /// Solana system program.
-    pub system_program: Program<'info, System>,
-}
+    /// Same as [`cancel_settlement_proposal`](crate::superstream::cancel_settlement_proposal), but the seed and name
+    /// are read from the stream account instead of being passed as arguments.
+    pub fn cancel_settlement_proposal_v2(ctx: Context<ProposeSettlement>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.cancel_settlement_proposal(&ctx.accounts.signer)?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
 
-/// Accounts struct for cancelling a stream.
-#[derive(Accounts)]
-#[instruction(seed: u64, name: String, recipient: Pubkey)]
-pub struct Cancel<'info> {
-    /// Stream PDA account.
-    #[account(
-        mut,
-        seeds = [
-            STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
-            mint.key().as_ref(),
-            name.as_bytes(),
-        ],
-        bump,
-    )]
-    pub stream: Account<'info, Stream>,
+    /// Accept the proposed settlement of a stream and terminate it. The escrow is split according to the settlement and
+    /// the stream is marked cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `settlement_amount` - The proposed settlement amount. Should be equal to the amount stored on the stream.
+    ///
+    /// For more information on the other arguments, see fields of the [`Stream`] struct.
+    pub fn terminate_with_settlement(
+        ctx: Context<TerminateWithSettlement>,
+        seed: u64,
+        name: String,
+        recipient: Pubkey,
+        settlement_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        require_keys_eq!(recipient, ctx.accounts.stream.recipient, StreamError::InvalidRecipient);
+        terminate_with_settlement_v2(ctx, settlement_amount)
+    }
 
-    /// Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent. After insolvency,
-    /// anyone can cancel.
-    pub signer: Signer<'info>,
+    /// Same as [`terminate_with_settlement`](crate::superstream::terminate_with_settlement), but the seed, name and
+    /// recipient are read from the stream account instead of being passed as arguments.
+    pub fn terminate_with_settlement_v2(ctx: Context<TerminateWithSettlement>, settlement_amount: u64) -> Result<()> {
+        let recipient = ctx.accounts.stream.recipient;
+        let stream = &mut ctx.accounts.stream;
+        let params = stream.terminate_with_settlement(&ctx.accounts.signer, recipient, settlement_amount)?;
+        ctx.accounts
+            .transfer_from_escrow_to_sender(params.transfer_amount_sender)?;
+        ctx.accounts
+            .transfer_from_escrow_to_recipient(params.transfer_amount_recipient)?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_cancellation(&params),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))
+    }
 
-    /// Stream sender account.
+    /// Freeze a stream in case of a dispute. Only the arbiter of the stream can freeze it. Withdrawals, cancellations,
+    /// settlements and refunds are halted till the stream is unfrozen or resolved.
     ///
-    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the funds
-    /// to the stream sender.
-    #[account(constraint = sender.key() == stream.sender)]
-    pub sender: UncheckedAccount<'info>,
-    /// SPL token mint account.
-    pub mint: Box<Account<'info, Mint>>,
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn freeze(ctx: Context<Freeze>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        freeze_v2(ctx)
+    }
 
-    /// Associated token account of the signer.
-    #[account(
-        mut,
-        constraint =
-            signer_token.mint == mint.key()
-            && signer_token.owner == signer.key(),
-    )]
-    pub signer_token: Box<Account<'info, TokenAccount>>,
-    /// Associated token account of the sender.
-    #[account(
-        mut,
-        constraint =
-            sender_token.mint == mint.key()
-            && sender_token.owner == sender.key(),
-    )]
+    /// Same as [`freeze`](crate::superstream::freeze), but the seed and name are read from the stream account instead
+    /// of being passed as arguments.
+    pub fn freeze_v2(ctx: Context<Freeze>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.freeze(&ctx.accounts.arbiter)?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
+
+    /// Unfreeze a frozen stream. Only the arbiter of the stream can unfreeze it.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn unfreeze(ctx: Context<Freeze>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        unfreeze_v2(ctx)
+    }
+
+    /// Same as [`unfreeze`](crate::superstream::unfreeze), but the seed and name are read from the stream account
+    /// instead of being passed as arguments.
+    pub fn unfreeze_v2(ctx: Context<Freeze>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        stream.unfreeze(&ctx.accounts.arbiter)?;
+        check_invariants(&ctx.accounts.stream, None)
+    }
+
+    /// Resolve the dispute of a frozen stream. Only the arbiter of the stream can resolve it. The escrow is split
+    /// according to the resolution and the stream is marked cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient_amount` - Total amount owed to the recipient, including the amount already withdrawn. Should be >=
+    ///   total withdrawn amount and <= total amount paid by the sender. The rest is returned to the sender.
+    ///
+    /// For more information on the other arguments, see fields of the [`Stream`] struct.
+    pub fn resolve(
+        ctx: Context<Resolve>,
+        seed: u64,
+        name: String,
+        recipient: Pubkey,
+        recipient_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        require_keys_eq!(recipient, ctx.accounts.stream.recipient, StreamError::InvalidRecipient);
+        resolve_v2(ctx, recipient_amount)
+    }
+
+    /// Same as [`resolve`](crate::superstream::resolve), but the seed, name and recipient are read from the stream
+    /// account instead of being passed as arguments.
+    pub fn resolve_v2(ctx: Context<Resolve>, recipient_amount: u64) -> Result<()> {
+        let recipient = ctx.accounts.stream.recipient;
+        let stream = &mut ctx.accounts.stream;
+        let params = stream.resolve(&ctx.accounts.arbiter, recipient, recipient_amount)?;
+        ctx.accounts
+            .transfer_from_escrow_to_sender(params.transfer_amount_sender)?;
+        ctx.accounts
+            .transfer_from_escrow_to_recipient(params.transfer_amount_recipient)?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_cancellation(&params),
+        )?;
+        check_invariants(&ctx.accounts.stream, Some(&mut ctx.accounts.escrow_token))
+    }
+
+    /// Close a stream which has stopped and has nothing left to withdraw or refund. The signer needs to be the sender
+    /// or the recipient. The stream is removed from the stream indexes, and the rent of the stream and escrow accounts
+    /// is returned to the sender. Any tokens sent to the escrow account directly are transferred to the sender.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn close(ctx: Context<Close>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        close_v2(ctx)
+    }
+
+    /// Same as [`close`](crate::superstream::close), but the seed and name are read from the stream account instead of
+    /// being passed as arguments.
+    pub fn close_v2(ctx: Context<Close>) -> Result<()> {
+        let stream = &ctx.accounts.stream;
+        stream.validate_close(&ctx.accounts.signer)?;
+        ctx.accounts.close_escrow()?;
+        ctx.accounts.remove_from_indexes()?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            ctx.accounts.stream.is_counted_in_mint_stats,
+            |stats| stats.record_close(ctx.accounts.stream.is_cancelled),
+        )
+    }
+
+    /// Migrate a stream in the legacy account layout to the newest layout. The stream account is reallocated and the
+    /// payer pays for the extra rent. Anyone can migrate a stream as it doesn't change the state of the stream.
+    ///
+    /// Streams in the legacy layout can be used with all the instructions, but the features introduced with the newest
+    /// layout, e.g. settlements or recipient changes, need the stream to be migrated first.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn migrate_stream(ctx: Context<MigrateStream>, seed: u64, name: String) -> Result<()> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        migrate_stream_v2(ctx)
+    }
+
+    /// Same as [`migrate_stream`](crate::superstream::migrate_stream), but the seed and name are read from the stream
+    /// account instead of being passed as arguments.
+    pub fn migrate_stream_v2(ctx: Context<MigrateStream>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        require!(stream.is_legacy(), StreamError::StreamAlreadyMigrated);
+        stream.version = STREAM_LAYOUT_VERSION;
+        check_invariants(&ctx.accounts.stream, None)
+    }
+
+    /// Convert a stream to the zero-copy layout. The signer needs to be the sender or the recipient and pays the rent of
+    /// the extra space. Zero-copy streams can only be used with `withdraw_zero_copy`, `topup_non_prepaid_zero_copy` and
+    /// `cancel_zero_copy` and need to be converted back for everything else. See [`ZeroCopyStream`].
+    pub fn convert_stream_to_zero_copy(ctx: Context<ConvertStreamToZeroCopy>) -> Result<()> {
+        convert_to_zero_copy(&ctx.accounts.stream, &ctx.accounts.signer, &ctx.accounts.system_program)
+    }
+
+    /// Convert a zero-copy stream back to the newest stream layout. The signer needs to be the sender or the
+    /// recipient.
+    pub fn convert_stream_from_zero_copy(ctx: Context<ConvertStreamFromZeroCopy>) -> Result<()> {
+        convert_from_zero_copy(&ctx.accounts.stream, &ctx.accounts.signer, &ctx.accounts.system_program)
+    }
+
+    /// Withdraw recipient funds from a zero-copy stream. See [`withdraw`](crate::superstream::withdraw).
+    pub fn withdraw_zero_copy(ctx: Context<WithdrawZeroCopy>) -> Result<()> {
+        let (params, snapshot, is_counted_in_mint_stats) = {
+            let mut stream = ctx.accounts.stream.load_mut()?;
+            let snapshot = StreamSnapshot::new(&stream);
+            let params = stream.withdraw(&ctx.accounts.signer)?;
+            (params, snapshot, stream.is_counted_in_mint_stats())
+        };
+        let (recipient_amount, signer_amount) = if ctx.accounts.signer_token.is_some() {
+            ctx.accounts
+                .transfer_from_escrow_to_signer(params.transfer_amount_signer)?;
+            ctx.accounts.transfer_from_escrow(params.transfer_amount_recipient)?;
+            (params.transfer_amount_recipient, params.transfer_amount_signer)
+        } else {
+            // The signer has not provided a token account to receive their reward, so the whole amount goes to the
+            // recipient.
+            let amount = params
+                .transfer_amount_recipient
+                .checked_add(params.transfer_amount_signer)
+                .ok_or(error!(StreamError::WithdrawAmountOutOfBounds))?;
+            ctx.accounts.transfer_from_escrow(amount)?;
+            (amount, 0)
+        };
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            is_counted_in_mint_stats,
+            |stats| stats.record_withdrawal(recipient_amount, signer_amount, params.is_insolvency_cancellation),
+        )?;
+        check_zero_copy_invariants(&ctx.accounts.stream, snapshot, Some(&mut ctx.accounts.escrow_token))
+    }
+
+    /// Topup a non-prepaid zero-copy stream. See [`topup_non_prepaid`](crate::superstream::topup_non_prepaid).
+    pub fn topup_non_prepaid_zero_copy(
+        ctx: Context<TopupNonPrepaidZeroCopy>,
+        topup_amount: u64,
+        is_strict: bool,
+    ) -> Result<u64> {
+        let (accepted_topup_amount, snapshot, is_counted_in_mint_stats) = {
+            let mut stream = ctx.accounts.stream.load_mut()?;
+            let snapshot = StreamSnapshot::new(&stream);
+            let accepted_topup_amount = stream.topup_non_prepaid(&ctx.accounts.signer, topup_amount, is_strict)?;
+            (accepted_topup_amount, snapshot, stream.is_counted_in_mint_stats())
+        };
+        ctx.accounts.transfer_to_escrow(accepted_topup_amount)?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            is_counted_in_mint_stats,
+            |stats| stats.record_deposit(accepted_topup_amount),
+        )?;
+        check_zero_copy_invariants(&ctx.accounts.stream, snapshot, Some(&mut ctx.accounts.escrow_token))?;
+        Ok(accepted_topup_amount)
+    }
+
+    /// Cancel a zero-copy stream. See [`cancel`](crate::superstream::cancel).
+    pub fn cancel_zero_copy(ctx: Context<CancelZeroCopy>) -> Result<()> {
+        let (params, snapshot, is_counted_in_mint_stats) = {
+            let mut stream = ctx.accounts.stream.load_mut()?;
+            let snapshot = StreamSnapshot::new(&stream);
+            let params = stream.cancel(&ctx.accounts.signer)?;
+            (params, snapshot, stream.is_counted_in_mint_stats())
+        };
+        ctx.accounts
+            .transfer_from_escrow_to_sender(params.transfer_amount_sender)?;
+        ctx.accounts
+            .transfer_from_escrow_to_signer(params.transfer_amount_signer)?;
+        ctx.accounts
+            .transfer_from_escrow_to_recipient(params.transfer_amount_recipient)?;
+        update_stats(
+            &ctx.accounts.mint_stats,
+            &ctx.accounts.global_stats,
+            is_counted_in_mint_stats,
+            |stats| stats.record_cancellation(&params),
+        )?;
+        check_zero_copy_invariants(&ctx.accounts.stream, snapshot, Some(&mut ctx.accounts.escrow_token))
+    }
+
+    /// Initialize the mint registry. Only the upgrade authority of the program can initialize the registry and they
+    /// become the authority of the registry.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`MintRegistry`] struct.
+    pub fn initialize_mint_registry(ctx: Context<InitializeMintRegistry>, is_enabled: bool) -> Result<()> {
+        let mint_registry = &mut ctx.accounts.mint_registry;
+        mint_registry.initialize(
+            ctx.accounts.authority.key(),
+            is_enabled,
+            *ctx.bumps.get("mint_registry").unwrap(),
+        );
+        Ok(())
+    }
+
+    /// Enable or disable the mint registry. When disabled, streams can be created for any mint.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`MintRegistry`] struct.
+    pub fn set_mint_registry_enabled(ctx: Context<UpdateMintRegistry>, is_enabled: bool) -> Result<()> {
+        let mint_registry = &mut ctx.accounts.mint_registry;
+        mint_registry.set_enabled(is_enabled);
+        Ok(())
+    }
+
+    /// Change the authority of the mint registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_authority` - The new authority
+    pub fn change_mint_registry_authority(ctx: Context<UpdateMintRegistry>, new_authority: Pubkey) -> Result<()> {
+        let mint_registry = &mut ctx.accounts.mint_registry;
+        mint_registry.change_authority(new_authority)
+    }
+
+    /// Add a mint to the mint registry.
+    pub fn add_allowed_mint(ctx: Context<AddAllowedMint>) -> Result<()> {
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        allowed_mint.initialize(ctx.accounts.mint.key(), *ctx.bumps.get("allowed_mint").unwrap())
+    }
+
+    /// Remove a mint from the mint registry. Existing streams of the mint are not affected.
+    pub fn remove_allowed_mint(_ctx: Context<RemoveAllowedMint>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Get the withdrawable amount of a stream at the current time. The result is returned through the return data of
+    /// the transaction. Programs calling this through CPI can decode it using `get()` on the returned value.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn get_withdrawable(ctx: Context<ViewStream>, seed: u64, name: String) -> Result<WithdrawableView> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        get_withdrawable_v2(ctx)
+    }
+
+    /// Same as [`get_withdrawable`](crate::superstream::get_withdrawable), but the seed and name are read from the
+    /// stream account instead of being passed as arguments.
+    pub fn get_withdrawable_v2(ctx: Context<ViewStream>) -> Result<WithdrawableView> {
+        ctx.accounts.stream.get_withdrawable_view(get_current_timestamp()?)
+    }
+
+    /// Get the solvency of a stream at the current time. The result is returned through the return data of the
+    /// transaction. Programs calling this through CPI can decode it using `get()` on the returned value.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn get_solvency(ctx: Context<ViewStream>, seed: u64, name: String) -> Result<SolvencyView> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        get_solvency_v2(ctx)
+    }
+
+    /// Same as [`get_solvency`](crate::superstream::get_solvency), but the seed and name are read from the stream
+    /// account instead of being passed as arguments.
+    pub fn get_solvency_v2(ctx: Context<ViewStream>) -> Result<SolvencyView> {
+        ctx.accounts.stream.get_solvency_view(get_current_timestamp()?)
+    }
+
+    /// Get the maximum acceptable topup amount of a stream at the current time. The result is returned through the
+    /// return data of the transaction. Programs calling this through CPI can decode it using `get()` on the returned
+    /// value.
+    ///
+    /// # Arguments
+    ///
+    /// For more information on the arguments, see fields of the [`Stream`] struct.
+    pub fn get_max_topup(ctx: Context<ViewStream>, seed: u64, name: String) -> Result<MaxTopupView> {
+        ctx.accounts.stream.validate_seed_and_name(seed, &name)?;
+        get_max_topup_v2(ctx)
+    }
+
+    /// Same as [`get_max_topup`](crate::superstream::get_max_topup), but the seed and name are read from the stream
+    /// account instead of being passed as arguments.
+    pub fn get_max_topup_v2(ctx: Context<ViewStream>) -> Result<MaxTopupView> {
+        ctx.accounts.stream.get_max_topup_view(get_current_timestamp()?)
+    }
+}
+
+/// Create a stream. Shared by all the create instructions, see [`CreateAccounts`].
+pub(crate) fn create<'info, T: CreateAccounts<'info>>(
+    ctx: &mut Context<'_, '_, '_, '_, T>,
+    seed: u64,
+    name: String,
+    recipient: Pubkey,
+    derivation: StreamDerivation,
+    params: CreateStreamParams,
+) -> Result<()> {
+    let CreateStreamParams::V1(params) = params;
+
+    require!(
+        is_token_account_rent_exempt(ctx.accounts.escrow_token())?,
+        StreamError::EscrowNotRentExempt,
+    );
+    require!(ctx.accounts.is_mint_allowed()?, StreamError::MintNotAllowed);
+
+    let mint = ctx.accounts.mint();
+    let sender = ctx.accounts.sender();
+    let stream = ctx.accounts.stream();
+    stream.initialize(
+        mint,
+        sender,
+        recipient,
+        name,
+        &params,
+        derivation,
+        seed,
+        *ctx.bumps.get("stream").unwrap(),
+    )?;
+
+    ctx.accounts.add_to_indexes(
+        *ctx.bumps.get("sender_index").unwrap(),
+        *ctx.bumps.get("recipient_index").unwrap(),
+    )?;
+
+    let stream = ctx.accounts.stream();
+    let deposited_amount = match params.kind {
+        StreamKind::Prepaid => stream.initialize_prepaid()?,
+        StreamKind::NonPrepaid {
+            topup_amount,
+            insolvency_grace_period,
+            recipient_deposit_share_bps,
+        } => {
+            stream.initialize_non_prepaid(topup_amount, insolvency_grace_period, recipient_deposit_share_bps)?;
+            topup_amount
+        },
+    };
+    ctx.accounts.transfer_to_escrow(deposited_amount)?;
+    ctx.accounts.record_creation(
+        *ctx.bumps.get("mint_stats").unwrap(),
+        *ctx.bumps.get("global_stats").unwrap(),
+        deposited_amount,
+    )?;
+    ctx.accounts.check_invariants()
+}
+
+/// Accounts struct for creating a new stream.
+#[derive(Accounts)]
+#[instruction(seed: u64, name: String, recipient: Pubkey, derivation: StreamDerivation)]
+pub struct CreateStream<'info> {
+    /// Stream PDA account. This is initialized by the program.
+    #[account(
+        init,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&name).as_ref(),
+            derivation.sender_seed(sender.key),
+            derivation.recipient_seed(&recipient),
+        ],
+        payer = sender,
+        space = Stream::space(&name),
+        bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Stream sender wallet.
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
+    pub sender_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Mint registry PDA account. It might not have been initialized, in which case streams can be created for any
+    /// mint.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is deserialized only if it has been initialized.
+    #[account(seeds = [MINT_REGISTRY_ACCOUNT_SEED], bump)]
+    pub mint_registry: UncheckedAccount<'info>,
+    /// Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.
+    ///
+    /// CHECK: The seeds constraint verifies the address. Only the existence of the account is checked.
+    #[account(
+        seeds = [
+            ALLOWED_MINT_ACCOUNT_SEED,
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub allowed_mint: UncheckedAccount<'info>,
+
+    /// Stream index PDA account of the sender. It is created if it doesn't exist.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is created and deserialized by the program.
+    #[account(
+        mut,
+        seeds = [
+            SENDER_INDEX_ACCOUNT_SEED,
+            sender.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sender_index: UncheckedAccount<'info>,
+    /// Stream index PDA account of the recipient. It is created if it doesn't exist.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is created and deserialized by the program.
+    #[account(
+        mut,
+        seeds = [
+            RECIPIENT_INDEX_ACCOUNT_SEED,
+            recipient.as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_index: UncheckedAccount<'info>,
+
+    /// Stats PDA account of the mint. It is created if it doesn't exist.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account. It is created if it doesn't exist.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for creating a new stream with the [`StreamDerivation::Global`] derivation. Used by the create
+/// instructions which don't take the derivation as an argument.
+#[derive(Accounts)]
+#[instruction(seed: u64, name: String, recipient: Pubkey)]
+pub struct Create<'info> {
+    /// Stream PDA account. This is initialized by the program.
+    #[account(
+        init,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&name).as_ref(),
+        ],
+        payer = sender,
+        space = Stream::space(&name),
+        bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Stream sender wallet.
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
+    pub sender_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Mint registry PDA account. It might not have been initialized, in which case streams can be created for any
+    /// mint.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is deserialized only if it has been initialized.
+    #[account(seeds = [MINT_REGISTRY_ACCOUNT_SEED], bump)]
+    pub mint_registry: UncheckedAccount<'info>,
+    /// Allowed mint PDA account. It is only needed to be initialized if the mint registry is enabled.
+    ///
+    /// CHECK: The seeds constraint verifies the address. Only the existence of the account is checked.
+    #[account(
+        seeds = [
+            ALLOWED_MINT_ACCOUNT_SEED,
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub allowed_mint: UncheckedAccount<'info>,
+
+    /// Stream index PDA account of the sender. It is created if it doesn't exist.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is created and deserialized by the program.
+    #[account(
+        mut,
+        seeds = [
+            SENDER_INDEX_ACCOUNT_SEED,
+            sender.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sender_index: UncheckedAccount<'info>,
+    /// Stream index PDA account of the recipient. It is created if it doesn't exist.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is created and deserialized by the program.
+    #[account(
+        mut,
+        seeds = [
+            RECIPIENT_INDEX_ACCOUNT_SEED,
+            recipient.as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_index: UncheckedAccount<'info>,
+
+    /// Stats PDA account of the mint. It is created if it doesn't exist.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account. It is created if it doesn't exist.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for cancelling a stream.
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the
+    /// insolvency grace period. After that, anyone can cancel.
+    pub signer: Signer<'info>,
+
+    /// Stream sender account.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the funds
+    /// to the stream sender.
+    #[account(constraint = sender.key() == stream.sender)]
+    pub sender: UncheckedAccount<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the signer.
+    #[account(
+        mut,
+        constraint =
+            signer_token.mint == mint.key()
+            && signer_token.owner == signer.key(),
+    )]
+    pub signer_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
+    pub sender_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token account of the recipient.
+    #[account(
+        mut,
+        constraint =
+            recipient_token.mint == mint.key()
+            && recipient_token.owner == stream.recipient,
+    )]
+    pub recipient_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts struct for withdrawing excess sender topup from a non-prepaid stream.
+#[derive(Accounts)]
+pub struct WithdrawExcessTopupNonPrepaidEnded<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet.
+    pub signer: Signer<'info>,
+
+    /// Stream sender account.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the funds
+    /// to the stream sender.
+    #[account(constraint = sender.key() == stream.sender)]
+    pub sender: UncheckedAccount<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
+    pub sender_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts struct for topping up a non-prepaid stream.
+#[derive(Accounts)]
+pub struct TopupNonPrepaid<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But
+    /// the refund when the stream gets cancelled will only go to the stream sender.
+    pub signer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+
+    /// Associated token account of the signer.
+    #[account(
+        mut,
+        constraint =
+            signer_token.mint == mint.key()
+            && signer_token.owner == signer.key(),
+    )]
+    pub signer_token: Account<'info, TokenAccount>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Account<'info, TokenAccount>,
+
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts struct for changing the sender of a non-prepaid stream.
+#[derive(Accounts)]
+pub struct ChangeSenderNonPrepaid<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    // Stream sender wallet.
+    pub sender: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for accepting the proposed sender change of a non-prepaid stream.
+#[derive(Accounts)]
+pub struct AcceptSenderChangeNonPrepaid<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Proposed new sender wallet. It pays for the extra space in its stream index account.
+    #[account(mut)]
+    pub new_sender: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+
+    /// Current stream sender account. The rent freed from its stream index account is returned to it.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the
+    /// lamports to the stream sender.
+    #[account(mut, constraint = sender.key() == stream.sender)]
+    pub sender: UncheckedAccount<'info>,
+    /// Stream index PDA account of the current sender.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is deserialized only if it has been initialized.
+    #[account(
+        mut,
+        seeds = [
+            SENDER_INDEX_ACCOUNT_SEED,
+            stream.sender.as_ref(),
+        ],
+        bump,
+    )]
+    pub sender_index: UncheckedAccount<'info>,
+    /// Stream index PDA account of the new sender. It is created if it doesn't exist.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is created and deserialized by the program.
+    #[account(
+        mut,
+        seeds = [
+            SENDER_INDEX_ACCOUNT_SEED,
+            new_sender.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub new_sender_index: UncheckedAccount<'info>,
+
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for withdrawing recipient funds from a stream and changing recipient of a stream.
+#[derive(Accounts)]
+pub struct WithdrawAndChangeRecipient<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the
+    /// signer. The recipient token account must be owned by the recipient stored in the stream, so if a malicious user
+    /// tries to send themselves as a recipient, the constraint for the recipient token account will fail.
+    pub signer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels
+    /// an insolvent stream. If not provided, the whole deposit goes to the recipient.
+    #[account(
+        mut,
+        constraint =
+            signer_token.mint == mint.key()
+            && signer_token.owner == signer.key(),
+    )]
+    pub signer_token: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Associated token account of the recipient.
+    #[account(
+        mut,
+        constraint =
+            recipient_token.mint == mint.key()
+            && recipient_token.owner == stream.recipient,
+    )]
+    pub recipient_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts struct for accepting the proposed recipient change of a stream.
+#[derive(Accounts)]
+pub struct AcceptRecipientChange<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Proposed new recipient wallet. It pays for the extra space in its stream index account.
+    #[account(mut)]
+    pub new_recipient: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Current stream recipient account. The rent freed from its stream index account is returned to it.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the
+    /// lamports to the stream recipient.
+    #[account(mut, constraint = current_recipient.key() == stream.recipient)]
+    pub current_recipient: UncheckedAccount<'info>,
+
+    /// Associated token account of the current recipient.
+    #[account(
+        mut,
+        constraint =
+            recipient_token.mint == mint.key()
+            && recipient_token.owner == stream.recipient,
+    )]
+    pub recipient_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Stream index PDA account of the current recipient.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is deserialized only if it has been initialized.
+    #[account(
+        mut,
+        seeds = [
+            RECIPIENT_INDEX_ACCOUNT_SEED,
+            stream.recipient.as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_index: UncheckedAccount<'info>,
+    /// Stream index PDA account of the new recipient. It is created if it doesn't exist.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is created and deserialized by the program.
+    #[account(
+        mut,
+        seeds = [
+            RECIPIENT_INDEX_ACCOUNT_SEED,
+            new_recipient.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub new_recipient_index: UncheckedAccount<'info>,
+
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for cancelling the proposed recipient change of a stream.
+#[derive(Accounts)]
+pub struct CancelRecipientChange<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Stream recipient wallet.
+    pub recipient: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for pausing a non-prepaid stream.
+#[derive(Accounts)]
+pub struct PauseNonPrepaid<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient.
+    pub signer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for resuming a non-prepaid stream.
+#[derive(Accounts)]
+pub struct ResumeNonPrepaid<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if
+    /// the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender).
+    pub signer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for accepting a stream pending recipient acceptance.
+#[derive(Accounts)]
+pub struct Accept<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Stream recipient wallet.
+    pub recipient: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for proposing or cancelling a settlement of a stream.
+#[derive(Accounts)]
+pub struct ProposeSettlement<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Signer needs to be either the sender or the recipient.
+    pub signer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for terminating a stream with a settlement.
+#[derive(Accounts)]
+pub struct TerminateWithSettlement<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Signer needs to be the party which did not propose the settlement.
+    pub signer: Signer<'info>,
+
+    /// Stream sender account.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the funds
+    /// to the stream sender.
+    #[account(constraint = sender.key() == stream.sender)]
+    pub sender: UncheckedAccount<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
     pub sender_token: Box<Account<'info, TokenAccount>>,
     /// Associated token account of the recipient.
     #[account(
         mut,
         constraint =
             recipient_token.mint == mint.key()
-            && recipient_token.owner == recipient,
+            && recipient_token.owner == stream.recipient,
     )]
     pub recipient_token: Box<Account<'info, TokenAccount>>,
     /// Associated token escrow account holding the funds for this stream.
@@ -518,29 +1977,63 @@ pub struct Cancel<'info> {
     )]
     pub escrow_token: Box<Account<'info, TokenAccount>>,
 
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
     /// SPL token program.
     pub token_program: Program<'info, Token>,
 }
 
-/// Accounts struct for withdrawing excess sender topup from a non-prepaid stream.
+/// Accounts struct for freezing or unfreezing a stream.
 #[derive(Accounts)]
-#[instruction(seed: u64, name: String)]
-pub struct WithdrawExcessTopupNonPrepaidEnded<'info> {
+pub struct Freeze<'info> {
     /// Stream PDA account.
     #[account(
         mut,
         seeds = [
             STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
+            stream.seed.to_le_bytes().as_ref(),
             mint.key().as_ref(),
-            name.as_bytes(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
         ],
-        bump,
+        bump = stream.bump,
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: Account<'info, VersionedStream>,
 
-    /// Signer wallet.
-    pub signer: Signer<'info>,
+    /// Arbiter wallet.
+    pub arbiter: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+}
+
+/// Accounts struct for resolving the dispute of a frozen stream.
+#[derive(Accounts)]
+pub struct Resolve<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Arbiter wallet.
+    pub arbiter: Signer<'info>,
 
     /// Stream sender account.
     ///
@@ -559,6 +2052,14 @@ pub struct WithdrawExcessTopupNonPrepaidEnded<'info> {
             && sender_token.owner == sender.key(),
     )]
     pub sender_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token account of the recipient.
+    #[account(
+        mut,
+        constraint =
+            recipient_token.mint == mint.key()
+            && recipient_token.owner == stream.recipient,
+    )]
+    pub recipient_token: Box<Account<'info, TokenAccount>>,
     /// Associated token escrow account holding the funds for this stream.
     #[account(
         mut,
@@ -568,41 +2069,237 @@ pub struct WithdrawExcessTopupNonPrepaidEnded<'info> {
     )]
     pub escrow_token: Box<Account<'info, TokenAccount>>,
 
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
     /// SPL token program.
     pub token_program: Program<'info, Token>,
 }
 
-/// Accounts struct for topping up a non-prepaid stream.
+/// Accounts struct for closing a stream.
 #[derive(Accounts)]
-#[instruction(seed: u64, name: String)]
-pub struct TopupNonPrepaid<'info> {
-    /// Stream PDA account.
+pub struct Close<'info> {
+    /// Stream PDA account. This is closed by the program and the rent is returned to the sender.
     #[account(
         mut,
         seeds = [
             STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
+            stream.seed.to_le_bytes().as_ref(),
             mint.key().as_ref(),
-            name.as_bytes(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+        close = sender,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Signer wallet. Either the sender or the recipient can close the stream.
+    pub signer: Signer<'info>,
+
+    /// Stream sender account.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the funds
+    /// to the stream sender.
+    #[account(mut, constraint = sender.key() == stream.sender)]
+    pub sender: UncheckedAccount<'info>,
+    /// Stream recipient account. The rent freed from its stream index account is returned to it.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the
+    /// lamports to the stream recipient.
+    #[account(mut, constraint = recipient.key() == stream.recipient)]
+    pub recipient: UncheckedAccount<'info>,
+    /// SPL token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
+    pub sender_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token escrow account holding the funds for this stream. This is closed by the program and the rent
+    /// is returned to the sender.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Box<Account<'info, TokenAccount>>,
+
+    /// Stream index PDA account of the sender.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is deserialized only if it has been initialized.
+    #[account(
+        mut,
+        seeds = [
+            SENDER_INDEX_ACCOUNT_SEED,
+            stream.sender.as_ref(),
         ],
         bump,
     )]
-    pub stream: Account<'info, Stream>,
+    pub sender_index: UncheckedAccount<'info>,
+    /// Stream index PDA account of the recipient.
+    ///
+    /// CHECK: The seeds constraint verifies the address. The account is deserialized only if it has been initialized.
+    #[account(
+        mut,
+        seeds = [
+            RECIPIENT_INDEX_ACCOUNT_SEED,
+            stream.recipient.as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_index: UncheckedAccount<'info>,
+
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts struct for migrating a stream to the newest account layout.
+#[derive(Accounts)]
+pub struct MigrateStream<'info> {
+    /// Stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+        realloc = Stream::space(&stream.name),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub stream: Account<'info, VersionedStream>,
+
+    /// Payer of the extra rent of the stream account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for converting a stream to the zero-copy layout.
+#[derive(Accounts)]
+pub struct ConvertStreamToZeroCopy<'info> {
+    /// Stream PDA account in any of the [`VersionedStream`] layouts.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            stream.mint.as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Box<Account<'info, ConvertibleStream>>,
+
+    /// Signer wallet. Signer needs to be either the sender or the recipient. They pay the rent of the extra space.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for converting a zero-copy stream back to the newest stream layout.
+#[derive(Accounts)]
+pub struct ConvertStreamFromZeroCopy<'info> {
+    /// Zero-copy stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            stream.mint.as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+        ],
+        bump = stream.bump,
+    )]
+    pub stream: Box<Account<'info, ConvertibleZeroCopyStream>>,
+
+    /// Signer wallet. Signer needs to be either the sender or the recipient.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for withdrawing recipient funds from a zero-copy stream.
+#[derive(Accounts)]
+pub struct WithdrawZeroCopy<'info> {
+    /// Zero-copy stream PDA account.
+    #[account(
+        mut,
+        seeds = [
+            STREAM_ACCOUNT_SEED,
+            stream.load()?.seed.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+            get_stream_name_seed(stream.load()?.name()?).as_ref(),
+            stream.load()?.sender_seed(),
+            stream.load()?.recipient_seed(),
+        ],
+        bump = stream.load()?.bump,
+    )]
+    pub stream: AccountLoader<'info, ZeroCopyStream>,
 
-    /// Signer wallet. Anyone can topup a stream. But the refund when the stream gets cancelled will only go to the
-    /// stream sender.
+    /// Signer wallet. Anybody can call the withdraw method. The withdrawn amount always goes to the recipient of the
+    /// stream.
     pub signer: Signer<'info>,
     /// SPL token mint account.
     pub mint: Account<'info, Mint>,
 
-    /// Associated token account of the signer.
+    /// Associated token account of the signer. It receives the signer's share of the deposit if the withdrawal cancels
+    /// an insolvent stream. If not provided, the whole deposit goes to the recipient.
     #[account(
         mut,
         constraint =
             signer_token.mint == mint.key()
             && signer_token.owner == signer.key(),
     )]
-    pub signer_token: Account<'info, TokenAccount>,
+    pub signer_token: Option<Account<'info, TokenAccount>>,
+
+    /// Associated token account of the recipient.
+    #[account(
+        mut,
+        constraint =
+            recipient_token.mint == mint.key()
+            && recipient_token.owner == stream.load()?.recipient,
+    )]
+    pub recipient_token: Account<'info, TokenAccount>,
     /// Associated token escrow account holding the funds for this stream.
     #[account(
         mut,
@@ -612,64 +2309,126 @@ pub struct TopupNonPrepaid<'info> {
     )]
     pub escrow_token: Account<'info, TokenAccount>,
 
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
     /// SPL token program.
     pub token_program: Program<'info, Token>,
 }
 
-/// Accounts struct for changing the sender of a non-prepaid stream.
+/// Accounts struct for topping up a non-prepaid zero-copy stream.
 #[derive(Accounts)]
-#[instruction(seed: u64, name: String)]
-pub struct ChangeSenderNonPrepaid<'info> {
-    /// Stream PDA account.
+pub struct TopupNonPrepaidZeroCopy<'info> {
+    /// Zero-copy stream PDA account.
     #[account(
         mut,
         seeds = [
             STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
+            stream.load()?.seed.to_le_bytes().as_ref(),
             mint.key().as_ref(),
-            name.as_bytes(),
+            get_stream_name_seed(stream.load()?.name()?).as_ref(),
+            stream.load()?.sender_seed(),
+            stream.load()?.recipient_seed(),
         ],
-        bump,
+        bump = stream.load()?.bump,
+    )]
+    pub stream: AccountLoader<'info, ZeroCopyStream>,
+
+    /// Signer wallet. Anyone can topup a stream, except within the insolvency grace period when only the sender can. But
+    /// the refund when the stream gets cancelled will only go to the stream sender.
+    pub signer: Signer<'info>,
+    /// SPL token mint account.
+    pub mint: Account<'info, Mint>,
+
+    /// Associated token account of the signer.
+    #[account(
+        mut,
+        constraint =
+            signer_token.mint == mint.key()
+            && signer_token.owner == signer.key(),
     )]
-    pub stream: Account<'info, Stream>,
+    pub signer_token: Account<'info, TokenAccount>,
+    /// Associated token escrow account holding the funds for this stream.
+    #[account(
+        mut,
+        constraint =
+            escrow_token.mint == mint.key()
+            && escrow_token.owner == stream.key(),
+    )]
+    pub escrow_token: Account<'info, TokenAccount>,
 
-    // Stream sender wallet.
-    pub sender: Signer<'info>,
-    /// SPL token mint account.
-    pub mint: Account<'info, Mint>,
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// SPL token program.
+    pub token_program: Program<'info, Token>,
 }
 
-/// Accounts struct for withdrawing recipient funds from a stream and changing recipient of a stream.
+/// Accounts struct for cancelling a zero-copy stream.
 #[derive(Accounts)]
-#[instruction(seed: u64, name: String, recipient: Pubkey)]
-pub struct WithdrawAndChangeRecipient<'info> {
-    /// Stream PDA account.
+pub struct CancelZeroCopy<'info> {
+    /// Zero-copy stream PDA account.
     #[account(
         mut,
         seeds = [
             STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
+            stream.load()?.seed.to_le_bytes().as_ref(),
             mint.key().as_ref(),
-            name.as_bytes(),
+            get_stream_name_seed(stream.load()?.name()?).as_ref(),
+            stream.load()?.sender_seed(),
+            stream.load()?.recipient_seed(),
         ],
-        bump,
+        bump = stream.load()?.bump,
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: AccountLoader<'info, ZeroCopyStream>,
 
-    /// Signer wallet. Anybody can call the withdraw method. The recipient of the withdrawn amount is not related to the
-    /// signer. Recipient is passed as an argument, based on which the stream PDA is accessed, so if a malicious user
-    /// tries to send themselves as a recipient, but a different stream account, the constraint for the stream account
-    /// will fail.
+    /// Signer wallet. Either the sender or the receiver can cancel the stream till it's solvent or within the
+    /// insolvency grace period. After that, anyone can cancel.
     pub signer: Signer<'info>,
+
+    /// Stream sender account.
+    ///
+    /// CHECK: Only 1 check is needed which is in the constraint. That is enough to verify that we are sending the funds
+    /// to the stream sender.
+    #[account(constraint = sender.key() == stream.load()?.sender)]
+    pub sender: UncheckedAccount<'info>,
     /// SPL token mint account.
     pub mint: Box<Account<'info, Mint>>,
 
+    /// Associated token account of the signer.
+    #[account(
+        mut,
+        constraint =
+            signer_token.mint == mint.key()
+            && signer_token.owner == signer.key(),
+    )]
+    pub signer_token: Box<Account<'info, TokenAccount>>,
+    /// Associated token account of the sender.
+    #[account(
+        mut,
+        constraint =
+            sender_token.mint == mint.key()
+            && sender_token.owner == sender.key(),
+    )]
+    pub sender_token: Box<Account<'info, TokenAccount>>,
     /// Associated token account of the recipient.
     #[account(
         mut,
         constraint =
             recipient_token.mint == mint.key()
-            && recipient_token.owner == recipient,
+            && recipient_token.owner == stream.load()?.recipient,
     )]
     pub recipient_token: Box<Account<'info, TokenAccount>>,
     /// Associated token escrow account holding the funds for this stream.
@@ -681,67 +2440,280 @@ pub struct WithdrawAndChangeRecipient<'info> {
     )]
     pub escrow_token: Box<Account<'info, TokenAccount>>,
 
+    /// Stats PDA account of the mint.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [MINT_STATS_ACCOUNT_SEED, mint.key().as_ref()], bump)]
+    pub mint_stats: UncheckedAccount<'info>,
+    /// Global stats PDA account.
+    /// CHECK: The seeds constraint verifies the address. See the `stats` module for how the account is used.
+    #[account(mut, seeds = [GLOBAL_STATS_ACCOUNT_SEED], bump)]
+    pub global_stats: UncheckedAccount<'info>,
+
     /// SPL token program.
     pub token_program: Program<'info, Token>,
 }
 
-/// Accounts struct for pausing a non-prepaid stream.
+/// Accounts struct for initializing the mint registry.
 #[derive(Accounts)]
-#[instruction(seed: u64, name: String)]
-pub struct PauseNonPrepaid<'info> {
-    /// Stream PDA account.
+pub struct InitializeMintRegistry<'info> {
+    /// Mint registry PDA account. This is initialized by the program.
+    #[account(
+        init,
+        seeds = [MINT_REGISTRY_ACCOUNT_SEED],
+        payer = authority,
+        space = MintRegistry::LENGTH,
+        bump,
+    )]
+    pub mint_registry: Account<'info, MintRegistry>,
+
+    /// Upgrade authority of the program. They become the authority of the mint registry.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Superstream program.
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, program::Superstream>,
+    /// Program data account of the Superstream program.
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()))]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts struct for updating the mint registry.
+#[derive(Accounts)]
+pub struct UpdateMintRegistry<'info> {
+    /// Mint registry PDA account.
     #[account(
         mut,
+        seeds = [MINT_REGISTRY_ACCOUNT_SEED],
+        bump = mint_registry.bump,
+        has_one = authority,
+    )]
+    pub mint_registry: Account<'info, MintRegistry>,
+
+    /// Authority of the mint registry.
+    pub authority: Signer<'info>,
+}
+
+/// Accounts struct for adding a mint to the mint registry.
+#[derive(Accounts)]
+pub struct AddAllowedMint<'info> {
+    /// Mint registry PDA account.
+    #[account(
+        seeds = [MINT_REGISTRY_ACCOUNT_SEED],
+        bump = mint_registry.bump,
+        has_one = authority,
+    )]
+    pub mint_registry: Account<'info, MintRegistry>,
+    /// Allowed mint PDA account. This is initialized by the program.
+    #[account(
+        init,
         seeds = [
-            STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
+            ALLOWED_MINT_ACCOUNT_SEED,
             mint.key().as_ref(),
-            name.as_bytes(),
         ],
+        payer = authority,
+        space = AllowedMint::LENGTH,
         bump,
     )]
-    pub stream: Account<'info, Stream>,
+    pub allowed_mint: Account<'info, AllowedMint>,
 
-    /// Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient.
-    pub signer: Signer<'info>,
+    /// Authority of the mint registry.
+    #[account(mut)]
+    pub authority: Signer<'info>,
     /// SPL token mint account.
     pub mint: Account<'info, Mint>,
+
+    /// Solana system program.
+    pub system_program: Program<'info, System>,
 }
 
-/// Accounts struct for resuming a non-prepaid stream.
+/// Accounts struct for removing a mint from the mint registry.
 #[derive(Accounts)]
-#[instruction(seed: u64, name: String)]
-pub struct ResumeNonPrepaid<'info> {
-    /// Stream PDA account.
+pub struct RemoveAllowedMint<'info> {
+    /// Mint registry PDA account.
+    #[account(
+        seeds = [MINT_REGISTRY_ACCOUNT_SEED],
+        bump = mint_registry.bump,
+        has_one = authority,
+    )]
+    pub mint_registry: Account<'info, MintRegistry>,
+    /// Allowed mint PDA account. This is closed by the program and the rent is returned to the authority.
     #[account(
         mut,
+        seeds = [
+            ALLOWED_MINT_ACCOUNT_SEED,
+            allowed_mint.mint.as_ref(),
+        ],
+        bump = allowed_mint.bump,
+        close = authority,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    /// Authority of the mint registry.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts struct for reading figures of a stream.
+#[derive(Accounts)]
+pub struct ViewStream<'info> {
+    /// Stream PDA account.
+    #[account(
         seeds = [
             STREAM_ACCOUNT_SEED,
-            seed.to_le_bytes().as_ref(),
+            stream.seed.to_le_bytes().as_ref(),
             mint.key().as_ref(),
-            name.as_bytes(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
         ],
-        bump,
+        bump = stream.bump,
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: Account<'info, VersionedStream>,
 
-    /// Signer wallet. Signer needs to be either the sender (if they are allowed to) or the recipient (exception is if
-    /// the stream was paused by the sender and recipient is not allowed to resume a stream paused by sender).
-    pub signer: Signer<'info>,
     /// SPL token mint account.
     pub mint: Account<'info, Mint>,
 }
 
-impl<'info> Create<'info> {
+/// Accounts of the instructions creating a stream. [`CreateStream`] and [`Create`] only differ in how the stream
+/// PDA is derived, so they share [`create`] through this trait.
+pub(crate) trait CreateAccounts<'info> {
+    /// Stream PDA account being created.
+    fn stream(&mut self) -> &mut Account<'info, VersionedStream>;
+
+    /// Associated token escrow account holding the funds for this stream.
+    fn escrow_token(&mut self) -> &mut Account<'info, TokenAccount>;
+
+    /// Stream sender wallet.
+    fn sender(&self) -> Pubkey;
+
+    /// SPL token mint account.
+    fn mint(&self) -> Pubkey;
+
+    /// Check if streams can be created for the mint. If the mint registry has not been initialized or is disabled, all
+    /// mints are allowed. Otherwise, the allowed mint account for the mint needs to exist.
+    fn is_mint_allowed(&self) -> Result<bool>;
+
     /// Transfer funds from the associated token account of the sender to associated token escrow account holding the
     /// funds for this stream.
-    pub fn transfer_to_escrow(&self, amount: u64) -> Result<()> {
-        transfer_to_escrow(
-            &self.sender,
-            &self.sender_token,
-            &self.escrow_token,
-            &self.token_program,
-            amount,
+    fn transfer_to_escrow(&self, amount: u64) -> Result<()>;
+
+    /// Record the stream and its initial deposit in the stats of the mint and the global stats. The sender pays for the
+    /// stats accounts which don't exist.
+    fn record_creation(&self, mint_stats_bump: u8, global_stats_bump: u8, deposited_amount: u64) -> Result<()>;
+
+    /// Add the stream to the stream indexes of the sender and the recipient. The sender pays for the extra space.
+    fn add_to_indexes(&self, sender_index_bump: u8, recipient_index_bump: u8) -> Result<()>;
+
+    /// Check the invariants of the new stream and its escrow.
+    fn check_invariants(&mut self) -> Result<()>;
+}
+
+/// Implement [`CreateAccounts`] for an accounts struct with the same fields as [`CreateStream`].
+macro_rules! impl_create_accounts {
+    ($accounts:ident) => {
+        impl<'info> CreateAccounts<'info> for $accounts<'info> {
+            fn stream(&mut self) -> &mut Account<'info, VersionedStream> {
+                &mut self.stream
+            }
+
+            fn escrow_token(&mut self) -> &mut Account<'info, TokenAccount> {
+                &mut self.escrow_token
+            }
+
+            fn sender(&self) -> Pubkey {
+                self.sender.key()
+            }
+
+            fn mint(&self) -> Pubkey {
+                self.mint.key()
+            }
+
+            fn is_mint_allowed(&self) -> Result<bool> {
+                if self.mint_registry.owner != &ID || self.mint_registry.data_is_empty() {
+                    return Ok(true);
+                }
+
+                let mint_registry = MintRegistry::try_deserialize(&mut &self.mint_registry.data.borrow()[..])?;
+                Ok(!mint_registry.is_enabled || (self.allowed_mint.owner == &ID && !self.allowed_mint.data_is_empty()))
+            }
+
+            fn transfer_to_escrow(&self, amount: u64) -> Result<()> {
+                transfer_to_escrow(
+                    &self.sender,
+                    &self.sender_token,
+                    &self.escrow_token,
+                    &self.token_program,
+                    amount,
+                )
+            }
+
+            fn record_creation(&self, mint_stats_bump: u8, global_stats_bump: u8, deposited_amount: u64) -> Result<()> {
+                record_creation(
+                    &self.mint_stats,
+                    self.mint.key(),
+                    mint_stats_bump,
+                    deposited_amount,
+                    &self.sender,
+                    &self.system_program,
+                )?;
+                record_global_creation(
+                    &self.global_stats,
+                    global_stats_bump,
+                    deposited_amount,
+                    &self.sender,
+                    &self.system_program,
+                )
+            }
+
+            fn add_to_indexes(&self, sender_index_bump: u8, recipient_index_bump: u8) -> Result<()> {
+                let stream_key = self.stream.key();
+                add_to_index(
+                    &self.sender_index,
+                    SENDER_INDEX_ACCOUNT_SEED,
+                    self.sender.key(),
+                    sender_index_bump,
+                    stream_key,
+                    &self.sender,
+                    &self.system_program,
+                )?;
+                add_to_index(
+                    &self.recipient_index,
+                    RECIPIENT_INDEX_ACCOUNT_SEED,
+                    self.stream.recipient,
+                    recipient_index_bump,
+                    stream_key,
+                    &self.sender,
+                    &self.system_program,
+                )
+            }
+
+            fn check_invariants(&mut self) -> Result<()> {
+                check_invariants(&self.stream, Some(&mut self.escrow_token))
+            }
+        }
+    };
+}
+
+impl_create_accounts!(CreateStream);
+impl_create_accounts!(Create);
+
+impl<'info> AcceptSenderChangeNonPrepaid<'info> {
+    /// Move the stream from the stream index of the current sender to the stream index of the new sender.
+    pub fn move_between_indexes(&self, new_sender_index_bump: u8) -> Result<()> {
+        let stream_key = self.stream.key();
+        remove_from_index(&self.sender_index, &stream_key, &self.sender)?;
+        add_to_index(
+            &self.new_sender_index,
+            SENDER_INDEX_ACCOUNT_SEED,
+            self.new_sender.key(),
+            new_sender_index_bump,
+            stream_key,
+            &self.new_sender,
+            &self.system_program,
         )
     }
 }
@@ -749,39 +2721,28 @@ impl<'info> Create<'info> {
 impl<'info> Cancel<'info> {
     /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
     /// token account of the sender.
-    pub fn transfer_from_escrow_to_sender(&self, seed: u64, name: &str, bump: u8, amount: u64) -> Result<()> {
-        self.transfer_from_escrow(&self.sender_token, seed, name, bump, amount)
+    pub fn transfer_from_escrow_to_sender(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.sender_token, amount)
     }
 
     /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
     /// token account of the signer.
-    pub fn transfer_from_escrow_to_signer(&self, seed: u64, name: &str, bump: u8, amount: u64) -> Result<()> {
-        self.transfer_from_escrow(&self.signer_token, seed, name, bump, amount)
+    pub fn transfer_from_escrow_to_signer(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.signer_token, amount)
     }
 
     /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
     /// token account of the recipient.
-    pub fn transfer_from_escrow_to_recipient(&self, seed: u64, name: &str, bump: u8, amount: u64) -> Result<()> {
-        self.transfer_from_escrow(&self.recipient_token, seed, name, bump, amount)
+    pub fn transfer_from_escrow_to_recipient(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.recipient_token, amount)
     }
 
-    fn transfer_from_escrow(
-        &self,
-        destination_token: &Account<'info, TokenAccount>,
-        seed: u64,
-        name: &str,
-        bump: u8,
-        amount: u64,
-    ) -> Result<()> {
+    fn transfer_from_escrow(&self, destination_token: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
         transfer_from_escrow(
             &self.stream,
             destination_token,
             &self.escrow_token,
             &self.token_program,
-            seed,
-            &self.mint.key(),
-            name,
-            bump,
             amount,
         )
     }
@@ -790,16 +2751,12 @@ impl<'info> Cancel<'info> {
 impl<'info> WithdrawExcessTopupNonPrepaidEnded<'info> {
     /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
     /// token account of the sender.
-    fn transfer_from_escrow(&self, seed: u64, name: &str, bump: u8, amount: u64) -> Result<()> {
+    fn transfer_from_escrow(&self, amount: u64) -> Result<()> {
         transfer_from_escrow(
             &self.stream,
             &self.sender_token,
             &self.escrow_token,
             &self.token_program,
-            seed,
-            &self.mint.key(),
-            name,
-            bump,
             amount,
         )
     }
@@ -820,19 +2777,207 @@ impl<'info> TopupNonPrepaid<'info> {
 }
 
 impl<'info> WithdrawAndChangeRecipient<'info> {
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the signer. Does nothing if the signer token account is not provided.
+    pub fn transfer_from_escrow_to_signer(&self, amount: u64) -> Result<()> {
+        match &self.signer_token {
+            Some(signer_token) => transfer_from_escrow(
+                &self.stream,
+                signer_token,
+                &self.escrow_token,
+                &self.token_program,
+                amount,
+            ),
+            None => Ok(()),
+        }
+    }
+
     /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
     /// token account of the recipient.
-    pub fn transfer_from_escrow(&self, seed: u64, name: &str, bump: u8, amount: u64) -> Result<()> {
+    pub fn transfer_from_escrow(&self, amount: u64) -> Result<()> {
         transfer_from_escrow(
             &self.stream,
             &self.recipient_token,
             &self.escrow_token,
             &self.token_program,
-            seed,
-            &self.mint.key(),
-            name,
-            bump,
             amount,
         )
     }
 }
+
+impl<'info> WithdrawZeroCopy<'info> {
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the signer. Does nothing if the signer token account is not provided.
+    pub fn transfer_from_escrow_to_signer(&self, amount: u64) -> Result<()> {
+        match &self.signer_token {
+            Some(signer_token) => transfer_from_zero_copy_escrow(
+                &self.stream,
+                signer_token,
+                &self.escrow_token,
+                &self.token_program,
+                amount,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the recipient.
+    pub fn transfer_from_escrow(&self, amount: u64) -> Result<()> {
+        transfer_from_zero_copy_escrow(
+            &self.stream,
+            &self.recipient_token,
+            &self.escrow_token,
+            &self.token_program,
+            amount,
+        )
+    }
+}
+
+impl<'info> TopupNonPrepaidZeroCopy<'info> {
+    /// Transfer funds from the associated token account of the signer to associated token escrow account holding the
+    /// funds for this stream.
+    pub fn transfer_to_escrow(&self, amount: u64) -> Result<()> {
+        transfer_to_escrow(
+            &self.signer,
+            &self.signer_token,
+            &self.escrow_token,
+            &self.token_program,
+            amount,
+        )
+    }
+}
+
+impl<'info> CancelZeroCopy<'info> {
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the sender.
+    pub fn transfer_from_escrow_to_sender(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.sender_token, amount)
+    }
+
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the signer.
+    pub fn transfer_from_escrow_to_signer(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.signer_token, amount)
+    }
+
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the recipient.
+    pub fn transfer_from_escrow_to_recipient(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.recipient_token, amount)
+    }
+
+    fn transfer_from_escrow(&self, destination_token: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        transfer_from_zero_copy_escrow(
+            &self.stream,
+            destination_token,
+            &self.escrow_token,
+            &self.token_program,
+            amount,
+        )
+    }
+}
+
+impl<'info> AcceptRecipientChange<'info> {
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the current recipient.
+    pub fn transfer_from_escrow(&self, amount: u64) -> Result<()> {
+        transfer_from_escrow(
+            &self.stream,
+            &self.recipient_token,
+            &self.escrow_token,
+            &self.token_program,
+            amount,
+        )
+    }
+
+    /// Move the stream from the stream index of the current recipient to the stream index of the new recipient.
+    pub fn move_between_indexes(&self, new_recipient_index_bump: u8) -> Result<()> {
+        let stream_key = self.stream.key();
+        remove_from_index(&self.recipient_index, &stream_key, &self.current_recipient)?;
+        add_to_index(
+            &self.new_recipient_index,
+            RECIPIENT_INDEX_ACCOUNT_SEED,
+            self.new_recipient.key(),
+            new_recipient_index_bump,
+            stream_key,
+            &self.new_recipient,
+            &self.system_program,
+        )
+    }
+}
+
+impl<'info> TerminateWithSettlement<'info> {
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the sender.
+    pub fn transfer_from_escrow_to_sender(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.sender_token, amount)
+    }
+
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the recipient.
+    pub fn transfer_from_escrow_to_recipient(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.recipient_token, amount)
+    }
+
+    fn transfer_from_escrow(&self, destination_token: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        transfer_from_escrow(
+            &self.stream,
+            destination_token,
+            &self.escrow_token,
+            &self.token_program,
+            amount,
+        )
+    }
+}
+
+impl<'info> Resolve<'info> {
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the sender.
+    pub fn transfer_from_escrow_to_sender(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.sender_token, amount)
+    }
+
+    /// Transfer funds from the associated token escrow account holding the funds for this stream to the associated
+    /// token account of the recipient.
+    pub fn transfer_from_escrow_to_recipient(&self, amount: u64) -> Result<()> {
+        self.transfer_from_escrow(&self.recipient_token, amount)
+    }
+
+    fn transfer_from_escrow(&self, destination_token: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        transfer_from_escrow(
+            &self.stream,
+            destination_token,
+            &self.escrow_token,
+            &self.token_program,
+            amount,
+        )
+    }
+}
+
+impl<'info> Close<'info> {
+    /// Transfer any funds left in the associated token escrow account to the associated token account of the sender
+    /// and close the escrow account.
+    pub fn close_escrow(&self) -> Result<()> {
+        transfer_from_escrow(
+            &self.stream,
+            &self.sender_token,
+            &self.escrow_token,
+            &self.token_program,
+            self.escrow_token.amount,
+        )?;
+        close_escrow(
+            &self.stream,
+            &self.sender.to_account_info(),
+            &self.escrow_token,
+            &self.token_program,
+        )
+    }
+
+    /// Remove the stream from the stream indexes of the sender and the recipient.
+    pub fn remove_from_indexes(&self) -> Result<()> {
+        let stream_key = self.stream.key();
+        remove_from_index(&self.sender_index, &stream_key, &self.sender)?;
+        remove_from_index(&self.recipient_index, &stream_key, &self.recipient)
+    }
+}
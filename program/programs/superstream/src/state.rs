@@ -2,7 +2,8 @@
 
 use std::cmp::min;
 
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
+use superstream_math::{get_permission_times, MathError, StreamState};
 
 use crate::{error::StreamError, utils::get_current_timestamp};
 
@@ -10,30 +11,201 @@ const ANCHOR_DISCRIMINATOR_LENGTH: usize = 8;
 
 const BOOL_LENGTH: usize = 1;
 const U8_LENGTH: usize = 1;
+const U16_LENGTH: usize = 2;
 const U64_LENGTH: usize = 8;
+const U128_LENGTH: usize = 16;
 const PUBLIC_KEY_LENGTH: usize = 32;
 const STRING_LENGTH_PREFIX: usize = 4;
+const VEC_LENGTH_PREFIX: usize = 4;
 
-/// Minimum length of a stream name.
+/// Minimum length of a stream name in unicode chars.
 pub const MIN_STREAM_NAME_LENGTH: usize = 2;
-/// Maximum length of a stream name.
+/// Maximum length of a stream name in unicode chars.
 pub const MAX_STREAM_NAME_LENGTH: usize = 100;
+/// Maximum length of a stream name in bytes, i.e. 4 bytes for each of the [`MAX_STREAM_NAME_LENGTH`] chars. Kept a
+/// literal since the IDL generator can only resolve array lengths that are plain constants.
+pub const MAX_STREAM_NAME_BYTES: usize = 400;
+const _: () = assert!(MAX_STREAM_NAME_BYTES == 4 * MAX_STREAM_NAME_LENGTH);
+
+/// Layout version of the stream accounts created before layout versions were introduced. The version byte of these
+/// accounts is the first byte of the zeroed reserved space which followed `deposit_needed`.
+pub const LEGACY_STREAM_LAYOUT_VERSION: u8 = 0;
+/// Layout version of the stream accounts created by this version of the program.
+pub const STREAM_LAYOUT_VERSION: u8 = 1;
+
+pub use superstream_math::{
+    CancelTransferParams, FlowRate, WithdrawTransferParams, BPS_DENOMINATOR, DEPOSIT_AMOUNT_PERIOD_IN_SECS,
+    FLOW_RATE_SCALE,
+};
+
+/// Mode used to derive the address of a stream PDA account.
+///
+/// The seeds of the stream PDA account are `[STREAM_ACCOUNT_SEED, seed, mint, name_seed, sender_seed,
+/// recipient_seed]`. The sender and recipient seeds are empty if they are not part of the derivation, so global streams
+/// keep their addresses.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StreamDerivation {
+    /// The address is derived from (seed, mint, name). Anyone who knows these can create a stream at the same address
+    /// before the sender does.
+    #[default]
+    Global,
+    /// The address is also derived from the sender, so only the sender can create a stream at this address.
+    Sender,
+    /// The address is also derived from the sender and the recipient.
+    SenderAndRecipient,
+}
+
+impl StreamDerivation {
+    /// Returns true if the sender is part of the stream PDA seeds.
+    pub fn includes_sender(self) -> bool {
+        matches!(self, Self::Sender | Self::SenderAndRecipient)
+    }
+
+    /// Returns true if the recipient is part of the stream PDA seeds.
+    pub fn includes_recipient(self) -> bool {
+        matches!(self, Self::SenderAndRecipient)
+    }
+
+    /// Get the sender seed of the stream PDA account. It is empty if the sender is not part of the seeds.
+    pub fn sender_seed(self, sender: &Pubkey) -> &[u8] {
+        if self.includes_sender() {
+            sender.as_ref()
+        } else {
+            &[]
+        }
+    }
+
+    /// Get the recipient seed of the stream PDA account. It is empty if the recipient is not part of the seeds.
+    pub fn recipient_seed(self, recipient: &Pubkey) -> &[u8] {
+        if self.includes_recipient() {
+            recipient.as_ref()
+        } else {
+            &[]
+        }
+    }
+}
 
-/// Deposit amount period (in seconds) for a non-prepaid stream. If a non-prepaid stream has unlimited lifetime or
-/// lifetime >= DEPOSIT_AMOUNT_PERIOD_IN_SECS, a security deposit is taken from the sender which would not be returned
-/// in case the stream becomes insolvent. This is done to make sure users keep topping up their streams on time.
-pub const DEPOSIT_AMOUNT_PERIOD_IN_SECS: u64 = 8 * 60 * 60; // 8 hrs
+/// Parameters of a new stream passed to the `create_stream` instruction. Every version is a separate variant, so new
+/// options can be added in a new version without breaking the callers of the older versions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum CreateStreamParams {
+    /// Version 1 of the parameters.
+    V1(CreateStreamParamsV1),
+}
+
+/// Version 1 of [`CreateStreamParams`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreateStreamParamsV1 {
+    /// Kind of the stream along with the options specific to the kind.
+    pub kind: StreamKind,
+    /// Schedule and amounts of the streaming payments.
+    pub schedule: StreamSchedule,
+    /// Permissions given by the sender.
+    pub permissions: StreamPermissions,
+    /// Arbiter of the stream. [`Pubkey::default()`] means no arbiter. See [`Stream::arbiter`].
+    pub arbiter: Pubkey,
+    /// If true, the stream only starts flowing once the recipient accepts it.
+    pub requires_recipient_acceptance: bool,
+}
+
+/// Kind of a stream.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamKind {
+    /// All the required amount is deposited on creation. Prepaid streams need an end time.
+    Prepaid,
+    /// The stream is topped up over time and a security deposit is taken from the sender.
+    NonPrepaid {
+        /// Initial topup amount for the stream. Should be >= the initial amount + 2 * the deposit needed. See
+        /// [`DEPOSIT_AMOUNT_PERIOD_IN_SECS`].
+        topup_amount: u64,
+        /// Active streaming time after insolvency during which only the sender can cure the insolvency. Should be <=
+        /// [`DEPOSIT_AMOUNT_PERIOD_IN_SECS`].
+        insolvency_grace_period: u64,
+        /// Share of the deposit (in basis points) given to the recipient if the stream is cancelled after becoming
+        /// insolvent. The rest is given to whoever cancels the stream.
+        recipient_deposit_share_bps: u16,
+    },
+}
+
+impl StreamKind {
+    /// Returns true if the stream is prepaid.
+    pub fn is_prepaid(&self) -> bool {
+        matches!(self, Self::Prepaid)
+    }
+}
+
+/// Schedule and amounts of the streaming payments of a stream. See the fields of the same name on [`Stream`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamSchedule {
+    /// Start time of the stream. A time in the past means the stream starts right away.
+    pub starts_at: u64,
+    /// End time of the stream. Can be 0 for non-prepaid streams to indicate no end time.
+    pub ends_at: u64,
+    /// Amount available to the recipient once stream starts.
+    pub initial_amount: u64,
+    /// Interval in which `flow_rate` tokens are streamed. [`FLOW_RATE_SCALE`] makes `flow_rate` a fixed-point amount per
+    /// second. See [`FlowRate`].
+    pub flow_interval: u64,
+    /// Number of tokens to stream per interval.
+    pub flow_rate: u64,
+}
+
+/// Permissions given by the sender of a stream. See the fields of the same name on [`Stream`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamPermissions {
+    /// Permission of the sender to cancel a solvent stream.
+    pub sender_can_cancel: StreamPermission,
+    /// Permission of the sender to change the sender.
+    pub sender_can_change_sender: StreamPermission,
+    /// Permission of the sender to pause the stream.
+    pub sender_can_pause: StreamPermission,
+    /// Permission of the recipient to resume a stream paused by the sender.
+    pub recipient_can_resume_pause_by_sender: StreamPermission,
+    /// Permission of anyone to withdraw on behalf of the recipient.
+    pub anyone_can_withdraw_for_recipient: StreamPermission,
+}
+
+/// A permission given on a stream, optionally unlocking and expiring at specific times.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamPermission {
+    /// If true, the permission is given.
+    pub is_allowed: bool,
+    /// Time from which the permission is active. 0 or a time in the past means it is active right away.
+    pub allowed_at: u64,
+    /// Time after which the permission is no longer active. 0 means it never expires.
+    pub expires_at: u64,
+}
+
+impl StreamPermission {
+    /// Create a permission from the positional arguments of the `create_prepaid` and `create_non_prepaid`
+    /// instructions.
+    pub fn new(is_allowed: bool, allowed_at: u64, expires_at: u64) -> Self {
+        Self {
+            is_allowed,
+            allowed_at,
+            expires_at,
+        }
+    }
+
+    /// Get the unlock and expiry times of the permission to store on a stream. Both are 0 if the permission is not
+    /// given.
+    fn get_times(&self) -> Result<(u64, u64)> {
+        get_permission_times(self.is_allowed, self.allowed_at, self.expires_at).map_err(math_error)
+    }
+}
 
 /// A payment stream with support for SPL tokens, prepaid and limited upfront payment, unlimited lifetime, cliffs and
 /// cancellations.
 ///
 /// Possible states of a stream:
 /// - Not started
+///     - Pending recipient acceptance
 ///     - Scheduled
 ///     - Cancelled before start
 /// - Started but not stopped
 ///     - Streaming
 ///     - Paused
+///     - Insolvent
 /// - Stopped
 ///     - Cancelled after start
 ///     - Ended
@@ -65,7 +237,8 @@ pub struct Stream {
 
     /// Amount available to the recipient once stream starts.
     pub initial_amount: u64,
-    /// Flow interval is the interval in which flow payments are released.
+    /// Flow interval is the interval in which flow payments are released. If == FLOW_RATE_SCALE, the flow rate is a
+    /// fixed-point amount per second. See [`FlowRate`].
     pub flow_interval: u64,
     /// Flow rate is the number of tokens to stream per interval.
     pub flow_rate: u64,
@@ -89,7 +262,7 @@ pub struct Stream {
 
     /// True if a solvent stream can be cancelled by the sender.
     pub sender_can_cancel: bool,
-    /// Time at which the sender is allowed to cancel a solvent stream.
+    /// Time at which the sender is allowed to cancel a solvent stream. This can be in the future.
     pub sender_can_cancel_at: u64,
 
     /// True if the sender can change the sender of the stream who will do the upcoming topups.
@@ -167,24 +340,128 @@ pub struct Stream {
     pub last_topup_amount: u64,
 
     /// Total deposit amount needed for the non-prepaid stream. These are needed in case the sender does not topup the
-    /// stream in time and the amount owed becomes > total topup amount. When that happens, anyone can cancel the
-    /// stream. The deposit amount will be distributed as a reward to whoever finds the insolvency and cancels the
-    /// stream.
+    /// stream in time and the amount owed becomes > total topup amount. When that happens and the insolvency grace
+    /// period is over, anyone can cancel the stream. The deposit amount will be split between the recipient and
+    /// whoever finds the insolvency and cancels the stream as a reward.
     ///
     /// INVARIANT: prepaid: == 0
     /// INVARIANT: unbounded: == DEPOSIT_AMOUNT_PERIOD_IN_SECS of streaming payments
     pub deposit_needed: u64,
+    /// Layout version of the stream account. It is at the same offset in all the layouts, so that the layout of an
+    /// account can be found before deserializing it. See [`VersionedStream`].
+    ///
+    /// INVARIANT: == LEGACY_STREAM_LAYOUT_VERSION || == STREAM_LAYOUT_VERSION
+    pub version: u8,
+    /// Active (!is_paused) streaming time after the stream becomes insolvent during which only the sender can cure the
+    /// insolvency by topping up. The flow keeps accruing as debt which is covered by the deposit. Only after this
+    /// period, the stream can be cancelled by anyone and the deposit is given as a reward.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: <= DEPOSIT_AMOUNT_PERIOD_IN_SECS
+    pub insolvency_grace_period: u64,
+    /// Share of the deposit (in basis points) given to the recipient when an insolvent stream is cancelled. The rest
+    /// of the deposit is given as a reward to whoever cancels the stream.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: unbounded: <= BPS_DENOMINATOR
+    pub recipient_deposit_share_bps: u16,
+
+    /// Total amount owed to the recipient proposed by either the sender or the recipient to terminate the stream. If
+    /// the other party accepts the settlement, the stream is cancelled and the escrow is split accordingly.
+    ///
+    /// INVARIANT: !is_settlement_proposed && !is_settled => == 0
+    pub settlement_amount: u64,
+    /// If true, a settlement has been proposed and is waiting to be accepted.
+    ///
+    /// INVARIANT: is_cancelled => == false
+    pub is_settlement_proposed: bool,
+    /// If true, the settlement has been proposed by the sender.
+    ///
+    /// INVARIANT: !is_settlement_proposed => == false
+    pub is_settlement_proposed_by_sender: bool,
+    /// If true, the stream has been terminated with a settlement accepted by both parties.
+    ///
+    /// INVARIANT: is_settled => is_cancelled && total_topup_amount == settlement_amount
+    pub is_settled: bool,
+
+    /// Arbiter address. The arbiter is a neutral third party who can freeze the stream in case of a dispute and later
+    /// resolve it by deciding how the escrow is split. If it is the default public key, the stream has no arbiter.
+    pub arbiter: Pubkey,
+    /// If true, the stream has been frozen by the arbiter. Withdrawals, cancellations, settlements and refunds are
+    /// halted until the arbiter unfreezes or resolves the stream. The flow is not affected.
+    ///
+    /// INVARIANT: arbiter == Pubkey::default() || is_cancelled => == false
+    pub is_frozen: bool,
+
+    /// If true, the stream is waiting for the recipient to accept it and the flow has not started. When the recipient
+    /// accepts, the schedule is shifted so that the stream effectively starts at the time of acceptance if it was
+    /// scheduled to start earlier. Before that, the sender can cancel the stream and recover the full escrow.
+    pub is_pending_acceptance: bool,
+
+    /// Proposed new sender address. The sender changes only when the new sender accepts. If it is the default public
+    /// key, no sender change has been proposed.
+    ///
+    /// INVARIANT: prepaid: == Pubkey::default()
+    pub pending_sender: Pubkey,
+    /// Proposed new recipient address. The recipient changes only when the new recipient accepts. If it is the default
+    /// public key, no recipient change has been proposed.
+    pub pending_recipient: Pubkey,
+
+    /// Time after which the sender is no longer allowed to cancel a solvent stream. If it is 0, the permission never
+    /// expires.
+    ///
+    /// INVARIANT: == 0 || > sender_can_cancel_at
+    pub sender_can_cancel_expires_at: u64,
+    /// Time after which the sender is no longer allowed to change the sender. If it is 0, the permission never expires.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: == 0 || > sender_can_change_sender_at
+    pub sender_can_change_sender_expires_at: u64,
+    /// Time after which the sender is no longer allowed to pause a stream. If it is 0, the permission never expires.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: == 0 || > sender_can_pause_at
+    pub sender_can_pause_expires_at: u64,
+    /// Time after which the recipient is no longer allowed to resume a stream which was paused by the sender. If it is
+    /// 0, the permission never expires.
+    ///
+    /// INVARIANT: prepaid: == 0
+    /// INVARIANT: == 0 || > recipient_can_resume_pause_by_sender_at
+    pub recipient_can_resume_pause_by_sender_expires_at: u64,
+    /// Time after which anyone is no longer allowed to withdraw on behalf of the recipient. If it is 0, the permission
+    /// never expires.
+    ///
+    /// INVARIANT: == 0 || > anyone_can_withdraw_for_recipient_at
+    pub anyone_can_withdraw_for_recipient_expires_at: u64,
+
+    /// Mode used to derive the stream PDA address.
+    pub derivation: StreamDerivation,
+    /// Sender at the time of creation if it is part of the stream PDA seeds. It doesn't change when the sender is
+    /// changed. If it is the default public key, the sender is not part of the seeds.
+    ///
+    /// INVARIANT: !derivation.includes_sender() => == Pubkey::default()
+    pub derivation_sender: Pubkey,
+    /// Recipient at the time of creation if it is part of the stream PDA seeds. It doesn't change when the recipient
+    /// is changed. If it is the default public key, the recipient is not part of the seeds.
+    ///
+    /// INVARIANT: !derivation.includes_recipient() => == Pubkey::default()
+    pub derivation_recipient: Pubkey,
+
+    /// True if the stream is counted in the [`MintStats`] of its mint. Streams created before mint stats were
+    /// introduced are not counted.
+    pub is_counted_in_mint_stats: bool,
 
     /// Extra space for program upgrades.
-    pub reserved: [u64; 16],
+    pub reserved: [u8; 7],
 
     /// Seed of the stream PDA. It's upto the client how they choose the seed. Each tuple (seed, mint, name) corresponds
-    /// to a unique stream.
+    /// to a unique stream for a particular derivation mode and derivation keys.
     pub seed: u64,
     /// The PDA bump.
     pub bump: u8,
 
-    /// Name of the stream. Should be unique for a particular set of (seed, mint).
+    /// Name of the stream. Should be unique for a particular set of (seed, mint). Names longer than 32 bytes are hashed
+    /// to derive the stream PDA address. See [`get_stream_name_seed`](crate::get_stream_name_seed).
     ///
     /// INVARIANT: Length <= 100 unicode chars or 400 bytes
     pub name: String,
@@ -228,7 +505,8 @@ pub struct Stream {
 ///                0
 impl Stream {
     /// Total size of a Stream account excluding space taken up by the name
-    const BASE_LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
+    #[allow(clippy::identity_op)]
+    pub const BASE_LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
         + 1 * BOOL_LENGTH       // is_prepaid - 9
         + 3 * PUBLIC_KEY_LENGTH // sender, recipient, mint - 105
         + 3 * U64_LENGTH        // created_at, starts_at, ends_at - 129
@@ -250,6 +528,31 @@ impl Stream {
         + 3 * U64_LENGTH        // total_withdrawn_amount, last_withdrawn_at, last_withdrawn_amount - 251
         + 3 * U64_LENGTH        // total_topup_amount, last_topup_at, last_topup_amount - 275
         + 1 * U64_LENGTH        // deposit_needed - 283
+        + 1 * U8_LENGTH         // version - 284
+        + 1 * U64_LENGTH        // insolvency_grace_period - 292
+        + 1 * U16_LENGTH        // recipient_deposit_share_bps - 294
+        + 1 * U64_LENGTH        // settlement_amount - 302
+        + 3 * BOOL_LENGTH       // is_settlement_proposed, is_settlement_proposed_by_sender, is_settled - 305
+        + 1 * PUBLIC_KEY_LENGTH // arbiter - 337
+        + 1 * BOOL_LENGTH       // is_frozen - 338
+        + 1 * BOOL_LENGTH       // is_pending_acceptance - 339
+        + 2 * PUBLIC_KEY_LENGTH // pending_sender, pending_recipient - 403
+        + 5 * U64_LENGTH        // *_expires_at - 443
+        + 1 * U8_LENGTH         // derivation - 444
+        + 2 * PUBLIC_KEY_LENGTH // derivation_sender, derivation_recipient - 508
+        + 1 * BOOL_LENGTH       // is_counted_in_mint_stats - 509
+        + 7 * U8_LENGTH         // reserved - 516
+        + 1 * U64_LENGTH        // seed - 524
+        + 1 * U8_LENGTH         // bump - 525
+    ;
+
+    /// Offset of the layout version in the stream account data.
+    pub const VERSION_OFFSET: usize = 283;
+
+    /// Total size of a stream account in the legacy layout excluding space taken up by the name. See
+    /// [`VersionedStream`].
+    #[allow(clippy::identity_op)]
+    pub const LEGACY_BASE_LENGTH: usize = Self::VERSION_OFFSET
         + 16 * U64_LENGTH       // reserved - 411
         + 1 * U64_LENGTH        // seed - 419
         + 1 * U8_LENGTH         // bump - 420
@@ -259,218 +562,267 @@ impl Stream {
         Self::BASE_LENGTH + STRING_LENGTH_PREFIX + name.len()
     }
 
+    /// Returns true if the stream account is in the legacy layout and needs to be migrated.
+    pub fn is_legacy(&self) -> bool {
+        self.version == LEGACY_STREAM_LAYOUT_VERSION
+    }
+
     // --- Utility functions --- BEGIN ---
 
+    /// Get the accounting state of the stream. All the stream math is done on the accounting state by the
+    /// `superstream-math` crate.
+    pub fn state(&self) -> StreamState {
+        StreamState {
+            is_prepaid: self.is_prepaid,
+            mint: self.mint,
+            sender: self.sender,
+            recipient: self.recipient,
+            created_at: self.created_at,
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+            initial_amount: self.initial_amount,
+            flow_interval: self.flow_interval,
+            flow_rate: self.flow_rate,
+            is_cancelled: self.is_cancelled,
+            is_cancelled_before_start: self.is_cancelled_before_start,
+            is_cancelled_by_sender: self.is_cancelled_by_sender,
+            cancelled_at: self.cancelled_at,
+            sender_can_cancel: self.sender_can_cancel,
+            sender_can_cancel_at: self.sender_can_cancel_at,
+            sender_can_change_sender: self.sender_can_change_sender,
+            sender_can_change_sender_at: self.sender_can_change_sender_at,
+            is_paused: self.is_paused,
+            is_paused_by_sender: self.is_paused_by_sender,
+            sender_can_pause: self.sender_can_pause,
+            sender_can_pause_at: self.sender_can_pause_at,
+            recipient_can_resume_pause_by_sender: self.recipient_can_resume_pause_by_sender,
+            recipient_can_resume_pause_by_sender_at: self.recipient_can_resume_pause_by_sender_at,
+            anyone_can_withdraw_for_recipient: self.anyone_can_withdraw_for_recipient,
+            anyone_can_withdraw_for_recipient_at: self.anyone_can_withdraw_for_recipient_at,
+            last_resumed_at: self.last_resumed_at,
+            accumulated_active_time: self.accumulated_active_time,
+            total_withdrawn_amount: self.total_withdrawn_amount,
+            last_withdrawn_at: self.last_withdrawn_at,
+            last_withdrawn_amount: self.last_withdrawn_amount,
+            total_topup_amount: self.total_topup_amount,
+            last_topup_at: self.last_topup_at,
+            last_topup_amount: self.last_topup_amount,
+            deposit_needed: self.deposit_needed,
+            insolvency_grace_period: self.insolvency_grace_period,
+            recipient_deposit_share_bps: self.recipient_deposit_share_bps,
+            settlement_amount: self.settlement_amount,
+            is_settlement_proposed: self.is_settlement_proposed,
+            is_settlement_proposed_by_sender: self.is_settlement_proposed_by_sender,
+            is_settled: self.is_settled,
+            arbiter: self.arbiter,
+            is_frozen: self.is_frozen,
+            is_pending_acceptance: self.is_pending_acceptance,
+            pending_sender: self.pending_sender,
+            pending_recipient: self.pending_recipient,
+            sender_can_cancel_expires_at: self.sender_can_cancel_expires_at,
+            sender_can_change_sender_expires_at: self.sender_can_change_sender_expires_at,
+            sender_can_pause_expires_at: self.sender_can_pause_expires_at,
+            recipient_can_resume_pause_by_sender_expires_at: self.recipient_can_resume_pause_by_sender_expires_at,
+            anyone_can_withdraw_for_recipient_expires_at: self.anyone_can_withdraw_for_recipient_expires_at,
+        }
+    }
+
+    fn set_state(&mut self, state: StreamState) {
+        self.is_prepaid = state.is_prepaid;
+        self.mint = state.mint;
+        self.sender = state.sender;
+        self.recipient = state.recipient;
+        self.created_at = state.created_at;
+        self.starts_at = state.starts_at;
+        self.ends_at = state.ends_at;
+        self.initial_amount = state.initial_amount;
+        self.flow_interval = state.flow_interval;
+        self.flow_rate = state.flow_rate;
+        self.is_cancelled = state.is_cancelled;
+        self.is_cancelled_before_start = state.is_cancelled_before_start;
+        self.is_cancelled_by_sender = state.is_cancelled_by_sender;
+        self.cancelled_at = state.cancelled_at;
+        self.sender_can_cancel = state.sender_can_cancel;
+        self.sender_can_cancel_at = state.sender_can_cancel_at;
+        self.sender_can_change_sender = state.sender_can_change_sender;
+        self.sender_can_change_sender_at = state.sender_can_change_sender_at;
+        self.is_paused = state.is_paused;
+        self.is_paused_by_sender = state.is_paused_by_sender;
+        self.sender_can_pause = state.sender_can_pause;
+        self.sender_can_pause_at = state.sender_can_pause_at;
+        self.recipient_can_resume_pause_by_sender = state.recipient_can_resume_pause_by_sender;
+        self.recipient_can_resume_pause_by_sender_at = state.recipient_can_resume_pause_by_sender_at;
+        self.anyone_can_withdraw_for_recipient = state.anyone_can_withdraw_for_recipient;
+        self.anyone_can_withdraw_for_recipient_at = state.anyone_can_withdraw_for_recipient_at;
+        self.last_resumed_at = state.last_resumed_at;
+        self.accumulated_active_time = state.accumulated_active_time;
+        self.total_withdrawn_amount = state.total_withdrawn_amount;
+        self.last_withdrawn_at = state.last_withdrawn_at;
+        self.last_withdrawn_amount = state.last_withdrawn_amount;
+        self.total_topup_amount = state.total_topup_amount;
+        self.last_topup_at = state.last_topup_at;
+        self.last_topup_amount = state.last_topup_amount;
+        self.deposit_needed = state.deposit_needed;
+        self.insolvency_grace_period = state.insolvency_grace_period;
+        self.recipient_deposit_share_bps = state.recipient_deposit_share_bps;
+        self.settlement_amount = state.settlement_amount;
+        self.is_settlement_proposed = state.is_settlement_proposed;
+        self.is_settlement_proposed_by_sender = state.is_settlement_proposed_by_sender;
+        self.is_settled = state.is_settled;
+        self.arbiter = state.arbiter;
+        self.is_frozen = state.is_frozen;
+        self.is_pending_acceptance = state.is_pending_acceptance;
+        self.pending_sender = state.pending_sender;
+        self.pending_recipient = state.pending_recipient;
+        self.sender_can_cancel_expires_at = state.sender_can_cancel_expires_at;
+        self.sender_can_change_sender_expires_at = state.sender_can_change_sender_expires_at;
+        self.sender_can_pause_expires_at = state.sender_can_pause_expires_at;
+        self.recipient_can_resume_pause_by_sender_expires_at = state.recipient_can_resume_pause_by_sender_expires_at;
+        self.anyone_can_withdraw_for_recipient_expires_at = state.anyone_can_withdraw_for_recipient_expires_at;
+    }
+
+    /// Run the stream math on the accounting state of the stream and save the updated state if it succeeds.
+    fn update<T>(&mut self, f: impl FnOnce(&mut StreamState) -> superstream_math::Result<T>) -> Result<T> {
+        let mut state = self.state();
+        let result = f(&mut state).map_err(math_error)?;
+        self.set_state(state);
+        Ok(result)
+    }
+
+    /// Get the sender seed of the stream PDA account. See [`StreamDerivation`].
+    pub fn sender_seed(&self) -> &[u8] {
+        self.derivation.sender_seed(&self.derivation_sender)
+    }
+
+    /// Get the recipient seed of the stream PDA account. See [`StreamDerivation`].
+    pub fn recipient_seed(&self) -> &[u8] {
+        self.derivation.recipient_seed(&self.derivation_recipient)
+    }
+
+    /// Check that the seed and name passed as instruction arguments match the ones stored in the stream. Instructions
+    /// that still take them as arguments call this since the account constraints only use the stored fields.
+    pub(crate) fn validate_seed_and_name(&self, seed: u64, name: &str) -> Result<()> {
+        require!(seed == self.seed && name == self.name, ErrorCode::ConstraintSeeds);
+        Ok(())
+    }
+
+    /// Returns true if the stream has flow payments.
     pub fn has_flow_payments(&self) -> bool {
-        self.flow_rate > 0 && (self.ends_at == 0 || self.ends_at > self.starts_at)
+        self.state().has_flow_payments()
+    }
+
+    /// Get the flow rate of the stream.
+    pub fn get_flow_rate(&self) -> FlowRate {
+        self.state().get_flow_rate()
     }
 
     /// Calculate the amount of prepaid needed for a prepaid stream. This is called when creating the stream.
     pub fn get_prepaid_amount_needed(&self) -> Result<u64> {
-        if !self.is_prepaid || self.ends_at == 0 {
-            Ok(0)
-        } else if !self.has_flow_payments() {
-            Ok(self.initial_amount)
-        } else {
-            self.initial_amount
-                .checked_add(
-                    ((self.ends_at - self.starts_at)
-                        .checked_mul(self.flow_rate)
-                        .ok_or(error!(StreamError::PrepaidAmountNeededOutOfBounds))?)
-                        / self.flow_interval,
-                )
-                .ok_or(error!(StreamError::PrepaidAmountNeededOutOfBounds))
-        }
+        self.state().get_prepaid_amount_needed().map_err(math_error)
     }
 
     /// Calculate the amount of deposit needed for the streaming payments excluding the initial amount. This is called
     /// when creating the stream.
     pub fn get_deposit_needed(&self) -> Result<u64> {
-        Ok(if self.is_prepaid || !self.has_flow_payments() {
-            0
-        } else {
-            let deposit_needed = if self.ends_at == 0 {
-                DEPOSIT_AMOUNT_PERIOD_IN_SECS
-                    .checked_mul(self.flow_rate)
-                    .ok_or(error!(StreamError::DepositAmountNeededOutOfBounds))?
-                    / self.flow_interval
-            } else {
-                min(DEPOSIT_AMOUNT_PERIOD_IN_SECS, self.ends_at - self.starts_at)
-                    .checked_mul(self.flow_rate)
-                    .ok_or(error!(StreamError::DepositAmountNeededOutOfBounds))?
-                    / self.flow_interval
-            };
-
-            if deposit_needed >= 10 {
-                deposit_needed
-            } else {
-                deposit_needed + 1
-            }
-        })
+        self.state().get_deposit_needed().map_err(math_error)
     }
 
-    pub fn get_stops_at(&self) -> u64 {
-        let cancelled_at = self.cancelled_at;
-        let ends_at = self.ends_at;
-        if cancelled_at == 0 {
-            ends_at
-        } else if ends_at == 0 {
-            cancelled_at
-        } else {
-            min(ends_at, cancelled_at)
-        }
+    /// Returns true if the sender is allowed to cancel a solvent stream at time `at`.
+    pub fn sender_can_cancel_now(&self, at: u64) -> bool {
+        self.state().sender_can_cancel_now(at)
     }
 
-    /// Check if the stream has stooped.
-    pub fn has_stopped(&self, at: u64) -> bool {
-        let stops_at = self.get_stops_at();
-        return stops_at > 0 && at > stops_at;
+    /// Returns true if the sender is allowed to change the sender at time `at`.
+    pub fn sender_can_change_sender_now(&self, at: u64) -> bool {
+        self.state().sender_can_change_sender_now(at)
     }
 
-    fn min_with_stopped_at(&self, at: u64) -> u64 {
-        let stops_at = self.get_stops_at();
-        if stops_at > 0 && at > stops_at {
-            // If the stream has been stopped for some reason - either ending or being cancelled - make at = stopped_at
-            // if the stream stopped before at. This will make sure, the amount is calculated only till the time the
-            // stream was active.
-            stops_at
-        } else {
-            at
-        }
+    /// Returns true if the sender is allowed to pause the stream at time `at`.
+    pub fn sender_can_pause_now(&self, at: u64) -> bool {
+        self.state().sender_can_pause_now(at)
     }
 
-    // INVARIANT: (stops_at == 0 || at <= stops_at) && at >= self.starts_at && self.has_flow_payments()
-    fn unsafe_get_active_time_after_start(&self, at: u64) -> Result<u64> {
-        Ok(if self.is_paused {
-            // INVARIANT: The stream is paused => accumulated time is the total time.
-            self.accumulated_active_time
-        } else if self.last_resumed_at == 0 {
-            // INVARIANT: The stream is not paused and was never resumed => stream was never paused.
-            at - self.starts_at
-        } else {
-            // SAFETY: INVARIANT: last_resumed_at != 0 =>
-            //     last_resumed_at >= starts_at && (ends_at == 0 || < ends_at) =>
-            //     last_resumed_at will never be > ends_at if ends_at > 0
-            (at - self.last_resumed_at)
-                .checked_add(self.accumulated_active_time)
-                .ok_or(error!(StreamError::AmountAvailableToWithdrawOutOfBounds))?
-        })
+    /// Returns true if the recipient is allowed to resume a stream paused by the sender at time `at`.
+    pub fn recipient_can_resume_pause_by_sender_now(&self, at: u64) -> bool {
+        self.state().recipient_can_resume_pause_by_sender_now(at)
+    }
+
+    /// Returns true if anyone is allowed to withdraw on behalf of the recipient at time `at`.
+    pub fn anyone_can_withdraw_for_recipient_now(&self, at: u64) -> bool {
+        self.state().anyone_can_withdraw_for_recipient_now(at)
+    }
+
+    /// Get the time at which the stream stops - either by ending or being cancelled. Returns 0 if the stream has no end
+    /// time and has not been cancelled.
+    pub fn get_stops_at(&self) -> u64 {
+        self.state().get_stops_at()
+    }
+
+    /// Check if the stream has stopped.
+    pub fn has_stopped(&self, at: u64) -> bool {
+        self.state().has_stopped(at)
     }
 
     /// Get the maximum acceptable topup amount.
     pub fn get_max_acceptable_topup_amount(&self, at: u64) -> Result<(bool, u64)> {
-        Ok(if self.is_prepaid || !self.has_flow_payments() {
-            (false, 0)
-        } else {
-            // Streams: non-prepaid, with flow payments.
-            let stops_at = self.get_stops_at();
-            if stops_at == 0 {
-                // Streams: non-prepaid, with flow payments, non-cancelled with no end time.
-                (true, 0)
-            } else if stops_at < self.starts_at {
-                // Streams: non-prepaid, with flow payments, stopped before start.
-                (false, 0)
-            } else {
-                // Streams: non-prepaid, with flow payments, cancelled after start time and/or with set end time after
-                // start time.
-                let total_possible_active_time = if at < self.starts_at {
-                    // Streams: non-prepaid, with flow payments, not-started, with set end time after start time. The
-                    // stream cannot be cancelled before current time.
-                    self.ends_at - self.starts_at
-                } else {
-                    // Streams: non-prepaid, with flow payments, started, cancelled after start time and/or with set end
-                    // time after start time.
-                    if stops_at <= at {
-                        // Streams: non-prepaid, with flow payments, started, stopped after start time.
-                        self.unsafe_get_active_time_after_start(stops_at)?
-                    } else {
-                        // Streams: non-prepaid, with flow payments, started, not already stopped => with set end time
-                        // after start time in the future.
-                        self.unsafe_get_active_time_after_start(at)?
-                            .checked_add(stops_at - at)
-                            .ok_or(error!(StreamError::TopupAmountOutOfBounds))?
-                    }
-                };
-
-                let total_possible_topup = if total_possible_active_time == 0 {
-                    self.initial_amount
-                } else {
-                    self.initial_amount
-                        .checked_add(
-                            (total_possible_active_time
-                                .checked_mul(self.flow_rate)
-                                .ok_or(error!(StreamError::TopupAmountOutOfBounds))?)
-                                / self.flow_interval,
-                        )
-                        .ok_or(error!(StreamError::TopupAmountOutOfBounds))?
-                };
-
-                (
-                    false,
-                    if total_possible_topup <= self.total_topup_amount {
-                        0
-                    } else {
-                        total_possible_topup - self.total_topup_amount
-                    },
-                )
-            }
-        })
+        self.state().get_max_acceptable_topup_amount(at).map_err(math_error)
     }
 
     /// Get the total amount owed to the recipient.
     pub fn get_amount_owed(&self, at: u64) -> Result<u64> {
-        let at = self.min_with_stopped_at(at);
+        self.state().get_amount_owed(at).map_err(math_error)
+    }
 
-        Ok(if at < self.starts_at {
-            0
-        } else if !self.has_flow_payments() {
-            self.initial_amount
-        } else {
-            let active_time = self.unsafe_get_active_time_after_start(at)?;
-            if active_time == 0 {
-                self.initial_amount
-            } else {
-                self.initial_amount
-                    .checked_add(
-                        (active_time
-                            .checked_mul(self.flow_rate)
-                            .ok_or(error!(StreamError::AmountAvailableToWithdrawOutOfBounds))?)
-                            / self.flow_interval,
-                    )
-                    .ok_or(error!(StreamError::AmountAvailableToWithdrawOutOfBounds))?
-            }
-        })
+    /// Check if the stream is solvent, i.e. the amount owed to the recipient is covered by the total topup amount.
+    pub fn is_solvent(&self, at: u64) -> Result<bool> {
+        self.state().is_solvent(at).map_err(math_error)
     }
 
-    fn mark_cancelled(&mut self, at: u64, signer: &Signer) {
-        self.is_cancelled = true;
-        self.is_cancelled_before_start = at < self.starts_at;
-        self.is_cancelled_by_sender = signer.key() == self.sender;
-        self.cancelled_at = at;
+    /// Get the time from which the stream is insolvent. See [`StreamState::insolvent_at`] for more information.
+    pub fn insolvent_at(&self) -> Option<u64> {
+        self.state().insolvent_at()
     }
 
-    fn add_topup_amount(&mut self, at: u64, latest_topup_amount: u64) -> Result<()> {
-        self.total_topup_amount = self
-            .total_topup_amount
-            .checked_add(latest_topup_amount)
-            .ok_or(error!(StreamError::TopupAmountOutOfBounds))?;
-        self.last_topup_at = at;
-        self.last_topup_amount = latest_topup_amount;
-        Ok(())
+    /// Get the status of the stream at time `at`. See [`StreamStatus`] for more information.
+    pub fn status(&self, at: u64) -> Result<StreamStatus> {
+        self.state().status(at).map(StreamStatus::from).map_err(math_error)
     }
 
-    fn add_withdrawn_amount(&mut self, at: u64, latest_withdrawn_amount: u64) -> Result<()> {
-        if latest_withdrawn_amount == 0 {
-            return Ok(());
-        }
+    /// Check if the stream is insolvent but still within the insolvency grace period.
+    pub fn is_in_insolvency_grace_period(&self, at: u64) -> Result<bool> {
+        self.state().is_in_insolvency_grace_period(at).map_err(math_error)
+    }
 
-        self.total_withdrawn_amount = self
-            .total_withdrawn_amount
-            .checked_add(latest_withdrawn_amount)
-            .ok_or(error!(StreamError::WithdrawAmountOutOfBounds))?;
-        self.last_withdrawn_at = at;
-        self.last_withdrawn_amount = latest_withdrawn_amount;
-        Ok(())
+    /// Get the withdrawable amount of the stream at time `at`.
+    pub fn get_withdrawable_view(&self, at: u64) -> Result<WithdrawableView> {
+        let amount_owed = self.get_amount_owed(at)?;
+        Ok(WithdrawableView {
+            at,
+            amount_owed,
+            total_withdrawn_amount: self.total_withdrawn_amount,
+            withdrawable_amount: min(amount_owed, self.total_topup_amount).saturating_sub(self.total_withdrawn_amount),
+        })
+    }
+
+    /// Get the solvency of the stream at time `at`.
+    pub fn get_solvency_view(&self, at: u64) -> Result<SolvencyView> {
+        Ok(SolvencyView {
+            at,
+            status: self.status(at)?,
+            is_solvent: self.is_solvent(at)?,
+            is_in_insolvency_grace_period: self.is_in_insolvency_grace_period(at)?,
+            insolvent_at: self.insolvent_at(),
+        })
+    }
+
+    /// Get the maximum acceptable topup amount of the stream at time `at`.
+    pub fn get_max_topup_view(&self, at: u64) -> Result<MaxTopupView> {
+        let (has_no_limit, max_acceptable_topup_amount) = self.get_max_acceptable_topup_amount(at)?;
+        Ok(MaxTopupView {
+            at,
+            has_no_limit,
+            max_acceptable_topup_amount,
+        })
     }
 
     // --- Utility functions --- END ---
@@ -480,33 +832,32 @@ impl Stream {
     /// Initialize a stream.
     pub fn initialize(
         &mut self,
-        is_prepaid: bool,
         mint: Pubkey,
         sender: Pubkey,
         recipient: Pubkey,
         name: String,
-        starts_at: u64,
-        ends_at: u64,
-        initial_amount: u64,
-        flow_interval: u64,
-        flow_rate: u64,
-        sender_can_cancel: bool,
-        sender_can_cancel_at: u64,
-        sender_can_change_sender: bool,
-        sender_can_change_sender_at: u64,
-        sender_can_pause: bool,
-        sender_can_pause_at: u64,
-        recipient_can_resume_pause_by_sender: bool,
-        recipient_can_resume_pause_by_sender_at: u64,
-        anyone_can_withdraw_for_recipient: bool,
-        anyone_can_withdraw_for_recipient_at: u64,
+        params: &CreateStreamParamsV1,
+        derivation: StreamDerivation,
         seed: u64,
         bump: u8,
     ) -> Result<()> {
+        let is_prepaid = params.kind.is_prepaid();
+        let StreamSchedule {
+            starts_at,
+            ends_at,
+            initial_amount,
+            flow_interval,
+            flow_rate,
+        } = params.schedule;
+        let permissions = &params.permissions;
+        let arbiter = params.arbiter;
+
         require!(recipient != Pubkey::default(), StreamError::EmptyRecipient);
-        require!(name.len() >= MIN_STREAM_NAME_LENGTH, StreamError::StreamNameTooShort);
-        require!(name.len() <= MAX_STREAM_NAME_LENGTH, StreamError::StreamNameTooLong);
+        let name_length = name.chars().count();
+        require!(name_length >= MIN_STREAM_NAME_LENGTH, StreamError::StreamNameTooShort);
+        require!(name_length <= MAX_STREAM_NAME_LENGTH, StreamError::StreamNameTooLong);
         require!(recipient != sender, StreamError::SameSenderAndRecipient);
+        require!(arbiter != sender && arbiter != recipient, StreamError::InvalidArbiter);
         require!(flow_interval > 0, StreamError::ZeroFlowInterval);
 
         let at = get_current_timestamp()?;
@@ -517,31 +868,15 @@ impl Stream {
             StreamError::InvalidEndsAt,
         );
 
-        let sender_can_cancel_at = if sender_can_cancel {
-            min(sender_can_cancel_at, at)
-        } else {
-            0
-        };
-        let sender_can_change_sender_at = if sender_can_change_sender {
-            min(sender_can_change_sender_at, at)
-        } else {
-            0
-        };
-        let sender_can_pause_at = if sender_can_pause {
-            min(sender_can_pause_at, at)
-        } else {
-            0
-        };
-        let recipient_can_resume_pause_by_sender_at = if recipient_can_resume_pause_by_sender {
-            min(recipient_can_resume_pause_by_sender_at, at)
-        } else {
-            0
-        };
-        let anyone_can_withdraw_for_recipient_at = if anyone_can_withdraw_for_recipient {
-            min(anyone_can_withdraw_for_recipient_at, at)
-        } else {
-            0
-        };
+        // Permissions unlocking in the future are kept as is, so that e.g. the sender can only cancel after some time.
+        let (sender_can_cancel_at, sender_can_cancel_expires_at) = permissions.sender_can_cancel.get_times()?;
+        let (sender_can_change_sender_at, sender_can_change_sender_expires_at) =
+            permissions.sender_can_change_sender.get_times()?;
+        let (sender_can_pause_at, sender_can_pause_expires_at) = permissions.sender_can_pause.get_times()?;
+        let (recipient_can_resume_pause_by_sender_at, recipient_can_resume_pause_by_sender_expires_at) =
+            permissions.recipient_can_resume_pause_by_sender.get_times()?;
+        let (anyone_can_withdraw_for_recipient_at, anyone_can_withdraw_for_recipient_expires_at) =
+            permissions.anyone_can_withdraw_for_recipient.get_times()?;
 
         self.is_prepaid = is_prepaid;
         self.is_cancelled = false;
@@ -558,16 +893,16 @@ impl Stream {
         self.initial_amount = initial_amount;
         self.flow_interval = flow_interval;
         self.flow_rate = flow_rate;
-        self.sender_can_cancel = sender_can_cancel;
+        self.sender_can_cancel = permissions.sender_can_cancel.is_allowed;
         self.sender_can_cancel_at = sender_can_cancel_at;
         self.cancelled_at = 0;
-        self.sender_can_change_sender = sender_can_change_sender;
+        self.sender_can_change_sender = permissions.sender_can_change_sender.is_allowed;
         self.sender_can_change_sender_at = sender_can_change_sender_at;
-        self.sender_can_pause = sender_can_pause;
+        self.sender_can_pause = permissions.sender_can_pause.is_allowed;
         self.sender_can_pause_at = sender_can_pause_at;
-        self.recipient_can_resume_pause_by_sender = recipient_can_resume_pause_by_sender;
+        self.recipient_can_resume_pause_by_sender = permissions.recipient_can_resume_pause_by_sender.is_allowed;
         self.recipient_can_resume_pause_by_sender_at = recipient_can_resume_pause_by_sender_at;
-        self.anyone_can_withdraw_for_recipient = anyone_can_withdraw_for_recipient;
+        self.anyone_can_withdraw_for_recipient = permissions.anyone_can_withdraw_for_recipient.is_allowed;
         self.anyone_can_withdraw_for_recipient_at = anyone_can_withdraw_for_recipient_at;
         self.last_resumed_at = 0;
         self.accumulated_active_time = 0;
@@ -578,6 +913,35 @@ impl Stream {
         self.last_topup_at = 0;
         self.last_topup_amount = 0;
         self.deposit_needed = self.get_deposit_needed()?;
+        self.version = STREAM_LAYOUT_VERSION;
+        self.insolvency_grace_period = 0;
+        self.recipient_deposit_share_bps = 0;
+        self.settlement_amount = 0;
+        self.is_settlement_proposed = false;
+        self.is_settlement_proposed_by_sender = false;
+        self.is_settled = false;
+        self.arbiter = arbiter;
+        self.is_frozen = false;
+        self.is_pending_acceptance = params.requires_recipient_acceptance;
+        self.pending_sender = Pubkey::default();
+        self.pending_recipient = Pubkey::default();
+        self.sender_can_cancel_expires_at = sender_can_cancel_expires_at;
+        self.sender_can_change_sender_expires_at = sender_can_change_sender_expires_at;
+        self.sender_can_pause_expires_at = sender_can_pause_expires_at;
+        self.recipient_can_resume_pause_by_sender_expires_at = recipient_can_resume_pause_by_sender_expires_at;
+        self.anyone_can_withdraw_for_recipient_expires_at = anyone_can_withdraw_for_recipient_expires_at;
+        self.derivation = derivation;
+        self.derivation_sender = if derivation.includes_sender() {
+            sender
+        } else {
+            Pubkey::default()
+        };
+        self.derivation_recipient = if derivation.includes_recipient() {
+            recipient
+        } else {
+            Pubkey::default()
+        };
+        self.is_counted_in_mint_stats = true;
         self.seed = seed;
         self.bump = bump;
         self.name = name;
@@ -591,278 +955,1222 @@ impl Stream {
 
     /// Initialize a prepaid stream.
     pub fn initialize_prepaid(&mut self) -> Result<u64> {
-        let prepaid_amount_needed = self.get_prepaid_amount_needed()?;
-        require!(prepaid_amount_needed > 0, StreamError::ZeroLifetimeAmount);
-        self.add_topup_amount(get_current_timestamp()?, prepaid_amount_needed)?;
-        Ok(prepaid_amount_needed)
+        let at = get_current_timestamp()?;
+        self.update(|state| state.initialize_prepaid(at))
     }
 
     /// Initialize a non-prepaid stream.
-    pub fn initialize_non_prepaid(&mut self, topup_amount: u64) -> Result<()> {
-        require!(topup_amount > 0, StreamError::ZeroAmount);
-
-        // Amount needed = initial_amount + 2 * deposit_amount.
-        //
-        // We are doing 2 times deposit amount, because if it was just once, the stream would become insolvent
-        // immediately.
-        let amount_needed = self
-            .initial_amount
-            .checked_add(
-                self.deposit_needed
-                    .checked_mul(2)
-                    .ok_or(error!(StreamError::DepositAmountNeededOutOfBounds))?,
-            )
-            .ok_or(error!(StreamError::DepositAmountNeededOutOfBounds))?;
-        require!(topup_amount >= amount_needed, StreamError::AmountLessThanAmountNeeded);
-        self.add_topup_amount(get_current_timestamp()?, topup_amount - self.deposit_needed)
+    pub fn initialize_non_prepaid(
+        &mut self,
+        topup_amount: u64,
+        insolvency_grace_period: u64,
+        recipient_deposit_share_bps: u16,
+    ) -> Result<()> {
+        let at = get_current_timestamp()?;
+        self.update(|state| {
+            state.initialize_non_prepaid(at, topup_amount, insolvency_grace_period, recipient_deposit_share_bps)
+        })
     }
 
     pub(crate) fn cancel(&mut self, key: Pubkey, signer: &Signer, recipient: Pubkey) -> Result<CancelTransferParams> {
-        require!(!self.is_cancelled, StreamError::StreamAlreadyCancelled);
-        require!(recipient == self.recipient, StreamError::InvalidRecipient);
-
         let at = get_current_timestamp()?;
-        self.mark_cancelled(at, signer);
-
-        let total_topup_amount = self.total_topup_amount;
-        let amount_owed = self.get_amount_owed(at)?;
-        if total_topup_amount < amount_owed {
-            // The stream is insolvent. Anyone can cancel.
-            let transfer_amount_recipient = if total_topup_amount > self.total_withdrawn_amount {
-                total_topup_amount - self.total_withdrawn_amount
-            } else {
-                0
-            };
-            self.add_withdrawn_amount(at, transfer_amount_recipient)?;
-
-            if self.is_prepaid {
-                msg!("Prepaid stream [{}] is insolvent. THIS SHOULD NEVER HAPPEN!!!", key);
-                Ok(CancelTransferParams {
-                    transfer_amount_sender: 0,
-                    transfer_amount_signer: 0,
-                    transfer_amount_recipient,
-                })
-            } else {
-                // The deposit is given as reward to the signer. The remaining topup amount can be withdrawn by the
-                // recipient.
-                let transfer_amount_signer = self.deposit_needed;
-                self.deposit_needed = 0;
-                Ok(CancelTransferParams {
-                    transfer_amount_sender: 0,
-                    transfer_amount_signer,
-                    transfer_amount_recipient,
-                })
-            }
-        } else {
-            // The stream is still solvent. Only the sender and recipient can cancel.
-            let signer_key = signer.key();
-            require!(
-                signer_key == self.sender || signer_key == self.recipient,
-                StreamError::UserUnauthorizedToCancel,
-            );
-            require!(
-                signer_key != self.sender || (self.sender_can_cancel && self.sender_can_cancel_at <= at),
-                StreamError::SenderCannotCancel,
-            );
-
-            // Return anything the sender paid - topup or deposit that is not owed to the recipient. The stream has been
-            // cancelled and stopped, so the deposit is no longer needed.
-            let transfer_amount_sender = total_topup_amount
-                .checked_add(self.deposit_needed)
-                .ok_or(error!(StreamError::CancellationRefundOutOfBounds))?
-                - amount_owed;
-
-            self.total_topup_amount = amount_owed;
-            self.deposit_needed = 0;
-
-            let transfer_amount_recipient = if amount_owed > self.total_withdrawn_amount {
-                amount_owed - self.total_withdrawn_amount
-            } else {
-                0
-            };
-            self.add_withdrawn_amount(at, transfer_amount_recipient)?;
-
-            Ok(CancelTransferParams {
-                transfer_amount_sender,
-                transfer_amount_signer: 0,
-                transfer_amount_recipient,
-            })
+        if self.is_prepaid && !self.is_cancelled && !self.is_solvent(at)? {
+            msg!("Prepaid stream [{}] is insolvent. THIS SHOULD NEVER HAPPEN!!!", key);
         }
+        self.update(|state| state.cancel(at, signer.key(), recipient))
     }
 
     pub(crate) fn withdraw_excess_topup_non_prepaid_ended(&mut self) -> Result<u64> {
-        require!(!self.is_cancelled, StreamError::StreamAlreadyCancelled);
+        let at = get_current_timestamp()?;
+        self.update(|state| state.withdraw_excess_topup_non_prepaid_ended(at))
+    }
 
+    pub(crate) fn topup_non_prepaid(&mut self, signer: &Signer, topup_amount: u64, is_strict: bool) -> Result<u64> {
         let at = get_current_timestamp()?;
-        require!(self.ends_at > 0 && self.ends_at < at, StreamError::StreamNotEnded);
+        self.update(|state| state.topup_non_prepaid(at, signer.key(), topup_amount, is_strict))
+    }
 
-        let total_topup_amount = self.total_topup_amount;
-        let amount_owed = self.get_amount_owed(at)?;
-        Ok(if total_topup_amount < amount_owed {
-            // The stream is insolvent. Nothing to do.
-            0
-        } else {
-            let deposit_needed = self.deposit_needed;
+    pub(crate) fn change_sender_non_prepaid(&mut self, sender: &Signer, new_sender: Pubkey) -> Result<()> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.change_sender_non_prepaid(at, sender.key(), new_sender))
+    }
 
-            self.total_topup_amount = amount_owed;
-            self.deposit_needed = 0;
+    pub(crate) fn accept_sender_change_non_prepaid(&mut self, new_sender: &Signer) -> Result<()> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.accept_sender_change_non_prepaid(at, new_sender.key()))
+    }
 
-            total_topup_amount
-                .checked_add(deposit_needed)
-                .ok_or(error!(StreamError::CancellationRefundOutOfBounds))?
-                - amount_owed
-        })
+    pub(crate) fn cancel_sender_change_non_prepaid(&mut self, sender: &Signer) -> Result<()> {
+        self.update(|state| state.cancel_sender_change_non_prepaid(sender.key()))
     }
 
-    pub(crate) fn topup_non_prepaid(&mut self, topup_amount: u64) -> Result<()> {
-        require!(topup_amount > 0, StreamError::ZeroAmount);
-        require!(!self.is_prepaid, StreamError::StreamIsPrepaid);
-        require!(self.has_flow_payments(), StreamError::StreamHasNoFlowPayments);
+    pub(crate) fn withdraw_and_change_recipient(
+        &mut self,
+        signer: &Signer,
+        recipient: Pubkey,
+        new_recipient: Pubkey,
+    ) -> Result<WithdrawTransferParams> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.withdraw_and_change_recipient(at, signer.key(), recipient, new_recipient))
+    }
 
+    pub(crate) fn accept_recipient_change(
+        &mut self,
+        new_recipient: &Signer,
+        recipient: Pubkey,
+    ) -> Result<WithdrawTransferParams> {
         let at = get_current_timestamp()?;
-        require!(!self.has_stopped(at), StreamError::StreamHasStopped);
+        self.update(|state| state.accept_recipient_change(at, new_recipient.key(), recipient))
+    }
 
-        let (no_limit, max_acceptable_topup) = self.get_max_acceptable_topup_amount(at)?;
-        if !no_limit && topup_amount > max_acceptable_topup {
-            require!(!self.has_stopped(at), StreamError::TopupAmountMoreThanMaxAcceptable);
-        }
+    pub(crate) fn cancel_recipient_change(&mut self, recipient: &Signer) -> Result<()> {
+        self.update(|state| state.cancel_recipient_change(recipient.key()))
+    }
 
-        self.add_topup_amount(get_current_timestamp()?, topup_amount)
+    pub(crate) fn pause_non_prepaid(&mut self, signer: &Signer) -> Result<()> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.pause_non_prepaid(at, signer.key()))
     }
 
-    pub(crate) fn change_sender_non_prepaid(&mut self, sender: &Signer, new_sender: Pubkey) -> Result<()> {
-        require!(!self.is_prepaid, StreamError::StreamIsPrepaid);
-        require!(sender.key() == self.sender, StreamError::InvalidSender);
-        require!(new_sender != Pubkey::default(), StreamError::InvalidNewSender);
-        require!(new_sender != self.sender, StreamError::SameSenders);
+    pub(crate) fn resume_non_prepaid(&mut self, signer: &Signer) -> Result<()> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.resume_non_prepaid(at, signer.key()))
+    }
 
+    pub(crate) fn accept(&mut self, signer: &Signer) -> Result<()> {
         let at = get_current_timestamp()?;
-        require!(
-            self.sender_can_change_sender && self.sender_can_change_sender_at <= at,
-            StreamError::SenderCannotChangeSender
-        );
-        require!(!self.has_stopped(at), StreamError::StreamHasStopped);
+        self.update(|state| state.accept(at, signer.key()))
+    }
 
-        self.sender = new_sender;
-        Ok(())
+    pub(crate) fn propose_settlement(&mut self, signer: &Signer, settlement_amount: u64) -> Result<()> {
+        self.update(|state| state.propose_settlement(signer.key(), settlement_amount))
     }
 
-    pub(crate) fn withdraw_and_change_recipient(
+    pub(crate) fn cancel_settlement_proposal(&mut self, signer: &Signer) -> Result<()> {
+        self.update(|state| state.cancel_settlement_proposal(signer.key()))
+    }
+
+    pub(crate) fn terminate_with_settlement(
         &mut self,
         signer: &Signer,
         recipient: Pubkey,
-        new_recipient: Pubkey,
-    ) -> Result<u64> {
-        require!(recipient == self.recipient, StreamError::InvalidRecipient);
-
+        settlement_amount: u64,
+    ) -> Result<CancelTransferParams> {
         let at = get_current_timestamp()?;
-        require!(
-            signer.key() == self.recipient
-                || (self.anyone_can_withdraw_for_recipient && self.anyone_can_withdraw_for_recipient_at <= at),
-            StreamError::UserUnauthorizedToWithdraw,
-        );
+        self.update(|state| state.terminate_with_settlement(at, signer.key(), recipient, settlement_amount))
+    }
+
+    pub(crate) fn freeze(&mut self, signer: &Signer) -> Result<()> {
+        self.update(|state| state.freeze(signer.key()))
+    }
+
+    pub(crate) fn unfreeze(&mut self, signer: &Signer) -> Result<()> {
+        self.update(|state| state.unfreeze(signer.key()))
+    }
 
-        let total_topup_amount = self.total_topup_amount;
+    pub(crate) fn resolve(
+        &mut self,
+        signer: &Signer,
+        recipient: Pubkey,
+        recipient_amount: u64,
+    ) -> Result<CancelTransferParams> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.resolve(at, signer.key(), recipient, recipient_amount))
+    }
 
+    pub(crate) fn validate_close(&self, signer: &Signer) -> Result<()> {
         let at = get_current_timestamp()?;
-        let mut amount_owed = self.get_amount_owed(at)?;
-        if amount_owed > total_topup_amount {
-            // The stream is insolvent. Cancel the stream if not already cancelled. Recipient is owed the whole topup
-            // amount and if the stream is not cancelled yet, also the deposit amount.
-            amount_owed = if self.is_cancelled {
-                total_topup_amount
-            } else {
-                self.mark_cancelled(at, signer);
-                total_topup_amount
-                    .checked_add(self.deposit_needed)
-                    .ok_or(error!(StreamError::WithdrawAmountOutOfBounds))?
-            }
+        self.state().close(at, signer.key()).map_err(math_error)
+    }
+
+    // --- Instruction functions --- END ---
+}
+
+/// Withdrawable amount of a stream. Returned by the `get_withdrawable` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithdrawableView {
+    /// Time at which the figures were computed.
+    pub at: u64,
+    /// Total amount owed to the recipient.
+    pub amount_owed: u64,
+    /// Total amount withdrawn by the recipient.
+    pub total_withdrawn_amount: u64,
+    /// Amount the recipient can withdraw. This doesn't include the recipient's share of the deposit which is only
+    /// available once an insolvent stream is cancelled.
+    pub withdrawable_amount: u64,
+}
+
+/// Solvency of a stream. Returned by the `get_solvency` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolvencyView {
+    /// Time at which the figures were computed.
+    pub at: u64,
+    /// Status of the stream.
+    pub status: StreamStatus,
+    /// True if the amount owed to the recipient is covered by the total topup amount.
+    pub is_solvent: bool,
+    /// True if the stream is insolvent but still within the insolvency grace period.
+    pub is_in_insolvency_grace_period: bool,
+    /// Time from which the stream is insolvent. See [`Stream::insolvent_at`] for more information.
+    pub insolvent_at: Option<u64>,
+}
+
+/// Maximum acceptable topup amount of a stream. Returned by the `get_max_topup` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxTopupView {
+    /// Time at which the figures were computed.
+    pub at: u64,
+    /// True if there is no limit on the topup amount.
+    pub has_no_limit: bool,
+    /// Maximum acceptable topup amount. Only valid if `has_no_limit` is false.
+    pub max_acceptable_topup_amount: u64,
+}
+
+/// Status of a stream at a point in time. These match the states documented on the [`Stream`] struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamStatus {
+    /// The stream has not started yet or is pending recipient acceptance.
+    Scheduled,
+    /// The stream has started, is not paused and is solvent.
+    Streaming,
+    /// The stream has started, is paused and is solvent.
+    Paused,
+    /// The stream has started and the amount owed to the recipient is more than the total topup amount. A paused stream
+    /// can be insolvent too.
+    Insolvent,
+    /// The stream has reached its end time.
+    Ended,
+    /// The stream has been cancelled, either before or after start.
+    Cancelled,
+}
+
+impl From<superstream_math::StreamStatus> for StreamStatus {
+    fn from(status: superstream_math::StreamStatus) -> Self {
+        match status {
+            superstream_math::StreamStatus::Scheduled => StreamStatus::Scheduled,
+            superstream_math::StreamStatus::Streaming => StreamStatus::Streaming,
+            superstream_math::StreamStatus::Paused => StreamStatus::Paused,
+            superstream_math::StreamStatus::Insolvent => StreamStatus::Insolvent,
+            superstream_math::StreamStatus::Ended => StreamStatus::Ended,
+            superstream_math::StreamStatus::Cancelled => StreamStatus::Cancelled,
         }
+    }
+}
+
+/// A stream account in any of the supported layouts. All the instructions take streams as `VersionedStream`, so that
+/// streams created before layout versions were introduced can still be used while they are being migrated.
+///
+/// Streams in the legacy layout are read with the fields introduced later set to their defaults and are written back
+/// in the legacy layout. Instructions which would change any of those fields fail with
+/// [`StreamError::StreamNotMigrated`] till the stream is migrated to the newest layout by the `migrate_stream`
+/// instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionedStream(Stream);
+
+impl VersionedStream {
+    /// Get the stream in the newest layout.
+    pub fn into_inner(self) -> Stream {
+        self.0
+    }
+}
+
+impl From<Stream> for VersionedStream {
+    fn from(stream: Stream) -> Self {
+        Self(stream)
+    }
+}
+
+impl std::ops::Deref for VersionedStream {
+    type Target = Stream;
 
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for VersionedStream {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Owner for VersionedStream {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountDeserialize for VersionedStream {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
         require!(
-            amount_owed >= self.total_withdrawn_amount,
-            StreamError::WithdrawnAmountGreaterThanAmountOwed,
+            buf.len() >= ANCHOR_DISCRIMINATOR_LENGTH,
+            ErrorCode::AccountDiscriminatorNotFound
         );
-        let amount_available_to_withdraw = amount_owed - self.total_withdrawn_amount;
-        self.add_withdrawn_amount(at, amount_available_to_withdraw)?;
-        if !self.is_cancelled && new_recipient != Pubkey::default() {
-            // Only the recipient can change the recipient.
-            require!(signer.key() == self.recipient, StreamError::UserUnauthorizedToWithdraw);
-            require!(new_recipient != self.recipient, StreamError::SameRecipients);
-            self.recipient = new_recipient;
+        require!(
+            buf[..ANCHOR_DISCRIMINATOR_LENGTH] == Stream::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        // Zeroed accounts being initialized are read as legacy streams too. Their version is set on initialization.
+        let version = buf.get(Stream::VERSION_OFFSET).copied().unwrap_or_default();
+        if version != LEGACY_STREAM_LAYOUT_VERSION {
+            return Stream::try_deserialize_unchecked(buf).map(Self);
         }
 
-        Ok(amount_available_to_withdraw)
+        let mut data = buf.get(ANCHOR_DISCRIMINATOR_LENGTH..).unwrap_or_default();
+        let legacy = LegacyStream::deserialize(&mut data).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        Ok(Self(legacy.into()))
     }
+}
 
-    pub(crate) fn pause_non_prepaid(&mut self, signer: &Signer) -> Result<()> {
-        require!(!self.is_prepaid, StreamError::StreamIsPrepaid);
-        require!(!self.is_paused, StreamError::StreamIsPaused);
-        require!(self.has_flow_payments(), StreamError::StreamHasNoFlowPayments);
+impl AccountSerialize for VersionedStream {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        if !self.is_legacy() {
+            return self.0.try_serialize(writer);
+        }
 
-        let signer_key = signer.key();
-        let is_sender = signer_key == self.sender;
-        let is_recipient = signer_key == self.recipient;
-        require!(is_sender || is_recipient, StreamError::UserUnauthorizedToPause);
+        let legacy = LegacyStream::from(&self.0);
+        require!(Stream::from(legacy.clone()) == self.0, StreamError::StreamNotMigrated);
+        writer
+            .write_all(&Stream::DISCRIMINATOR)
+            .map_err(|_| error!(ErrorCode::AccountDidNotSerialize))?;
+        legacy
+            .serialize(writer)
+            .map_err(|_| error!(ErrorCode::AccountDidNotSerialize))
+    }
+}
 
-        let at = get_current_timestamp()?;
-        require!(
-            is_recipient || (self.sender_can_pause && self.sender_can_pause_at <= at),
-            StreamError::SenderCannotPause
-        );
+/// Layout of the stream accounts created before layout versions were introduced. It has the fields of [`Stream`] upto
+/// `deposit_needed` followed by zeroed reserved space, which is where the version byte is in the newer layouts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+struct LegacyStream {
+    is_prepaid: bool,
+    mint: Pubkey,
+    sender: Pubkey,
+    recipient: Pubkey,
+    created_at: u64,
+    starts_at: u64,
+    ends_at: u64,
+    initial_amount: u64,
+    flow_interval: u64,
+    flow_rate: u64,
+    is_cancelled: bool,
+    is_cancelled_before_start: bool,
+    is_cancelled_by_sender: bool,
+    cancelled_at: u64,
+    sender_can_cancel: bool,
+    sender_can_cancel_at: u64,
+    sender_can_change_sender: bool,
+    sender_can_change_sender_at: u64,
+    is_paused: bool,
+    is_paused_by_sender: bool,
+    sender_can_pause: bool,
+    sender_can_pause_at: u64,
+    recipient_can_resume_pause_by_sender: bool,
+    recipient_can_resume_pause_by_sender_at: u64,
+    anyone_can_withdraw_for_recipient: bool,
+    anyone_can_withdraw_for_recipient_at: u64,
+    last_resumed_at: u64,
+    accumulated_active_time: u64,
+    total_withdrawn_amount: u64,
+    last_withdrawn_at: u64,
+    last_withdrawn_amount: u64,
+    total_topup_amount: u64,
+    last_topup_at: u64,
+    last_topup_amount: u64,
+    deposit_needed: u64,
+    reserved: [u64; 16],
+    seed: u64,
+    bump: u8,
+    name: String,
+}
 
-        require!(!self.has_stopped(at), StreamError::StreamHasStopped);
+impl From<LegacyStream> for Stream {
+    fn from(legacy: LegacyStream) -> Self {
+        Self {
+            is_prepaid: legacy.is_prepaid,
+            mint: legacy.mint,
+            sender: legacy.sender,
+            recipient: legacy.recipient,
+            created_at: legacy.created_at,
+            starts_at: legacy.starts_at,
+            ends_at: legacy.ends_at,
+            initial_amount: legacy.initial_amount,
+            flow_interval: legacy.flow_interval,
+            flow_rate: legacy.flow_rate,
+            is_cancelled: legacy.is_cancelled,
+            is_cancelled_before_start: legacy.is_cancelled_before_start,
+            is_cancelled_by_sender: legacy.is_cancelled_by_sender,
+            cancelled_at: legacy.cancelled_at,
+            sender_can_cancel: legacy.sender_can_cancel,
+            sender_can_cancel_at: legacy.sender_can_cancel_at,
+            sender_can_change_sender: legacy.sender_can_change_sender,
+            sender_can_change_sender_at: legacy.sender_can_change_sender_at,
+            is_paused: legacy.is_paused,
+            is_paused_by_sender: legacy.is_paused_by_sender,
+            sender_can_pause: legacy.sender_can_pause,
+            sender_can_pause_at: legacy.sender_can_pause_at,
+            recipient_can_resume_pause_by_sender: legacy.recipient_can_resume_pause_by_sender,
+            recipient_can_resume_pause_by_sender_at: legacy.recipient_can_resume_pause_by_sender_at,
+            anyone_can_withdraw_for_recipient: legacy.anyone_can_withdraw_for_recipient,
+            anyone_can_withdraw_for_recipient_at: legacy.anyone_can_withdraw_for_recipient_at,
+            last_resumed_at: legacy.last_resumed_at,
+            accumulated_active_time: legacy.accumulated_active_time,
+            total_withdrawn_amount: legacy.total_withdrawn_amount,
+            last_withdrawn_at: legacy.last_withdrawn_at,
+            last_withdrawn_amount: legacy.last_withdrawn_amount,
+            total_topup_amount: legacy.total_topup_amount,
+            last_topup_at: legacy.last_topup_at,
+            last_topup_amount: legacy.last_topup_amount,
+            deposit_needed: legacy.deposit_needed,
+            version: LEGACY_STREAM_LAYOUT_VERSION,
+            insolvency_grace_period: 0,
+            recipient_deposit_share_bps: 0,
+            settlement_amount: 0,
+            is_settlement_proposed: false,
+            is_settlement_proposed_by_sender: false,
+            is_settled: false,
+            arbiter: Pubkey::default(),
+            is_frozen: false,
+            is_pending_acceptance: false,
+            pending_sender: Pubkey::default(),
+            pending_recipient: Pubkey::default(),
+            sender_can_cancel_expires_at: 0,
+            sender_can_change_sender_expires_at: 0,
+            sender_can_pause_expires_at: 0,
+            recipient_can_resume_pause_by_sender_expires_at: 0,
+            anyone_can_withdraw_for_recipient_expires_at: 0,
+            derivation: StreamDerivation::Global,
+            derivation_sender: Pubkey::default(),
+            derivation_recipient: Pubkey::default(),
+            is_counted_in_mint_stats: false,
+            reserved: [0; 7],
+            seed: legacy.seed,
+            bump: legacy.bump,
+            name: legacy.name,
+        }
+    }
+}
 
-        // Update accumulated_active_time if there has been any flow till `at`.
-        if at > self.starts_at {
-            self.accumulated_active_time = self.unsafe_get_active_time_after_start(at)?;
+impl From<&Stream> for LegacyStream {
+    fn from(stream: &Stream) -> Self {
+        Self {
+            is_prepaid: stream.is_prepaid,
+            mint: stream.mint,
+            sender: stream.sender,
+            recipient: stream.recipient,
+            created_at: stream.created_at,
+            starts_at: stream.starts_at,
+            ends_at: stream.ends_at,
+            initial_amount: stream.initial_amount,
+            flow_interval: stream.flow_interval,
+            flow_rate: stream.flow_rate,
+            is_cancelled: stream.is_cancelled,
+            is_cancelled_before_start: stream.is_cancelled_before_start,
+            is_cancelled_by_sender: stream.is_cancelled_by_sender,
+            cancelled_at: stream.cancelled_at,
+            sender_can_cancel: stream.sender_can_cancel,
+            sender_can_cancel_at: stream.sender_can_cancel_at,
+            sender_can_change_sender: stream.sender_can_change_sender,
+            sender_can_change_sender_at: stream.sender_can_change_sender_at,
+            is_paused: stream.is_paused,
+            is_paused_by_sender: stream.is_paused_by_sender,
+            sender_can_pause: stream.sender_can_pause,
+            sender_can_pause_at: stream.sender_can_pause_at,
+            recipient_can_resume_pause_by_sender: stream.recipient_can_resume_pause_by_sender,
+            recipient_can_resume_pause_by_sender_at: stream.recipient_can_resume_pause_by_sender_at,
+            anyone_can_withdraw_for_recipient: stream.anyone_can_withdraw_for_recipient,
+            anyone_can_withdraw_for_recipient_at: stream.anyone_can_withdraw_for_recipient_at,
+            last_resumed_at: stream.last_resumed_at,
+            accumulated_active_time: stream.accumulated_active_time,
+            total_withdrawn_amount: stream.total_withdrawn_amount,
+            last_withdrawn_at: stream.last_withdrawn_at,
+            last_withdrawn_amount: stream.last_withdrawn_amount,
+            total_topup_amount: stream.total_topup_amount,
+            last_topup_at: stream.last_topup_at,
+            last_topup_amount: stream.last_topup_amount,
+            deposit_needed: stream.deposit_needed,
+            reserved: [0; 16],
+            seed: stream.seed,
+            bump: stream.bump,
+            name: stream.name.clone(),
         }
+    }
+}
 
-        self.is_paused = true;
-        self.is_paused_by_sender = is_sender;
+/// A stream in the zero-copy layout. Zero-copy streams are accessed in place through an `AccountLoader` instead of
+/// being deserialized and serialized again by every instruction. The name is stored in a fixed-size buffer, the booleans
+/// are packed in [`Self::flags`] and the fields are ordered so that they are aligned without any padding.
+///
+/// Streams are converted to and from this layout by the `convert_stream_to_zero_copy` and
+/// `convert_stream_from_zero_copy` instructions. Zero-copy streams can only be used with `withdraw_zero_copy`,
+/// `topup_non_prepaid_zero_copy` and `cancel_zero_copy`. They need to be converted back for everything else. See
+/// [`Stream`] for the documentation of the fields.
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct ZeroCopyStream {
+    pub mint: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub arbiter: Pubkey,
+    pub pending_sender: Pubkey,
+    pub pending_recipient: Pubkey,
+    pub derivation_sender: Pubkey,
+    pub derivation_recipient: Pubkey,
 
-        Ok(())
+    pub created_at: u64,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub initial_amount: u64,
+    pub flow_interval: u64,
+    pub flow_rate: u64,
+    pub cancelled_at: u64,
+    pub sender_can_cancel_at: u64,
+    pub sender_can_change_sender_at: u64,
+    pub sender_can_pause_at: u64,
+    pub recipient_can_resume_pause_by_sender_at: u64,
+    pub anyone_can_withdraw_for_recipient_at: u64,
+    pub last_resumed_at: u64,
+    pub accumulated_active_time: u64,
+    pub total_withdrawn_amount: u64,
+    pub last_withdrawn_at: u64,
+    pub last_withdrawn_amount: u64,
+    pub total_topup_amount: u64,
+    pub last_topup_at: u64,
+    pub last_topup_amount: u64,
+    pub deposit_needed: u64,
+    pub insolvency_grace_period: u64,
+    pub settlement_amount: u64,
+    pub sender_can_cancel_expires_at: u64,
+    pub sender_can_change_sender_expires_at: u64,
+    pub sender_can_pause_expires_at: u64,
+    pub recipient_can_resume_pause_by_sender_expires_at: u64,
+    pub anyone_can_withdraw_for_recipient_expires_at: u64,
+    pub seed: u64,
+
+    /// Booleans of the stream packed as bit flags. See the `ZeroCopyStream::IS_*` and `ZeroCopyStream::*_CAN_*`
+    /// constants.
+    pub flags: u32,
+    pub recipient_deposit_share_bps: u16,
+    /// Length of the name in bytes.
+    ///
+    /// INVARIANT: <= MAX_STREAM_NAME_BYTES
+    pub name_length: u16,
+    /// [`StreamDerivation`] as a `u8`.
+    pub derivation: u8,
+    pub bump: u8,
+    pub padding: [u8; 6],
+
+    /// Extra space for program upgrades.
+    pub reserved: [u64; 8],
+
+    /// UTF-8 bytes of the name followed by zeroes.
+    pub name: [u8; MAX_STREAM_NAME_BYTES],
+}
+
+// The struct is marked as `Pod` without bytemuck checking it, so make sure it has no padding.
+const _: () = assert!(
+    std::mem::size_of::<ZeroCopyStream>()
+        == 8 * PUBLIC_KEY_LENGTH + 29 * U64_LENGTH + 8 + 8 + 8 * U64_LENGTH + MAX_STREAM_NAME_BYTES
+);
+
+impl ZeroCopyStream {
+    /// Total size of a zero-copy stream account.
+    pub const LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH + std::mem::size_of::<Self>();
+
+    pub const IS_PREPAID: u32 = 1 << 0;
+    pub const IS_CANCELLED: u32 = 1 << 1;
+    pub const IS_CANCELLED_BEFORE_START: u32 = 1 << 2;
+    pub const IS_CANCELLED_BY_SENDER: u32 = 1 << 3;
+    pub const SENDER_CAN_CANCEL: u32 = 1 << 4;
+    pub const SENDER_CAN_CHANGE_SENDER: u32 = 1 << 5;
+    pub const IS_PAUSED: u32 = 1 << 6;
+    pub const IS_PAUSED_BY_SENDER: u32 = 1 << 7;
+    pub const SENDER_CAN_PAUSE: u32 = 1 << 8;
+    pub const RECIPIENT_CAN_RESUME_PAUSE_BY_SENDER: u32 = 1 << 9;
+    pub const ANYONE_CAN_WITHDRAW_FOR_RECIPIENT: u32 = 1 << 10;
+    pub const IS_SETTLEMENT_PROPOSED: u32 = 1 << 11;
+    pub const IS_SETTLEMENT_PROPOSED_BY_SENDER: u32 = 1 << 12;
+    pub const IS_SETTLED: u32 = 1 << 13;
+    pub const IS_FROZEN: u32 = 1 << 14;
+    pub const IS_PENDING_ACCEPTANCE: u32 = 1 << 15;
+    pub const IS_COUNTED_IN_MINT_STATS: u32 = 1 << 16;
+
+    /// Returns true if the bit flag is set.
+    pub fn flag(&self, flag: u32) -> bool {
+        self.flags & flag != 0
     }
 
-    pub(crate) fn resume_non_prepaid(&mut self, signer: &Signer) -> Result<()> {
-        require!(!self.is_prepaid, StreamError::StreamIsPrepaid);
-        require!(self.is_paused, StreamError::StreamIsNotPaused);
+    fn set_flag(&mut self, flag: u32, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    /// Get the mode used to derive the stream PDA address.
+    pub fn derivation(&self) -> StreamDerivation {
+        match self.derivation {
+            1 => StreamDerivation::Sender,
+            2 => StreamDerivation::SenderAndRecipient,
+            _ => StreamDerivation::Global,
+        }
+    }
 
-        let signer_key = signer.key();
-        let is_sender = signer_key == self.sender;
-        let is_recipient = signer_key == self.recipient;
-        require!(is_sender || is_recipient, StreamError::UserUnauthorizedToResume);
+    /// Get the name of the stream.
+    pub fn name(&self) -> Result<&str> {
+        std::str::from_utf8(&self.name[..self.name_length as usize])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
 
-        let at = get_current_timestamp()?;
+    /// Get the sender seed of the stream PDA account. See [`StreamDerivation`].
+    pub fn sender_seed(&self) -> &[u8] {
+        self.derivation().sender_seed(&self.derivation_sender)
+    }
+
+    /// Get the recipient seed of the stream PDA account. See [`StreamDerivation`].
+    pub fn recipient_seed(&self) -> &[u8] {
+        self.derivation().recipient_seed(&self.derivation_recipient)
+    }
+
+    /// Returns true if the stream is counted in the [`MintStats`] of its mint.
+    pub fn is_counted_in_mint_stats(&self) -> bool {
+        self.flag(Self::IS_COUNTED_IN_MINT_STATS)
+    }
+
+    /// Get the accounting state of the stream. See [`Stream::state`].
+    pub fn state(&self) -> StreamState {
+        StreamState {
+            is_prepaid: self.flag(Self::IS_PREPAID),
+            mint: self.mint,
+            sender: self.sender,
+            recipient: self.recipient,
+            created_at: self.created_at,
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+            initial_amount: self.initial_amount,
+            flow_interval: self.flow_interval,
+            flow_rate: self.flow_rate,
+            is_cancelled: self.flag(Self::IS_CANCELLED),
+            is_cancelled_before_start: self.flag(Self::IS_CANCELLED_BEFORE_START),
+            is_cancelled_by_sender: self.flag(Self::IS_CANCELLED_BY_SENDER),
+            cancelled_at: self.cancelled_at,
+            sender_can_cancel: self.flag(Self::SENDER_CAN_CANCEL),
+            sender_can_cancel_at: self.sender_can_cancel_at,
+            sender_can_change_sender: self.flag(Self::SENDER_CAN_CHANGE_SENDER),
+            sender_can_change_sender_at: self.sender_can_change_sender_at,
+            is_paused: self.flag(Self::IS_PAUSED),
+            is_paused_by_sender: self.flag(Self::IS_PAUSED_BY_SENDER),
+            sender_can_pause: self.flag(Self::SENDER_CAN_PAUSE),
+            sender_can_pause_at: self.sender_can_pause_at,
+            recipient_can_resume_pause_by_sender: self.flag(Self::RECIPIENT_CAN_RESUME_PAUSE_BY_SENDER),
+            recipient_can_resume_pause_by_sender_at: self.recipient_can_resume_pause_by_sender_at,
+            anyone_can_withdraw_for_recipient: self.flag(Self::ANYONE_CAN_WITHDRAW_FOR_RECIPIENT),
+            anyone_can_withdraw_for_recipient_at: self.anyone_can_withdraw_for_recipient_at,
+            last_resumed_at: self.last_resumed_at,
+            accumulated_active_time: self.accumulated_active_time,
+            total_withdrawn_amount: self.total_withdrawn_amount,
+            last_withdrawn_at: self.last_withdrawn_at,
+            last_withdrawn_amount: self.last_withdrawn_amount,
+            total_topup_amount: self.total_topup_amount,
+            last_topup_at: self.last_topup_at,
+            last_topup_amount: self.last_topup_amount,
+            deposit_needed: self.deposit_needed,
+            insolvency_grace_period: self.insolvency_grace_period,
+            recipient_deposit_share_bps: self.recipient_deposit_share_bps,
+            settlement_amount: self.settlement_amount,
+            is_settlement_proposed: self.flag(Self::IS_SETTLEMENT_PROPOSED),
+            is_settlement_proposed_by_sender: self.flag(Self::IS_SETTLEMENT_PROPOSED_BY_SENDER),
+            is_settled: self.flag(Self::IS_SETTLED),
+            arbiter: self.arbiter,
+            is_frozen: self.flag(Self::IS_FROZEN),
+            is_pending_acceptance: self.flag(Self::IS_PENDING_ACCEPTANCE),
+            pending_sender: self.pending_sender,
+            pending_recipient: self.pending_recipient,
+            sender_can_cancel_expires_at: self.sender_can_cancel_expires_at,
+            sender_can_change_sender_expires_at: self.sender_can_change_sender_expires_at,
+            sender_can_pause_expires_at: self.sender_can_pause_expires_at,
+            recipient_can_resume_pause_by_sender_expires_at: self.recipient_can_resume_pause_by_sender_expires_at,
+            anyone_can_withdraw_for_recipient_expires_at: self.anyone_can_withdraw_for_recipient_expires_at,
+        }
+    }
+
+    fn set_state(&mut self, state: StreamState) {
+        self.mint = state.mint;
+        self.sender = state.sender;
+        self.recipient = state.recipient;
+        self.arbiter = state.arbiter;
+        self.pending_sender = state.pending_sender;
+        self.pending_recipient = state.pending_recipient;
+        self.set_accounting_state(&state);
+    }
+
+    /// Save everything in the accounting state of the stream except the keys.
+    fn set_accounting_state(&mut self, state: &StreamState) {
+        self.set_flag(Self::IS_PREPAID, state.is_prepaid);
+        self.created_at = state.created_at;
+        self.starts_at = state.starts_at;
+        self.ends_at = state.ends_at;
+        self.initial_amount = state.initial_amount;
+        self.flow_interval = state.flow_interval;
+        self.flow_rate = state.flow_rate;
+        self.set_flag(Self::IS_CANCELLED, state.is_cancelled);
+        self.set_flag(Self::IS_CANCELLED_BEFORE_START, state.is_cancelled_before_start);
+        self.set_flag(Self::IS_CANCELLED_BY_SENDER, state.is_cancelled_by_sender);
+        self.cancelled_at = state.cancelled_at;
+        self.set_flag(Self::SENDER_CAN_CANCEL, state.sender_can_cancel);
+        self.sender_can_cancel_at = state.sender_can_cancel_at;
+        self.set_flag(Self::SENDER_CAN_CHANGE_SENDER, state.sender_can_change_sender);
+        self.sender_can_change_sender_at = state.sender_can_change_sender_at;
+        self.set_flag(Self::IS_PAUSED, state.is_paused);
+        self.set_flag(Self::IS_PAUSED_BY_SENDER, state.is_paused_by_sender);
+        self.set_flag(Self::SENDER_CAN_PAUSE, state.sender_can_pause);
+        self.sender_can_pause_at = state.sender_can_pause_at;
+        self.set_flag(
+            Self::RECIPIENT_CAN_RESUME_PAUSE_BY_SENDER,
+            state.recipient_can_resume_pause_by_sender,
+        );
+        self.recipient_can_resume_pause_by_sender_at = state.recipient_can_resume_pause_by_sender_at;
+        self.set_flag(
+            Self::ANYONE_CAN_WITHDRAW_FOR_RECIPIENT,
+            state.anyone_can_withdraw_for_recipient,
+        );
+        self.anyone_can_withdraw_for_recipient_at = state.anyone_can_withdraw_for_recipient_at;
+        self.last_resumed_at = state.last_resumed_at;
+        self.accumulated_active_time = state.accumulated_active_time;
+        self.total_withdrawn_amount = state.total_withdrawn_amount;
+        self.last_withdrawn_at = state.last_withdrawn_at;
+        self.last_withdrawn_amount = state.last_withdrawn_amount;
+        self.total_topup_amount = state.total_topup_amount;
+        self.last_topup_at = state.last_topup_at;
+        self.last_topup_amount = state.last_topup_amount;
+        self.deposit_needed = state.deposit_needed;
+        self.insolvency_grace_period = state.insolvency_grace_period;
+        self.recipient_deposit_share_bps = state.recipient_deposit_share_bps;
+        self.settlement_amount = state.settlement_amount;
+        self.set_flag(Self::IS_SETTLEMENT_PROPOSED, state.is_settlement_proposed);
+        self.set_flag(
+            Self::IS_SETTLEMENT_PROPOSED_BY_SENDER,
+            state.is_settlement_proposed_by_sender,
+        );
+        self.set_flag(Self::IS_SETTLED, state.is_settled);
+        self.set_flag(Self::IS_FROZEN, state.is_frozen);
+        self.set_flag(Self::IS_PENDING_ACCEPTANCE, state.is_pending_acceptance);
+        self.sender_can_cancel_expires_at = state.sender_can_cancel_expires_at;
+        self.sender_can_change_sender_expires_at = state.sender_can_change_sender_expires_at;
+        self.sender_can_pause_expires_at = state.sender_can_pause_expires_at;
+        self.recipient_can_resume_pause_by_sender_expires_at = state.recipient_can_resume_pause_by_sender_expires_at;
+        self.anyone_can_withdraw_for_recipient_expires_at = state.anyone_can_withdraw_for_recipient_expires_at;
+    }
+
+    /// Run the stream math on the accounting state of the stream and save the updated state if it succeeds. None of the
+    /// zero-copy instructions change the keys of the stream, so they are not written back. This saves copying most of
+    /// the state.
+    fn update<T>(&mut self, f: impl FnOnce(&mut StreamState) -> superstream_math::Result<T>) -> Result<T> {
+        let mut state = self.state();
+        let result = f(&mut state).map_err(math_error)?;
+        #[cfg(feature = "invariant-checks")]
         require!(
-            is_sender
-                || !self.is_paused_by_sender
-                || (self.recipient_can_resume_pause_by_sender && self.recipient_can_resume_pause_by_sender_at <= at),
-            StreamError::RecipientCannotResumePauseBySender
+            state.mint == self.mint
+                && state.sender == self.sender
+                && state.recipient == self.recipient
+                && state.arbiter == self.arbiter
+                && state.pending_sender == self.pending_sender
+                && state.pending_recipient == self.pending_recipient,
+            StreamError::InvariantViolation
         );
+        self.set_accounting_state(&state);
+        Ok(result)
+    }
 
-        require!(!self.has_stopped(at), StreamError::StreamHasStopped);
+    /// Copy a stream into the zero-copy layout. The reserved space is not copied.
+    pub(crate) fn copy_from_stream(&mut self, stream: &Stream) -> Result<()> {
+        let name = stream.name.as_bytes();
+        require!(name.len() <= MAX_STREAM_NAME_BYTES, StreamError::StreamNameTooLong);
+
+        self.set_state(stream.state());
+        self.set_flag(Self::IS_COUNTED_IN_MINT_STATS, stream.is_counted_in_mint_stats);
+        self.derivation = stream.derivation as u8;
+        self.derivation_sender = stream.derivation_sender;
+        self.derivation_recipient = stream.derivation_recipient;
+        self.seed = stream.seed;
+        self.bump = stream.bump;
+        self.name_length = name.len() as u16;
+        self.name = [0; MAX_STREAM_NAME_BYTES];
+        self.name[..name.len()].copy_from_slice(name);
+        Ok(())
+    }
 
-        self.is_paused = false;
-        self.is_paused_by_sender = false;
+    /// Copy the stream into the newest [`Stream`] layout.
+    pub fn to_stream(&self) -> Result<Stream> {
+        let state = self.state();
+        Ok(Stream {
+            is_prepaid: state.is_prepaid,
+            mint: state.mint,
+            sender: state.sender,
+            recipient: state.recipient,
+            created_at: state.created_at,
+            starts_at: state.starts_at,
+            ends_at: state.ends_at,
+            initial_amount: state.initial_amount,
+            flow_interval: state.flow_interval,
+            flow_rate: state.flow_rate,
+            is_cancelled: state.is_cancelled,
+            is_cancelled_before_start: state.is_cancelled_before_start,
+            is_cancelled_by_sender: state.is_cancelled_by_sender,
+            cancelled_at: state.cancelled_at,
+            sender_can_cancel: state.sender_can_cancel,
+            sender_can_cancel_at: state.sender_can_cancel_at,
+            sender_can_change_sender: state.sender_can_change_sender,
+            sender_can_change_sender_at: state.sender_can_change_sender_at,
+            is_paused: state.is_paused,
+            is_paused_by_sender: state.is_paused_by_sender,
+            sender_can_pause: state.sender_can_pause,
+            sender_can_pause_at: state.sender_can_pause_at,
+            recipient_can_resume_pause_by_sender: state.recipient_can_resume_pause_by_sender,
+            recipient_can_resume_pause_by_sender_at: state.recipient_can_resume_pause_by_sender_at,
+            anyone_can_withdraw_for_recipient: state.anyone_can_withdraw_for_recipient,
+            anyone_can_withdraw_for_recipient_at: state.anyone_can_withdraw_for_recipient_at,
+            last_resumed_at: state.last_resumed_at,
+            accumulated_active_time: state.accumulated_active_time,
+            total_withdrawn_amount: state.total_withdrawn_amount,
+            last_withdrawn_at: state.last_withdrawn_at,
+            last_withdrawn_amount: state.last_withdrawn_amount,
+            total_topup_amount: state.total_topup_amount,
+            last_topup_at: state.last_topup_at,
+            last_topup_amount: state.last_topup_amount,
+            deposit_needed: state.deposit_needed,
+            version: STREAM_LAYOUT_VERSION,
+            insolvency_grace_period: state.insolvency_grace_period,
+            recipient_deposit_share_bps: state.recipient_deposit_share_bps,
+            settlement_amount: state.settlement_amount,
+            is_settlement_proposed: state.is_settlement_proposed,
+            is_settlement_proposed_by_sender: state.is_settlement_proposed_by_sender,
+            is_settled: state.is_settled,
+            arbiter: state.arbiter,
+            is_frozen: state.is_frozen,
+            is_pending_acceptance: state.is_pending_acceptance,
+            pending_sender: state.pending_sender,
+            pending_recipient: state.pending_recipient,
+            sender_can_cancel_expires_at: state.sender_can_cancel_expires_at,
+            sender_can_change_sender_expires_at: state.sender_can_change_sender_expires_at,
+            sender_can_pause_expires_at: state.sender_can_pause_expires_at,
+            recipient_can_resume_pause_by_sender_expires_at: state.recipient_can_resume_pause_by_sender_expires_at,
+            anyone_can_withdraw_for_recipient_expires_at: state.anyone_can_withdraw_for_recipient_expires_at,
+            derivation: self.derivation(),
+            derivation_sender: self.derivation_sender,
+            derivation_recipient: self.derivation_recipient,
+            is_counted_in_mint_stats: self.is_counted_in_mint_stats(),
+            reserved: [0; 7],
+            seed: self.seed,
+            bump: self.bump,
+            name: self.name()?.to_owned(),
+        })
+    }
+
+    pub(crate) fn topup_non_prepaid(&mut self, signer: &Signer, topup_amount: u64, is_strict: bool) -> Result<u64> {
+        let at = get_current_timestamp()?;
+        self.update(|state| state.topup_non_prepaid(at, signer.key(), topup_amount, is_strict))
+    }
 
-        // Update last_resumed_at if there has been any flow till `at`.
-        if at > self.starts_at {
-            self.last_resumed_at = at;
+    pub(crate) fn withdraw(&mut self, signer: &Signer) -> Result<WithdrawTransferParams> {
+        let at = get_current_timestamp()?;
+        let recipient = self.recipient;
+        self.update(|state| state.withdraw_and_change_recipient(at, signer.key(), recipient, Pubkey::default()))
+    }
+
+    pub(crate) fn cancel(&mut self, signer: &Signer) -> Result<CancelTransferParams> {
+        let at = get_current_timestamp()?;
+        let recipient = self.recipient;
+        self.update(|state| state.cancel(at, signer.key(), recipient))
+    }
+}
+
+/// Implement the account traits of a stream being converted to another layout. The stream is read in the layout `$from`
+/// and is never written back on exit, since the conversion instructions store the stream in the other layout
+/// themselves.
+macro_rules! impl_convertible_stream {
+    ($name:ident, $from:ty) => {
+        impl Owner for $name {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
         }
 
+        impl AccountDeserialize for $name {
+            fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+                <$from>::try_deserialize(buf).and_then(Self::try_from)
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                <$from>::try_deserialize_unchecked(buf).and_then(Self::try_from)
+            }
+        }
+
+        impl AccountSerialize for $name {}
+
+        impl std::ops::Deref for $name {
+            type Target = Stream;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+}
+
+/// A stream account in any of the [`VersionedStream`] layouts being converted to the zero-copy layout. Unlike
+/// `Account<VersionedStream>`, it is not written back on exit, so the instruction can store the zero-copy layout.
+#[derive(Clone)]
+pub struct ConvertibleStream(Stream);
+
+impl TryFrom<VersionedStream> for ConvertibleStream {
+    type Error = Error;
+
+    fn try_from(stream: VersionedStream) -> Result<Self> {
+        Ok(Self(stream.into_inner()))
+    }
+}
+
+impl_convertible_stream!(ConvertibleStream, VersionedStream);
+
+/// A stream account in the [`ZeroCopyStream`] layout being converted back to the newest [`Stream`] layout. Unlike
+/// `AccountLoader<ZeroCopyStream>`, it doesn't write the zero-copy discriminator back on exit.
+#[derive(Clone)]
+pub struct ConvertibleZeroCopyStream(Stream);
+
+impl TryFrom<ZeroCopyStream> for ConvertibleZeroCopyStream {
+    type Error = Error;
+
+    fn try_from(stream: ZeroCopyStream) -> Result<Self> {
+        stream.to_stream().map(Self)
+    }
+}
+
+impl_convertible_stream!(ConvertibleZeroCopyStream, ZeroCopyStream);
+
+/// Convert an error returned by the stream math to the program error with the same name.
+fn math_error(err: MathError) -> Error {
+    error!(StreamError::from(err))
+}
+
+/// Registry of SPL token mints for which streams can be created. There is only 1 registry per deployment of the
+/// program.
+///
+/// If the registry has not been initialized or is disabled, streams can be created for any mint, i.e. the deployment is
+/// permissionless. If it is enabled, streams can only be created for mints which have an [`AllowedMint`] account.
+#[account]
+#[derive(Debug, PartialEq, Eq)]
+pub struct MintRegistry {
+    /// Protocol authority address. Only the authority can manage the registry and the allowed mints.
+    pub authority: Pubkey,
+    /// If true, the registry is enabled and streams can only be created for allowed mints.
+    pub is_enabled: bool,
+
+    /// Extra space for program upgrades.
+    pub reserved: [u64; 8],
+
+    /// The PDA bump.
+    pub bump: u8,
+}
+
+impl MintRegistry {
+    /// Total size of a MintRegistry account.
+    pub const LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // authority - 40
+        + BOOL_LENGTH       // is_enabled - 41
+        + 8 * U64_LENGTH    // reserved - 105
+        + U8_LENGTH         // bump - 106
+    ;
+
+    /// Initialize the mint registry.
+    pub fn initialize(&mut self, authority: Pubkey, is_enabled: bool, bump: u8) {
+        self.authority = authority;
+        self.is_enabled = is_enabled;
+        self.bump = bump;
+    }
+
+    /// Enable or disable the mint registry.
+    pub fn set_enabled(&mut self, is_enabled: bool) {
+        self.is_enabled = is_enabled;
+    }
+
+    /// Change the authority of the mint registry.
+    pub fn change_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require!(new_authority != Pubkey::default(), StreamError::InvalidNewAuthority);
+        self.authority = new_authority;
         Ok(())
     }
+}
 
-    // --- Instruction functions --- END ---
+/// An SPL token mint for which streams can be created when the [`MintRegistry`] is enabled.
+#[account]
+#[derive(Debug, PartialEq, Eq)]
+pub struct AllowedMint {
+    /// SPL token mint address.
+    pub mint: Pubkey,
+    /// Time at which the mint was added to the registry.
+    pub added_at: u64,
+
+    /// The PDA bump.
+    pub bump: u8,
 }
 
-/// Record of funds to be transferred once a stream is cancelled.
-pub struct CancelTransferParams {
-    /// Transfer fund amount to the stream sender.
-    pub transfer_amount_sender: u64,
-    /// Transfer fund amount to the signer.
-    pub transfer_amount_signer: u64,
-    /// Transfer fund amount to the stream recipient.
-    pub transfer_amount_recipient: u64,
+impl AllowedMint {
+    /// Total size of an AllowedMint account.
+    pub const LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // mint - 40
+        + U64_LENGTH        // added_at - 48
+        + U8_LENGTH         // bump - 49
+    ;
+
+    /// Initialize an allowed mint.
+    pub fn initialize(&mut self, mint: Pubkey, bump: u8) -> Result<()> {
+        self.mint = mint;
+        self.added_at = get_current_timestamp()?;
+        self.bump = bump;
+        Ok(())
+    }
 }
+
+/// Index of the streams of a user, either as the sender or as the recipient, so that wallets can list all the streams
+/// of a user with a single account fetch. There are 2 index accounts per user - one for the streams sent by the user and
+/// one for the streams received by the user.
+///
+/// The index is updated when streams are created or closed and when their sender or recipient changes. Streams created
+/// before the indexes were introduced are not indexed.
+#[account]
+#[derive(Debug, PartialEq, Eq)]
+pub struct StreamIndex {
+    /// Address of the user whose streams are indexed.
+    pub owner: Pubkey,
+
+    /// The PDA bump.
+    pub bump: u8,
+
+    /// Stream PDA addresses. The order is not maintained when streams are removed.
+    pub streams: Vec<Pubkey>,
+}
+
+impl StreamIndex {
+    /// Total size of a StreamIndex account excluding space taken up by the streams.
+    pub const BASE_LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner - 40
+        + U8_LENGTH         // bump - 41
+        + VEC_LENGTH_PREFIX // streams - 45
+    ;
+
+    pub fn space(streams_count: usize) -> usize {
+        Self::BASE_LENGTH + streams_count * PUBLIC_KEY_LENGTH
+    }
+
+    /// Add a stream to the index. Returns false if the stream was already indexed.
+    pub fn add(&mut self, stream: Pubkey) -> bool {
+        if self.streams.contains(&stream) {
+            return false;
+        }
+        self.streams.push(stream);
+        true
+    }
+
+    /// Remove a stream from the index. Returns false if the stream was not indexed.
+    pub fn remove(&mut self, stream: &Pubkey) -> bool {
+        match self.streams.iter().position(|indexed_stream| indexed_stream == stream) {
+            Some(index) => {
+                self.streams.swap_remove(index);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Figures of all the streams of an SPL token mint, so that dashboards can read protocol-wide figures with a single
+/// account fetch instead of scanning all the stream accounts. There is 1 stats account per mint and it is created along
+/// with the first stream of the mint.
+///
+/// The stats are updated by every instruction which moves funds in or out of an escrow account or stops a stream.
+/// Streams created before mint stats were introduced are not counted. Amounts never decrease and saturate instead of
+/// overflowing, so that they can never block an instruction.
+///
+/// INVARIANT: total_deposited_amount ==
+///                total_withdrawn_amount + total_refunded_amount + total_rewarded_amount
+///                + sum of escrow amounts of the counted streams (excluding tokens sent to the escrows directly)
+#[account]
+#[derive(Debug, PartialEq, Eq)]
+pub struct MintStats {
+    /// SPL token mint address.
+    pub mint: Pubkey,
+
+    /// Total number of streams created.
+    pub stream_count: u64,
+    /// Number of streams which have been neither cancelled nor closed. Streams which have ended are active till they
+    /// are closed.
+    pub active_stream_count: u64,
+    /// Number of streams cancelled because they were insolvent and their insolvency grace period was over.
+    pub insolvency_cancellation_count: u64,
+
+    /// Total amount transferred to the escrow accounts by senders - prepaid amounts and topups.
+    pub total_deposited_amount: u128,
+    /// Total amount transferred from the escrow accounts to recipients.
+    pub total_withdrawn_amount: u128,
+    /// Total amount returned from the escrow accounts to senders.
+    pub total_refunded_amount: u128,
+    /// Total amount of deposits given as rewards to the accounts which cancelled insolvent streams.
+    pub total_rewarded_amount: u128,
+
+    /// Extra space for program upgrades.
+    pub reserved: [u64; 8],
+
+    /// The PDA bump.
+    pub bump: u8,
+}
+
+impl MintStats {
+    /// Total size of a MintStats account.
+    pub const LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // mint - 40
+        + 3 * U64_LENGTH    // stream_count, active_stream_count, insolvency_cancellation_count - 64
+        + 4 * U128_LENGTH   // total_deposited_amount, total_withdrawn_amount, total_refunded_amount,
+                            // total_rewarded_amount - 128
+        + 8 * U64_LENGTH    // reserved - 192
+        + U8_LENGTH         // bump - 193
+    ;
+}
+
+/// Figures of all the streams of all the mints, which are the sums of the figures of all the [`MintStats`] accounts.
+/// There is a single global stats account and it is created along with the first stream.
+///
+/// The global stats are updated with the same changes as the mint stats and count the same streams.
+///
+/// INVARIANT: total_deposited_amount ==
+///                total_withdrawn_amount + total_refunded_amount + total_rewarded_amount
+///                + sum of escrow amounts of the counted streams (excluding tokens sent to the escrows directly)
+#[account]
+#[derive(Debug, PartialEq, Eq)]
+pub struct GlobalStats {
+    /// Total number of streams created.
+    pub stream_count: u64,
+    /// Number of streams which have been neither cancelled nor closed. Streams which have ended are active till they
+    /// are closed.
+    pub active_stream_count: u64,
+    /// Number of streams cancelled because they were insolvent and their insolvency grace period was over.
+    pub insolvency_cancellation_count: u64,
+
+    /// Total amount transferred to the escrow accounts by senders - prepaid amounts and topups.
+    pub total_deposited_amount: u128,
+    /// Total amount transferred from the escrow accounts to recipients.
+    pub total_withdrawn_amount: u128,
+    /// Total amount returned from the escrow accounts to senders.
+    pub total_refunded_amount: u128,
+    /// Total amount of deposits given as rewards to the accounts which cancelled insolvent streams.
+    pub total_rewarded_amount: u128,
+
+    /// Extra space for program upgrades.
+    pub reserved: [u64; 8],
+
+    /// The PDA bump.
+    pub bump: u8,
+}
+
+impl GlobalStats {
+    /// Total size of a GlobalStats account.
+    pub const LENGTH: usize = ANCHOR_DISCRIMINATOR_LENGTH
+        + 3 * U64_LENGTH    // stream_count, active_stream_count, insolvency_cancellation_count - 32
+        + 4 * U128_LENGTH   // total_deposited_amount, total_withdrawn_amount, total_refunded_amount,
+                            // total_rewarded_amount - 96
+        + 8 * U64_LENGTH    // reserved - 160
+        + U8_LENGTH         // bump - 161
+    ;
+}
+
+/// Changes of the figures of a stats account, shared by [`MintStats`] and [`GlobalStats`] so that both are always
+/// updated the same way.
+pub trait StreamStats {
+    /// Record a new stream and its initial deposit.
+    fn record_creation(&mut self, deposited_amount: u64);
+
+    /// Record a deposit to an escrow account.
+    fn record_deposit(&mut self, amount: u64);
+
+    /// Record a refund to a sender.
+    fn record_refund(&mut self, amount: u64);
+
+    /// Record a withdrawal to a recipient along with the reward given to the signer. If the withdrawal cancelled an
+    /// insolvent stream, the stream is no longer active.
+    fn record_withdrawal(&mut self, recipient_amount: u64, signer_amount: u64, is_insolvency_cancellation: bool);
+
+    /// Record the cancellation of a stream, including a termination with a settlement or a resolution by the arbiter.
+    fn record_cancellation(&mut self, params: &CancelTransferParams);
+
+    /// Record the closing of a stream. Cancelled streams are already not active.
+    fn record_close(&mut self, is_cancelled: bool);
+}
+
+/// Implement [`StreamStats`] for a stats account with the same figures as [`MintStats`].
+macro_rules! impl_stream_stats {
+    ($name:ident) => {
+        impl StreamStats for $name {
+            fn record_creation(&mut self, deposited_amount: u64) {
+                self.stream_count = self.stream_count.saturating_add(1);
+                self.active_stream_count = self.active_stream_count.saturating_add(1);
+                self.record_deposit(deposited_amount);
+            }
+
+            fn record_deposit(&mut self, amount: u64) {
+                self.total_deposited_amount = self.total_deposited_amount.saturating_add(amount as u128);
+            }
+
+            fn record_refund(&mut self, amount: u64) {
+                self.total_refunded_amount = self.total_refunded_amount.saturating_add(amount as u128);
+            }
+
+            fn record_withdrawal(
+                &mut self,
+                recipient_amount: u64,
+                signer_amount: u64,
+                is_insolvency_cancellation: bool,
+            ) {
+                self.total_withdrawn_amount = self
+                    .total_withdrawn_amount
+                    .saturating_add(recipient_amount as u128);
+                self.total_rewarded_amount = self.total_rewarded_amount.saturating_add(signer_amount as u128);
+                if is_insolvency_cancellation {
+                    self.record_stop(true);
+                }
+            }
+
+            fn record_cancellation(&mut self, params: &CancelTransferParams) {
+                self.record_refund(params.transfer_amount_sender);
+                self.record_withdrawal(
+                    params.transfer_amount_recipient,
+                    params.transfer_amount_signer,
+                    false,
+                );
+                self.record_stop(params.is_insolvency_cancellation);
+            }
+
+            fn record_close(&mut self, is_cancelled: bool) {
+                if !is_cancelled {
+                    self.record_stop(false);
+                }
+            }
+        }
+
+        impl $name {
+            fn record_stop(&mut self, is_insolvency_cancellation: bool) {
+                self.active_stream_count = self.active_stream_count.saturating_sub(1);
+                if is_insolvency_cancellation {
+                    self.insolvency_cancellation_count = self.insolvency_cancellation_count.saturating_add(1);
+                }
+            }
+        }
+    };
+}
+
+impl_stream_stats!(MintStats);
+impl_stream_stats!(GlobalStats);
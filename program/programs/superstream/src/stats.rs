@@ -0,0 +1,120 @@
+//! Module for maintaining the stats accounts of the mints and the global stats account. See [`MintStats`] and
+//! [`GlobalStats`].
+//!
+//! The instructions take the stats accounts as `UncheckedAccount`s at the `["mint_stats", mint]` and `["global_stats"]`
+//! PDAs, which the seeds constraints verify. The create instructions create the accounts if they don't exist yet. The
+//! other instructions only deserialize them if the stream is counted in the stats, so streams created before the stats
+//! were introduced keep working with stats accounts which have never been created.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{GlobalStats, MintStats, StreamStats},
+    utils::{create_program_account, is_program_account_initialized, load_program_account, store_program_account},
+    GLOBAL_STATS_ACCOUNT_SEED, MINT_STATS_ACCOUNT_SEED,
+};
+
+/// Record a new stream in the stats account of its mint, creating the stats account if it doesn't exist. The payer pays
+/// the rent of the stats account.
+pub(crate) fn record_creation<'info>(
+    mint_stats: &UncheckedAccount<'info>,
+    mint: Pubkey,
+    bump: u8,
+    deposited_amount: u64,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let mint_stats = mint_stats.to_account_info();
+    let mut stats = load_or_create_stats(
+        &mint_stats,
+        MintStats::LENGTH,
+        &[MINT_STATS_ACCOUNT_SEED, mint.as_ref(), &[bump]],
+        payer,
+        system_program,
+        || MintStats {
+            mint,
+            stream_count: 0,
+            active_stream_count: 0,
+            insolvency_cancellation_count: 0,
+            total_deposited_amount: 0,
+            total_withdrawn_amount: 0,
+            total_refunded_amount: 0,
+            total_rewarded_amount: 0,
+            reserved: [0; 8],
+            bump,
+        },
+    )?;
+    stats.record_creation(deposited_amount);
+    store_program_account(&mint_stats, &stats)
+}
+
+/// Record a new stream in the global stats account, creating the global stats account if it doesn't exist. The payer
+/// pays the rent of the global stats account.
+pub(crate) fn record_global_creation<'info>(
+    global_stats: &UncheckedAccount<'info>,
+    bump: u8,
+    deposited_amount: u64,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let global_stats = global_stats.to_account_info();
+    let mut stats = load_or_create_stats(
+        &global_stats,
+        GlobalStats::LENGTH,
+        &[GLOBAL_STATS_ACCOUNT_SEED, &[bump]],
+        payer,
+        system_program,
+        || GlobalStats {
+            stream_count: 0,
+            active_stream_count: 0,
+            insolvency_cancellation_count: 0,
+            total_deposited_amount: 0,
+            total_withdrawn_amount: 0,
+            total_refunded_amount: 0,
+            total_rewarded_amount: 0,
+            reserved: [0; 8],
+            bump,
+        },
+    )?;
+    stats.record_creation(deposited_amount);
+    store_program_account(&global_stats, &stats)
+}
+
+/// Update the stats account of the mint of a stream and the global stats account with the same change. This is a no-op
+/// for streams created before mint stats were introduced, i.e. if `is_counted_in_mint_stats` of the stream is false.
+pub(crate) fn update_stats(
+    mint_stats: &UncheckedAccount,
+    global_stats: &UncheckedAccount,
+    is_counted_in_mint_stats: bool,
+    f: impl Fn(&mut dyn StreamStats),
+) -> Result<()> {
+    if !is_counted_in_mint_stats {
+        return Ok(());
+    }
+
+    let mint_stats = mint_stats.to_account_info();
+    let mut stats = load_program_account::<MintStats>(&mint_stats)?;
+    f(&mut stats);
+    store_program_account(&mint_stats, &stats)?;
+
+    let global_stats = global_stats.to_account_info();
+    let mut stats = load_program_account::<GlobalStats>(&global_stats)?;
+    f(&mut stats);
+    store_program_account(&global_stats, &stats)
+}
+
+fn load_or_create_stats<'info, T: AccountDeserialize>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_stats: impl FnOnce() -> T,
+) -> Result<T> {
+    if is_program_account_initialized(account) {
+        load_program_account(account)
+    } else {
+        create_program_account(account, space, signer_seeds, payer, system_program)?;
+        Ok(new_stats())
+    }
+}
@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
 
-use crate::{error::StreamError, Stream, STREAM_ACCOUNT_SEED};
+use crate::{
+    error::StreamError,
+    get_stream_name_seed,
+    state::{VersionedStream, ZeroCopyStream},
+    STREAM_ACCOUNT_SEED,
+};
 
 pub(crate) fn transfer_to_escrow<'info>(
     sender: &Signer<'info>,
@@ -26,14 +31,10 @@ pub(crate) fn transfer_to_escrow<'info>(
 }
 
 pub(crate) fn transfer_from_escrow<'info>(
-    stream: &Account<'info, Stream>,
+    stream: &Account<'info, VersionedStream>,
     destination_token: &Account<'info, TokenAccount>,
     escrow_token: &Account<'info, TokenAccount>,
     token_program: &Program<'info, Token>,
-    seed: u64,
-    mint: &Pubkey,
-    name: &str,
-    bump: u8,
     amount: u64,
 ) -> Result<()> {
     if amount == 0 {
@@ -54,12 +55,93 @@ pub(crate) fn transfer_from_escrow<'info>(
             cpi_accounts,
             &[&[
                 STREAM_ACCOUNT_SEED,
-                seed.to_le_bytes().as_ref(),
+                stream.seed.to_le_bytes().as_ref(),
+                stream.mint.as_ref(),
+                get_stream_name_seed(&stream.name).as_ref(),
+                stream.sender_seed(),
+                stream.recipient_seed(),
+                &[stream.bump],
+            ]],
+        ),
+        amount,
+    )
+}
+
+/// Transfer funds from the escrow account of a zero-copy stream.
+pub(crate) fn transfer_from_zero_copy_escrow<'info>(
+    stream: &AccountLoader<'info, ZeroCopyStream>,
+    destination_token: &Account<'info, TokenAccount>,
+    escrow_token: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    require!(escrow_token.amount >= amount, StreamError::EscrowInsufficientFunds,);
+    // The stream account data can't be borrowed during the CPI, so the seeds are copied out of it.
+    let (seed, mint, name_seed, derivation, derivation_sender, derivation_recipient, bump) = {
+        let stream = stream.load()?;
+        (
+            stream.seed.to_le_bytes(),
+            stream.mint,
+            get_stream_name_seed(stream.name()?).into_owned(),
+            stream.derivation(),
+            stream.derivation_sender,
+            stream.derivation_recipient,
+            stream.bump,
+        )
+    };
+    let cpi_program = token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: escrow_token.to_account_info(),
+        to: destination_token.to_account_info(),
+        authority: stream.to_account_info(),
+    };
+
+    transfer(
+        CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            &[&[
+                STREAM_ACCOUNT_SEED,
+                seed.as_ref(),
                 mint.as_ref(),
-                name.as_bytes(),
+                name_seed.as_ref(),
+                derivation.sender_seed(&derivation_sender),
+                derivation.recipient_seed(&derivation_recipient),
                 &[bump],
             ]],
         ),
         amount,
     )
 }
+
+pub(crate) fn close_escrow<'info>(
+    stream: &Account<'info, VersionedStream>,
+    destination: &AccountInfo<'info>,
+    escrow_token: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let cpi_program = token_program.to_account_info();
+    let cpi_accounts = CloseAccount {
+        account: escrow_token.to_account_info(),
+        destination: destination.clone(),
+        authority: stream.to_account_info(),
+    };
+
+    close_account(CpiContext::new_with_signer(
+        cpi_program,
+        cpi_accounts,
+        &[&[
+            STREAM_ACCOUNT_SEED,
+            stream.seed.to_le_bytes().as_ref(),
+            stream.mint.as_ref(),
+            get_stream_name_seed(&stream.name).as_ref(),
+            stream.sender_seed(),
+            stream.recipient_seed(),
+            &[stream.bump],
+        ]],
+    ))
+}
@@ -1,4 +1,7 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer},
+};
 use anchor_spl::token::TokenAccount;
 
 pub(crate) fn get_current_timestamp() -> Result<u64> {
@@ -11,3 +14,96 @@ pub(crate) fn is_token_account_rent_exempt<T: AccountSerialize + AccountDeserial
 ) -> Result<bool> {
     Ok(Rent::get()?.is_exempt(account.to_account_info().lamports(), TokenAccount::LEN))
 }
+
+/// Check if a PDA account owned by the program has been created. Such accounts are passed as unchecked accounts when
+/// they are created lazily by the program.
+pub(crate) fn is_program_account_initialized(account: &AccountInfo) -> bool {
+    account.owner == &crate::ID && !account.data_is_empty()
+}
+
+/// Create a PDA account owned by the program. Anyone can send lamports to the address before it is created, in which
+/// case it can only be allocated and assigned.
+pub(crate) fn create_program_account<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let lamports_needed = Rent::get()?.minimum_balance(space);
+    let cpi_program = system_program.to_account_info();
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        let cpi_accounts = CreateAccount {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        return create_account(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &[signer_seeds]),
+            lamports_needed,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    if current_lamports < lamports_needed {
+        let cpi_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        transfer(
+            CpiContext::new(cpi_program.clone(), cpi_accounts),
+            lamports_needed - current_lamports,
+        )?;
+    }
+    let cpi_accounts = Allocate {
+        account_to_allocate: account.clone(),
+    };
+    allocate(
+        CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, &[signer_seeds]),
+        space as u64,
+    )?;
+    let cpi_accounts = Assign {
+        account_to_assign: account.clone(),
+    };
+    assign(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, &[signer_seeds]),
+        &crate::ID,
+    )
+}
+
+/// Resize a program account. The payer pays the rent of any extra space. The rent of any freed space is kept in the
+/// account.
+pub(crate) fn resize_program_account<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let lamports_needed = Rent::get()?.minimum_balance(space);
+    if account.lamports() < lamports_needed {
+        let cpi_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        transfer(
+            CpiContext::new(system_program.to_account_info(), cpi_accounts),
+            lamports_needed - account.lamports(),
+        )?;
+    }
+    account.realloc(space, false)?;
+    Ok(())
+}
+
+/// Deserialize a program account passed as an unchecked account. The account discriminator is checked.
+pub(crate) fn load_program_account<T: AccountDeserialize>(account: &AccountInfo) -> Result<T> {
+    let data = account.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Serialize a program account passed as an unchecked account, along with its discriminator.
+pub(crate) fn store_program_account<T: AccountSerialize>(account: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    value.try_serialize(&mut writer)
+}
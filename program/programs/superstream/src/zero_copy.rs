@@ -0,0 +1,50 @@
+//! Module for converting streams between the Borsh and the zero-copy layouts. See [`ZeroCopyStream`].
+
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    error::StreamError,
+    state::{ConvertibleStream, ConvertibleZeroCopyStream, Stream, VersionedStream, ZeroCopyStream},
+    utils::{resize_program_account, store_program_account},
+};
+
+/// Convert a stream to the zero-copy layout. The signer needs to be the sender or the recipient and pays the rent of
+/// the extra space.
+pub(crate) fn convert_to_zero_copy<'info>(
+    stream: &Account<'info, ConvertibleStream>,
+    signer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    require!(
+        signer.key() == stream.sender || signer.key() == stream.recipient,
+        StreamError::UserUnauthorizedToConvert
+    );
+
+    let stream_info = stream.to_account_info();
+    resize_program_account(&stream_info, ZeroCopyStream::LENGTH, signer, system_program)?;
+    {
+        let mut data = stream_info.try_borrow_mut_data()?;
+        data.fill(0);
+        data[..ZeroCopyStream::DISCRIMINATOR.len()].copy_from_slice(&ZeroCopyStream::DISCRIMINATOR);
+    }
+    let zero_copy_stream = AccountLoader::<ZeroCopyStream>::try_from(&stream_info)?;
+    zero_copy_stream.load_mut()?.copy_from_stream(stream)?;
+    Ok(())
+}
+
+/// Convert a zero-copy stream back to the newest [`Stream`] layout. The signer needs to be the sender or the recipient.
+/// The rent of the freed space is kept in the stream account and is returned to the sender when the stream is closed.
+pub(crate) fn convert_from_zero_copy<'info>(
+    stream: &Account<'info, ConvertibleZeroCopyStream>,
+    signer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    require!(
+        signer.key() == stream.sender || signer.key() == stream.recipient,
+        StreamError::UserUnauthorizedToConvert
+    );
+
+    let stream_info = stream.to_account_info();
+    resize_program_account(&stream_info, Stream::space(&stream.name), signer, system_program)?;
+    store_program_account(&stream_info, &VersionedStream::from(Stream::clone(stream)))
+}
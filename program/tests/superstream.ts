@@ -1,4 +1,5 @@
 import { ok, strictEqual } from 'node:assert'
+import { createHash } from 'node:crypto'
 
 import { AnchorError, AnchorProvider, BN, setProvider, web3, workspace, type Program } from '@coral-xyz/anchor'
 import { splTokenProgram } from '@coral-xyz/spl-token'
@@ -16,6 +17,60 @@ import {
 import type { Superstream } from '@/target/types/superstream'
 
 const STREAM_ACCOUNT_SEED = 'stream'
+const MAX_STREAM_NAME_SEED_LENGTH = 32
+const MINT_REGISTRY_ACCOUNT_SEED = 'mint_registry'
+const ALLOWED_MINT_ACCOUNT_SEED = 'allowed_mint'
+const SENDER_INDEX_ACCOUNT_SEED = 'sender_index'
+const RECIPIENT_INDEX_ACCOUNT_SEED = 'recipient_index'
+const MINT_STATS_ACCOUNT_SEED = 'mint_stats'
+const GLOBAL_STATS_ACCOUNT_SEED = 'global_stats'
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new web3.PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+
+type StreamPermission = { isAllowed: boolean; allowedAt: BN; expiresAt: BN }
+
+type StreamPermissions = {
+  senderCanCancel: StreamPermission
+  senderCanChangeSender: StreamPermission
+  senderCanPause: StreamPermission
+  recipientCanResumePauseBySender: StreamPermission
+  anyoneCanWithdrawForRecipient: StreamPermission
+}
+
+type StreamSchedule = { startsAt: BN; endsAt: BN; initialAmount: BN; flowInterval: BN; flowRate: BN }
+
+type CreateStreamOptions = {
+  seed?: BN
+  name: string
+  recipient: web3.PublicKey
+  derivation?: { global: object } | { sender: object } | { senderAndRecipient: object }
+  kind?:
+    | { prepaid: object }
+    | { nonPrepaid: { topupAmount: BN; insolvencyGracePeriod: BN; recipientDepositShareBps: number } }
+  schedule?: Partial<StreamSchedule>
+  permissions?: Partial<StreamPermissions>
+  arbiter?: web3.PublicKey
+  requiresRecipientAcceptance?: boolean
+}
+
+const NO_PERMISSION: StreamPermission = { isAllowed: false, allowedAt: new BN(0), expiresAt: new BN(0) }
+const ALLOWED_PERMISSION: StreamPermission = { isAllowed: true, allowedAt: new BN(0), expiresAt: new BN(0) }
+
+const DEFAULT_STREAM_SCHEDULE: StreamSchedule = {
+  startsAt: new BN(0),
+  endsAt: new BN(0),
+  initialAmount: new BN(0),
+  flowInterval: new BN(1),
+  flowRate: new BN(1),
+}
+
+const DEFAULT_STREAM_PERMISSIONS: StreamPermissions = {
+  senderCanCancel: ALLOWED_PERMISSION,
+  senderCanChangeSender: NO_PERMISSION,
+  senderCanPause: NO_PERMISSION,
+  recipientCanResumePauseBySender: NO_PERMISSION,
+  anyoneCanWithdrawForRecipient: NO_PERMISSION,
+}
 
 describe('superstream', () => {
   const provider = AnchorProvider.env()
@@ -33,6 +88,60 @@ describe('superstream', () => {
   let senderToken = web3.PublicKey.default
   let senderTokenAmount = new BN(1e10)
 
+  const createStream = async ({
+    seed = new BN(0),
+    name,
+    recipient,
+    derivation = { global: {} },
+    kind = { prepaid: {} },
+    schedule = {},
+    permissions = {},
+    arbiter = web3.PublicKey.default,
+    requiresRecipientAcceptance = false,
+  }: CreateStreamOptions) => {
+    const [streamPublicKey] = getStreamPublicKey(
+      program.programId,
+      seed,
+      mint,
+      name,
+      'global' in derivation ? undefined : sender.publicKey,
+      'senderAndRecipient' in derivation ? recipient : undefined,
+    )
+    const escrowToken = await createAssociatedTokenAccount(provider, mint, streamPublicKey)
+    const params = {
+      v1: [
+        {
+          kind,
+          schedule: { ...DEFAULT_STREAM_SCHEDULE, ...schedule },
+          permissions: { ...DEFAULT_STREAM_PERMISSIONS, ...permissions },
+          arbiter,
+          requiresRecipientAcceptance,
+        },
+      ],
+    }
+
+    await program.methods
+      .createStream(seed, name, recipient, derivation, params)
+      .accounts({
+        stream: streamPublicKey,
+        sender: sender.publicKey,
+        mint,
+        senderToken,
+        escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        mintRegistry: getMintRegistryPublicKey(program.programId)[0],
+        allowedMint: getAllowedMintPublicKey(program.programId, mint)[0],
+        senderIndex: getSenderIndexPublicKey(program.programId, sender.publicKey)[0],
+        recipientIndex: getRecipientIndexPublicKey(program.programId, recipient)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+
+    return { streamPublicKey, escrowToken }
+  }
+
   it('Initializes test setup', async () => {
     mint = await createMint(provider)
     senderToken = await createAssociatedTokenAccount(provider, mint, sender.publicKey)
@@ -78,6 +187,12 @@ describe('superstream', () => {
         mint,
         senderToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        mintRegistry: getMintRegistryPublicKey(program.programId)[0],
+        allowedMint: getAllowedMintPublicKey(program.programId, mint)[0],
+        senderIndex: getSenderIndexPublicKey(program.programId, sender.publicKey)[0],
+        recipientIndex: getRecipientIndexPublicKey(program.programId, recipient.publicKey)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
       })
@@ -99,6 +214,8 @@ describe('superstream', () => {
         mint,
         recipientToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -119,6 +236,8 @@ describe('superstream', () => {
         senderToken,
         recipientToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -140,6 +259,8 @@ describe('superstream', () => {
         mint,
         recipientToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -189,6 +310,12 @@ describe('superstream', () => {
           mint,
           senderToken,
           escrowToken,
+          mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+          globalStats: getGlobalStatsPublicKey(program.programId)[0],
+          mintRegistry: getMintRegistryPublicKey(program.programId)[0],
+          allowedMint: getAllowedMintPublicKey(program.programId, mint)[0],
+          senderIndex: getSenderIndexPublicKey(program.programId, sender.publicKey)[0],
+          recipientIndex: getRecipientIndexPublicKey(program.programId, recipient.publicKey)[0],
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
@@ -227,6 +354,12 @@ describe('superstream', () => {
           mint,
           senderToken,
           escrowToken,
+          mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+          globalStats: getGlobalStatsPublicKey(program.programId)[0],
+          mintRegistry: getMintRegistryPublicKey(program.programId)[0],
+          allowedMint: getAllowedMintPublicKey(program.programId, mint)[0],
+          senderIndex: getSenderIndexPublicKey(program.programId, sender.publicKey)[0],
+          recipientIndex: getRecipientIndexPublicKey(program.programId, recipient.publicKey)[0],
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
@@ -266,6 +399,12 @@ describe('superstream', () => {
         mint,
         senderToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        mintRegistry: getMintRegistryPublicKey(program.programId)[0],
+        allowedMint: getAllowedMintPublicKey(program.programId, mint)[0],
+        senderIndex: getSenderIndexPublicKey(program.programId, sender.publicKey)[0],
+        recipientIndex: getRecipientIndexPublicKey(program.programId, recipient.publicKey)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
       })
@@ -287,6 +426,8 @@ describe('superstream', () => {
         mint,
         recipientToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -311,6 +452,8 @@ describe('superstream', () => {
           senderToken,
           recipientToken,
           escrowToken,
+          mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+          globalStats: getGlobalStatsPublicKey(program.programId)[0],
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([randomSigner])
@@ -331,6 +474,8 @@ describe('superstream', () => {
         senderToken,
         recipientToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -352,6 +497,8 @@ describe('superstream', () => {
         mint,
         recipientToken,
         escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc()
@@ -361,103 +508,1334 @@ describe('superstream', () => {
     recipientTokenAccount = await fetchTokenAccount(recipientToken)
     approximatelyEqualBN(recipientTokenAccount.amount, new BN(1000 + diffOnCancel * 10))
   })
-})
 
-function strictEqualBN(actual: BN, expected: BN) {
-  if (!actual.eq(expected)) {
-    strictEqual(actual, expected)
-  }
-}
+  it('Creates a sender-scoped stream', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
 
-const DELTA = new BN(15)
+    const seed = new BN(0)
+    const name = 's1'
+    const [globalStreamPublicKey] = getStreamPublicKey(program.programId, seed, mint, name)
+    const endsAt = Math.floor(Date.now() / 1000) + 10
 
-function approximatelyEqualBN(actual: BN, expected: BN) {
-  if (actual.lt(expected.sub(DELTA)) || actual.gt(expected.add(DELTA))) {
-    strictEqual(actual.toString(), expected.toString())
-  }
-}
+    const { streamPublicKey, escrowToken } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      derivation: { sender: {} },
+      schedule: { endsAt: new BN(endsAt), initialAmount: new BN(1000) },
+    })
+    ok(!streamPublicKey.equals(globalStreamPublicKey))
 
-function getStreamPublicKey(
-  programId: web3.PublicKey,
-  seed: BN,
-  mint: web3.PublicKey,
-  name: string,
-): [web3.PublicKey, number] {
-  return web3.PublicKey.findProgramAddressSync(
-    [Buffer.from(STREAM_ACCOUNT_SEED), seed.toArrayLike(Buffer, 'le', 8), mint.toBuffer(), Buffer.from(name)],
-    new web3.PublicKey(programId),
-  )
-}
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    ok('sender' in stream.derivation)
+    ok(stream.derivationSender.equals(sender.publicKey))
+    ok(stream.derivationRecipient.equals(web3.PublicKey.default))
 
-async function createMint(provider: AnchorProvider): Promise<web3.PublicKey> {
-  const authority = provider.wallet.publicKey
-  const mint = web3.Keypair.generate()
-  const lamports = await getMinimumBalanceForRentExemptMint(provider.connection)
+    await program.methods
+      .cancel(seed, name, recipient.publicKey)
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        sender: sender.publicKey,
+        mint,
+        signerToken: senderToken,
+        senderToken,
+        recipientToken,
+        escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
 
-  const transaction = new web3.Transaction().add(
-    web3.SystemProgram.createAccount({
-      fromPubkey: provider.wallet.publicKey,
-      newAccountPubkey: mint.publicKey,
-      space: MINT_SIZE,
-      lamports,
-      programId: TOKEN_PROGRAM_ID,
-    }),
-    createInitializeMintInstruction(mint.publicKey, 9, authority, authority, TOKEN_PROGRAM_ID),
-  )
+    const recipientTokenAccount = await fetchTokenAccount(recipientToken)
+    ok(recipientTokenAccount.amount.gte(new BN(1000)))
+  })
 
-  await provider.sendAndConfirm(transaction, [mint])
-  return mint.publicKey
-}
+  it('Creates a long prepaid stream whose flow amount overflows u64 only before the division', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
 
-async function createAssociatedTokenAccount(
-  provider: AnchorProvider,
-  mint: web3.PublicKey,
-  owner: web3.PublicKey,
-): Promise<web3.PublicKey> {
-  const [instructions, associatedTokenAccountPublicKey] = await createAssociatedTokenAccountInstructions(
-    provider,
-    mint,
-    owner,
-  )
-  await provider.sendAndConfirm(new web3.Transaction().add(...instructions))
-  return associatedTokenAccountPublicKey
-}
+    const seed = new BN(0)
+    const name = 's5'
+    // 1e8 secs * 2e11 > u64::MAX, while the prepaid amount of 1e8 * 2e11 / 1e12 = 2e7 fits easily.
+    const endsAt = Math.floor(Date.now() / 1000) + 1e8
+    const flowInterval = new BN(1e12)
+    const flowRate = new BN(2e11)
 
-async function createAssociatedTokenAccountInstructions(
-  provider: AnchorProvider,
-  mint: web3.PublicKey,
-  owner: web3.PublicKey,
-): Promise<[web3.TransactionInstruction[], web3.PublicKey]> {
-  const associatedToken = await getAssociatedTokenAddress(
-    mint,
-    owner,
-    true,
-    TOKEN_PROGRAM_ID,
-    ASSOCIATED_TOKEN_PROGRAM_ID,
-  )
-  return [
-    [
-      createAssociatedTokenAccountInstruction(
-        provider.wallet.publicKey,
-        associatedToken,
-        owner,
+    const { streamPublicKey, escrowToken } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      schedule: { endsAt: new BN(endsAt), flowInterval, flowRate },
+    })
+
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    strictEqualBN(stream.totalTopupAmount, stream.endsAt.sub(stream.startsAt).mul(flowRate).div(flowInterval))
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, stream.totalTopupAmount)
+
+    await program.methods
+      .cancel(seed, name, recipient.publicKey)
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        sender: sender.publicKey,
         mint,
-        TOKEN_PROGRAM_ID,
-        ASSOCIATED_TOKEN_PROGRAM_ID,
-      ),
-    ],
-    associatedToken,
-  ]
-}
+        signerToken: senderToken,
+        senderToken,
+        recipientToken,
+        escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
 
-async function mintTo(
-  provider: AnchorProvider,
-  mint: web3.PublicKey,
-  destination: web3.PublicKey,
-  amount: number,
-): Promise<void> {
-  const transaction = new web3.Transaction().add(
-    createMintToInstruction(mint, destination, provider.wallet.publicKey, amount),
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+  })
+
+  it('Creates a prepaid stream with a fixed-point flow rate and leaves no dust in the escrow on cancel', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    const seed = new BN(0)
+    const name = 's6'
+    // 1000 tokens per 30-day month is 385.802469... tokens per second, which is stored scaled by FLOW_RATE_SCALE.
+    const month = 30 * 24 * 60 * 60
+    const flowRateScale = new BN(1e9)
+    const flowRate = new BN(1000).mul(flowRateScale).div(new BN(month))
+    const endsAt = Math.floor(Date.now() / 1000) + month
+
+    const { streamPublicKey, escrowToken } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      schedule: { endsAt: new BN(endsAt), flowInterval: flowRateScale, flowRate },
+    })
+
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    const prepaidAmount = stream.endsAt.sub(stream.startsAt).mul(flowRate).div(flowRateScale)
+    strictEqualBN(stream.totalTopupAmount, prepaidAmount)
+    ok(prepaidAmount.lte(new BN(1000)))
+
+    await sleep(2000)
+    const senderAmountBefore = (await fetchTokenAccount(senderToken)).amount
+    await program.methods
+      .cancel(seed, name, recipient.publicKey)
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        sender: sender.publicKey,
+        mint,
+        signerToken: senderToken,
+        senderToken,
+        recipientToken,
+        escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
+
+    const refundedAmount = (await fetchTokenAccount(senderToken)).amount.sub(senderAmountBefore)
+    const recipientAmount = (await fetchTokenAccount(recipientToken)).amount
+    strictEqualBN(refundedAmount.add(recipientAmount), prepaidAmount)
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+  })
+
+  it('Creates a non-prepaid stream from versioned params and cancels it without the seed and name', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    const seed = new BN(0)
+    const name = 's7'
+    const { streamPublicKey, escrowToken } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      derivation: { sender: {} },
+      kind: {
+        nonPrepaid: {
+          topupAmount: new BN(1000000),
+          insolvencyGracePeriod: new BN(60),
+          recipientDepositShareBps: 5000,
+        },
+      },
+      schedule: { initialAmount: new BN(1000), flowRate: new BN(10) },
+    })
+
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    ok(!stream.isPrepaid)
+    ok('sender' in stream.derivation)
+    ok(stream.senderCanCancel)
+    ok(!stream.senderCanPause)
+    strictEqualBN(stream.insolvencyGracePeriod, new BN(60))
+    strictEqual(stream.recipientDepositShareBps, 5000)
+    strictEqualBN(stream.totalTopupAmount.add(stream.depositNeeded), new BN(1000000))
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(1000000))
+
+    const cancelAccounts = {
+      stream: streamPublicKey,
+      signer: sender.publicKey,
+      sender: sender.publicKey,
+      mint,
+      signerToken: senderToken,
+      senderToken,
+      recipientToken,
+      escrowToken,
+      mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+      globalStats: getGlobalStatsPublicKey(program.programId)[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }
+
+    try {
+      await program.methods.cancel(seed, 's7-other', recipient.publicKey).accounts(cancelAccounts).rpc()
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 2006)
+    }
+
+    await program.methods.cancelV2().accounts(cancelAccounts).rpc()
+
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+  })
+
+  it('Indexes streams, tracks mint and global stats, checks the layout version and closes a stopped stream', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    const seed = new BN(0)
+    const name = 's3'
+    const [senderIndex] = getSenderIndexPublicKey(program.programId, sender.publicKey)
+    const [recipientIndex] = getRecipientIndexPublicKey(program.programId, recipient.publicKey)
+    const [mintStats] = getMintStatsPublicKey(program.programId, mint)
+    const [globalStats] = getGlobalStatsPublicKey(program.programId)
+    const statsBefore = await program.account.mintStats.fetch(mintStats)
+    const globalStatsBefore = await program.account.globalStats.fetch(globalStats)
+    const endsAt = Math.floor(Date.now() / 1000) + 2
+
+    const { streamPublicKey, escrowToken } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      schedule: { endsAt: new BN(endsAt), initialAmount: new BN(1000), flowRate: new BN(0) },
+      permissions: { senderCanCancel: NO_PERMISSION, anyoneCanWithdrawForRecipient: ALLOWED_PERMISSION },
+    })
+
+    ok((await program.account.streamIndex.fetch(senderIndex)).streams.some((s) => s.equals(streamPublicKey)))
+    const recipientStreamIndex = await program.account.streamIndex.fetch(recipientIndex)
+    strictEqual(recipientStreamIndex.streams.length, 1)
+    ok(recipientStreamIndex.streams[0].equals(streamPublicKey))
+
+    strictEqual((await program.account.stream.fetch(streamPublicKey)).version, 1)
+    try {
+      await program.methods
+        .migrateStream(seed, name)
+        .accounts({
+          stream: streamPublicKey,
+          payer: sender.publicKey,
+          mint,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .rpc()
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6065)
+    }
+
+    await program.methods
+      .withdraw(seed, name, recipient.publicKey)
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        mint,
+        recipientToken,
+        escrowToken,
+        mintStats,
+        globalStats,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
+
+    await sleep(4000)
+
+    await program.methods
+      .close(seed, name)
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        sender: sender.publicKey,
+        recipient: recipient.publicKey,
+        mint,
+        senderToken,
+        escrowToken,
+        mintStats,
+        globalStats,
+        senderIndex,
+        recipientIndex,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
+
+    strictEqual(await program.account.stream.fetchNullable(streamPublicKey), null)
+    ok(!(await program.account.streamIndex.fetch(senderIndex)).streams.some((s) => s.equals(streamPublicKey)))
+    strictEqual((await program.account.streamIndex.fetch(recipientIndex)).streams.length, 0)
+
+    const statsAfter = await program.account.mintStats.fetch(mintStats)
+    strictEqualBN(statsAfter.streamCount, statsBefore.streamCount.add(new BN(1)))
+    strictEqualBN(statsAfter.activeStreamCount, statsBefore.activeStreamCount)
+    strictEqualBN(statsAfter.totalDepositedAmount, statsBefore.totalDepositedAmount.add(new BN(1000)))
+    strictEqualBN(statsAfter.totalWithdrawnAmount, statsBefore.totalWithdrawnAmount.add(new BN(1000)))
+    strictEqualBN(statsAfter.totalRefundedAmount, statsBefore.totalRefundedAmount)
+
+    const globalStatsAfter = await program.account.globalStats.fetch(globalStats)
+    strictEqualBN(globalStatsAfter.streamCount, globalStatsBefore.streamCount.add(new BN(1)))
+    strictEqualBN(globalStatsAfter.activeStreamCount, globalStatsBefore.activeStreamCount)
+    strictEqualBN(globalStatsAfter.totalDepositedAmount, globalStatsBefore.totalDepositedAmount.add(new BN(1000)))
+    strictEqualBN(globalStatsAfter.totalWithdrawnAmount, globalStatsBefore.totalWithdrawnAmount.add(new BN(1000)))
+    strictEqualBN(globalStatsAfter.totalRefundedAmount, globalStatsBefore.totalRefundedAmount)
+  })
+
+  it('Measures compute units of withdrawals and topups of zero-copy streams', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    const seed = new BN(0)
+    const name = 's4'
+    const [mintStats] = getMintStatsPublicKey(program.programId, mint)
+    const [globalStats] = getGlobalStatsPublicKey(program.programId)
+
+    const { streamPublicKey, escrowToken } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { initialAmount: new BN(1000), flowRate: new BN(10) },
+    })
+
+    await sleep(2000)
+    const withdrawUnits = await getComputeUnits(
+      provider,
+      await program.methods
+        .withdraw(seed, name, recipient.publicKey)
+        .accounts({
+          stream: streamPublicKey,
+          signer: sender.publicKey,
+          mint,
+          recipientToken,
+          escrowToken,
+          mintStats,
+          globalStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc({ commitment: 'confirmed' }),
+    )
+    const topupUnits = await getComputeUnits(
+      provider,
+      await program.methods
+        .topupNonPrepaid(seed, name, new BN(1000), false)
+        .accounts({
+          stream: streamPublicKey,
+          signer: sender.publicKey,
+          mint,
+          signerToken: senderToken,
+          escrowToken,
+          mintStats,
+          globalStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc({ commitment: 'confirmed' }),
+    )
+
+    const streamBefore = await program.account.stream.fetch(streamPublicKey)
+    await program.methods
+      .convertStreamToZeroCopy()
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+    const zeroCopyStream = await program.account.zeroCopyStream.fetch(streamPublicKey)
+    strictEqual(Buffer.from(zeroCopyStream.name.slice(0, zeroCopyStream.nameLength)).toString(), name)
+    strictEqualBN(zeroCopyStream.totalTopupAmount, streamBefore.totalTopupAmount)
+
+    await sleep(2000)
+    const recipientAmountBefore = (await fetchTokenAccount(recipientToken)).amount
+    const zeroCopyWithdrawUnits = await getComputeUnits(
+      provider,
+      await program.methods
+        .withdrawZeroCopy()
+        .accounts({
+          stream: streamPublicKey,
+          signer: sender.publicKey,
+          mint,
+          recipientToken,
+          escrowToken,
+          mintStats,
+          globalStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc({ commitment: 'confirmed' }),
+    )
+    ok((await fetchTokenAccount(recipientToken)).amount.gt(recipientAmountBefore))
+    const zeroCopyTopupUnits = await getComputeUnits(
+      provider,
+      await program.methods
+        .topupNonPrepaidZeroCopy(new BN(1000), false)
+        .accounts({
+          stream: streamPublicKey,
+          signer: sender.publicKey,
+          mint,
+          signerToken: senderToken,
+          escrowToken,
+          mintStats,
+          globalStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc({ commitment: 'confirmed' }),
+    )
+    console.log(`withdraw: ${withdrawUnits} CU, withdraw_zero_copy: ${zeroCopyWithdrawUnits} CU`)
+    console.log(`topup_non_prepaid: ${topupUnits} CU, topup_non_prepaid_zero_copy: ${zeroCopyTopupUnits} CU`)
+
+    await program.methods
+      .convertStreamFromZeroCopy()
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    strictEqual(stream.name, name)
+    strictEqualBN(stream.totalTopupAmount, streamBefore.totalTopupAmount.add(new BN(1000)))
+  })
+
+  it('Cancels zero-copy streams', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+    const randomSigner = web3.Keypair.generate()
+    const randomSignerToken = await createAssociatedTokenAccount(provider, mint, randomSigner.publicKey)
+
+    const [mintStats] = getMintStatsPublicKey(program.programId, mint)
+
+    const [globalStats] = getGlobalStatsPublicKey(program.programId)
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's8',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { initialAmount: new BN(1000), flowRate: new BN(10) },
+    })
+    await program.methods
+      .convertStreamToZeroCopy()
+      .accounts({
+        stream: streamPublicKey,
+        signer: sender.publicKey,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+
+    const cancelAccounts = {
+      stream: streamPublicKey,
+      sender: sender.publicKey,
+      mint,
+      senderToken,
+      recipientToken,
+      escrowToken,
+      mintStats,
+      globalStats,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }
+    try {
+      await program.methods
+        .cancelZeroCopy()
+        .accounts({ ...cancelAccounts, signer: randomSigner.publicKey, signerToken: randomSignerToken })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6027)
+    }
+
+    await program.methods
+      .cancelZeroCopy()
+      .accounts({ ...cancelAccounts, signer: sender.publicKey, signerToken: senderToken })
+      .rpc()
+
+    const zeroCopyStream = await program.account.zeroCopyStream.fetch(streamPublicKey)
+    ok((zeroCopyStream.flags & ZERO_COPY_IS_CANCELLED) !== 0)
+    ok((await fetchTokenAccount(recipientToken)).amount.gte(new BN(1000)))
+    strictEqual((await fetchTokenAccount(escrowToken)).amount.toString(), '0')
+  })
+
+  it('Creates streams only for the allowed mints while the mint registry is enabled', async () => {
+    const recipient = web3.Keypair.generate()
+    const randomSigner = web3.Keypair.generate()
+
+    const [mintRegistry] = getMintRegistryPublicKey(program.programId)
+    const [programData] = web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+    )
+    await program.methods
+      .initializeMintRegistry(true)
+      .accounts({
+        mintRegistry,
+        authority: sender.publicKey,
+        program: program.programId,
+        programData,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+    const registry = await program.account.mintRegistry.fetch(mintRegistry)
+    ok(registry.authority.equals(sender.publicKey))
+    ok(registry.isEnabled)
+
+    const kind = {
+      nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+    }
+    try {
+      await createStream({ name: 's9', recipient: recipient.publicKey, kind })
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6040)
+    }
+
+    await program.methods
+      .addAllowedMint()
+      .accounts({
+        mintRegistry,
+        allowedMint: getAllowedMintPublicKey(program.programId, mint)[0],
+        authority: sender.publicKey,
+        mint,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc()
+    const { streamPublicKey } = await createStream({ name: 's10', recipient: recipient.publicKey, kind })
+    ok((await program.account.stream.fetch(streamPublicKey)).mint.equals(mint))
+
+    try {
+      await program.methods
+        .setMintRegistryEnabled(false)
+        .accounts({ mintRegistry, authority: randomSigner.publicKey })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 2001)
+    }
+
+    // Disable the registry again so that the other tests can create streams for any mint.
+    await program.methods.setMintRegistryEnabled(false).accounts({ mintRegistry, authority: sender.publicKey }).rpc()
+    ok(!(await program.account.mintRegistry.fetch(mintRegistry)).isEnabled)
+  })
+
+  it('Stores the insolvency grace period and keeps others from cancelling a solvent stream', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+    const randomSigner = web3.Keypair.generate()
+    const randomSignerToken = await createAssociatedTokenAccount(provider, mint, randomSigner.publicKey)
+
+    try {
+      await createStream({
+        name: 's11',
+        recipient: recipient.publicKey,
+        kind: {
+          nonPrepaid: {
+            topupAmount: new BN(1000000),
+            insolvencyGracePeriod: new BN(8 * 60 * 60 + 1),
+            recipientDepositShareBps: 0,
+          },
+        },
+      })
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6042)
+    }
+
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's12',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: {
+          topupAmount: new BN(1000000),
+          insolvencyGracePeriod: new BN(8 * 60 * 60),
+          recipientDepositShareBps: 0,
+        },
+      },
+    })
+    strictEqualBN((await program.account.stream.fetch(streamPublicKey)).insolvencyGracePeriod, new BN(8 * 60 * 60))
+
+    const cancelAccounts = {
+      stream: streamPublicKey,
+      sender: sender.publicKey,
+      mint,
+      senderToken,
+      recipientToken,
+      escrowToken,
+      mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+      globalStats: getGlobalStatsPublicKey(program.programId)[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }
+    try {
+      await program.methods
+        .cancelV2()
+        .accounts({ ...cancelAccounts, signer: randomSigner.publicKey, signerToken: randomSignerToken })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6027)
+    }
+
+    const senderAmountBefore = (await fetchTokenAccount(senderToken)).amount
+    await program.methods
+      .cancelV2()
+      .accounts({ ...cancelAccounts, signer: sender.publicKey, signerToken: senderToken })
+      .rpc()
+
+    // The stream is solvent, so the sender gets back everything not owed to the recipient, including the deposit.
+    const refundedAmount = (await fetchTokenAccount(senderToken)).amount.sub(senderAmountBefore)
+    const recipientAmount = (await fetchTokenAccount(recipientToken)).amount
+    strictEqualBN(refundedAmount.add(recipientAmount), new BN(1000000))
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+    strictEqualBN((await fetchTokenAccount(randomSignerToken)).amount, new BN(0))
+  })
+
+  it('Stores the recipient share of the deposit and refunds the whole deposit of a solvent stream', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    try {
+      await createStream({
+        name: 's13',
+        recipient: recipient.publicKey,
+        kind: {
+          nonPrepaid: {
+            topupAmount: new BN(1000000),
+            insolvencyGracePeriod: new BN(0),
+            recipientDepositShareBps: 10001,
+          },
+        },
+      })
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6044)
+    }
+
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's14',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(60), recipientDepositShareBps: 2500 },
+      },
+      schedule: { flowRate: new BN(10) },
+    })
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    strictEqual(stream.recipientDepositShareBps, 2500)
+    strictEqualBN(stream.depositNeeded, new BN(8 * 60 * 60 * 10))
+
+    await sleep(2000)
+    const senderAmountBefore = (await fetchTokenAccount(senderToken)).amount
+    await program.methods
+      .cancelV2()
+      .accounts({
+        stream: streamPublicKey,
+        signer: recipient.publicKey,
+        sender: sender.publicKey,
+        mint,
+        signerToken: recipientToken,
+        senderToken,
+        recipientToken,
+        escrowToken,
+        mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+        globalStats: getGlobalStatsPublicKey(program.programId)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([recipient])
+      .rpc()
+
+    // The deposit is split only when an insolvent stream is cancelled. A solvent stream returns it to the sender.
+    const refundedAmount = (await fetchTokenAccount(senderToken)).amount.sub(senderAmountBefore)
+    const recipientAmount = (await fetchTokenAccount(recipientToken)).amount
+    ok(recipientAmount.gtn(0))
+    ok(refundedAmount.gte(stream.depositNeeded))
+    strictEqualBN(refundedAmount.add(recipientAmount), new BN(1000000))
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+  })
+
+  it('Terminates a stream with a settlement accepted by the other party', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's15',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { flowRate: new BN(10) },
+    })
+
+    await program.methods
+      .proposeSettlementV2(new BN(5000))
+      .accounts({ stream: streamPublicKey, signer: sender.publicKey, mint })
+      .rpc()
+    let stream = await program.account.stream.fetch(streamPublicKey)
+    ok(stream.isSettlementProposed)
+    ok(stream.isSettlementProposedBySender)
+    strictEqualBN(stream.settlementAmount, new BN(5000))
+
+    const terminateAccounts = {
+      stream: streamPublicKey,
+      sender: sender.publicKey,
+      mint,
+      senderToken,
+      recipientToken,
+      escrowToken,
+      mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+      globalStats: getGlobalStatsPublicKey(program.programId)[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }
+    try {
+      await program.methods
+        .terminateWithSettlementV2(new BN(5000))
+        .accounts({ ...terminateAccounts, signer: sender.publicKey })
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6045)
+    }
+    try {
+      await program.methods
+        .terminateWithSettlementV2(new BN(4000))
+        .accounts({ ...terminateAccounts, signer: recipient.publicKey })
+        .signers([recipient])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6047)
+    }
+
+    const senderAmountBefore = (await fetchTokenAccount(senderToken)).amount
+    await program.methods
+      .terminateWithSettlementV2(new BN(5000))
+      .accounts({ ...terminateAccounts, signer: recipient.publicKey })
+      .signers([recipient])
+      .rpc()
+
+    stream = await program.account.stream.fetch(streamPublicKey)
+    ok(stream.isSettled)
+    ok(stream.isCancelled)
+    strictEqualBN((await fetchTokenAccount(recipientToken)).amount, new BN(5000))
+    strictEqualBN((await fetchTokenAccount(senderToken)).amount.sub(senderAmountBefore), new BN(1000000 - 5000))
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+  })
+
+  it('Freezes a stream and resolves the dispute by the arbiter', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+    const arbiter = web3.Keypair.generate()
+    const randomSigner = web3.Keypair.generate()
+
+    const [mintStats] = getMintStatsPublicKey(program.programId, mint)
+
+    const [globalStats] = getGlobalStatsPublicKey(program.programId)
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's16',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { flowRate: new BN(10) },
+      arbiter: arbiter.publicKey,
+    })
+
+    try {
+      await program.methods
+        .freezeV2()
+        .accounts({ stream: streamPublicKey, arbiter: randomSigner.publicKey, mint })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6050)
+    }
+
+    await program.methods
+      .freezeV2()
+      .accounts({ stream: streamPublicKey, arbiter: arbiter.publicKey, mint })
+      .signers([arbiter])
+      .rpc()
+    ok((await program.account.stream.fetch(streamPublicKey)).isFrozen)
+
+    try {
+      await program.methods
+        .cancelV2()
+        .accounts({
+          stream: streamPublicKey,
+          signer: sender.publicKey,
+          sender: sender.publicKey,
+          mint,
+          signerToken: senderToken,
+          senderToken,
+          recipientToken,
+          escrowToken,
+          mintStats,
+          globalStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6051)
+    }
+
+    const senderAmountBefore = (await fetchTokenAccount(senderToken)).amount
+    await program.methods
+      .resolveV2(new BN(3000))
+      .accounts({
+        stream: streamPublicKey,
+        arbiter: arbiter.publicKey,
+        sender: sender.publicKey,
+        mint,
+        senderToken,
+        recipientToken,
+        escrowToken,
+        mintStats,
+        globalStats,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([arbiter])
+      .rpc()
+
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    ok(!stream.isFrozen)
+    ok(stream.isCancelled)
+    strictEqualBN((await fetchTokenAccount(recipientToken)).amount, new BN(3000))
+    strictEqualBN((await fetchTokenAccount(senderToken)).amount.sub(senderAmountBefore), new BN(1000000 - 3000))
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, new BN(0))
+  })
+
+  it('Starts a stream pending recipient acceptance once the recipient accepts it', async () => {
+    const recipient = web3.Keypair.generate()
+    const randomSigner = web3.Keypair.generate()
+
+    const { streamPublicKey } = await createStream({
+      name: 's17',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { flowRate: new BN(10) },
+      requiresRecipientAcceptance: true,
+    })
+    const streamBefore = await program.account.stream.fetch(streamPublicKey)
+    ok(streamBefore.isPendingAcceptance)
+
+    try {
+      await program.methods
+        .acceptV2()
+        .accounts({ stream: streamPublicKey, recipient: randomSigner.publicKey, mint })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6055)
+    }
+
+    await sleep(2000)
+    await program.methods
+      .acceptV2()
+      .accounts({ stream: streamPublicKey, recipient: recipient.publicKey, mint })
+      .signers([recipient])
+      .rpc()
+
+    // The flow starts at the time of acceptance.
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    ok(!stream.isPendingAcceptance)
+    ok(stream.startsAt.gt(streamBefore.startsAt))
+
+    try {
+      await program.methods
+        .acceptV2()
+        .accounts({ stream: streamPublicKey, recipient: recipient.publicKey, mint })
+        .signers([recipient])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6054)
+    }
+  })
+
+  it('Changes the sender and the recipient once the new ones accept', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+    const newSender = web3.Keypair.generate()
+    const newRecipient = web3.Keypair.generate()
+    const randomSigner = web3.Keypair.generate()
+    await transferLamports(provider, newSender.publicKey, web3.LAMPORTS_PER_SOL / 10)
+    await transferLamports(provider, newRecipient.publicKey, web3.LAMPORTS_PER_SOL / 10)
+
+    const [mintStats] = getMintStatsPublicKey(program.programId, mint)
+
+    const [globalStats] = getGlobalStatsPublicKey(program.programId)
+    const [senderIndex] = getSenderIndexPublicKey(program.programId, sender.publicKey)
+    const [newSenderIndex] = getSenderIndexPublicKey(program.programId, newSender.publicKey)
+    const [recipientIndex] = getRecipientIndexPublicKey(program.programId, recipient.publicKey)
+    const [newRecipientIndex] = getRecipientIndexPublicKey(program.programId, newRecipient.publicKey)
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's18',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { flowRate: new BN(10) },
+      permissions: { senderCanChangeSender: ALLOWED_PERMISSION },
+    })
+
+    await program.methods
+      .changeSenderNonPrepaidV2(newSender.publicKey)
+      .accounts({ stream: streamPublicKey, sender: sender.publicKey, mint })
+      .rpc()
+    ok((await program.account.stream.fetch(streamPublicKey)).pendingSender.equals(newSender.publicKey))
+
+    const acceptSenderChangeAccounts = {
+      stream: streamPublicKey,
+      mint,
+      sender: sender.publicKey,
+      senderIndex,
+      systemProgram: web3.SystemProgram.programId,
+    }
+    try {
+      await program.methods
+        .acceptSenderChangeNonPrepaidV2()
+        .accounts({
+          ...acceptSenderChangeAccounts,
+          newSender: randomSigner.publicKey,
+          newSenderIndex: getSenderIndexPublicKey(program.programId, randomSigner.publicKey)[0],
+        })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6004)
+    }
+
+    await program.methods
+      .acceptSenderChangeNonPrepaidV2()
+      .accounts({ ...acceptSenderChangeAccounts, newSender: newSender.publicKey, newSenderIndex })
+      .signers([newSender])
+      .rpc()
+
+    let stream = await program.account.stream.fetch(streamPublicKey)
+    ok(stream.sender.equals(newSender.publicKey))
+    ok(stream.pendingSender.equals(web3.PublicKey.default))
+    ok(!(await program.account.streamIndex.fetch(senderIndex)).streams.some((s) => s.equals(streamPublicKey)))
+    ok((await program.account.streamIndex.fetch(newSenderIndex)).streams.some((s) => s.equals(streamPublicKey)))
+
+    await program.methods
+      .withdrawAndChangeRecipientV2(newRecipient.publicKey)
+      .accounts({
+        stream: streamPublicKey,
+        signer: recipient.publicKey,
+        mint,
+        recipientToken,
+        escrowToken,
+        mintStats,
+        globalStats,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([recipient])
+      .rpc()
+    ok((await program.account.stream.fetch(streamPublicKey)).pendingRecipient.equals(newRecipient.publicKey))
+
+    const acceptRecipientChangeAccounts = {
+      stream: streamPublicKey,
+      mint,
+      currentRecipient: recipient.publicKey,
+      recipientToken,
+      escrowToken,
+      recipientIndex,
+      mintStats,
+      globalStats,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: web3.SystemProgram.programId,
+    }
+    try {
+      await program.methods
+        .acceptRecipientChangeV2()
+        .accounts({
+          ...acceptRecipientChangeAccounts,
+          newRecipient: randomSigner.publicKey,
+          newRecipientIndex: getRecipientIndexPublicKey(program.programId, randomSigner.publicKey)[0],
+        })
+        .signers([randomSigner])
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6058)
+    }
+
+    await program.methods
+      .acceptRecipientChangeV2()
+      .accounts({ ...acceptRecipientChangeAccounts, newRecipient: newRecipient.publicKey, newRecipientIndex })
+      .signers([newRecipient])
+      .rpc()
+
+    stream = await program.account.stream.fetch(streamPublicKey)
+    ok(stream.recipient.equals(newRecipient.publicKey))
+    ok(stream.pendingRecipient.equals(web3.PublicKey.default))
+    strictEqual((await program.account.streamIndex.fetch(recipientIndex)).streams.length, 0)
+    ok((await program.account.streamIndex.fetch(newRecipientIndex)).streams.some((s) => s.equals(streamPublicKey)))
+  })
+
+  it('Applies permissions only between their unlock and expiry times', async () => {
+    const recipient = web3.Keypair.generate()
+    const recipientToken = await createAssociatedTokenAccount(provider, mint, recipient.publicKey)
+
+    const now = Math.floor(Date.now() / 1000)
+    const kind = {
+      nonPrepaid: { topupAmount: new BN(1000000), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+    }
+    try {
+      await createStream({
+        name: 's19',
+        recipient: recipient.publicKey,
+        kind,
+        permissions: {
+          senderCanCancel: { isAllowed: true, allowedAt: new BN(now + 60), expiresAt: new BN(now + 60) },
+        },
+      })
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6059)
+    }
+
+    const cancelExpiresAt = now + 5
+    const pauseAllowedAt = now + 60 * 60
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's20',
+      recipient: recipient.publicKey,
+      kind,
+      permissions: {
+        senderCanCancel: { isAllowed: true, allowedAt: new BN(0), expiresAt: new BN(cancelExpiresAt) },
+        senderCanPause: { isAllowed: true, allowedAt: new BN(pauseAllowedAt), expiresAt: new BN(0) },
+      },
+    })
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    strictEqualBN(stream.senderCanCancelExpiresAt, new BN(cancelExpiresAt))
+    strictEqualBN(stream.senderCanPauseAt, new BN(pauseAllowedAt))
+
+    try {
+      await program.methods
+        .pauseNonPrepaidV2()
+        .accounts({ stream: streamPublicKey, signer: sender.publicKey, mint })
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6035)
+    }
+
+    await sleep((cancelExpiresAt + 3) * 1000 - Date.now())
+    try {
+      await program.methods
+        .cancelV2()
+        .accounts({
+          stream: streamPublicKey,
+          signer: sender.publicKey,
+          sender: sender.publicKey,
+          mint,
+          signerToken: senderToken,
+          senderToken,
+          recipientToken,
+          escrowToken,
+          mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+          globalStats: getGlobalStatsPublicKey(program.programId)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6028)
+    }
+    ok(!(await program.account.stream.fetch(streamPublicKey)).isCancelled)
+  })
+
+  it('Rejects topups above the maximum when strict and only takes the maximum otherwise', async () => {
+    const recipient = web3.Keypair.generate()
+
+    const endsAt = Math.floor(Date.now() / 1000) + 24 * 60 * 60
+    const { streamPublicKey, escrowToken } = await createStream({
+      name: 's21',
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(57600), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+      schedule: { endsAt: new BN(endsAt) },
+    })
+    let stream = await program.account.stream.fetch(streamPublicKey)
+    // The flow rate is 1 token per second, so the stream can use at most 1 token per second of its lifetime.
+    const maxTopupAmount = stream.endsAt.sub(stream.startsAt).sub(stream.totalTopupAmount)
+    ok(maxTopupAmount.gtn(0))
+
+    const topupAccounts = {
+      stream: streamPublicKey,
+      signer: sender.publicKey,
+      mint,
+      signerToken: senderToken,
+      escrowToken,
+      mintStats: getMintStatsPublicKey(program.programId, mint)[0],
+      globalStats: getGlobalStatsPublicKey(program.programId)[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }
+    try {
+      await program.methods.topupNonPrepaidV2(maxTopupAmount.addn(1000), true).accounts(topupAccounts).rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 6022)
+    }
+
+    const senderAmountBefore = (await fetchTokenAccount(senderToken)).amount
+    await program.methods.topupNonPrepaidV2(maxTopupAmount.addn(1000), false).accounts(topupAccounts).rpc()
+
+    strictEqualBN(senderAmountBefore.sub((await fetchTokenAccount(senderToken)).amount), maxTopupAmount)
+    strictEqualBN((await fetchTokenAccount(escrowToken)).amount, maxTopupAmount.addn(57600))
+    stream = await program.account.stream.fetch(streamPublicKey)
+    strictEqualBN(stream.totalTopupAmount, stream.endsAt.sub(stream.startsAt))
+  })
+
+  it('Returns the withdrawable amount, the solvency and the maximum topup of a stream', async () => {
+    const recipient = web3.Keypair.generate()
+
+    const seed = new BN(0)
+    const name = 's22'
+    const { streamPublicKey } = await createStream({
+      seed,
+      name,
+      recipient: recipient.publicKey,
+      kind: {
+        nonPrepaid: { topupAmount: new BN(57600), insolvencyGracePeriod: new BN(0), recipientDepositShareBps: 0 },
+      },
+    })
+    const stream = await program.account.stream.fetch(streamPublicKey)
+    const viewAccounts = { stream: streamPublicKey, mint }
+
+    await sleep(2000)
+    const withdrawable = await program.methods.getWithdrawableV2().accounts(viewAccounts).view()
+    ok(withdrawable.amountOwed.gtn(0))
+    strictEqualBN(withdrawable.totalWithdrawnAmount, new BN(0))
+    strictEqualBN(withdrawable.withdrawableAmount, withdrawable.amountOwed)
+
+    // The total topup amount covers 28800 secs at 1 token per sec, so the stream is insolvent from the next sec.
+    const solvency = await program.methods.getSolvencyV2().accounts(viewAccounts).view()
+    ok(solvency.isSolvent)
+    ok(!solvency.isInInsolvencyGracePeriod)
+    ok('streaming' in solvency.status)
+    strictEqualBN(solvency.insolventAt, stream.startsAt.addn(28801))
+
+    const maxTopup = await program.methods.getMaxTopupV2().accounts(viewAccounts).view()
+    ok(maxTopup.hasNoLimit)
+
+    try {
+      await program.methods.getWithdrawable(seed, 's22-other').accounts(viewAccounts).rpc()
+      ok(false)
+    } catch (e) {
+      ok(e instanceof AnchorError)
+      strictEqual(e.error.errorCode.number, 2006)
+    }
+  })
+})
+
+async function getComputeUnits(provider: AnchorProvider, signature: string): Promise<number> {
+  const tx = await provider.connection.getTransaction(signature, {
+    commitment: 'confirmed',
+    maxSupportedTransactionVersion: 0,
+  })
+  return tx?.meta?.computeUnitsConsumed ?? 0
+}
+
+function strictEqualBN(actual: BN, expected: BN) {
+  if (!actual.eq(expected)) {
+    strictEqual(actual, expected)
+  }
+}
+
+const DELTA = new BN(15)
+
+const ZERO_COPY_IS_CANCELLED = 1 << 1
+
+function approximatelyEqualBN(actual: BN, expected: BN) {
+  if (actual.lt(expected.sub(DELTA)) || actual.gt(expected.add(DELTA))) {
+    strictEqual(actual.toString(), expected.toString())
+  }
+}
+
+function getStreamPublicKey(
+  programId: web3.PublicKey,
+  seed: BN,
+  mint: web3.PublicKey,
+  name: string,
+  derivationSender?: web3.PublicKey,
+  derivationRecipient?: web3.PublicKey,
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(STREAM_ACCOUNT_SEED),
+      seed.toArrayLike(Buffer, 'le', 8),
+      mint.toBuffer(),
+      getStreamNameSeed(name),
+      derivationSender ? derivationSender.toBuffer() : Buffer.alloc(0),
+      derivationRecipient ? derivationRecipient.toBuffer() : Buffer.alloc(0),
+    ],
+    new web3.PublicKey(programId),
+  )
+}
+
+function getStreamNameSeed(name: string): Buffer {
+  const nameBuffer = Buffer.from(name)
+  return nameBuffer.length <= MAX_STREAM_NAME_SEED_LENGTH ? nameBuffer : createHash('sha256').update(nameBuffer).digest()
+}
+
+function getMintStatsPublicKey(programId: web3.PublicKey, mint: web3.PublicKey): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(MINT_STATS_ACCOUNT_SEED), mint.toBuffer()],
+    new web3.PublicKey(programId),
+  )
+}
+
+function getGlobalStatsPublicKey(programId: web3.PublicKey): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync([Buffer.from(GLOBAL_STATS_ACCOUNT_SEED)], new web3.PublicKey(programId))
+}
+
+function getMintRegistryPublicKey(programId: web3.PublicKey): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync([Buffer.from(MINT_REGISTRY_ACCOUNT_SEED)], new web3.PublicKey(programId))
+}
+
+function getAllowedMintPublicKey(programId: web3.PublicKey, mint: web3.PublicKey): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(ALLOWED_MINT_ACCOUNT_SEED), mint.toBuffer()],
+    new web3.PublicKey(programId),
+  )
+}
+
+function getSenderIndexPublicKey(programId: web3.PublicKey, sender: web3.PublicKey): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(SENDER_INDEX_ACCOUNT_SEED), sender.toBuffer()],
+    new web3.PublicKey(programId),
+  )
+}
+
+function getRecipientIndexPublicKey(programId: web3.PublicKey, recipient: web3.PublicKey): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(RECIPIENT_INDEX_ACCOUNT_SEED), recipient.toBuffer()],
+    new web3.PublicKey(programId),
+  )
+}
+
+async function createMint(provider: AnchorProvider): Promise<web3.PublicKey> {
+  const authority = provider.wallet.publicKey
+  const mint = web3.Keypair.generate()
+  const lamports = await getMinimumBalanceForRentExemptMint(provider.connection)
+
+  const transaction = new web3.Transaction().add(
+    web3.SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: mint.publicKey,
+      space: MINT_SIZE,
+      lamports,
+      programId: TOKEN_PROGRAM_ID,
+    }),
+    createInitializeMintInstruction(mint.publicKey, 9, authority, authority, TOKEN_PROGRAM_ID),
+  )
+
+  await provider.sendAndConfirm(transaction, [mint])
+  return mint.publicKey
+}
+
+async function createAssociatedTokenAccount(
+  provider: AnchorProvider,
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+): Promise<web3.PublicKey> {
+  const [instructions, associatedTokenAccountPublicKey] = await createAssociatedTokenAccountInstructions(
+    provider,
+    mint,
+    owner,
+  )
+  await provider.sendAndConfirm(new web3.Transaction().add(...instructions))
+  return associatedTokenAccountPublicKey
+}
+
+async function createAssociatedTokenAccountInstructions(
+  provider: AnchorProvider,
+  mint: web3.PublicKey,
+  owner: web3.PublicKey,
+): Promise<[web3.TransactionInstruction[], web3.PublicKey]> {
+  const associatedToken = await getAssociatedTokenAddress(
+    mint,
+    owner,
+    true,
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  )
+  return [
+    [
+      createAssociatedTokenAccountInstruction(
+        provider.wallet.publicKey,
+        associatedToken,
+        owner,
+        mint,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      ),
+    ],
+    associatedToken,
+  ]
+}
+
+async function mintTo(
+  provider: AnchorProvider,
+  mint: web3.PublicKey,
+  destination: web3.PublicKey,
+  amount: number,
+): Promise<void> {
+  const transaction = new web3.Transaction().add(
+    createMintToInstruction(mint, destination, provider.wallet.publicKey, amount),
+  )
+  await provider.sendAndConfirm(transaction)
+}
+
+async function transferLamports(
+  provider: AnchorProvider,
+  destination: web3.PublicKey,
+  lamports: number,
+): Promise<void> {
+  const transaction = new web3.Transaction().add(
+    web3.SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: destination, lamports }),
   )
   await provider.sendAndConfirm(transaction)
 }